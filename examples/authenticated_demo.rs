@@ -2,6 +2,7 @@ use anilist_sdk::client::AniListClient;
 use anilist_sdk::error::AniListError;
 use anilist_sdk::utils::{RetryConfig, rate_limit_delay, retry_with_backoff};
 use dotenv::dotenv;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -18,18 +19,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(popular_anime) => {
             println!("✅ Popular anime (first 3):");
             for anime in popular_anime {
-                if let Some(title) = &anime.title {
-                    println!(
-                        "  - {} (ID: {})",
-                        title
-                            .user_preferred
-                            .as_ref()
-                            .or(title.english.as_ref())
-                            .or(title.romaji.as_ref())
-                            .unwrap_or(&"Unknown".to_string()),
-                        anime.id
-                    );
-                }
+                let title = &anime.title;
+                println!(
+                    "  - {} (ID: {})",
+                    title
+                        .user_preferred
+                        .as_ref()
+                        .or(title.english.as_ref())
+                        .or(title.romaji.as_ref())
+                        .unwrap_or(&"Unknown".to_string()),
+                    anime.id
+                );
             }
         }
         Err(e) => {
@@ -45,12 +45,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🔄 Example with Retry Logic");
     println!("===========================");
 
-    let retry_config = RetryConfig {
-        max_retries: 3,
-        base_delay_ms: 1000,
-        exponential_backoff: true,
-        max_delay_ms: 10000,
-    };
+    let retry_config = RetryConfig::builder()
+        .max_retries(3)
+        .base_delay(Duration::from_secs(1))
+        .max_delay(Duration::from_secs(10))
+        .build();
 
     let search_result = retry_with_backoff(
         || async {
@@ -62,6 +61,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     if results.is_empty() {
                         Err(AniListError::GraphQL {
                             message: "No search results found".to_string(),
+                            details: Vec::new(),
                         })
                     } else {
                         Ok(results)
@@ -77,18 +77,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(search_results) => {
             println!("✅ Search results for 'Attack on Titan':");
             for anime in search_results {
-                if let Some(title) = &anime.title {
-                    println!(
-                        "  - {} (ID: {})",
-                        title
-                            .user_preferred
-                            .as_ref()
-                            .or(title.english.as_ref())
-                            .or(title.romaji.as_ref())
-                            .unwrap_or(&"Unknown".to_string()),
-                        anime.id
-                    );
-                }
+                let title = &anime.title;
+                println!(
+                    "  - {} (ID: {})",
+                    title
+                        .user_preferred
+                        .as_ref()
+                        .or(title.english.as_ref())
+                        .or(title.romaji.as_ref())
+                        .unwrap_or(&"Unknown".to_string()),
+                    anime.id
+                );
             }
         }
         Err(e) => {
@@ -158,16 +157,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(popular_characters) => {
             println!("✅ Popular characters:");
             for character in popular_characters {
-                if let Some(name) = &character.name {
-                    println!(
-                        "  - {} (ID: {})",
-                        name.user_preferred
-                            .as_ref()
-                            .or(name.full.as_ref())
-                            .unwrap_or(&"Unknown".to_string()),
-                        character.id
-                    );
-                }
+                let name = &character.name;
+                println!(
+                    "  - {} (ID: {})",
+                    name.user_preferred
+                        .as_ref()
+                        .or(name.full.as_ref())
+                        .unwrap_or(&"Unknown".to_string()),
+                    character.id
+                );
             }
         }
         Err(e) => {
@@ -207,6 +205,20 @@ fn handle_api_error(error: &AniListError) {
             println!("   ⏰ Rate limit exceeded");
             println!("   💡 Tip: Slow down your requests and try again in a minute");
         }
+        AniListError::Config { message } => {
+            println!("   ⚙️  Configuration error: {}", message);
+            println!("   💡 Tip: Check how the client is set up");
+        }
+        AniListError::BudgetUnavailable {
+            requested,
+            available,
+        } => {
+            println!(
+                "   ⏳ Could not reserve {} requests ({} available)",
+                requested, available
+            );
+            println!("   💡 Tip: Retry later or reserve fewer requests");
+        }
         AniListError::BurstLimit => {
             println!("   💨 Burst limit exceeded - requests sent too quickly");
             println!("   💡 Tip: Add meaningful delays between requests");
@@ -219,6 +231,14 @@ fn handle_api_error(error: &AniListError) {
             println!("   🚫 Access denied - check your token permissions");
             println!("   💡 Tip: Verify your token is valid and has required scope");
         }
+        AniListError::Private { resource } => {
+            println!("   🙈 The requested {} is private", resource);
+            println!("   💡 Tip: Only the owner can see this data");
+        }
+        AniListError::AdultContentBlocked { message } => {
+            println!("   🔞 Adult content blocked: {}", message);
+            println!("   💡 Tip: Log in and enable adult content in your AniList settings");
+        }
         AniListError::NotFound => {
             println!("   🔍 Resource not found");
             println!("   💡 Tip: Check if the ID or search query is valid");
@@ -227,11 +247,23 @@ fn handle_api_error(error: &AniListError) {
             println!("   ❌ Bad request: {}", message);
             println!("   💡 Tip: Check your query parameters");
         }
+        AniListError::InvalidInput { field, message } => {
+            println!("   ✏️  Invalid {}: {}", field, message);
+            println!("   💡 Tip: Pages start at 1 and hold at most 50 items");
+        }
+        AniListError::RequestAborted { message } => {
+            println!("   🪝 Request stopped by a hook: {}", message);
+            println!("   💡 Tip: Check the hooks added to the client");
+        }
+        AniListError::PageLimitReached { pages } => {
+            println!("   📚 Gave up after {} pages", pages);
+            println!("   💡 Tip: The result was too large to read in full");
+        }
         AniListError::ServerError { status, message } => {
             println!("   🖥️  Server error ({}): {}", status, message);
             println!("   💡 Tip: Try again later, this is usually temporary");
         }
-        AniListError::GraphQL { message } => {
+        AniListError::GraphQL { message, .. } => {
             println!("   📊 GraphQL error: {}", message);
             println!("   💡 Tip: Check your query syntax and variables");
         }
@@ -239,6 +271,10 @@ fn handle_api_error(error: &AniListError) {
             println!("   🌐 Network error: {}", e);
             println!("   💡 Tip: Check your internet connection");
         }
+        AniListError::Timeout { elapsed } => {
+            println!("   ⌛ Request timed out after {:?}", elapsed);
+            println!("   💡 Tip: AniList may be slow right now; retry or raise the timeout");
+        }
         AniListError::Json(e) => {
             println!("   📄 JSON parsing error: {}", e);
             println!("   💡 Tip: This might indicate an API response format change");
@@ -0,0 +1,661 @@
+//! Records live responses into `tests/fixtures/`.
+//!
+//! Runs the same calls the fixture tests replay and writes each raw response
+//! in the fixture format described in `tests/fixtures/README.md`, so the
+//! corpus can be refreshed when AniList changes.
+//!
+//! ```text
+//! cargo run --example capture_fixtures -- [NAME...]
+//! ```
+//!
+//! With no names every fixture is captured. The token from `ANILIST_TOKEN`
+//! (or `.env`) is used for the calls that need one; it is never written out,
+//! and any occurrence of it in a response is replaced with `<redacted>`.
+//! Error fixtures (`error_*.json`) are maintained by hand.
+
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::envelope::headers_of_interest;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::models::{
+    ActivityFeedFilter, ActivityType, FavouriteItem, FavouriteKind, FuzzyDate, MediaFormat,
+    MediaListStatus, MediaSearchFilter, MediaSeason, MediaSort, MediaType, RecommendationRating,
+    SaveListEntry,
+};
+use anilist_sdk::transport::{
+    HttpTransport, Transport, TransportFuture, TransportRequest, TransportResponse,
+};
+use anilist_sdk::utils::pagination::AllPages;
+use futures_util::FutureExt;
+use serde_json::{Value, json};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Sends requests over HTTP and keeps the last response.
+#[derive(Default)]
+struct RecordingTransport {
+    http: HttpTransport,
+    last: Mutex<Option<TransportResponse>>,
+}
+
+impl Transport for RecordingTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self.http.send(request).await?;
+            *self.last.lock().unwrap() = Some(response.clone());
+            Ok(response)
+        })
+    }
+}
+
+type Call = Box<dyn Fn(AniListClient) -> Pin<Box<dyn Future<Output = Result<(), AniListError>>>>>;
+
+/// A fixture name, the call it documents, and the call itself.
+struct Capture {
+    name: &'static str,
+    method: &'static str,
+    call: Call,
+}
+
+macro_rules! capture {
+    // rustfmt wraps long calls in a block, which would drop the endpoint
+    // before the call's future is awaited
+    ($name:literal, |$client:ident| { $($call:tt)* }) => {
+        capture!($name, |$client| $($call)*)
+    };
+    ($name:literal, |$client:ident| $call:expr) => {
+        Capture {
+            name: $name,
+            method: stringify!($call),
+            call: Box::new(|$client: AniListClient| {
+                Box::pin(async move { $call.await.map(|_| ()) })
+            }),
+        }
+    };
+}
+
+fn captures() -> Vec<Capture> {
+    vec![
+        capture!("anime_get_by_id", |client| client.anime().get_by_id(1)),
+        capture!("anime_get_by_mal_id", |client| client
+            .anime()
+            .get_by_mal_id(16498)),
+        capture!("anime_get_relations", |client| client
+            .anime()
+            .get_relations(16498)),
+        capture!("anime_get_characters", |client| client
+            .anime()
+            .get_characters(16498, 1, 3)),
+        capture!("anime_get_staff", |client| client
+            .anime()
+            .get_staff(16498, 1, 4)),
+        capture!("anime_get_streaming_episodes", |client| client
+            .anime()
+            .get_streaming_episodes(21)),
+        capture!("anime_get_streaming_links", |client| client
+            .anime()
+            .get_streaming_links(140960)),
+        capture!("anime_get_stats", |client| client.anime().get_stats(1)),
+        capture!("anime_get_popular", |client| client
+            .anime()
+            .get_popular(1, 2)),
+        capture!("anime_search_advanced", |client| client
+            .anime()
+            .search_advanced(
+                &MediaSearchFilter::new()
+                    .genres(["Action"])
+                    .year_range(2015, 2020)
+                    .formats([MediaFormat::Tv])
+                    .sort(MediaSort::ScoreDesc),
+                1,
+                2
+            )),
+        capture!("anime_get_by_tags", |client| client.anime().get_by_tags(
+            &["Time Travel"],
+            Some(80),
+            1,
+            2
+        )),
+        capture!("anime_get_trending", |client| client
+            .anime()
+            .get_trending(1, 2)),
+        capture!("anime_get_by_ids", |client| client
+            .anime()
+            .get_by_ids(&[1, 5, 999999999])),
+        capture!("anime_search", |client| client.anime().search(
+            "cowboy bebop",
+            1,
+            2
+        )),
+        capture!("anime_autocomplete", |client| client
+            .anime()
+            .autocomplete("bebop", 2)),
+        capture!("anime_get_by_season", |client| client
+            .anime()
+            .get_by_season("FALL", 2023, 1, 2)),
+        // The chart's second request, for the shows continuing into the season
+        capture!("anime_get_season_chart", |client| client
+            .anime()
+            .get_season_chart(MediaSeason::Fall, 2023, 1, 2)),
+        capture!("anime_get_top_rated", |client| client
+            .anime()
+            .get_top_rated(1, 2)),
+        capture!("anime_get_by_genre", |client| client.anime().get_by_genre(
+            &["Action"],
+            MediaSort::ScoreDesc,
+            1,
+            2
+        )),
+        capture!("anime_get_airing", |client| client.anime().get_airing(1, 2)),
+        capture!("anime_get_premiering_within", |client| client
+            .anime()
+            .get_premiering_within(90, 1, 2)),
+        capture!("manga_get_by_id", |client| client.manga().get_by_id(30013)),
+        capture!("manga_get_stats", |client| client.manga().get_stats(30013)),
+        capture!("manga_get_popular", |client| client
+            .manga()
+            .get_popular(1, 2)),
+        capture!("manga_get_trending", |client| client
+            .manga()
+            .get_trending(1, 2)),
+        capture!("manga_search", |client| client.manga().search(
+            "fullmetal alchemist",
+            1,
+            2
+        )),
+        capture!("manga_autocomplete", |client| client
+            .manga()
+            .autocomplete("berserk", 2)),
+        capture!("manga_get_top_rated", |client| client
+            .manga()
+            .get_top_rated(1, 2)),
+        capture!("manga_get_releasing", |client| client
+            .manga()
+            .get_releasing(1, 2)),
+        capture!("manga_get_completed", |client| client
+            .manga()
+            .get_completed(1, 2)),
+        capture!("manga_get_adaptation_airing", |client| client
+            .manga()
+            .get_adaptation_airing(132029)),
+        capture!("character_get_by_id", |client| client
+            .character()
+            .get_by_id(11)),
+        capture!("character_get_popular", |client| client
+            .character()
+            .get_popular(1, 2)),
+        capture!("character_search", |client| client
+            .character()
+            .search("edward", 1, 2)),
+        capture!("character_get_today_birthday", |client| client
+            .character()
+            .get_today_birthday(1, 1)),
+        capture!("character_get_most_favorited", |client| client
+            .character()
+            .get_most_favorited(1, 2)),
+        capture!("staff_get_by_id", |client| client.staff().get_by_id(95185)),
+        capture!("staff_get_popular", |client| client
+            .staff()
+            .get_popular(1, 2)),
+        capture!("staff_search", |client| client
+            .staff()
+            .search("watanabe", 1, 2)),
+        capture!("staff_get_today_birthday", |client| client
+            .staff()
+            .get_today_birthday(1, 1)),
+        capture!("staff_get_most_favorited", |client| client
+            .staff()
+            .get_most_favorited(1, 2)),
+        capture!("studio_get_by_id", |client| client.studio().get_by_id(14)),
+        capture!("studio_get_popular", |client| client
+            .studio()
+            .get_popular(1, 2)),
+        capture!("studio_search", |client| client
+            .studio()
+            .search("bones", 1, 2)),
+        capture!("studio_get_most_favorited", |client| client
+            .studio()
+            .get_most_favorited(1, 2)),
+        // Flips the favourite on the token's account; capture it again to flip it back
+        capture!("studio_toggle_favorite", |client| client
+            .studio()
+            .toggle_favorite(14)),
+        capture!("user_get_by_id", |client| client.user().get_by_id(5123456)),
+        capture!("user_get_by_id_as_html", |client| client
+            .user()
+            .get_by_id_as_html(5123456)),
+        capture!("user_get_by_name", |client| client
+            .user()
+            .get_by_name("Donator")),
+        capture!("user_get_by_name_as_html", |client| client
+            .user()
+            .get_by_name_as_html("Donator")),
+        capture!("user_get_current_user", |client| client
+            .user()
+            .get_current_user()),
+        capture!("user_get_current_user_as_html", |client| client
+            .user()
+            .get_current_user_as_html()),
+        // The list requests come after the viewer request, which the
+        // user_get_current_user fixture covers
+        capture!("user_get_current_user_anime_list", |client| client
+            .user()
+            .get_current_user_anime_list(None)),
+        capture!("user_get_current_user_anime_list_all", |client| client
+            .user()
+            .get_current_user_anime_list_all(None)
+            .map(AllPages::into_result)),
+        capture!("user_get_next_up", |client| client.user().get_next_up()),
+        capture!("user_get_list_collection", |client| client
+            .user()
+            .get_list_collection(
+                5123456,
+                MediaType::Manga,
+                Some(MediaListStatus::Current),
+                2
+            )),
+        capture!("user_get_list_entries_updated_since", |client| client
+            .user()
+            .get_list_entries_updated_since(
+                5123456,
+                MediaType::Anime,
+                1728777600
+            )),
+        capture!("user_search", |client| client
+            .user()
+            .search("donator", 1, 2)),
+        capture!("user_get_most_anime_watched", |client| client
+            .user()
+            .get_most_anime_watched(1, 2)),
+        capture!("user_get_most_manga_read", |client| client
+            .user()
+            .get_most_manga_read(1, 2)),
+        capture!("user_get_favourite_set", |client| client
+            .user()
+            .get_favourite_set()),
+        // These change the token's account; capture each toggle again to undo it
+        capture!("user_toggle_follow", |client| client
+            .user()
+            .toggle_follow(312)),
+        capture!("user_update_about", |client| client
+            .user()
+            .update_about("Hello __world__")),
+        capture!("user_toggle_favorite", |client| client
+            .user()
+            .toggle_favorite(Some(171018), None)),
+        capture!("user_toggle_favourite_item", |client| client
+            .user()
+            .toggle_favourite_item(FavouriteItem {
+                kind: FavouriteKind::Anime,
+                id: 16498,
+            })),
+        capture!("user_save_list_entry", |client| client
+            .user()
+            .save_list_entry(
+                &SaveListEntry::for_media(21).progress(1120).private(true)
+            )),
+        // Pass the ID of an entry on the token's list
+        capture!("user_update_media_list_progress", |client| client
+            .user()
+            .update_media_list_progress(98765401, 1121)),
+        capture!("user_update_media_list_status", |client| client
+            .user()
+            .update_media_list_status(
+                98765402,
+                MediaListStatus::Completed,
+                Some(FuzzyDate {
+                    year: Some(2024),
+                    month: Some(12),
+                    day: Some(20),
+                }),
+            )),
+        capture!("activity_get_activity_by_id", |client| client
+            .activity()
+            .get_activity_by_id(701234567)),
+        capture!("activity_get_recent_activities", |client| client
+            .activity()
+            .get_recent_activities(1, 3)),
+        capture!("activity_get_recent_activities_filtered", |client| client
+            .activity()
+            .get_recent_activities_filtered(
+                1,
+                2,
+                &ActivityFeedFilter::new().types(&[ActivityType::AnimeList]),
+            )),
+        capture!("activity_get_following_activities", |client| client
+            .activity()
+            .get_following_activities(1, 2)),
+        capture!("activity_get_following_activities_filtered", |client| {
+            client.activity().get_following_activities_filtered(
+                1,
+                2,
+                &ActivityFeedFilter::new().types(&[ActivityType::AnimeList]),
+            )
+        }),
+        capture!("activity_get_user_activities", |client| client
+            .activity()
+            .get_user_activities(5123456, 1, 2)),
+        capture!("activity_get_user_activities_filtered", |client| client
+            .activity()
+            .get_user_activities_filtered(
+                5123456,
+                1,
+                2,
+                &ActivityFeedFilter::new().types(&[ActivityType::Text]),
+            )),
+        capture!("activity_get_text_activities", |client| client
+            .activity()
+            .get_text_activities(1, 2)),
+        capture!("activity_get_activity_detail", |client| client
+            .activity()
+            .get_activity_detail(812000010)),
+        capture!("activity_get_activity_replies", |client| client
+            .activity()
+            .get_activity_replies(701234567, 1, 2)),
+        capture!("activity_get_subscribed_activities", |client| client
+            .activity()
+            .get_subscribed_activities(1, 2)),
+        capture!("client_health_check", |client| client.health_check()),
+        // The activity request, after the viewer ID probe
+        capture!("activity_get_my_latest_list_activity", |client| client
+            .activity()
+            .get_my_latest_list_activity(171018)),
+        capture!("activity_get_likers", |client| client
+            .activity()
+            .get_likers(701234567, 1, 2)),
+        capture!("activity_get_reply_likers", |client| client
+            .activity()
+            .get_reply_likers(30001, 1, 2)),
+        // These post as the token's account; pass the created activity's ID
+        // to the delete, and capture each toggle again to undo it
+        capture!("activity_create_text_activity", |client| client
+            .activity()
+            .create_text_activity("Dandadan episode 3 lived up to the hype.")),
+        capture!("activity_post_activity_reply", |client| client
+            .activity()
+            .post_activity_reply(
+                812000007,
+                "The opening alone sold me on it."
+            )),
+        capture!("activity_toggle_activity_like", |client| client
+            .activity()
+            .toggle_activity_like(812000001)),
+        capture!("activity_toggle_activity_reply_like", |client| client
+            .activity()
+            .toggle_activity_reply_like(30001)),
+        capture!("activity_toggle_activity_subscription", |client| client
+            .activity()
+            .toggle_activity_subscription(701234567, true)),
+        capture!("activity_toggle_activity_pin", |client| client
+            .activity()
+            .toggle_activity_pin(701234567, true)),
+        capture!("activity_delete_activity", |client| client
+            .activity()
+            .delete_activity(812000020)),
+        capture!("forum_get_thread_by_id", |client| client
+            .forum()
+            .get_thread_by_id(4)),
+        capture!("forum_get_thread_by_id_as_html", |client| client
+            .forum()
+            .get_thread_by_id_as_html(4)),
+        capture!("forum_get_recent_threads", |client| client
+            .forum()
+            .get_recent_threads(1, 3)),
+        capture!("forum_get_recent_threads_as_html", |client| client
+            .forum()
+            .get_recent_threads_as_html(1, 3)),
+        // Needs a moderator account; fails with AccessDenied otherwise
+        capture!("forum_lock_thread", |client| client
+            .forum()
+            .lock_thread(74000, true)),
+        capture!("forum_sticky_thread", |client| client
+            .forum()
+            .sticky_thread(75310, true)),
+        // Records the thread page, sent after the viewer ID probe
+        capture!("forum_get_my_threads", |client| client
+            .forum()
+            .get_my_threads(1, 2)),
+        capture!("forum_get_my_commented_threads", |client| client
+            .forum()
+            .get_my_commented_threads(1, 2)),
+        capture!("forum_get_subscribed_threads", |client| client
+            .forum()
+            .get_subscribed_threads(1, 2)),
+        capture!("forum_search_threads", |client| client
+            .forum()
+            .search_threads("frieren", 1, 2)),
+        capture!("forum_get_thread_comments", |client| client
+            .forum()
+            .get_thread_comments(69800, 1, 2)),
+        // The mutations below act on the token's account
+        capture!("forum_create_thread", |client| client
+            .forum()
+            .create_thread(
+                "Cowboy Bebop rewatch club",
+                "One session a week, starting with Asteroid Blues.",
+                Some(vec![2])
+            )),
+        capture!("forum_post_comment", |client| client
+            .forum()
+            .post_comment(75310, "The animation in the chase was unreal.")),
+        capture!("forum_toggle_thread_subscription", |client| client
+            .forum()
+            .toggle_thread_subscription(69800, true)),
+        capture!("forum_toggle_thread_like", |client| client
+            .forum()
+            .toggle_thread_like(75310)),
+        capture!("forum_toggle_comment_like", |client| client
+            .forum()
+            .toggle_comment_like(2101234)),
+        capture!("forum_get_thread_likers", |client| client
+            .forum()
+            .get_thread_likers(75310, 1, 2)),
+        capture!("forum_get_comment_likers", |client| client
+            .forum()
+            .get_comment_likers(2101234, 1, 2)),
+        capture!("review_get_review_by_id", |client| client
+            .review()
+            .get_review_by_id(2000)),
+        capture!("review_get_recent_reviews", |client| client
+            .review()
+            .get_recent_reviews(1, 2)),
+        capture!("review_get_reviews_for_media", |client| client
+            .review()
+            .get_reviews_for_media(154587, 1, 2)),
+        capture!(
+            "review_get_reviews_for_media_with_viewer_status",
+            |client| client
+                .review()
+                .get_reviews_for_media_with_viewer_status(154587, 1, 2)
+        ),
+        capture!("review_get_reviews_by_user", |client| client
+            .review()
+            .get_reviews_by_user(5123456, 1, 2)),
+        capture!("review_get_top_rated_reviews", |client| client
+            .review()
+            .get_top_rated_reviews(1, 2)),
+        // These post as the token's account; pass the saved review's ID to the delete
+        capture!("review_save_review", |client| client.review().save_review(
+            171018,
+            &"Dandadan opens with a bet about ghosts and aliens and never stops escalating. "
+                .repeat(30),
+            Some("Three episodes of pure momentum"),
+            Some(90),
+            Some(false)
+        )),
+        capture!("review_rate_review", |client| client
+            .review()
+            .rate_review(2000, "UP_VOTE")),
+        capture!("review_delete_review", |client| client
+            .review()
+            .delete_review(6201)),
+        capture!("recommendation_get_recommendation_by_id", |client| client
+            .recommendation()
+            .get_recommendation_by_id(1500)),
+        capture!("recommendation_get_recent_recommendations", |client| client
+            .recommendation()
+            .get_recent_recommendations(1, 2)),
+        capture!("recommendation_get_recommendations_for_media", |client| {
+            client
+                .recommendation()
+                .get_recommendations_for_media(154587, 1, 2)
+        }),
+        capture!("recommendation_get_top_rated_recommendations", |client| {
+            client.recommendation().get_top_rated_recommendations(1, 2)
+        }),
+        capture!("recommendation_get_my_recommendation", |client| client
+            .recommendation()
+            .get_my_recommendation(1, 205)),
+        // The ranking request, after the viewer's list requests
+        capture!("recommendation_get_personalized", |client| client
+            .recommendation()
+            .get_personalized(3)),
+        // These rate as the token's account; capture
+        // recommendation_get_my_recommendation before them
+        capture!("recommendation_save_recommendation", |client| client
+            .recommendation()
+            .save_recommendation(171018, 145064, Some(1))),
+        capture!("recommendation_rate_recommendation", |client| client
+            .recommendation()
+            .rate_recommendation(1500, 1)),
+        capture!("recommendation_recommend_or_update", |client| client
+            .recommendation()
+            .recommend_or_update((1, 205), RecommendationRating::RateUp)),
+        capture!("airing_get_upcoming_episodes", |client| client
+            .airing()
+            .get_upcoming_episodes(1, 1)),
+        capture!("airing_get_today_episodes", |client| client
+            .airing()
+            .get_today_episodes(1, 2)),
+        capture!("airing_get_recently_aired", |client| client
+            .airing()
+            .get_recently_aired(1, 2)),
+        capture!("airing_get_schedule_for_media", |client| client
+            .airing()
+            .get_schedule_for_media(171018, 1, 2)),
+        capture!("airing_get_schedule_by_id", |client| client
+            .airing()
+            .get_schedule_by_id(372188)),
+        capture!("airing_get_episodes_in_range", |client| client
+            .airing()
+            .get_episodes_in_range(1729382400, 1729987200, 1, 2)),
+        capture!("airing_get_next_episode", |client| client
+            .airing()
+            .get_next_episode(171018)),
+        capture!("notification_get_notifications", |client| client
+            .notification()
+            .get_notifications(1, 3)),
+        capture!("notification_get_unread_count", |client| client
+            .notification()
+            .get_unread_count()),
+        capture!("notification_get_notifications_by_type", |client| client
+            .notification()
+            .get_notifications_by_type("AIRING", 1, 2)),
+        capture!("notification_mark_notifications_as_read", |client| client
+            .notification()
+            .mark_notifications_as_read(vec![900005, 900003])),
+    ]
+}
+
+/// Converts a response to the fixture format, dropping headers that are not
+/// useful for replay and scrubbing the token.
+fn fixture(method: &str, response: &TransportResponse, token: Option<&str>) -> Value {
+    let mut headers = headers_of_interest(&response.headers);
+    if let Some(Ok(content_type)) = response.headers.get("content-type").map(|v| v.to_str()) {
+        headers.insert("content-type".to_string(), content_type.to_string());
+    }
+    let headers: serde_json::Map<String, Value> = headers
+        .into_iter()
+        .map(|(name, value)| (name, Value::String(value)))
+        .collect();
+
+    let body =
+        serde_json::from_slice(&response.body).unwrap_or_else(|_| Value::String(response.text()));
+    // Calls rustfmt split over several lines keep their trailing comma
+    let method = method
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(",)", ")");
+    let fixture = json!({
+        "method": method.trim_start_matches("client."),
+        "status": response.status,
+        "headers": headers,
+        "body": body,
+    });
+
+    match token {
+        Some(token) => {
+            let text = fixture.to_string().replace(token, "<redacted>");
+            serde_json::from_str(&text).expect("scrubbed fixture is valid JSON")
+        }
+        None => fixture,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+    let names: Vec<String> = std::env::args().skip(1).collect();
+    let token = std::env::var("ANILIST_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty());
+
+    let captures = captures();
+    if let Some(unknown) = names
+        .iter()
+        .find(|name| !captures.iter().any(|capture| capture.name == name.as_str()))
+    {
+        eprintln!("unknown fixture: {unknown}");
+        std::process::exit(2);
+    }
+
+    let recorder = Arc::new(RecordingTransport::default());
+    let mut client = match &token {
+        Some(token) => AniListClient::with_token(token.clone()),
+        None => AniListClient::new(),
+    };
+    client.set_transport(recorder.clone());
+    // Only gates the moderation calls client-side; AniList still checks the
+    // account's role
+    client.set_moderator_mode(true);
+
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut failed = false;
+    for capture in captures
+        .iter()
+        .filter(|capture| names.is_empty() || names.iter().any(|name| name == capture.name))
+    {
+        recorder.last.lock().unwrap().take();
+        let result = (capture.call)(client.clone()).await;
+        let Some(response) = recorder.last.lock().unwrap().take() else {
+            eprintln!("{:<42} no response: {result:?}", capture.name);
+            failed = true;
+            continue;
+        };
+        if let Err(error) = &result {
+            // Still written, so the change can be inspected in the diff
+            eprintln!("{:<42} call failed: {error}", capture.name);
+            failed = true;
+        }
+
+        let fixture = fixture(capture.method, &response, token.as_deref());
+        let path = dir.join(format!("{}.json", capture.name));
+        let text = serde_json::to_string_pretty(&fixture).unwrap() + "\n";
+        std::fs::write(&path, text).expect("failed to write fixture");
+        println!(
+            "{:<42} {} -> {}",
+            capture.name,
+            response.status,
+            path.display()
+        );
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
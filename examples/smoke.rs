@@ -0,0 +1,400 @@
+//! Smoke test against the live API.
+//!
+//! Calls one representative method on every endpoint and prints a pass/fail
+//! matrix, so schema drift shows up as a failing row (deserialization failures
+//! are reported separately) instead of as a bug report.
+//!
+//! ```text
+//! cargo run --example smoke -- [--token TOKEN] [--read-only] [--json] [--delay-ms MS]
+//! ```
+//!
+//! Without `--token` the `ANILIST_TOKEN` environment variable (or `.env`) is
+//! used; with no token at all the authenticated checks are skipped. Mutations
+//! only run with a token and without `--read-only`, and each one is applied
+//! twice so the account ends up unchanged. The process exits with status 1 if
+//! any check failed.
+
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::utils::{RetryConfig, rate_limit_delay, retry_with_backoff};
+use serde::Serialize;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+const USAGE: &str =
+    "usage: cargo run --example smoke -- [--token TOKEN] [--read-only] [--json] [--delay-ms MS]";
+
+struct Options {
+    token: Option<String>,
+    read_only: bool,
+    json: bool,
+    delay_ms: u64,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options {
+            token: None,
+            read_only: false,
+            json: false,
+            delay_ms: 1000,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--token" => {
+                    options.token = Some(args.next().ok_or("--token needs a value")?);
+                }
+                "--delay-ms" => {
+                    let value = args.next().ok_or("--delay-ms needs a value")?;
+                    options.delay_ms = value
+                        .parse()
+                        .map_err(|_| format!("invalid --delay-ms value: {value}"))?;
+                }
+                "--read-only" => options.read_only = true,
+                "--json" => options.json = true,
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                other => return Err(format!("unknown argument: {other}\n{USAGE}")),
+            }
+        }
+        if options.token.is_none() {
+            options.token = std::env::var("ANILIST_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty());
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckResult {
+    endpoint: &'static str,
+    method: &'static str,
+    status: Status,
+    /// Failure category, see [`failure_kind`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'static str>,
+    /// Error message for failures, reason for skipped checks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    elapsed_ms: u128,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    /// `endpoint.method` of every check whose response did not match the models
+    deserialization_failures: Vec<String>,
+    checks: Vec<CheckResult>,
+}
+
+/// Groups errors into the categories shown in the matrix.
+fn failure_kind(error: &AniListError) -> &'static str {
+    match error {
+        AniListError::Json(_) => "deserialize",
+        AniListError::Network(_) => "network",
+        AniListError::RateLimit { .. }
+        | AniListError::RateLimitSimple
+        | AniListError::BurstLimit => "rate_limit",
+        AniListError::AuthenticationRequired | AniListError::AccessDenied => "auth",
+        AniListError::GraphQL { .. } | AniListError::BadRequest { .. } => "graphql",
+        AniListError::ServerError { .. } => "server",
+        AniListError::NotFound => "not_found",
+        _ => "other",
+    }
+}
+
+/// Runs checks one after another, pacing and retrying them.
+struct Runner {
+    retry: RetryConfig,
+    delay_ms: u64,
+    results: Vec<CheckResult>,
+}
+
+impl Runner {
+    /// Runs `operation` with retries on rate limiting and records the outcome.
+    ///
+    /// Returns the value on success so later checks can build on it.
+    async fn check<T, F, Fut>(
+        &mut self,
+        endpoint: &'static str,
+        method: &'static str,
+        operation: F,
+    ) -> Option<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AniListError>>,
+    {
+        if !self.results.is_empty() {
+            rate_limit_delay(self.delay_ms).await;
+        }
+
+        let started = Instant::now();
+        let outcome = retry_with_backoff(operation, self.retry.clone()).await;
+        let elapsed_ms = started.elapsed().as_millis();
+
+        let (status, kind, detail, value) = match outcome {
+            Ok(value) => (Status::Pass, None, None, Some(value)),
+            Err(error) => (
+                Status::Fail,
+                Some(failure_kind(&error)),
+                Some(error.to_string()),
+                None,
+            ),
+        };
+        self.results.push(CheckResult {
+            endpoint,
+            method,
+            status,
+            kind,
+            detail,
+            elapsed_ms,
+        });
+        value
+    }
+
+    fn skip(&mut self, endpoint: &'static str, method: &'static str, reason: &str) {
+        self.results.push(CheckResult {
+            endpoint,
+            method,
+            status: Status::Skip,
+            kind: None,
+            detail: Some(reason.to_string()),
+            elapsed_ms: 0,
+        });
+    }
+
+    fn report(self) -> Report {
+        let count = |status| self.results.iter().filter(|r| r.status == status).count();
+        Report {
+            passed: count(Status::Pass),
+            failed: count(Status::Fail),
+            skipped: count(Status::Skip),
+            deserialization_failures: self
+                .results
+                .iter()
+                .filter(|r| r.kind == Some("deserialize"))
+                .map(|r| format!("{}.{}", r.endpoint, r.method))
+                .collect(),
+            checks: self.results,
+        }
+    }
+}
+
+async fn run(client: &AniListClient, runner: &mut Runner, options: &Options) {
+    runner
+        .check("anime", "get_by_id", || async move {
+            client.anime().get_by_id(1).await
+        })
+        .await;
+    runner
+        .check("anime", "get_popular", || async move {
+            client.anime().get_popular(1, 5).await
+        })
+        .await;
+    runner
+        .check("manga", "get_by_id", || async move {
+            client.manga().get_by_id(30002).await
+        })
+        .await;
+    runner
+        .check("character", "get_by_id", || async move {
+            client.character().get_by_id(1).await
+        })
+        .await;
+    runner
+        .check("staff", "get_popular", || async move {
+            client.staff().get_popular(1, 5).await
+        })
+        .await;
+    runner
+        .check("studio", "get_popular", || async move {
+            client.studio().get_popular(1, 5).await
+        })
+        .await;
+    runner
+        .check("user", "get_by_id", || async move {
+            client.user().get_by_id(1).await
+        })
+        .await;
+    let threads = runner
+        .check("forum", "get_recent_threads", || async move {
+            client.forum().get_recent_threads(1, 5).await
+        })
+        .await;
+    let activities = runner
+        .check("activity", "get_recent_activities", || async move {
+            client.activity().get_recent_activities(1, 5).await
+        })
+        .await;
+    runner
+        .check("review", "get_recent_reviews", || async move {
+            client.review().get_recent_reviews(1, 5).await
+        })
+        .await;
+    runner
+        .check(
+            "recommendation",
+            "get_recent_recommendations",
+            || async move {
+                client
+                    .recommendation()
+                    .get_recent_recommendations(1, 5)
+                    .await
+            },
+        )
+        .await;
+    runner
+        .check("airing", "get_upcoming_episodes", || async move {
+            client.airing().get_upcoming_episodes(1, 5).await
+        })
+        .await;
+
+    if !client.has_token() {
+        runner.skip("user", "get_current_user", "no token");
+        runner.skip("notification", "get_notifications", "no token");
+    } else {
+        runner
+            .check("user", "get_current_user", || async move {
+                client.user().get_current_user().await
+            })
+            .await;
+        runner
+            .check("notification", "get_notifications", || async move {
+                client.notification().get_notifications(1, 5).await
+            })
+            .await;
+    }
+
+    let mutation_skip = if !client.has_token() {
+        Some("no token")
+    } else if options.read_only {
+        Some("read-only")
+    } else {
+        None
+    };
+    let activity_id = activities.and_then(|a| a.first().map(|a| a.id));
+    let thread_id = threads.and_then(|t| t.first().map(|t| t.id));
+
+    match (mutation_skip, activity_id) {
+        (Some(reason), _) => runner.skip("activity", "toggle_activity_like", reason),
+        (None, None) => runner.skip("activity", "toggle_activity_like", "no activity to like"),
+        (None, Some(id)) => {
+            // Toggling twice leaves the like as it was
+            runner
+                .check("activity", "toggle_activity_like", || async move {
+                    client.activity().toggle_activity_like(id).await?;
+                    client.activity().toggle_activity_like(id).await
+                })
+                .await;
+        }
+    }
+    match (mutation_skip, thread_id) {
+        (Some(reason), _) => runner.skip("forum", "toggle_thread_like", reason),
+        (None, None) => runner.skip("forum", "toggle_thread_like", "no thread to like"),
+        (None, Some(id)) => {
+            runner
+                .check("forum", "toggle_thread_like", || async move {
+                    client.forum().toggle_thread_like(id).await?;
+                    client.forum().toggle_thread_like(id).await
+                })
+                .await;
+        }
+    }
+}
+
+fn print_matrix(report: &Report) {
+    let endpoint_width = report
+        .checks
+        .iter()
+        .map(|c| c.endpoint.len())
+        .max()
+        .unwrap_or(0);
+    let method_width = report
+        .checks
+        .iter()
+        .map(|c| c.method.len())
+        .max()
+        .unwrap_or(0);
+
+    for check in &report.checks {
+        let status = match check.status {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Skip => "SKIP",
+        };
+        let mut line = format!(
+            "{status}  {:endpoint_width$}  {:method_width$}  {:>6}ms",
+            check.endpoint, check.method, check.elapsed_ms
+        );
+        if let Some(kind) = check.kind {
+            line.push_str(&format!("  [{kind}]"));
+        }
+        if let Some(detail) = &check.detail {
+            line.push_str(&format!("  {detail}"));
+        }
+        println!("{line}");
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} skipped",
+        report.passed, report.failed, report.skipped
+    );
+    if !report.deserialization_failures.is_empty() {
+        println!("Deserialization failures (possible API drift):");
+        for path in &report.deserialization_failures {
+            println!("  - {path}");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
+
+    let client = match &options.token {
+        Some(token) => AniListClient::with_token(token.clone()),
+        None => AniListClient::new(),
+    };
+    let mut runner = Runner {
+        retry: RetryConfig::builder()
+            .max_retries(3)
+            .base_delay(Duration::from_secs(2))
+            .max_delay(Duration::from_secs(60))
+            .build(),
+        delay_ms: options.delay_ms,
+        results: Vec::new(),
+    };
+
+    run(&client, &mut runner, &options).await;
+    let report = runner.report();
+
+    if options.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("report serializes")
+        );
+    } else {
+        print_matrix(&report);
+    }
+
+    if report.failed > 0 {
+        std::process::exit(1);
+    }
+}
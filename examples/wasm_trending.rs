@@ -0,0 +1,40 @@
+//! Fetches trending anime from the browser.
+//!
+//! ```text
+//! cargo build --example wasm_trending --target wasm32-unknown-unknown --features wasm
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/debug/examples/wasm_trending.wasm
+//! ```
+//!
+//! Load `pkg/wasm_trending.js` from a page (`<script type="module">import init
+//! from "./pkg/wasm_trending.js"; init();</script>`); the titles are logged to
+//! the browser console. AniList allows cross-origin requests, so no proxy is
+//! needed.
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use anilist_sdk::client::AniListClient;
+
+    wasm_bindgen_futures::spawn_local(async {
+        let client = AniListClient::new();
+        let line = match client.anime().get_trending(1, 10).await {
+            Ok(trending) => trending
+                .iter()
+                .map(|anime| {
+                    let title = anime.title.romaji.as_deref().unwrap_or("untitled");
+                    format!("{title} ({})", anime.id)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(error) => format!("failed to fetch trending anime: {error}"),
+        };
+        web_sys::console::log_1(&line.into());
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    eprintln!(
+        "build this example for wasm32-unknown-unknown with `--features wasm`; see its source"
+    );
+}
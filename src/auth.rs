@@ -0,0 +1,195 @@
+//! # Token Refresh
+//!
+//! AniList access tokens are long-lived but do expire. A client given a
+//! callback with [`AniListClient::on_token_expired`] asks it for a new token
+//! when a request is rejected with a 401, or before sending when the token's
+//! known expiry (see [`AniListClient::set_token_expiry`]) has passed, and then
+//! retries the request once with the new token.
+//!
+//! The callback and the tokens it returns are shared by every clone of the
+//! client, including the endpoint structs, so one refresh serves them all.
+//! Concurrent requests rejected at the same time wait for a single refresh.
+//!
+//! [`AniListClient::on_token_expired`]: crate::AniListClient::on_token_expired
+//! [`AniListClient::set_token_expiry`]: crate::AniListClient::set_token_expiry
+
+use crate::error::AniListError;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+/// A token returned by a refresh callback, with its expiry when known.
+///
+/// Callbacks may return a plain `String` instead, for tokens without a known
+/// expiry.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::auth::RefreshedToken;
+/// use chrono::{Duration, Utc};
+///
+/// let token = RefreshedToken::new("new-token").expires_at(Utc::now() + Duration::days(365));
+/// assert!(token.expires_at.is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshedToken {
+    /// The new access token
+    pub token: String,
+    /// When the new token stops being accepted, if known
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshedToken {
+    /// A token without a known expiry.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            expires_at: None,
+        }
+    }
+
+    /// Sets when the token expires.
+    pub fn expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+}
+
+impl From<String> for RefreshedToken {
+    fn from(token: String) -> Self {
+        Self::new(token)
+    }
+}
+
+impl From<&str> for RefreshedToken {
+    fn from(token: &str) -> Self {
+        Self::new(token)
+    }
+}
+
+/// An access token that never shows up in `Debug` or `Display` output.
+///
+/// Both print `***redacted***`; read the token itself with
+/// [`SecretToken::expose`]. With the `zeroize` feature, the token's buffer is
+/// overwritten when it is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::auth::SecretToken;
+///
+/// let token = SecretToken::new("abc123");
+/// assert_eq!(format!("{token:?}"), "\"***redacted***\"");
+/// assert_eq!(token.expose(), "abc123");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SecretToken(String);
+
+/// What [`SecretToken`] prints instead of the token.
+const REDACTED: &str = "***redacted***";
+
+impl SecretToken {
+    /// Wraps `token`.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// The token itself, e.g. for an `Authorization` header.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for SecretToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(REDACTED, f)
+    }
+}
+
+impl std::fmt::Display for SecretToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl From<String> for SecretToken {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretToken {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+/// The future returned by a boxed refresh callback.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type RefreshFuture =
+    Pin<Box<dyn Future<Output = Result<RefreshedToken, AniListError>> + Send>>;
+
+/// The future returned by a boxed refresh callback.
+#[cfg(target_arch = "wasm32")]
+pub(crate) type RefreshFuture = Pin<Box<dyn Future<Output = Result<RefreshedToken, AniListError>>>>;
+
+type Callback = Box<dyn Fn() -> RefreshFuture + Send + Sync>;
+
+/// A refreshed token and its expiry, as kept by [`TokenRefresher`].
+pub(crate) type Replacement = (SecretToken, Option<DateTime<Utc>>);
+
+/// The refresh callback and the tokens it has handed out, shared by clones.
+pub(crate) struct TokenRefresher {
+    callback: Callback,
+    /// Each refreshed token, keyed by the token it replaced
+    replacements: Mutex<HashMap<SecretToken, Replacement>>,
+    /// Held while the callback runs, so concurrent refreshes call it once
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+impl TokenRefresher {
+    pub(crate) fn new(callback: Callback) -> Self {
+        Self {
+            callback,
+            replacements: Mutex::new(HashMap::new()),
+            refreshing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// The latest replacement for `token`, if it has been refreshed.
+    pub(crate) fn replacement(&self, token: &SecretToken) -> Option<Replacement> {
+        let replacements = self.replacements.lock().unwrap_or_else(|e| e.into_inner());
+        let mut latest = replacements.get(token)?;
+        // Bounded in case a callback handed back a token it replaced before
+        for _ in 0..replacements.len() {
+            match replacements.get(&latest.0) {
+                Some(next) => latest = next,
+                None => break,
+            }
+        }
+        Some(latest.clone())
+    }
+
+    /// Replaces `stale`, calling the callback unless another request already
+    /// did.
+    pub(crate) async fn refresh(&self, stale: &SecretToken) -> Result<Replacement, AniListError> {
+        let _refreshing = self.refreshing.lock().await;
+        if let Some(fresh) = self.replacement(stale) {
+            return Ok(fresh);
+        }
+        let fresh = (self.callback)().await?;
+        let fresh = (SecretToken::new(fresh.token), fresh.expires_at);
+        if &fresh.0 == stale {
+            return Ok(fresh);
+        }
+        self.replacements
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(stale.clone(), fresh.clone());
+        Ok(fresh)
+    }
+}
@@ -0,0 +1,621 @@
+//! # Batching
+//!
+//! Combines several lookups into a single GraphQL request using aliases, so
+//! hydrating a list of ten anime costs one request of the rate limit budget
+//! instead of ten.
+//!
+//! Each lookup's document is merged into one query: its root field gets a
+//! generated alias (`b0`, `b1`, ...) and its variables are prefixed with that
+//! alias, so two lookups that both use `$id` do not collide. Identical typed
+//! lookups are only sent once.
+//!
+//! A lookup that fails, e.g. for an ID that does not exist, does not fail the
+//! batch: AniList returns `null` for that alias together with an error whose
+//! `path` names it, and [`BatchResponse`] reports that error for that item
+//! only. Errors that cannot be attributed to an item, such as an invalid
+//! document, fail the whole batch.
+//!
+//! AniList limits how complex a single query may be, so keep batches to a
+//! few dozen lookups.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::AniListClient;
+//!
+//! let client = AniListClient::new();
+//! let response = client
+//!     .batch()
+//!     .anime_by_id(1)
+//!     .anime_by_id(20)
+//!     .character_by_id(417)
+//!     .send()
+//!     .await?;
+//!
+//! let bebop = response.anime(1).unwrap()?;
+//! match response.character(417).unwrap() {
+//!     Ok(character) => println!("{:?}", character.name),
+//!     Err(error) => println!("character lookup failed: {error}"),
+//! }
+//! ```
+
+use crate::client::AniListClient;
+use crate::error::AniListError;
+use crate::models::social::Studio;
+use crate::models::{Anime, Character, Manga, Staff, User};
+use crate::queries;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+/// A typed lookup by ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Lookup {
+    Anime(i32),
+    Manga(i32),
+    Character(i32),
+    Staff(i32),
+    Studio(i32),
+    User(i32),
+}
+
+impl Lookup {
+    fn document(self) -> &'static str {
+        match self {
+            Lookup::Anime(_) => queries::anime::GET_BY_ID,
+            Lookup::Manga(_) => queries::manga::GET_BY_ID,
+            Lookup::Character(_) => queries::character::GET_BY_ID,
+            Lookup::Staff(_) => queries::staff::GET_BY_ID,
+            Lookup::Studio(_) => queries::studio::GET_BY_ID,
+            Lookup::User(_) => queries::user::GET_BY_ID,
+        }
+    }
+
+    fn id(self) -> i32 {
+        match self {
+            Lookup::Anime(id)
+            | Lookup::Manga(id)
+            | Lookup::Character(id)
+            | Lookup::Staff(id)
+            | Lookup::Studio(id)
+            | Lookup::User(id) => id,
+        }
+    }
+}
+
+/// How an item of a batch is looked up in the response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Lookup(Lookup),
+    Raw(String),
+}
+
+/// One document of a batch and its variables.
+#[derive(Debug, Clone)]
+struct Part {
+    key: Key,
+    document: String,
+    variables: Map<String, Value>,
+}
+
+/// Several lookups to send as one request; see the [module docs](self).
+///
+/// Created with [`AniListClient::batch`].
+#[derive(Clone)]
+pub struct Batch {
+    client: AniListClient,
+    parts: Vec<Part>,
+    /// The first problem found while adding parts, reported by `send`
+    invalid: Option<String>,
+}
+
+impl Batch {
+    pub(crate) fn new(client: AniListClient) -> Self {
+        Self {
+            client,
+            parts: Vec::new(),
+            invalid: None,
+        }
+    }
+
+    fn lookup(mut self, lookup: Lookup) -> Self {
+        let key = Key::Lookup(lookup);
+        if !self.parts.iter().any(|part| part.key == key) {
+            let mut variables = Map::new();
+            variables.insert("id".to_string(), json!(lookup.id()));
+            self.parts.push(Part {
+                key,
+                document: lookup.document().to_string(),
+                variables,
+            });
+        }
+        self
+    }
+
+    /// Adds an anime lookup, read back with [`BatchResponse::anime`].
+    pub fn anime_by_id(self, id: i32) -> Self {
+        self.lookup(Lookup::Anime(id))
+    }
+
+    /// Adds a manga lookup, read back with [`BatchResponse::manga`].
+    pub fn manga_by_id(self, id: i32) -> Self {
+        self.lookup(Lookup::Manga(id))
+    }
+
+    /// Adds a character lookup, read back with [`BatchResponse::character`].
+    pub fn character_by_id(self, id: i32) -> Self {
+        self.lookup(Lookup::Character(id))
+    }
+
+    /// Adds a staff lookup, read back with [`BatchResponse::staff`].
+    pub fn staff_by_id(self, id: i32) -> Self {
+        self.lookup(Lookup::Staff(id))
+    }
+
+    /// Adds a studio lookup, read back with [`BatchResponse::studio`].
+    pub fn studio_by_id(self, id: i32) -> Self {
+        self.lookup(Lookup::Studio(id))
+    }
+
+    /// Adds a user lookup, read back with [`BatchResponse::user`].
+    pub fn user_by_id(self, id: i32) -> Self {
+        self.lookup(Lookup::User(id))
+    }
+
+    /// Adds any query with a single root field, read back with
+    /// [`BatchResponse::get`] under `key`.
+    ///
+    /// `variables` must serialize to a JSON object, or be `()`. Documents may
+    /// declare variables and fragments; both are merged with the rest of the
+    /// batch. Keys must be unique within a batch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let response = client
+    ///     .batch()
+    ///     .query(
+    ///         "rankings",
+    ///         "query ($id: Int) { Media(id: $id) { rankings { rank context } } }",
+    ///         json!({ "id": 1 }),
+    ///     )
+    ///     .send()
+    ///     .await?;
+    /// let media: serde_json::Value = response.get("rankings").unwrap()?;
+    /// ```
+    pub fn query(
+        mut self,
+        key: impl Into<String>,
+        document: &str,
+        variables: impl Serialize,
+    ) -> Self {
+        let key = key.into();
+        let variables = match serde_json::to_value(variables) {
+            Ok(Value::Null) => Map::new(),
+            Ok(Value::Object(map)) => map,
+            Ok(other) => {
+                self.invalidate(format!(
+                    "variables for batch key {key:?} must serialize to a JSON object, got {other}"
+                ));
+                return self;
+            }
+            Err(error) => {
+                self.invalidate(format!(
+                    "variables for batch key {key:?} failed to serialize: {error}"
+                ));
+                return self;
+            }
+        };
+        if self
+            .parts
+            .iter()
+            .any(|part| part.key == Key::Raw(key.clone()))
+        {
+            self.invalidate(format!("duplicate batch key {key:?}"));
+            return self;
+        }
+        let key = Key::Raw(key);
+        self.parts.push(Part {
+            key,
+            document: document.to_string(),
+            variables,
+        });
+        self
+    }
+
+    fn invalidate(&mut self, message: String) {
+        self.invalid.get_or_insert(message);
+    }
+
+    /// Number of distinct items in the batch.
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Returns `true` if nothing has been added.
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Sends every item as one request.
+    ///
+    /// An empty batch returns an empty response without sending anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AniListError::BadRequest`] if an item could not be added
+    /// (non-object variables, a duplicate key) or a document is not a query
+    /// with a single root field. Errors of the request as a whole, such as a
+    /// network failure or a rate limit, are returned as from any other call;
+    /// errors of single items are reported by [`BatchResponse`].
+    pub async fn send(self) -> Result<BatchResponse, AniListError> {
+        if let Some(message) = self.invalid {
+            return Err(AniListError::BadRequest { message });
+        }
+        if self.parts.is_empty() {
+            return Ok(BatchResponse::default());
+        }
+
+        let (document, variables) = merge(&self.parts)?;
+        let response = self
+            .client
+            .send_query(&document, Some(variables), true)
+            .await?;
+        let keys = self.parts.into_iter().map(|part| part.key).collect();
+        Ok(BatchResponse::from_value(keys, response.value))
+    }
+}
+
+impl std::fmt::Debug for Batch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Batch")
+            .field("parts", &self.parts)
+            .field("invalid", &self.invalid)
+            .finish()
+    }
+}
+
+/// What came back for one item.
+#[derive(Debug, Clone)]
+enum Outcome {
+    Data(Value),
+    /// The GraphQL errors attributed to the item
+    Errors(Value),
+    /// `null` without any error
+    Missing,
+}
+
+/// The results of a [`Batch`], one per item.
+///
+/// Each getter returns `None` if the item was not part of the batch, and
+/// otherwise the item's own result: a failed lookup is reported as the same
+/// [`AniListError`] a single call would have returned (e.g.
+/// [`AniListError::NotFound`] or [`AniListError::Private`]).
+#[derive(Debug, Clone, Default)]
+pub struct BatchResponse {
+    items: HashMap<Key, Outcome>,
+}
+
+impl BatchResponse {
+    /// Splits a response into per-item outcomes; item `i` has alias `b{i}`.
+    fn from_value(keys: Vec<Key>, mut value: Value) -> Self {
+        let mut data = match value.get_mut("data").map(Value::take) {
+            Some(Value::Object(data)) => data,
+            _ => Map::new(),
+        };
+
+        let mut attributed: HashMap<String, Vec<Value>> = HashMap::new();
+        let mut unattributed = Vec::new();
+        if let Some(Value::Array(errors)) = value.get_mut("errors").map(Value::take) {
+            for error in errors {
+                match error["path"][0].as_str() {
+                    Some(alias) => attributed.entry(alias.to_string()).or_default().push(error),
+                    None => unattributed.push(error),
+                }
+            }
+        }
+
+        let items = keys
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| {
+                let alias = alias(index);
+                let outcome = match (data.remove(&alias), attributed.remove(&alias)) {
+                    (_, Some(errors)) => Outcome::Errors(Value::Array(errors)),
+                    (Some(Value::Null) | None, None) if !unattributed.is_empty() => {
+                        Outcome::Errors(Value::Array(unattributed.clone()))
+                    }
+                    (Some(Value::Null) | None, None) => Outcome::Missing,
+                    (Some(value), None) => Outcome::Data(value),
+                };
+                (key, outcome)
+            })
+            .collect();
+        Self { items }
+    }
+
+    fn item<T: DeserializeOwned>(&self, key: &Key) -> Option<Result<T, AniListError>> {
+        self.items.get(key).map(|outcome| match outcome {
+            Outcome::Data(value) => T::deserialize(value).map_err(AniListError::from),
+            Outcome::Errors(errors) => Err(AniListError::from_graphql_errors(errors)),
+            Outcome::Missing => Err(AniListError::NotFound),
+        })
+    }
+
+    /// The anime added with [`Batch::anime_by_id`].
+    pub fn anime(&self, id: i32) -> Option<Result<Anime, AniListError>> {
+        self.item(&Key::Lookup(Lookup::Anime(id)))
+    }
+
+    /// The manga added with [`Batch::manga_by_id`].
+    pub fn manga(&self, id: i32) -> Option<Result<Manga, AniListError>> {
+        self.item(&Key::Lookup(Lookup::Manga(id)))
+    }
+
+    /// The character added with [`Batch::character_by_id`].
+    pub fn character(&self, id: i32) -> Option<Result<Character, AniListError>> {
+        self.item(&Key::Lookup(Lookup::Character(id)))
+    }
+
+    /// The staff member added with [`Batch::staff_by_id`].
+    pub fn staff(&self, id: i32) -> Option<Result<Staff, AniListError>> {
+        self.item(&Key::Lookup(Lookup::Staff(id)))
+    }
+
+    /// The studio added with [`Batch::studio_by_id`].
+    pub fn studio(&self, id: i32) -> Option<Result<Studio, AniListError>> {
+        self.item(&Key::Lookup(Lookup::Studio(id)))
+    }
+
+    /// The user added with [`Batch::user_by_id`].
+    pub fn user(&self, id: i32) -> Option<Result<User, AniListError>> {
+        self.item(&Key::Lookup(Lookup::User(id)))
+    }
+
+    /// The root field of the query added with [`Batch::query`] under `key`.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<Result<T, AniListError>> {
+        self.item(&Key::Raw(key.to_string()))
+    }
+
+    /// Number of items in the response.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the batch was empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Number of items that failed.
+    pub fn failed(&self) -> usize {
+        self.items
+            .values()
+            .filter(|outcome| !matches!(outcome, Outcome::Data(_)))
+            .count()
+    }
+}
+
+impl AniListClient {
+    /// Starts a [`Batch`] of lookups sent as a single request.
+    ///
+    /// See [`crate::batch`].
+    pub fn batch(&self) -> Batch {
+        Batch::new(self.clone())
+    }
+}
+
+fn alias(index: usize) -> String {
+    format!("b{index}")
+}
+
+fn invalid_document(message: &str) -> AniListError {
+    AniListError::BadRequest {
+        message: format!("cannot batch document: {message}"),
+    }
+}
+
+/// Merges the parts into one document with aliased root fields, and the
+/// variables to send with it.
+fn merge(parts: &[Part]) -> Result<(String, HashMap<String, Value>), AniListError> {
+    let mut definitions = Vec::new();
+    let mut fields = Vec::new();
+    let mut fragments: Vec<&str> = Vec::new();
+    let mut variables = HashMap::new();
+
+    for (index, part) in parts.iter().enumerate() {
+        let alias = alias(index);
+        let operation = Operation::parse(&part.document)?;
+        let prefix = format!("{alias}_");
+
+        definitions.extend(
+            operation
+                .definitions
+                .iter()
+                .map(|definition| prefix_variables(definition, &prefix)),
+        );
+        fields.push(format!(
+            "  {alias}: {}",
+            prefix_variables(operation.field, &prefix)
+        ));
+        if !operation.fragments.is_empty() && !fragments.contains(&operation.fragments) {
+            fragments.push(operation.fragments);
+        }
+        for (name, value) in &part.variables {
+            variables.insert(format!("{prefix}{name}"), value.clone());
+        }
+    }
+
+    let mut document = String::from("query");
+    if !definitions.is_empty() {
+        document.push_str(&format!(" ({})", definitions.join(", ")));
+    }
+    document.push_str(" {\n");
+    document.push_str(&fields.join("\n"));
+    document.push_str("\n}\n");
+    for fragment in fragments {
+        document.push_str(fragment);
+        document.push('\n');
+    }
+    Ok((document, variables))
+}
+
+/// The pieces of a query document with a single root field.
+#[derive(Debug)]
+struct Operation<'a> {
+    /// Variable definitions such as `$id: Int`
+    definitions: Vec<String>,
+    /// The root field without any alias, arguments and selection included
+    field: &'a str,
+    /// Fragment definitions following the operation
+    fragments: &'a str,
+}
+
+impl<'a> Operation<'a> {
+    fn parse(document: &'a str) -> Result<Self, AniListError> {
+        let open = find_outside_strings(document, 0, |c| c == '{')
+            .ok_or_else(|| invalid_document("no selection set"))?;
+        let header = document[..open].trim();
+        if !(header.is_empty() || keyword(header) == "query") {
+            return Err(invalid_document("only queries can be batched"));
+        }
+
+        let mut definitions = Vec::new();
+        if let Some(start) = header.find('(') {
+            let end = matching(header, start).ok_or_else(|| invalid_document("unclosed `(`"))?;
+            // Every definition starts with `$`, and defaults cannot contain one
+            definitions.extend(
+                header[start + 1..end]
+                    .split('$')
+                    .map(|definition| definition.trim().trim_end_matches(',').trim_end())
+                    .filter(|definition| !definition.is_empty())
+                    .map(|definition| format!("${definition}")),
+            );
+        }
+
+        let close = matching(document, open).ok_or_else(|| invalid_document("unclosed `{`"))?;
+        let selection = document[open + 1..close].trim();
+        let fragments = document[close + 1..].trim();
+
+        // The root field: `[alias:] name [(arguments)] [{ selection }]`
+        let name_end = selection
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(selection.len());
+        if name_end == 0 {
+            return Err(invalid_document("expected a root field"));
+        }
+        let mut field = selection;
+        let after_name = selection[name_end..].trim_start();
+        if let Some(rest) = after_name.strip_prefix(':') {
+            // An alias of the caller's own is replaced by the batch alias
+            field = rest.trim_start();
+        }
+        let field_name_end = field
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(field.len());
+        let mut end = field_name_end;
+        let rest = &field[end..];
+        if rest.trim_start().starts_with('(') {
+            let start = end + (rest.len() - rest.trim_start().len());
+            end = matching(field, start).ok_or_else(|| invalid_document("unclosed `(`"))? + 1;
+        }
+        let rest = &field[end..];
+        if rest.trim_start().starts_with('{') {
+            let start = end + (rest.len() - rest.trim_start().len());
+            end = matching(field, start).ok_or_else(|| invalid_document("unclosed `{`"))? + 1;
+        }
+        if !field[end..].trim().is_empty() {
+            return Err(invalid_document(
+                "only documents with a single root field can be batched",
+            ));
+        }
+
+        Ok(Self {
+            definitions,
+            field: &field[..end],
+            fragments,
+        })
+    }
+}
+
+/// The leading keyword of an operation header, e.g. `query` for
+/// `query Name($id: Int)`.
+fn keyword(header: &str) -> &str {
+    let end = header
+        .find(|c: char| !c.is_alphanumeric())
+        .unwrap_or(header.len());
+    &header[..end]
+}
+
+/// Finds the first character at or after `from` matching `predicate`,
+/// skipping string literals and comments.
+fn find_outside_strings(
+    text: &str,
+    from: usize,
+    predicate: impl Fn(char) -> bool,
+) -> Option<usize> {
+    let mut chars = text[from..].char_indices().map(|(i, c)| (i + from, c));
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => {
+                let mut escaped = false;
+                for (_, c) in chars.by_ref() {
+                    match c {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => break,
+                        _ => escaped = false,
+                    }
+                }
+            }
+            '#' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if predicate(c) => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The index of the bracket closing the one at `open`.
+fn matching(text: &str, open: usize) -> Option<usize> {
+    let (opening, closing) = match text[open..].chars().next()? {
+        '(' => ('(', ')'),
+        '{' => ('{', '}'),
+        '[' => ('[', ']'),
+        _ => return None,
+    };
+    let mut depth = 0usize;
+    let mut from = open;
+    loop {
+        let index = find_outside_strings(text, from, |c| c == opening || c == closing)?;
+        if text[index..].starts_with(opening) {
+            depth += 1;
+        } else {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+        from = index + 1;
+    }
+}
+
+/// Prefixes every variable reference outside string literals, turning `$id`
+/// into `$b0_id`.
+fn prefix_variables(text: &str, prefix: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut from = 0;
+    while let Some(index) = find_outside_strings(text, from, |c| c == '$') {
+        output.push_str(&text[from..=index]);
+        output.push_str(prefix);
+        from = index + 1;
+    }
+    output.push_str(&text[from..]);
+    output
+}
@@ -0,0 +1,248 @@
+//! # Response Cache
+//!
+//! A small in-memory cache with a fixed time-to-live, used to avoid repeating
+//! identical requests in quick succession (e.g. typeahead autocomplete, where
+//! users often retype the same prefix).
+//!
+//! Clients built with [`AniListClientBuilder::cache`] also keep one for
+//! query responses; see [`CacheConfig`], and can share identical requests
+//! that are still in flight; see
+//! [`AniListClientBuilder::coalesce_requests`].
+//!
+//! [`AniListClientBuilder::cache`]: crate::client::AniListClientBuilder::cache
+//! [`AniListClientBuilder::coalesce_requests`]: crate::client::AniListClientBuilder::coalesce_requests
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+use web_time::Instant;
+
+/// Settings of the opt-in response cache.
+///
+/// Successful query responses are kept for `ttl`, keyed on the document, its
+/// variables and the token, and shared by all clones of the client. Mutations
+/// and errors are never cached.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::AniListClient;
+/// use anilist_sdk::cache::CacheConfig;
+/// use std::time::Duration;
+///
+/// let client = AniListClient::builder()
+///     .cache(CacheConfig {
+///         ttl: Duration::from_secs(300),
+///         max_entries: 1000,
+///     })
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// How long a response is reused
+    pub ttl: Duration,
+    /// Responses kept at most; the oldest is dropped to make room
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    /// Five minutes, 1000 entries.
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(300),
+            max_entries: 1000,
+        }
+    }
+}
+
+/// A thread-safe map whose entries expire after a fixed duration.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::cache::TtlCache;
+/// use std::time::Duration;
+///
+/// let cache = TtlCache::new(Duration::from_secs(30));
+/// cache.insert("naruto".to_string(), vec![20]);
+/// assert_eq!(cache.get(&"naruto".to_string()), Some(vec![20]));
+/// ```
+#[derive(Debug)]
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    max_entries: Option<usize>,
+    entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    /// Creates an empty cache whose entries live for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            max_entries: None,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates an empty cache whose entries live for `ttl`, holding at most
+    /// `max_entries` of them.
+    pub fn with_max_entries(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            max_entries: Some(max_entries),
+            ..Self::new(ttl)
+        }
+    }
+
+    /// Returns how long entries stay valid.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns a clone of the cached value if it exists and has not expired.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.get_at(key, Instant::now())
+    }
+
+    /// Stores a value, replacing any previous entry for the key.
+    pub fn insert(&self, key: K, value: V) {
+        self.insert_at(key, value, Instant::now());
+    }
+
+    /// Like [`TtlCache::get`], evaluated at the given instant.
+    pub fn get_at(&self, key: &K, now: Instant) -> Option<V> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|(stored_at, _)| now.saturating_duration_since(*stored_at) < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Like [`TtlCache::insert`], stored at the given instant.
+    ///
+    /// Expired entries are purged on every insert so the cache cannot grow
+    /// without bound. If the cache is still full, the oldest entry is dropped.
+    pub fn insert_at(&self, key: K, value: V, now: Instant) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (stored_at, _)| now.saturating_duration_since(*stored_at) < self.ttl);
+        if let Some(max_entries) = self.max_entries {
+            while entries.len() >= max_entries.max(1) && !entries.contains_key(&key) {
+                let Some(oldest) = entries.values().map(|(stored_at, _)| *stored_at).min() else {
+                    break;
+                };
+                let mut evicted = false;
+                entries.retain(|_, (stored_at, _)| {
+                    let evict = !evicted && *stored_at == oldest;
+                    evicted |= evict;
+                    !evict
+                });
+            }
+        }
+        entries.insert(key, (now, value));
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Returns the number of stored entries, including expired ones not yet purged.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns `true` if no entries are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Requests currently in flight, so identical ones can wait for the first
+/// instead of being sent again.
+pub(crate) struct InFlight<K, V> {
+    requests: Mutex<HashMap<K, watch::Receiver<Option<V>>>>,
+}
+
+/// Whether a caller sends the request itself or waits for another's.
+pub(crate) enum Joined<'a, K: Eq + Hash, V> {
+    /// No identical request is in flight; send it and [`Leader::finish`].
+    Leader(Leader<'a, K, V>),
+    /// An identical request is in flight; wait for it with [`Follower::wait`].
+    Follower(Follower<V>),
+}
+
+/// The caller sending a request others may be waiting for.
+///
+/// Dropping it without calling [`Leader::finish`], e.g. because the request
+/// was cancelled or panicked, releases the waiters to send their own.
+pub(crate) struct Leader<'a, K: Eq + Hash, V> {
+    in_flight: &'a InFlight<K, V>,
+    key: K,
+    sender: watch::Sender<Option<V>>,
+}
+
+/// A caller waiting for an identical request sent by a [`Leader`].
+pub(crate) struct Follower<V> {
+    receiver: watch::Receiver<Option<V>>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> InFlight<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Joins the request for `key`, becoming its leader if none is in flight.
+    pub(crate) fn join(&self, key: K) -> Joined<'_, K, V> {
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(receiver) = requests.get(&key) {
+            return Joined::Follower(Follower {
+                receiver: receiver.clone(),
+            });
+        }
+        let (sender, receiver) = watch::channel(None);
+        requests.insert(key.clone(), receiver);
+        Joined::Leader(Leader {
+            in_flight: self,
+            key,
+            sender,
+        })
+    }
+}
+
+impl<K: Eq + Hash, V> Leader<'_, K, V> {
+    /// Hands `value` to every waiting follower.
+    pub(crate) fn finish(self, value: V) {
+        self.sender.send_replace(Some(value));
+    }
+}
+
+impl<K: Eq + Hash, V> Drop for Leader<'_, K, V> {
+    fn drop(&mut self) {
+        let mut requests = self
+            .in_flight
+            .requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        // Later callers start a new request rather than reuse this response
+        if requests
+            .get(&self.key)
+            .is_some_and(|receiver| receiver.same_channel(&self.sender.subscribe()))
+        {
+            requests.remove(&self.key);
+        }
+    }
+}
+
+impl<V: Clone> Follower<V> {
+    /// Waits for the leader's value, or `None` if the leader gave up.
+    pub(crate) async fn wait(mut self) -> Option<V> {
+        let value = self.receiver.wait_for(Option::is_some).await.ok()?;
+        value.clone()
+    }
+}
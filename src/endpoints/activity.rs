@@ -1,7 +1,14 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
-use crate::models::social::{Activity, ActivityReply, TextActivity};
+use crate::limits;
+use crate::models::Page;
+use crate::models::social::{
+    Activity, ActivityFeedFilter, ActivityReply, ActivityUnion, LikeableType, ListActivity,
+    TextActivity, UserPreview,
+};
 use crate::queries;
+use crate::utils::pagination::paginate;
+use futures_util::Stream;
 use serde_json::json;
 use std::collections::HashMap;
 
@@ -19,55 +26,153 @@ impl ActivityEndpoint {
         &self,
         page: i32,
         per_page: i32,
+    ) -> Result<Vec<Activity>, AniListError> {
+        self.get_recent_activities_filtered(page, per_page, &ActivityFeedFilter::default())
+            .await
+    }
+
+    /// Get recent activities from the global feed, filtered
+    ///
+    /// Activity types are filtered by AniList; other filters are applied after
+    /// the page is fetched, so fewer than `per_page` activities may be returned.
+    pub async fn get_recent_activities_filtered(
+        &self,
+        page: i32,
+        per_page: i32,
+        filter: &ActivityFeedFilter,
     ) -> Result<Vec<Activity>, AniListError> {
         let query = queries::activity::GET_RECENT_ACTIVITIES;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let mut variables = limits::page_variables(page, per_page)?;
+        if let Some(types) = &filter.types {
+            variables.insert("typeIn".to_string(), json!(types));
+        }
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["activities"].clone();
-        let activities: Vec<Activity> = serde_json::from_value(data)?;
-        Ok(activities)
+        let activities: Vec<Activity> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "activities"])
+            .await?;
+        Ok(filter.apply(activities))
     }
 
     /// Get activities from following users (requires authentication)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
     pub async fn get_following_activities(
         &self,
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Activity>, AniListError> {
+        self.get_following_activities_filtered(page, per_page, &ActivityFeedFilter::default())
+            .await
+    }
+
+    /// Get activities from following users, filtered (requires authentication)
+    ///
+    /// Activity types are filtered by AniList; other filters are applied after
+    /// the page is fetched, so fewer than `per_page` activities may be returned.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_following_activities_filtered(
+        &self,
+        page: i32,
+        per_page: i32,
+        filter: &ActivityFeedFilter,
+    ) -> Result<Vec<Activity>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
         let query = queries::activity::GET_FOLLOWING_ACTIVITIES;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let mut variables = limits::page_variables(page, per_page)?;
+        if let Some(types) = &filter.types {
+            variables.insert("typeIn".to_string(), json!(types));
+        }
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["activities"].clone();
-        let activities: Vec<Activity> = serde_json::from_value(data)?;
-        Ok(activities)
+        let activities: Vec<Activity> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "activities"])
+            .await?;
+        Ok(filter.apply(activities))
     }
 
     /// Get user activities by user ID
+    ///
+    /// Returns [`AniListError::Private`] if the user's profile is private.
     pub async fn get_user_activities(
         &self,
         user_id: i32,
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Activity>, AniListError> {
+        self.get_user_activities_filtered(user_id, page, per_page, &ActivityFeedFilter::default())
+            .await
+    }
+
+    /// Get user activities by user ID, with the page's pagination info
+    ///
+    /// Returns [`AniListError::Private`] if the user's profile is private.
+    pub async fn get_user_activities_page(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<Activity>, AniListError> {
         let query = queries::activity::GET_USER_ACTIVITIES;
 
         let mut variables = HashMap::new();
         variables.insert("userId".to_string(), json!(user_id));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
         let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["activities"].clone();
-        let activities: Vec<Activity> = serde_json::from_value(data)?;
-        Ok(activities)
+        Page::extract(response, "activities")
+    }
+
+    /// Stream every activity of a user, newest first, fetching `per_page` at
+    /// a time
+    ///
+    /// See [`crate::utils::pagination`].
+    pub fn get_user_activities_stream(
+        &self,
+        user_id: i32,
+        per_page: i32,
+    ) -> impl Stream<Item = Result<Activity, AniListError>> + use<> {
+        paginate(self.client.clone(), move |client, page| async move {
+            client
+                .activity()
+                .get_user_activities_page(user_id, page, per_page)
+                .await
+        })
+    }
+
+    /// Get user activities by user ID, filtered
+    ///
+    /// Activity types are filtered by AniList; other filters are applied after
+    /// the page is fetched, so fewer than `per_page` activities may be returned.
+    pub async fn get_user_activities_filtered(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+        filter: &ActivityFeedFilter,
+    ) -> Result<Vec<Activity>, AniListError> {
+        let query = queries::activity::GET_USER_ACTIVITIES;
+
+        let mut variables = HashMap::new();
+        variables.insert("userId".to_string(), json!(user_id));
+        variables.extend(limits::page_variables(page, per_page)?);
+        if let Some(types) = &filter.types {
+            variables.insert("typeIn".to_string(), json!(types));
+        }
+
+        let activities: Vec<Activity> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "activities"])
+            .await?;
+        Ok(filter.apply(activities))
     }
 
     /// Get text activities
@@ -78,13 +183,12 @@ impl ActivityEndpoint {
     ) -> Result<Vec<TextActivity>, AniListError> {
         let query = queries::activity::GET_TEXT_ACTIVITIES;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["activities"].clone();
-        let activities: Vec<TextActivity> = serde_json::from_value(data)?;
+        let activities: Vec<TextActivity> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "activities"])
+            .await?;
         Ok(activities)
     }
 
@@ -95,9 +199,28 @@ impl ActivityEndpoint {
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Activity"].clone();
-        let activity: Activity = serde_json::from_value(data)?;
+        let activity: Activity = self
+            .client
+            .query_as(query, Some(variables), &["data", "Activity"])
+            .await?;
+        Ok(activity)
+    }
+
+    /// Get activity by ID with the fields specific to its kind
+    ///
+    /// Unlike [`Self::get_activity_by_id`], this includes the text of text
+    /// activities, the media and progress of list activities, and the message
+    /// body of message activities.
+    pub async fn get_activity_detail(&self, id: i32) -> Result<ActivityUnion, AniListError> {
+        let query = queries::activity::GET_ACTIVITY_DETAIL;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(id));
+
+        let activity: ActivityUnion = self
+            .client
+            .query_as(query, Some(variables), &["data", "Activity"])
+            .await?;
         Ok(activity)
     }
 
@@ -112,25 +235,33 @@ impl ActivityEndpoint {
 
         let mut variables = HashMap::new();
         variables.insert("activityId".to_string(), json!(activity_id));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["activityReplies"].clone();
-        let replies: Vec<ActivityReply> = serde_json::from_value(data)?;
+        let replies: Vec<ActivityReply> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "activityReplies"])
+            .await?;
         Ok(replies)
     }
 
     /// Create a text activity (requires authentication)
     pub async fn create_text_activity(&self, text: &str) -> Result<TextActivity, AniListError> {
+        limits::check_min_chars(
+            "Activity text",
+            text,
+            limits::TEXT_MIN_CHARS,
+            "TEXT_MIN_CHARS",
+        )?;
+
         let query = queries::activity::CREATE_TEXT_ACTIVITY;
 
         let mut variables = HashMap::new();
         variables.insert("text".to_string(), json!(text));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["SaveTextActivity"].clone();
-        let activity: TextActivity = serde_json::from_value(data)?;
+        let activity: TextActivity = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveTextActivity"])
+            .await?;
         Ok(activity)
     }
 
@@ -140,15 +271,18 @@ impl ActivityEndpoint {
         activity_id: i32,
         text: &str,
     ) -> Result<ActivityReply, AniListError> {
+        limits::check_min_chars("Reply text", text, limits::TEXT_MIN_CHARS, "TEXT_MIN_CHARS")?;
+
         let query = queries::activity::REPLY_TO_ACTIVITY;
 
         let mut variables = HashMap::new();
         variables.insert("activityId".to_string(), json!(activity_id));
         variables.insert("text".to_string(), json!(text));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["SaveActivityReply"].clone();
-        let reply: ActivityReply = serde_json::from_value(data)?;
+        let reply: ActivityReply = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveActivityReply"])
+            .await?;
         Ok(reply)
     }
 
@@ -160,9 +294,10 @@ impl ActivityEndpoint {
         variables.insert("id".to_string(), json!(id));
         variables.insert("type".to_string(), json!("ACTIVITY"));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["ToggleLikeV2"].clone();
-        let activity: Activity = serde_json::from_value(data)?;
+        let activity: Activity = self
+            .client
+            .query_as(query, Some(variables), &["data", "ToggleLikeV2"])
+            .await?;
         Ok(activity)
     }
 
@@ -174,12 +309,118 @@ impl ActivityEndpoint {
         variables.insert("id".to_string(), json!(id));
         variables.insert("type".to_string(), json!("ACTIVITY_REPLY"));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["ToggleLikeV2"].clone();
-        let reply: ActivityReply = serde_json::from_value(data)?;
+        let reply: ActivityReply = self
+            .client
+            .query_as(query, Some(variables), &["data", "ToggleLikeV2"])
+            .await?;
         Ok(reply)
     }
 
+    /// Get activities the authenticated user is subscribed to (requires authentication)
+    ///
+    /// Subscribed activities notify the user about new replies; use
+    /// [`Self::toggle_activity_subscription`] to unsubscribe.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_subscribed_activities(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<Activity>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
+        let query = queries::activity::GET_SUBSCRIBED_ACTIVITIES;
+
+        let variables = limits::page_variables(page, per_page)?;
+
+        let response = self.client.query(query, Some(variables)).await?;
+        Ok(Page::from_page_value(
+            &response["data"]["Page"],
+            "activities",
+        )?)
+    }
+
+    /// Subscribe to or unsubscribe from an activity (requires authentication)
+    pub async fn toggle_activity_subscription(
+        &self,
+        id: i32,
+        subscribe: bool,
+    ) -> Result<Activity, AniListError> {
+        let query = queries::activity::TOGGLE_ACTIVITY_SUBSCRIPTION;
+
+        let mut variables = HashMap::new();
+        variables.insert("activityId".to_string(), json!(id));
+        variables.insert("subscribe".to_string(), json!(subscribe));
+
+        let activity: Activity = self
+            .client
+            .query_as(
+                query,
+                Some(variables),
+                &["data", "ToggleActivitySubscription"],
+            )
+            .await?;
+        Ok(activity)
+    }
+
+    /// Get the viewer's most recent list activity for a media (requires authentication)
+    ///
+    /// Saving a list entry makes AniList post (or merge into) a list activity;
+    /// call this right after an update to link to it. Returns `None` if the
+    /// viewer has no list activity for the media, e.g. when every update was
+    /// private.
+    ///
+    /// Makes two requests: one for the viewer's ID and one for the activity.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_my_latest_list_activity(
+        &self,
+        media_id: i32,
+    ) -> Result<Option<ListActivity>, AniListError> {
+        let viewer_id = self.client.viewer_id().await?;
+        let query = queries::activity::GET_LATEST_LIST_ACTIVITY;
+
+        let mut variables = HashMap::new();
+        variables.insert("userId".to_string(), json!(viewer_id));
+        variables.insert("mediaId".to_string(), json!(media_id));
+
+        let activities: Vec<ListActivity> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "activities"])
+            .await?;
+        Ok(activities.into_iter().next())
+    }
+
+    /// Pin or unpin one of the viewer's activities on their profile (requires authentication)
+    ///
+    /// Only text and list activities can be pinned; the returned activity
+    /// reports the new state in `is_pinned`.
+    pub async fn toggle_activity_pin(
+        &self,
+        id: i32,
+        pinned: bool,
+    ) -> Result<ActivityUnion, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
+        let query = queries::activity::TOGGLE_ACTIVITY_PIN;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(id));
+        variables.insert("pinned".to_string(), json!(pinned));
+
+        let activity: ActivityUnion = self
+            .client
+            .query_as(query, Some(variables), &["data", "ToggleActivityPin"])
+            .await?;
+        Ok(activity)
+    }
+
     /// Delete an activity (requires authentication and ownership)
     pub async fn delete_activity(&self, id: i32) -> Result<bool, AniListError> {
         let query = queries::activity::DELETE_ACTIVITY;
@@ -193,4 +434,58 @@ impl ActivityEndpoint {
             .unwrap_or(false);
         Ok(deleted)
     }
+
+    /// Get the users who liked an activity
+    pub async fn get_likers(
+        &self,
+        activity_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<UserPreview>, AniListError> {
+        fetch_likers(
+            &self.client,
+            activity_id,
+            LikeableType::Activity,
+            page,
+            per_page,
+        )
+        .await
+    }
+
+    /// Get the users who liked an activity reply
+    pub async fn get_reply_likers(
+        &self,
+        reply_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<UserPreview>, AniListError> {
+        fetch_likers(
+            &self.client,
+            reply_id,
+            LikeableType::ActivityReply,
+            page,
+            per_page,
+        )
+        .await
+    }
+}
+
+/// Fetch a page of users who liked any likeable entity (shared with the forum endpoint)
+pub(crate) async fn fetch_likers(
+    client: &AniListClient,
+    likeable_id: i32,
+    likeable_type: LikeableType,
+    page: i32,
+    per_page: i32,
+) -> Result<Page<UserPreview>, AniListError> {
+    let query = queries::activity::GET_LIKERS;
+
+    let mut variables = HashMap::new();
+    variables.insert("likeableId".to_string(), json!(likeable_id));
+    variables.insert("type".to_string(), json!(likeable_type));
+    variables.extend(limits::page_variables(page, per_page)?);
+
+    let response = client.query(query, Some(variables)).await?;
+    let likers = Page::from_page_value(&response["data"]["Page"], "likes")?;
+    Ok(likers)
 }
@@ -1,7 +1,11 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
+use crate::limits;
+use crate::models::Page;
 use crate::models::social::AiringSchedule;
 use crate::queries;
+use crate::utils::pagination::paginate;
+use futures_util::Stream;
 use serde_json::json;
 use std::collections::HashMap;
 
@@ -20,23 +24,32 @@ impl AiringEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<AiringSchedule>, AniListError> {
-        let query = queries::airing::GET_UPCOMING_EPISODES;
-
-        let current_timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
+        Ok(self.get_upcoming_episodes_page(page, per_page).await?.items)
+    }
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
-        variables.insert("airingAtGreater".to_string(), json!(current_timestamp));
-        variables.insert("sort".to_string(), json!(["TIME"]));
+    /// Get upcoming airing episodes, with the page's pagination info
+    pub async fn get_upcoming_episodes_page(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<AiringSchedule>, AniListError> {
+        fetch_upcoming_page(&self.client, now_timestamp(), page, per_page).await
+    }
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["airingSchedules"].clone();
-        let schedules: Vec<AiringSchedule> = serde_json::from_value(data)?;
-        Ok(schedules)
+    /// Stream every upcoming airing episode, soonest first, fetching
+    /// `per_page` at a time
+    ///
+    /// "Upcoming" is fixed when the stream is created, so episodes airing
+    /// while it is read do not shift later pages. See
+    /// [`crate::utils::pagination`].
+    pub fn get_upcoming_episodes_stream(
+        &self,
+        per_page: i32,
+    ) -> impl Stream<Item = Result<AiringSchedule, AniListError>> + use<> {
+        let after = now_timestamp();
+        paginate(self.client.clone(), move |client, page| async move {
+            fetch_upcoming_page(&client, after, page, per_page).await
+        })
     }
 
     /// Get airing episodes for today
@@ -45,8 +58,8 @@ impl AiringEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<AiringSchedule>, AniListError> {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
+        let now = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
 
@@ -55,16 +68,15 @@ impl AiringEndpoint {
 
         let query = queries::airing::GET_TODAY_EPISODES;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let mut variables = limits::page_variables(page, per_page)?;
         variables.insert("airingAtGreater".to_string(), json!(start_of_day));
         variables.insert("airingAtLesser".to_string(), json!(end_of_day));
-        variables.insert("sort".to_string(), json!(["TIME"]));
+        variables.insert("sort".to_string(), json!([limits::sort::AIRING_TIME]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["airingSchedules"].clone();
-        let schedules: Vec<AiringSchedule> = serde_json::from_value(data)?;
+        let schedules: Vec<AiringSchedule> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "airingSchedules"])
+            .await?;
         Ok(schedules)
     }
 
@@ -74,22 +86,21 @@ impl AiringEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<AiringSchedule>, AniListError> {
-        let current_timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
+        let current_timestamp = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
 
         let query = queries::airing::GET_RECENTLY_AIRED;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let mut variables = limits::page_variables(page, per_page)?;
         variables.insert("airingAtLesser".to_string(), json!(current_timestamp));
-        variables.insert("sort".to_string(), json!(["TIME_DESC"]));
+        variables.insert("sort".to_string(), json!([limits::sort::AIRING_TIME_DESC]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["airingSchedules"].clone();
-        let schedules: Vec<AiringSchedule> = serde_json::from_value(data)?;
+        let schedules: Vec<AiringSchedule> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "airingSchedules"])
+            .await?;
         Ok(schedules)
     }
 
@@ -104,13 +115,13 @@ impl AiringEndpoint {
 
         let mut variables = HashMap::new();
         variables.insert("mediaId".to_string(), json!(media_id));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
-        variables.insert("sort".to_string(), json!(["TIME"]));
+        variables.extend(limits::page_variables(page, per_page)?);
+        variables.insert("sort".to_string(), json!([limits::sort::AIRING_TIME]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["airingSchedules"].clone();
-        let schedules: Vec<AiringSchedule> = serde_json::from_value(data)?;
+        let schedules: Vec<AiringSchedule> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "airingSchedules"])
+            .await?;
         Ok(schedules)
     }
 
@@ -121,9 +132,10 @@ impl AiringEndpoint {
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["AiringSchedule"].clone();
-        let schedule: AiringSchedule = serde_json::from_value(data)?;
+        let schedule: AiringSchedule = self
+            .client
+            .query_as(query, Some(variables), &["data", "AiringSchedule"])
+            .await?;
         Ok(schedule)
     }
 
@@ -137,16 +149,15 @@ impl AiringEndpoint {
     ) -> Result<Vec<AiringSchedule>, AniListError> {
         let query = queries::airing::GET_EPISODES_IN_RANGE;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let mut variables = limits::page_variables(page, per_page)?;
         variables.insert("airingAtGreater".to_string(), json!(start_timestamp));
         variables.insert("airingAtLesser".to_string(), json!(end_timestamp));
-        variables.insert("sort".to_string(), json!(["TIME"]));
+        variables.insert("sort".to_string(), json!([limits::sort::AIRING_TIME]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["airingSchedules"].clone();
-        let schedules: Vec<AiringSchedule> = serde_json::from_value(data)?;
+        let schedules: Vec<AiringSchedule> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "airingSchedules"])
+            .await?;
         Ok(schedules)
     }
 
@@ -155,8 +166,8 @@ impl AiringEndpoint {
         &self,
         media_id: i32,
     ) -> Result<Option<AiringSchedule>, AniListError> {
-        let current_timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
+        let current_timestamp = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
 
@@ -179,3 +190,28 @@ impl AiringEndpoint {
         Ok(None)
     }
 }
+
+/// The current Unix time in seconds.
+fn now_timestamp() -> i64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Fetch a page of episodes airing after the Unix time `after`, soonest first.
+async fn fetch_upcoming_page(
+    client: &AniListClient,
+    after: i64,
+    page: i32,
+    per_page: i32,
+) -> Result<Page<AiringSchedule>, AniListError> {
+    let query = queries::airing::GET_UPCOMING_EPISODES;
+
+    let mut variables = limits::page_variables(page, per_page)?;
+    variables.insert("airingAtGreater".to_string(), json!(after));
+    variables.insert("sort".to_string(), json!([limits::sort::AIRING_TIME]));
+
+    let response = client.query(query, Some(variables)).await?;
+    Page::extract(response, "airingSchedules")
+}
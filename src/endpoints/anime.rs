@@ -4,12 +4,24 @@
 //! It includes methods for searching, browsing, and retrieving detailed information
 //! about anime series and movies.
 
-use crate::client::AniListClient;
+use crate::client::{AniListClient, extract};
+use crate::envelope::ResponseEnvelope;
 use crate::error::AniListError;
-use crate::models::Anime;
+use crate::limits;
+use crate::models::social::MediaType;
+use crate::models::{
+    Anime, CharacterEdge, ExternalLinkType, MediaExternalLink, MediaRelationEdge,
+    MediaSearchFilter, MediaSeason, MediaSort, MediaStats, MediaSuggestion, Page, StaffEdge,
+    StaffLanguage, StreamingEpisode,
+};
 use crate::queries;
-use serde_json::json;
-use std::collections::HashMap;
+use crate::utils::merge;
+use crate::utils::normalize_search;
+use crate::utils::pagination::{AllPages, collect_all, paginate};
+use crate::utils::seasons::{self, SeasonEntryKind, SeasonYear};
+use futures_util::Stream;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
 
 /// Endpoint for anime-related API operations.
 ///
@@ -62,7 +74,7 @@ impl AnimeEndpoint {
     /// # Parameters
     ///
     /// * `page` - The page number to retrieve (1-based indexing). Must be positive.
-    /// * `per_page` - Number of anime to return per page (1-50). Higher values may impact performance.
+    /// * `per_page` - Number of anime to return per page; values above 50 are fetched as several pages (see [`AniListClient::set_strict_page_size`]).
     ///
     /// # Returns
     ///
@@ -103,16 +115,10 @@ impl AnimeEndpoint {
     /// The popularity ranking is updated regularly by AniList and may change over time.
     /// Results are consistent within short time periods but may vary across longer periods.
     pub async fn get_popular(&self, page: i32, per_page: i32) -> Result<Vec<Anime>, AniListError> {
-        let query = queries::anime::GET_POPULAR;
-
         let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.insert("sort".to_string(), json!(["POPULARITY_DESC"]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let anime_list: Vec<Anime> = serde_json::from_value(data)?;
-        Ok(anime_list)
+        self.fetch_media_page(page, per_page, variables).await
     }
 
     /// Retrieves currently trending anime with pagination support.
@@ -124,7 +130,7 @@ impl AnimeEndpoint {
     /// # Parameters
     ///
     /// * `page` - The page number to retrieve (1-based indexing). Must be positive.
-    /// * `per_page` - Number of anime to return per page (1-50). Higher values may impact performance.
+    /// * `per_page` - Number of anime to return per page; values above 50 are fetched as several pages (see [`AniListClient::set_strict_page_size`]).
     ///
     /// # Returns
     ///
@@ -161,29 +167,352 @@ impl AnimeEndpoint {
     /// Trending data is updated in real-time and can change frequently throughout
     /// the day based on user activity and engagement patterns.
     pub async fn get_trending(&self, page: i32, per_page: i32) -> Result<Vec<Anime>, AniListError> {
-        let query = queries::anime::GET_TRENDING;
-
         let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.insert("sort".to_string(), json!(["TRENDING_DESC"]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let anime_list: Vec<Anime> = serde_json::from_value(data)?;
-        Ok(anime_list)
+        self.fetch_media_page(page, per_page, variables).await
     }
 
     /// Get anime by ID
+    ///
+    /// Adult titles requested without a token (or by an account that has not
+    /// enabled adult content) fail with [`AniListError::AdultContentBlocked`].
     pub async fn get_by_id(&self, id: i32) -> Result<Anime, AniListError> {
+        Ok(self.get_by_id_with_meta(id).await?.value)
+    }
+
+    /// Get anime by ID along with the response status, timing and
+    /// rate-limit/request-ID headers, for debugging
+    pub async fn get_by_id_with_meta(
+        &self,
+        id: i32,
+    ) -> Result<ResponseEnvelope<Anime>, AniListError> {
+        let query = queries::anime::GET_BY_ID;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(id));
+
+        let response = self.client.query_with_meta(query, Some(variables)).await?;
+        response.try_map(|json| extract(json, &["data", "Media"]))
+    }
+
+    /// Get anime by its MyAnimeList ID
+    ///
+    /// Selects the same fields as [`Self::get_by_id`]; the returned anime's
+    /// [`id`](Anime::id) is its AniList ID and [`id_mal`](Anime::id_mal) the
+    /// one looked up. MAL IDs with no AniList entry fail with
+    /// [`AniListError::NotFound`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let aot = client.anime().get_by_mal_id(16498).await?;
+    /// println!("AniList ID: {}", aot.id);
+    /// ```
+    pub async fn get_by_mal_id(&self, mal_id: i32) -> Result<Anime, AniListError> {
         let query = queries::anime::GET_BY_ID;
 
         let mut variables = HashMap::new();
+        variables.insert("idMal".to_string(), json!(mal_id));
+
+        self.client
+            .query_as(query, Some(variables), &["data", "Media"])
+            .await
+    }
+
+    /// Get several anime by ID, usually in one request
+    ///
+    /// Sends AniList's `id_in` filter instead of one [`Self::get_by_id`] per
+    /// ID. Up to [`limits::MAX_PER_PAGE`] distinct IDs go in one request;
+    /// longer lists are split into several, waiting
+    /// [`AniListClient::page_delay`] between them. Duplicate IDs are only
+    /// requested once.
+    ///
+    /// The result is aligned with `ids`: each entry is the anime for the ID at
+    /// the same position, or `None` when AniList returned nothing for it (an
+    /// ID that does not exist, or an adult title the viewer cannot see). An
+    /// empty `ids` slice sends no request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let ids = [1, 20, 999_999_999];
+    /// let anime = client.anime().get_by_ids(&ids).await?;
+    /// let missing: Vec<i32> = ids
+    ///     .iter()
+    ///     .zip(&anime)
+    ///     .filter(|(_, anime)| anime.is_none())
+    ///     .map(|(id, _)| *id)
+    ///     .collect();
+    /// ```
+    pub async fn get_by_ids(&self, ids: &[i32]) -> Result<Vec<Option<Anime>>, AniListError> {
+        let mut seen = HashSet::new();
+        let distinct: Vec<i32> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+
+        let delay = self.client.page_delay();
+        let mut found: HashMap<i32, Anime> = HashMap::new();
+        for (index, chunk) in distinct.chunks(limits::MAX_PER_PAGE as usize).enumerate() {
+            if index > 0 && !delay.is_zero() {
+                crate::runtime::sleep(delay).await;
+            }
+            let mut variables = HashMap::new();
+            variables.insert("ids".to_string(), json!(chunk));
+
+            let anime = self
+                .fetch_media_page(1, chunk.len() as i32, variables)
+                .await?;
+            found.extend(anime.into_iter().map(|anime| (anime.id, anime)));
+        }
+
+        Ok(ids.iter().map(|id| found.get(id).cloned()).collect())
+    }
+
+    /// Get the episodes of an anime on legal streaming sites, in AniList's
+    /// order
+    ///
+    /// Only fetches the links, for callers that do not need the rest of
+    /// [`Self::get_by_id`] (which selects them too). Long-running shows can
+    /// list hundreds of episodes; anime without any listed return an empty
+    /// list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// for episode in client.anime().get_streaming_episodes(21).await? {
+    ///     println!("{:?} on {:?}: {:?}", episode.title, episode.site, episode.url);
+    /// }
+    /// ```
+    pub async fn get_streaming_episodes(
+        &self,
+        id: i32,
+    ) -> Result<Vec<StreamingEpisode>, AniListError> {
+        let query = queries::anime::GET_STREAMING_EPISODES;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(id));
+
+        let episodes: Option<Vec<StreamingEpisode>> = self
+            .client
+            .query_as(
+                query,
+                Some(variables),
+                &["data", "Media", "streamingEpisodes"],
+            )
+            .await?;
+        Ok(episodes.unwrap_or_default())
+    }
+
+    /// Get an anime's links to streaming platforms, in AniList's order
+    ///
+    /// Keeps the external links of type
+    /// [`Streaming`](ExternalLinkType::Streaming) that are not disabled, as
+    /// AniList lists them. Unlike [`Anime::streaming_sites`], unknown sites
+    /// are kept and spelling variants are not merged. Anime without any
+    /// return an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// for link in client.anime().get_streaming_links(140960).await? {
+    ///     println!("{} ({:?}): {:?}", link.site, link.language, link.url);
+    /// }
+    /// ```
+    pub async fn get_streaming_links(
+        &self,
+        id: i32,
+    ) -> Result<Vec<MediaExternalLink>, AniListError> {
+        let query = queries::anime::GET_EXTERNAL_LINKS;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(id));
+
+        let links: Option<Vec<MediaExternalLink>> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Media", "externalLinks"])
+            .await?;
+        Ok(links
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|link| {
+                link.link_type == Some(ExternalLinkType::Streaming)
+                    && link.is_disabled != Some(true)
+            })
+            .collect())
+    }
+
+    /// Get how users have scored and listed an anime, e.g. for a ratings
+    /// histogram
+    ///
+    /// Entries nobody has scored or listed yet return empty distributions.
+    /// Manga IDs work too, see
+    /// [`MangaEndpoint::get_stats`](crate::endpoints::MangaEndpoint::get_stats).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let stats = client.anime().get_stats(1).await?;
+    /// for bucket in &stats.score_distribution {
+    ///     println!("{:>3}: {}", bucket.score, bucket.amount);
+    /// }
+    /// ```
+    pub async fn get_stats(&self, id: i32) -> Result<MediaStats, AniListError> {
+        fetch_stats(&self.client, id).await
+    }
+
+    /// Get the media related to an anime: sequels, prequels, side stories,
+    /// its source material and so on
+    ///
+    /// Relations cross media types, so check each node's
+    /// [`media_type`](crate::models::MediaRef::media_type): the `SOURCE` of an
+    /// anime is usually a manga. Each node carries its title, format, status
+    /// and cover image. Relations to entries AniList hides from the viewer
+    /// (such as adult titles without a token) are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anilist_sdk::models::MediaRelation;
+    ///
+    /// for edge in client.anime().get_relations(16498).await? {
+    ///     if edge.relation_type == Some(MediaRelation::Sequel) {
+    ///         let title = &edge.node.unwrap().media.title;
+    ///         println!("Sequel: {:?}", title.as_ref().and_then(|t| t.romaji.as_deref()));
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_relations(&self, id: i32) -> Result<Vec<MediaRelationEdge>, AniListError> {
+        let query = queries::anime::GET_RELATIONS;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(id));
+
+        let edges: Option<Vec<MediaRelationEdge>> = self
+            .client
+            .query_as(
+                query,
+                Some(variables),
+                &["data", "Media", "relations", "edges"],
+            )
+            .await?;
+        Ok(edges
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|edge| edge.node.is_some())
+            .collect())
+    }
+
+    /// Get an anime's characters with their role and Japanese voice actors,
+    /// main characters first
+    ///
+    /// See [`Self::get_characters_page`] for other dub languages and the
+    /// pagination info.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anilist_sdk::models::CharacterRole;
+    ///
+    /// for edge in client.anime().get_characters(16498, 1, 25).await? {
+    ///     if edge.role == Some(CharacterRole::Main) {
+    ///         let voiced_by: Vec<_> = edge
+    ///             .voice_actors
+    ///             .iter()
+    ///             .flatten()
+    ///             .filter_map(|staff| staff.name.as_ref()?.full.clone())
+    ///             .collect();
+    ///         println!("{:?} voiced by {voiced_by:?}", edge.node.map(|c| c.id));
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_characters(
+        &self,
+        id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<CharacterEdge>, AniListError> {
+        Ok(self
+            .get_characters_page(id, StaffLanguage::Japanese, page, per_page)
+            .await?
+            .items)
+    }
+
+    /// Get one page of an anime's characters with their role and the voice
+    /// actors of the `language` dub
+    ///
+    /// Characters are ordered by role (main, supporting, then background).
+    /// Pages come from the anime's `characters` connection, so the page info
+    /// is the connection's own. Unlike top-level list methods, a `per_page`
+    /// above [`limits::MAX_PER_PAGE`] cannot be split into several requests
+    /// and fails with [`AniListError::InvalidInput`].
+    pub async fn get_characters_page(
+        &self,
+        id: i32,
+        language: StaffLanguage,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<CharacterEdge>, AniListError> {
+        if per_page > limits::MAX_PER_PAGE {
+            return Err(limits::per_page_too_large(per_page.into()));
+        }
+        let query = queries::anime::GET_CHARACTERS;
+
+        let mut variables = limits::page_variables(page, per_page)?;
         variables.insert("id".to_string(), json!(id));
+        variables.insert("language".to_string(), json!(language));
 
         let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Media"].clone();
-        let anime: Anime = serde_json::from_value(data)?;
-        Ok(anime)
+        let connection: Value = extract(response, &["data", "Media", "characters"])?;
+        Ok(Page::from_page_value(&connection, "edges")?)
+    }
+
+    /// Get an anime's staff credits, most relevant first
+    ///
+    /// Each edge pairs a person with their [`role`](StaffEdge::role), a
+    /// free-form credit such as `"Director"` or `"Music"`. One person can
+    /// appear several times with different roles. See
+    /// [`Self::get_staff_page`] for the pagination info.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let credits = client.anime().get_staff(16498, 1, 25).await?;
+    /// if let Some(director) = credits.iter().find(|edge| edge.role.as_deref() == Some("Director")) {
+    ///     let name = director.node.as_ref().and_then(|staff| staff.name.as_ref());
+    ///     println!("Director: {:?}", name.and_then(|name| name.full.as_deref()));
+    /// }
+    /// ```
+    pub async fn get_staff(
+        &self,
+        id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<StaffEdge>, AniListError> {
+        Ok(self.get_staff_page(id, page, per_page).await?.items)
+    }
+
+    /// Get one page of an anime's staff credits
+    ///
+    /// Pages come from the anime's `staff` connection, so the page info is
+    /// the connection's own, and a `per_page` above
+    /// [`limits::MAX_PER_PAGE`] fails with [`AniListError::InvalidInput`]
+    /// as for [`Self::get_characters_page`].
+    pub async fn get_staff_page(
+        &self,
+        id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<StaffEdge>, AniListError> {
+        if per_page > limits::MAX_PER_PAGE {
+            return Err(limits::per_page_too_large(per_page.into()));
+        }
+        let query = queries::anime::GET_STAFF;
+
+        let mut variables = limits::page_variables(page, per_page)?;
+        variables.insert("id".to_string(), json!(id));
+
+        let response = self.client.query(query, Some(variables)).await?;
+        let connection: Value = extract(response, &["data", "Media", "staff"])?;
+        Ok(Page::from_page_value(&connection, "edges")?)
     }
 
     /// Searches for anime by title with pagination support.
@@ -197,7 +526,7 @@ impl AnimeEndpoint {
     /// * `search` - The search query string. Can be partial titles, alternative titles, or keywords.
     ///   Supports searches in romaji, English, and native languages.
     /// * `page` - The page number to retrieve (1-based indexing). Must be positive.
-    /// * `per_page` - Number of results to return per page (1-50). Higher values may impact performance.
+    /// * `per_page` - Number of results to return per page; values above 50 are fetched as several pages (see [`AniListClient::set_strict_page_size`]).
     ///
     /// # Returns
     ///
@@ -207,7 +536,9 @@ impl AnimeEndpoint {
     /// # Errors
     ///
     /// This method can return:
-    /// - [`AniListError::BadRequest`] if parameters are invalid
+    /// - [`AniListError::BadRequest`] if parameters are invalid, including an
+    ///   empty or whitespace-only search term (rejected before any request is
+    ///   sent; see [`AniListClient::set_lenient_search`])
     /// - [`AniListError::RateLimit`] if rate limits are exceeded  
     /// - [`AniListError::Network`] for connection issues
     /// - [`AniListError::Json`] if response parsing fails
@@ -238,6 +569,7 @@ impl AnimeEndpoint {
     /// - Try alternative titles if initial search doesn't yield expected results
     /// - Search supports both romaji and native script titles
     /// - Partial matches are supported (e.g., "attack" will match "Attack on Titan")
+    /// - Surrounding whitespace is trimmed and internal runs collapse to a single space
     ///
     /// # Note
     ///
@@ -249,17 +581,133 @@ impl AnimeEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Anime>, AniListError> {
-        let query = queries::anime::SEARCH;
+        Ok(self.search_page(search, page, per_page).await?.items)
+    }
 
+    /// Search anime by title, returning the page's pagination info with it
+    ///
+    /// Same as [`Self::search`]; a blank search on a lenient client returns
+    /// an empty last page.
+    pub async fn search_page(
+        &self,
+        search: &str,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<Anime>, AniListError> {
+        let Some(search) = self.client.prepare_search(search)? else {
+            return Ok(Page::default());
+        };
         let mut variables = HashMap::new();
         variables.insert("search".to_string(), json!(search));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.insert("sort".to_string(), json!(["SEARCH_MATCH"]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let anime_list: Vec<Anime> = serde_json::from_value(data)?;
-        Ok(anime_list)
+        self.fetch_media_paged(page, per_page, variables).await
+    }
+
+    /// Stream every anime matching `search`, fetching `per_page` at a time
+    ///
+    /// Pages are fetched as the stream is polled, with
+    /// [`AniListClient::page_delay`] between them; see
+    /// [`crate::utils::pagination`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use std::pin::pin;
+    ///
+    /// let mut results = pin!(client.anime().search_stream("one piece", 25));
+    /// while let Some(anime) = results.next().await {
+    ///     println!("{}", anime?.title.romaji);
+    /// }
+    /// ```
+    pub fn search_stream(
+        &self,
+        search: &str,
+        per_page: i32,
+    ) -> impl Stream<Item = Result<Anime, AniListError>> + use<> {
+        let search = search.to_string();
+        paginate(self.client.clone(), move |client, page| {
+            let search = search.clone();
+            async move { client.anime().search_page(&search, page, per_page).await }
+        })
+    }
+
+    /// Search anime by any combination of title, genres, tags, years,
+    /// formats, statuses, score, episode count and list membership
+    ///
+    /// Only the fields set on `filter` are sent; see [`MediaSearchFilter`].
+    ///
+    /// Fails without sending a request with
+    /// [`AniListError::AuthenticationRequired`] when `filter.on_list` is set
+    /// and the client has no token, with [`AniListError::BadRequest`] for a
+    /// blank `filter.search` (an empty result on a lenient client, like
+    /// [`Self::search`]), and with [`AniListError::InvalidInput`] for year or
+    /// episode bounds out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anilist_sdk::models::{MediaFormat, MediaSearchFilter, MediaSort};
+    ///
+    /// let filter = MediaSearchFilter::new()
+    ///     .genres(["Action"])
+    ///     .year_range(2015, 2020)
+    ///     .formats([MediaFormat::Tv])
+    ///     .sort(MediaSort::ScoreDesc);
+    /// let anime = client.anime().search_advanced(&filter, 1, 25).await?;
+    /// ```
+    pub async fn search_advanced(
+        &self,
+        filter: &MediaSearchFilter,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        if filter.on_list.is_some() && !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+        if let Some(search) = &filter.search
+            && self.client.prepare_search(search)?.is_none()
+        {
+            return Ok(Vec::new());
+        }
+        self.fetch_media_page(page, per_page, filter.variables()?)
+            .await
+    }
+
+    /// Get lightweight anime suggestions for a typeahead search box.
+    ///
+    /// Only the ID, preferred title, format, season year and a medium cover
+    /// image are requested, keeping payloads small for low-latency typeahead.
+    /// Identical searches within a minute are answered from a short-lived
+    /// cache shared between clones of the client.
+    ///
+    /// # Parameters
+    ///
+    /// * `search` - The partial title typed by the user
+    /// * `limit` - Maximum number of suggestions to return
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use anilist_sdk::client::AniListClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AniListClient::new();
+    /// let suggestions = client.anime().autocomplete("frier", 5).await?;
+    ///
+    /// for suggestion in suggestions {
+    ///     println!("{:?} ({:?})", suggestion.display_title(), suggestion.season_year);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn autocomplete(
+        &self,
+        search: &str,
+        limit: i32,
+    ) -> Result<Vec<MediaSuggestion>, AniListError> {
+        fetch_suggestions(&self.client, MediaType::Anime, search, limit).await
     }
 
     /// Get anime by season and year
@@ -270,18 +718,139 @@ impl AnimeEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Anime>, AniListError> {
-        let query = queries::anime::GET_BY_SEASON;
+        self.fetch_media_page(page, per_page, season_variables(season, year))
+            .await
+    }
+
+    /// Get the anime of the season airing now (in UTC), most popular first
+    ///
+    /// See [`seasons::season_for_date`] for how dates map to seasons.
+    pub async fn get_current_season(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        let (season, year) = seasons::current_season();
+        self.get_by_season(season.as_str(), year, page, per_page)
+            .await
+    }
+
+    /// Get the anime of the season after the one airing now (in UTC), most
+    /// popular first
+    ///
+    /// In Fall this is Winter of the next year.
+    pub async fn get_next_season(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        let (season, year) = seasons::next_season(seasons::current_season());
+        self.get_by_season(season.as_str(), year, page, per_page)
+            .await
+    }
+
+    /// Get a season's entire lineup, most popular first
+    ///
+    /// Fetches pages of [`limits::MAX_PER_PAGE`] until the last one, waiting
+    /// [`AniListClient::page_delay`] between them and stopping after
+    /// [`AniListClient::max_pages`]. An error part way through is returned
+    /// with the anime fetched before it; see [`AllPages`].
+    pub async fn get_by_season_all(&self, season: &str, year: i32) -> AllPages<Anime> {
+        let season = season.to_string();
+        collect_all(self.client.clone(), move |client, page| {
+            let variables = season_variables(&season, year);
+            async move {
+                client
+                    .anime()
+                    .fetch_media_paged(page, limits::MAX_PER_PAGE, variables)
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Get a season chart: the season's anime plus the shows continuing into
+    /// it, each marked as new, leftover or movie/special
+    ///
+    /// AniList lists a show only under the season it started in, so the
+    /// leftovers are fetched with a second request: for the current season,
+    /// shows still releasing that started earlier; for other seasons, shows
+    /// that started earlier and ended during or after it. Long-running shows
+    /// without an end date are therefore missing from past charts. Each page
+    /// holds up to `per_page` season entries followed by up to `per_page`
+    /// leftovers. See [`Self::classify_season_entries`] for the classification.
+    ///
+    /// Fails with [`AniListError::InvalidInput`] if `year` is too far out for
+    /// AniList's date filters.
+    pub async fn get_season_chart(
+        &self,
+        season: MediaSeason,
+        year: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<(Anime, SeasonEntryKind)>, AniListError> {
+        let start = seasons::season_start((season, year));
+        let day_before = start.pred_opt().unwrap_or(start);
+        let (Some(starts_before), Some(ends_after)) = (
+            seasons::fuzzy_date_int(start),
+            seasons::fuzzy_date_int(day_before),
+        ) else {
+            return Err(AniListError::InvalidInput {
+                field: "year".to_string(),
+                message: format!("{year} is out of range"),
+            });
+        };
+
+        let entries = self
+            .get_by_season(season.as_str(), year, page, per_page)
+            .await?;
 
         let mut variables = HashMap::new();
-        variables.insert("season".to_string(), json!(season.to_uppercase()));
-        variables.insert("year".to_string(), json!(year));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.insert("startDateLesser".to_string(), json!(starts_before));
+        variables.insert("sort".to_string(), json!(["POPULARITY_DESC"]));
+        if (season, year) == seasons::current_season() {
+            variables.insert("status".to_string(), json!("RELEASING"));
+        } else {
+            variables.insert("endDateGreater".to_string(), json!(ends_after));
+        }
+        let leftovers = self.fetch_media_page(page, per_page, variables).await?;
+        let entries = merge::dedup_by_id(entries.into_iter().chain(leftovers));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let anime_list: Vec<Anime> = serde_json::from_value(data)?;
-        Ok(anime_list)
+        Ok(self.classify_season_entries(entries, season, year))
+    }
+
+    /// Classify anime listed for `season` of `year` as new, leftover or
+    /// movie/special
+    ///
+    /// Uses only fields already on the model, see
+    /// [`seasons::classify_season_entry`]. No request is made.
+    pub fn classify_season_entries(
+        &self,
+        entries: Vec<Anime>,
+        season: MediaSeason,
+        year: i32,
+    ) -> Vec<(Anime, SeasonEntryKind)> {
+        entries
+            .into_iter()
+            .map(|anime| {
+                let kind = seasons::classify_season_entry(&anime, (season, year));
+                (anime, kind)
+            })
+            .collect()
+    }
+
+    /// Get the seasons before and after `season` for archive navigation
+    ///
+    /// Returns `(previous, next)`, each ready to pass to
+    /// [`Self::get_by_season`] via [`MediaSeason::as_str`]. Either side is
+    /// `None` at the ends of the archive (Winter 1940, and Fall of next year).
+    /// No request is made.
+    pub fn get_season_neighbors(
+        &self,
+        season: MediaSeason,
+        year: i32,
+    ) -> (Option<SeasonYear>, Option<SeasonYear>) {
+        seasons::neighbors_within((season, year), seasons::current_season().1 + 1)
     }
 
     /// Get top rated anime
@@ -290,29 +859,269 @@ impl AnimeEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Anime>, AniListError> {
-        let query = queries::anime::GET_TOP_RATED;
+        self.get_top_rated_with_min_popularity(page, per_page, None)
+            .await
+    }
 
+    /// Get top rated anime that at least `min_popularity` users have on their lists
+    ///
+    /// The plain score ranking favours obscure titles rated highly by a handful
+    /// of users. AniList cannot filter on the number of scores directly, so
+    /// list popularity is used as the sample-size guard: a higher threshold
+    /// removes small-sample outliers but also hides genuinely good niche titles.
+    /// `None` applies no threshold, matching [`Self::get_top_rated`].
+    pub async fn get_top_rated_with_min_popularity(
+        &self,
+        page: i32,
+        per_page: i32,
+        min_popularity: Option<i32>,
+    ) -> Result<Vec<Anime>, AniListError> {
         let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.insert("sort".to_string(), json!(["SCORE_DESC"]));
+        // AniList's filter is exclusive; shift it so the threshold itself is kept
+        variables.insert(
+            "popularityGreater".to_string(),
+            json!(min_popularity.map(|min| min.saturating_sub(1))),
+        );
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let anime_list: Vec<Anime> = serde_json::from_value(data)?;
-        Ok(anime_list)
+        self.fetch_media_page(page, per_page, variables).await
+    }
+
+    /// Get anime with every one of `genres`, ordered by `sort`
+    ///
+    /// Uses AniList's `genre_in` filter, which keeps anime that have all of
+    /// the given genres, and pages like every other list method. AniList does
+    /// not reject genre names it does not know: a misspelled genre simply
+    /// matches nothing and yields an empty page. An empty `genres` slice
+    /// fails with [`AniListError::InvalidInput`] without sending a request,
+    /// since it would otherwise browse every anime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anilist_sdk::models::MediaSort;
+    ///
+    /// let top_action = client
+    ///     .anime()
+    ///     .get_by_genre(&["Action"], MediaSort::ScoreDesc, 1, 25)
+    ///     .await?;
+    /// ```
+    pub async fn get_by_genre(
+        &self,
+        genres: &[&str],
+        sort: MediaSort,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        self.get_by_genre_excluding(genres, &[], sort, page, per_page)
+            .await
+    }
+
+    /// Get anime with every one of `genres` and none of `excluded`, ordered by `sort`
+    ///
+    /// Same as [`Self::get_by_genre`], also sending `excluded` as AniList's
+    /// `genre_not_in` filter when it is not empty, e.g. action anime that are
+    /// not also comedies.
+    pub async fn get_by_genre_excluding(
+        &self,
+        genres: &[&str],
+        excluded: &[&str],
+        sort: MediaSort,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        if genres.is_empty() {
+            return Err(AniListError::InvalidInput {
+                field: "genres".to_string(),
+                message: "must name at least one genre".to_string(),
+            });
+        }
+        let mut filter = MediaSearchFilter::new().genres(genres.iter().copied()).sort(sort);
+        if !excluded.is_empty() {
+            filter = filter.exclude_genres(excluded.iter().copied());
+        }
+
+        self.fetch_media_page(page, per_page, filter.variables()?)
+            .await
+    }
+
+    /// Get anime with every one of `tags`, most popular first
+    ///
+    /// Uses AniList's `tag_in` filter. With `min_tag_rank`, a tag only counts
+    /// when it is ranked at least that high (0 to 100) on the anime, which
+    /// leaves out titles where the tag is a minor element. The returned
+    /// anime include their [`tags`](Anime::tags) with ranks. An empty `tags`
+    /// slice fails with [`AniListError::InvalidInput`] without sending a
+    /// request.
+    ///
+    /// To filter by tag category (`tagCategory_in`) instead, use
+    /// [`Self::search_advanced`] with [`MediaSearchFilter::tag_categories`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let time_travel = client
+    ///     .anime()
+    ///     .get_by_tags(&["Time Travel"], Some(80), 1, 25)
+    ///     .await?;
+    /// ```
+    pub async fn get_by_tags(
+        &self,
+        tags: &[&str],
+        min_tag_rank: Option<i32>,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        self.get_by_tags_sorted(tags, min_tag_rank, MediaSort::PopularityDesc, page, per_page)
+            .await
+    }
+
+    /// Same as [`Self::get_by_tags`], ordered by `sort`
+    pub async fn get_by_tags_sorted(
+        &self,
+        tags: &[&str],
+        min_tag_rank: Option<i32>,
+        sort: MediaSort,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        if tags.is_empty() {
+            return Err(AniListError::InvalidInput {
+                field: "tags".to_string(),
+                message: "must name at least one tag".to_string(),
+            });
+        }
+        let mut filter = MediaSearchFilter::new().tags(tags.iter().copied()).sort(sort);
+        if let Some(rank) = min_tag_rank {
+            filter = filter.minimum_tag_rank(rank);
+        }
+
+        self.fetch_media_page(page, per_page, filter.variables()?)
+            .await
     }
 
     /// Get currently airing anime
     pub async fn get_airing(&self, page: i32, per_page: i32) -> Result<Vec<Anime>, AniListError> {
-        let query = queries::anime::GET_AIRING;
+        let mut variables = HashMap::new();
+        variables.insert("status".to_string(), json!("RELEASING"));
+        variables.insert("sort".to_string(), json!(["POPULARITY_DESC"]));
+
+        self.fetch_media_page(page, per_page, variables).await
+    }
+
+    /// Get anime premiering in the next `days` days (today included), soonest first
+    ///
+    /// Unlike the next season's listing, this only includes titles that have
+    /// not started yet and have a start date inside the window, computed from
+    /// today's UTC date (see [`seasons::premiere_window`]). Titles whose start
+    /// date is only known to the month appear once the window reaches that
+    /// month.
+    ///
+    /// Fails with [`AniListError::InvalidInput`] if `days` reaches past the
+    /// dates AniList can filter on.
+    pub async fn get_premiering_within(
+        &self,
+        days: u32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Anime>, AniListError> {
+        let (after, before) = seasons::premiere_window(chrono::Utc::now().date_naive(), days)?;
 
         let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.insert("status".to_string(), json!("NOT_YET_RELEASED"));
+        variables.insert("startDateGreater".to_string(), json!(after));
+        variables.insert("startDateLesser".to_string(), json!(before));
+        variables.insert("sort".to_string(), json!(["START_DATE"]));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let anime_list: Vec<Anime> = serde_json::from_value(data)?;
-        Ok(anime_list)
+        self.fetch_media_page(page, per_page, variables).await
     }
+
+    /// Fetches one page of anime through the shared browse document.
+    ///
+    /// Every list method goes through here so they all select the same
+    /// fields; `variables` carries the method's sort and filters.
+    async fn fetch_media_page(
+        &self,
+        page: i32,
+        per_page: i32,
+        variables: HashMap<String, Value>,
+    ) -> Result<Vec<Anime>, AniListError> {
+        Ok(self.fetch_media_paged(page, per_page, variables).await?.items)
+    }
+
+    /// Like [`Self::fetch_media_page`], keeping the pagination info.
+    async fn fetch_media_paged(
+        &self,
+        page: i32,
+        per_page: i32,
+        mut variables: HashMap<String, Value>,
+    ) -> Result<Page<Anime>, AniListError> {
+        variables.extend(limits::page_variables(page, per_page)?);
+
+        let response = self
+            .client
+            .query(queries::anime::GET_MEDIA_PAGE, Some(variables))
+            .await?;
+        Page::extract(response, "media")
+    }
+}
+
+/// Browse variables for the anime of `season` in `year`, most popular first.
+fn season_variables(season: &str, year: i32) -> HashMap<String, Value> {
+    let mut variables = HashMap::new();
+    variables.insert("season".to_string(), json!(season.to_uppercase()));
+    variables.insert("year".to_string(), json!(year));
+    variables.insert("sort".to_string(), json!(["POPULARITY_DESC"]));
+    variables
+}
+
+/// Fetches the score and list status distributions of any media entry.
+pub(crate) async fn fetch_stats(
+    client: &AniListClient,
+    id: i32,
+) -> Result<MediaStats, AniListError> {
+    let query = queries::anime::GET_STATS;
+
+    let mut variables = HashMap::new();
+    variables.insert("id".to_string(), json!(id));
+
+    let stats: Option<MediaStats> = client
+        .query_as(query, Some(variables), &["data", "Media", "stats"])
+        .await?;
+    Ok(stats.unwrap_or_default())
+}
+
+/// Fetches media suggestions of the given type, consulting the client's
+/// autocomplete cache first.
+pub(crate) async fn fetch_suggestions(
+    client: &AniListClient,
+    media_type: MediaType,
+    search: &str,
+    limit: i32,
+) -> Result<Vec<MediaSuggestion>, AniListError> {
+    // An empty typeahead box is normal input, not an error
+    let Ok(search) = normalize_search(search) else {
+        return Ok(Vec::new());
+    };
+    let cache_key = format!("{media_type:?}:{limit}:{}", search.to_lowercase());
+
+    if let Some(suggestions) = client.autocomplete_cache().get(&cache_key) {
+        return Ok(suggestions);
+    }
+
+    let query = queries::anime::AUTOCOMPLETE;
+
+    let mut variables = HashMap::new();
+    variables.insert("search".to_string(), json!(search));
+    variables.insert("type".to_string(), json!(media_type));
+    variables.insert("perPage".to_string(), json!(limit));
+
+    let suggestions: Vec<MediaSuggestion> = client
+        .query_as(query, Some(variables), &["data", "Page", "media"])
+        .await?;
+
+    client
+        .autocomplete_cache()
+        .insert(cache_key, suggestions.clone());
+    Ok(suggestions)
 }
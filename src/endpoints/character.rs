@@ -1,5 +1,6 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
+use crate::limits;
 use crate::models::character::Character;
 use crate::queries;
 use serde_json::json;
@@ -22,13 +23,12 @@ impl CharacterEndpoint {
     ) -> Result<Vec<Character>, AniListError> {
         let query = queries::character::GET_POPULAR;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["characters"].clone();
-        let characters: Vec<Character> = serde_json::from_value(data)?;
+        let characters: Vec<Character> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "characters"])
+            .await?;
         Ok(characters)
     }
 
@@ -39,9 +39,10 @@ impl CharacterEndpoint {
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Character"].clone();
-        let character: Character = serde_json::from_value(data)?;
+        let character: Character = self
+            .client
+            .query_as(query, Some(variables), &["data", "Character"])
+            .await?;
         Ok(character)
     }
 
@@ -52,16 +53,19 @@ impl CharacterEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Character>, AniListError> {
+        let Some(search) = self.client.prepare_search(search)? else {
+            return Ok(Vec::new());
+        };
         let query = queries::character::SEARCH;
 
         let mut variables = HashMap::new();
         variables.insert("search".to_string(), json!(search));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["characters"].clone();
-        let characters: Vec<Character> = serde_json::from_value(data)?;
+        let characters: Vec<Character> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "characters"])
+            .await?;
         Ok(characters)
     }
 
@@ -73,13 +77,12 @@ impl CharacterEndpoint {
     ) -> Result<Vec<Character>, AniListError> {
         let query = queries::character::GET_TODAY_BIRTHDAY;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["characters"].clone();
-        let characters: Vec<Character> = serde_json::from_value(data)?;
+        let characters: Vec<Character> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "characters"])
+            .await?;
 
         Ok(characters)
     }
@@ -92,13 +95,12 @@ impl CharacterEndpoint {
     ) -> Result<Vec<Character>, AniListError> {
         let query = queries::character::GET_MOST_FAVORITED;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["characters"].clone();
-        let characters: Vec<Character> = serde_json::from_value(data)?;
+        let characters: Vec<Character> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "characters"])
+            .await?;
         Ok(characters)
     }
 }
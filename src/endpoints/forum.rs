@@ -1,6 +1,11 @@
-use crate::client::AniListClient;
+use crate::client::{AniListClient, extract};
+use crate::endpoints::activity::fetch_likers;
 use crate::error::AniListError;
-use crate::models::social::{Thread, ThreadComment};
+use crate::limits;
+use crate::models::Page;
+use crate::models::social::{
+    LikeableType, Thread, ThreadComment, ThreadFilter, ThreadModeration, UserPreview,
+};
 use crate::queries;
 use serde_json::json;
 use std::collections::HashMap;
@@ -20,31 +25,230 @@ impl ForumEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Thread>, AniListError> {
-        let query = queries::forum::GET_RECENT_THREADS;
+        self.fetch_recent_threads(page, per_page, false).await
+    }
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+    /// Get recent threads with their bodies rendered as HTML
+    pub async fn get_recent_threads_as_html(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Thread>, AniListError> {
+        self.fetch_recent_threads(page, per_page, true).await
+    }
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["threads"].clone();
-        let threads: Vec<Thread> = serde_json::from_value(data)?;
-        Ok(threads)
+    /// Get recent threads, filtered
+    ///
+    /// Filters are applied after the page is fetched, so fewer than
+    /// `per_page` threads may be returned.
+    pub async fn get_recent_threads_filtered(
+        &self,
+        page: i32,
+        per_page: i32,
+        filter: &ThreadFilter,
+    ) -> Result<Vec<Thread>, AniListError> {
+        let threads = self.fetch_recent_threads(page, per_page, false).await?;
+        Ok(filter.apply(threads))
     }
 
     /// Get thread by ID
     pub async fn get_thread_by_id(&self, id: i32) -> Result<Thread, AniListError> {
+        self.fetch_thread_by_id(id, false).await
+    }
+
+    /// Get thread by ID with its body rendered as HTML
+    pub async fn get_thread_by_id_as_html(&self, id: i32) -> Result<Thread, AniListError> {
+        self.fetch_thread_by_id(id, true).await
+    }
+
+    /// Lock or unlock a thread (requires authentication and a moderator role)
+    ///
+    /// The client must have moderator mode enabled with
+    /// [`AniListClient::set_moderator_mode`]; otherwise, and when the account
+    /// lacks the moderator role, [`AniListError::AccessDenied`] is returned.
+    pub async fn lock_thread(
+        &self,
+        id: i32,
+        locked: bool,
+    ) -> Result<ThreadModeration, AniListError> {
+        self.moderate_thread(id, "locked", locked).await
+    }
+
+    /// Sticky or unsticky a thread (requires authentication and a moderator role)
+    ///
+    /// The client must have moderator mode enabled with
+    /// [`AniListClient::set_moderator_mode`]; otherwise, and when the account
+    /// lacks the moderator role, [`AniListError::AccessDenied`] is returned.
+    pub async fn sticky_thread(
+        &self,
+        id: i32,
+        sticky: bool,
+    ) -> Result<ThreadModeration, AniListError> {
+        self.moderate_thread(id, "sticky", sticky).await
+    }
+
+    async fn moderate_thread(
+        &self,
+        id: i32,
+        flag: &str,
+        value: bool,
+    ) -> Result<ThreadModeration, AniListError> {
+        if !self.client.is_moderator_mode() {
+            return Err(AniListError::AccessDenied);
+        }
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
+        let query = queries::forum::MODERATE_THREAD;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(id));
+        variables.insert(flag.to_string(), json!(value));
+
+        let response = match self.client.query(query, Some(variables)).await {
+            Ok(response) => response,
+            // AniList answers non-moderators with "Unauthorized" even though
+            // the token itself is valid
+            Err(AniListError::AuthenticationRequired) => return Err(AniListError::AccessDenied),
+            Err(AniListError::GraphQL { message, .. })
+                if message.to_lowercase().contains("unauthorized") =>
+            {
+                return Err(AniListError::AccessDenied);
+            }
+            Err(e) => return Err(e),
+        };
+        extract(response, &["data", "SaveThread"])
+    }
+
+    async fn fetch_recent_threads(
+        &self,
+        page: i32,
+        per_page: i32,
+        as_html: bool,
+    ) -> Result<Vec<Thread>, AniListError> {
+        let query = queries::forum::GET_RECENT_THREADS;
+
+        let mut variables = limits::page_variables(page, per_page)?;
+        variables.insert("asHtml".to_string(), json!(as_html));
+
+        let threads: Vec<Thread> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "threads"])
+            .await?;
+        Ok(threads)
+    }
+
+    async fn fetch_thread_by_id(&self, id: i32, as_html: bool) -> Result<Thread, AniListError> {
         let query = queries::forum::GET_THREAD_BY_ID;
 
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
+        variables.insert("asHtml".to_string(), json!(as_html));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Thread"].clone();
-        let thread: Thread = serde_json::from_value(data)?;
+        let thread: Thread = self
+            .client
+            .query_as(query, Some(variables), &["data", "Thread"])
+            .await?;
+        Ok(thread)
+    }
+
+    /// Get threads started by the authenticated user (requires authentication)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_my_threads(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<Thread>, AniListError> {
+        limits::check_page(page, per_page)?;
+        let viewer_id = self.client.viewer_id().await?;
+        self.fetch_threads_by_user(page, per_page, Some(viewer_id), None, None)
+            .await
+    }
+
+    /// Get threads the authenticated user has commented on (requires authentication)
+    ///
+    /// AniList has no "threads commented on by user" filter, so this uses the
+    /// `replyUserId` argument: it only returns threads whose *most recent* reply
+    /// was posted by the viewer. Threads where someone else replied afterwards
+    /// drop out of the result.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_my_commented_threads(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<Thread>, AniListError> {
+        limits::check_page(page, per_page)?;
+        let viewer_id = self.client.viewer_id().await?;
+        self.fetch_threads_by_user(page, per_page, None, Some(viewer_id), None)
+            .await
+    }
+
+    /// Get threads the authenticated user is subscribed to (requires authentication)
+    ///
+    /// Subscribed threads notify the user about new comments; use
+    /// [`Self::toggle_thread_subscription`] to unsubscribe.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_subscribed_threads(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<Thread>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+        self.fetch_threads_by_user(page, per_page, None, None, Some(true))
+            .await
+    }
+
+    /// Subscribe to or unsubscribe from a thread (requires authentication)
+    pub async fn toggle_thread_subscription(
+        &self,
+        id: i32,
+        subscribe: bool,
+    ) -> Result<Thread, AniListError> {
+        let query = queries::forum::TOGGLE_THREAD_SUBSCRIPTION;
+
+        let mut variables = HashMap::new();
+        variables.insert("threadId".to_string(), json!(id));
+        variables.insert("subscribe".to_string(), json!(subscribe));
+
+        let thread: Thread = self
+            .client
+            .query_as(
+                query,
+                Some(variables),
+                &["data", "ToggleThreadSubscription"],
+            )
+            .await?;
         Ok(thread)
     }
 
+    async fn fetch_threads_by_user(
+        &self,
+        page: i32,
+        per_page: i32,
+        user_id: Option<i32>,
+        reply_user_id: Option<i32>,
+        subscribed: Option<bool>,
+    ) -> Result<Page<Thread>, AniListError> {
+        let query = queries::forum::GET_THREADS_BY_USER;
+
+        let mut variables = limits::page_variables(page, per_page)?;
+        variables.insert("userId".to_string(), json!(user_id));
+        variables.insert("replyUserId".to_string(), json!(reply_user_id));
+        variables.insert("subscribed".to_string(), json!(subscribed));
+
+        let response = self.client.query(query, Some(variables)).await?;
+        Ok(Page::from_page_value(&response["data"]["Page"], "threads")?)
+    }
+
     /// Search threads
     pub async fn search_threads(
         &self,
@@ -52,17 +256,35 @@ impl ForumEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Thread>, AniListError> {
+        self.search_threads_filtered(search, page, per_page, &ThreadFilter::default())
+            .await
+    }
+
+    /// Search threads, filtered
+    ///
+    /// Filters are applied after the page is fetched, so fewer than
+    /// `per_page` threads may be returned.
+    pub async fn search_threads_filtered(
+        &self,
+        search: &str,
+        page: i32,
+        per_page: i32,
+        filter: &ThreadFilter,
+    ) -> Result<Vec<Thread>, AniListError> {
+        let Some(search) = self.client.prepare_search(search)? else {
+            return Ok(Vec::new());
+        };
         let query = queries::forum::SEARCH_THREADS;
 
         let mut variables = HashMap::new();
         variables.insert("search".to_string(), json!(search));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["threads"].clone();
-        let threads: Vec<Thread> = serde_json::from_value(data)?;
-        Ok(threads)
+        let threads: Vec<Thread> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "threads"])
+            .await?;
+        Ok(filter.apply(threads))
     }
 
     /// Get thread comments
@@ -76,12 +298,12 @@ impl ForumEndpoint {
 
         let mut variables = HashMap::new();
         variables.insert("threadId".to_string(), json!(thread_id));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["threadComments"].clone();
-        let comments: Vec<ThreadComment> = serde_json::from_value(data)?;
+        let comments: Vec<ThreadComment> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "threadComments"])
+            .await?;
         Ok(comments)
     }
 
@@ -92,6 +314,19 @@ impl ForumEndpoint {
         body: &str,
         categories: Option<Vec<i32>>,
     ) -> Result<Thread, AniListError> {
+        limits::check_min_chars(
+            "Thread title",
+            title,
+            limits::TEXT_MIN_CHARS,
+            "TEXT_MIN_CHARS",
+        )?;
+        limits::check_min_chars(
+            "Thread body",
+            body,
+            limits::TEXT_MIN_CHARS,
+            "TEXT_MIN_CHARS",
+        )?;
+
         let query = queries::forum::CREATE_THREAD;
 
         let mut variables = HashMap::new();
@@ -101,9 +336,10 @@ impl ForumEndpoint {
             variables.insert("categories".to_string(), json!(cats));
         }
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["SaveThread"].clone();
-        let thread: Thread = serde_json::from_value(data)?;
+        let thread: Thread = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveThread"])
+            .await?;
         Ok(thread)
     }
 
@@ -113,15 +349,18 @@ impl ForumEndpoint {
         thread_id: i32,
         comment: &str,
     ) -> Result<ThreadComment, AniListError> {
+        limits::check_min_chars("Comment", comment, limits::TEXT_MIN_CHARS, "TEXT_MIN_CHARS")?;
+
         let query = queries::forum::COMMENT_ON_THREAD;
 
         let mut variables = HashMap::new();
         variables.insert("threadId".to_string(), json!(thread_id));
         variables.insert("comment".to_string(), json!(comment));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["SaveThreadComment"].clone();
-        let thread_comment: ThreadComment = serde_json::from_value(data)?;
+        let thread_comment: ThreadComment = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveThreadComment"])
+            .await?;
         Ok(thread_comment)
     }
 
@@ -133,9 +372,10 @@ impl ForumEndpoint {
         variables.insert("id".to_string(), json!(id));
         variables.insert("type".to_string(), json!("THREAD"));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["ToggleLikeV2"].clone();
-        let thread: Thread = serde_json::from_value(data)?;
+        let thread: Thread = self
+            .client
+            .query_as(query, Some(variables), &["data", "ToggleLikeV2"])
+            .await?;
         Ok(thread)
     }
 
@@ -147,9 +387,44 @@ impl ForumEndpoint {
         variables.insert("id".to_string(), json!(id));
         variables.insert("type".to_string(), json!("THREAD_COMMENT"));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["ToggleLikeV2"].clone();
-        let comment: ThreadComment = serde_json::from_value(data)?;
+        let comment: ThreadComment = self
+            .client
+            .query_as(query, Some(variables), &["data", "ToggleLikeV2"])
+            .await?;
         Ok(comment)
     }
+
+    /// Get the users who liked a thread
+    pub async fn get_thread_likers(
+        &self,
+        thread_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<UserPreview>, AniListError> {
+        fetch_likers(
+            &self.client,
+            thread_id,
+            LikeableType::Thread,
+            page,
+            per_page,
+        )
+        .await
+    }
+
+    /// Get the users who liked a thread comment
+    pub async fn get_comment_likers(
+        &self,
+        comment_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<UserPreview>, AniListError> {
+        fetch_likers(
+            &self.client,
+            comment_id,
+            LikeableType::ThreadComment,
+            page,
+            per_page,
+        )
+        .await
+    }
 }
@@ -1,7 +1,14 @@
-use crate::client::AniListClient;
+use crate::client::{AniListClient, extract};
+use crate::endpoints::anime::{fetch_stats, fetch_suggestions};
+use crate::envelope::ResponseEnvelope;
 use crate::error::AniListError;
-use crate::models::Manga;
+use crate::limits;
+use crate::models::relation::{MediaRelationEdge, select_airing_adaptation};
+use crate::models::social::MediaType;
+use crate::models::{AiringSchedule, Manga, MediaRef, MediaStats, MediaSuggestion, Page};
 use crate::queries;
+use crate::utils::pagination::paginate;
+use futures_util::Stream;
 use serde_json::json;
 use std::collections::HashMap;
 
@@ -18,13 +25,12 @@ impl MangaEndpoint {
     pub async fn get_popular(&self, page: i32, per_page: i32) -> Result<Vec<Manga>, AniListError> {
         let query = queries::manga::GET_POPULAR;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let manga_list: Vec<Manga> = serde_json::from_value(data)?;
+        let manga_list: Vec<Manga> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "media"])
+            .await?;
         Ok(manga_list)
     }
 
@@ -32,27 +38,51 @@ impl MangaEndpoint {
     pub async fn get_trending(&self, page: i32, per_page: i32) -> Result<Vec<Manga>, AniListError> {
         let query = queries::manga::GET_TRENDING;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let manga_list: Vec<Manga> = serde_json::from_value(data)?;
+        let manga_list: Vec<Manga> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "media"])
+            .await?;
         Ok(manga_list)
     }
 
     /// Get manga by ID
+    ///
+    /// Adult titles requested without a token (or by an account that has not
+    /// enabled adult content) fail with [`AniListError::AdultContentBlocked`].
     pub async fn get_by_id(&self, id: i32) -> Result<Manga, AniListError> {
+        Ok(self.get_by_id_with_meta(id).await?.value)
+    }
+
+    /// Get manga by ID along with the response status, timing and
+    /// rate-limit/request-ID headers, for debugging
+    pub async fn get_by_id_with_meta(
+        &self,
+        id: i32,
+    ) -> Result<ResponseEnvelope<Manga>, AniListError> {
         let query = queries::manga::GET_BY_ID;
 
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Media"].clone();
-        let manga: Manga = serde_json::from_value(data)?;
-        Ok(manga)
+        let response = self.client.query_with_meta(query, Some(variables)).await?;
+        response.try_map(|json| extract(json, &["data", "Media"]))
+    }
+
+    /// Get lightweight manga suggestions for a typeahead search box
+    pub async fn autocomplete(
+        &self,
+        search: &str,
+        limit: i32,
+    ) -> Result<Vec<MediaSuggestion>, AniListError> {
+        fetch_suggestions(&self.client, MediaType::Manga, search, limit).await
+    }
+
+    /// Get how users have scored and listed a manga, see
+    /// [`AnimeEndpoint::get_stats`](crate::endpoints::AnimeEndpoint::get_stats)
+    pub async fn get_stats(&self, id: i32) -> Result<MediaStats, AniListError> {
+        fetch_stats(&self.client, id).await
     }
 
     /// Search manga by title
@@ -62,16 +92,19 @@ impl MangaEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Manga>, AniListError> {
+        let Some(search) = self.client.prepare_search(search)? else {
+            return Ok(Vec::new());
+        };
         let query = queries::manga::SEARCH;
 
         let mut variables = HashMap::new();
         variables.insert("search".to_string(), json!(search));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let manga_list: Vec<Manga> = serde_json::from_value(data)?;
+        let manga_list: Vec<Manga> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "media"])
+            .await?;
         Ok(manga_list)
     }
 
@@ -81,15 +114,36 @@ impl MangaEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Manga>, AniListError> {
-        let query = queries::manga::GET_TOP_RATED;
+        self.get_top_rated_with_min_popularity(page, per_page, None)
+            .await
+    }
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+    /// Get top rated manga that at least `min_popularity` users have on their lists
+    ///
+    /// The plain score ranking favours obscure titles rated highly by a handful
+    /// of users. AniList cannot filter on the number of scores directly, so
+    /// list popularity is used as the sample-size guard: a higher threshold
+    /// removes small-sample outliers but also hides genuinely good niche titles.
+    /// `None` applies no threshold, matching [`Self::get_top_rated`].
+    pub async fn get_top_rated_with_min_popularity(
+        &self,
+        page: i32,
+        per_page: i32,
+        min_popularity: Option<i32>,
+    ) -> Result<Vec<Manga>, AniListError> {
+        let query = queries::manga::GET_TOP_RATED;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let manga_list: Vec<Manga> = serde_json::from_value(data)?;
+        let mut variables = limits::page_variables(page, per_page)?;
+        // AniList's filter is exclusive; shift it so the threshold itself is kept
+        variables.insert(
+            "popularityGreater".to_string(),
+            json!(min_popularity.map(|min| min.saturating_sub(1))),
+        );
+
+        let manga_list: Vec<Manga> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "media"])
+            .await?;
         Ok(manga_list)
     }
 
@@ -99,16 +153,33 @@ impl MangaEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Manga>, AniListError> {
+        Ok(self.get_releasing_page(page, per_page).await?.items)
+    }
+
+    /// Get currently releasing manga, with the page's pagination info
+    pub async fn get_releasing_page(
+        &self,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Page<Manga>, AniListError> {
         let query = queries::manga::GET_RELEASING;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
         let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let manga_list: Vec<Manga> = serde_json::from_value(data)?;
-        Ok(manga_list)
+        Page::extract(response, "media")
+    }
+
+    /// Stream every currently releasing manga, fetching `per_page` at a time
+    ///
+    /// See [`crate::utils::pagination`].
+    pub fn get_releasing_stream(
+        &self,
+        per_page: i32,
+    ) -> impl Stream<Item = Result<Manga, AniListError>> + use<> {
+        paginate(self.client.clone(), move |client, page| async move {
+            client.manga().get_releasing_page(page, per_page).await
+        })
     }
 
     /// Get completed manga
@@ -119,13 +190,36 @@ impl MangaEndpoint {
     ) -> Result<Vec<Manga>, AniListError> {
         let query = queries::manga::GET_COMPLETED;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["media"].clone();
-        let manga_list: Vec<Manga> = serde_json::from_value(data)?;
+        let manga_list: Vec<Manga> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "media"])
+            .await?;
         Ok(manga_list)
     }
+
+    /// Get the next airing episode of a manga's anime adaptation
+    ///
+    /// Returns `None` if the manga has no anime adaptation with a scheduled
+    /// episode. When there are several, a currently releasing one is preferred.
+    pub async fn get_adaptation_airing(
+        &self,
+        manga_id: i32,
+    ) -> Result<Option<(MediaRef, AiringSchedule)>, AniListError> {
+        let query = queries::manga::GET_ADAPTATION_AIRING;
+
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), json!(manga_id));
+
+        let edges: Option<Vec<MediaRelationEdge>> = self
+            .client
+            .query_as(
+                query,
+                Some(variables),
+                &["data", "Media", "relations", "edges"],
+            )
+            .await?;
+        Ok(edges.and_then(|edges| select_airing_adaptation(&edges)))
+    }
 }
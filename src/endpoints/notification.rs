@@ -1,9 +1,21 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
-use crate::models::social::Notification;
+use crate::limits::{self, MAX_NOTIFICATION_IDS_PER_MUTATION};
+use crate::models::social::{
+    MarkReadReport, MediaType, Notification, NotificationTarget, ResolvedResource,
+};
 use crate::queries;
 use serde_json::json;
 use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+/// Times a failed mark-as-read chunk is retried before it is reported as failed
+const MARK_READ_CHUNK_RETRIES: u32 = 2;
+
+/// Wait before the first retry of a failed chunk, doubled for each retry after
+/// it, unless the error says how long to wait
+const MARK_READ_RETRY_DELAY: Duration = Duration::from_secs(1);
 
 pub struct NotificationEndpoint {
     client: AniListClient,
@@ -15,25 +27,38 @@ impl NotificationEndpoint {
     }
 
     /// Get user notifications (requires authentication)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
     pub async fn get_notifications(
         &self,
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Notification>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
         let query = queries::notification::GET_NOTIFICATIONS;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["notifications"].clone();
-        let notifications: Vec<Notification> = serde_json::from_value(data)?;
+        let notifications: Vec<Notification> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "notifications"])
+            .await?;
         Ok(notifications)
     }
 
     /// Get unread notification count (requires authentication)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
     pub async fn get_unread_count(&self) -> Result<i32, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
         let query = queries::notification::GET_UNREAD_COUNT;
 
         let response = self.client.query(query, None).await?;
@@ -44,37 +69,164 @@ impl NotificationEndpoint {
     }
 
     /// Get notifications by type (requires authentication)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
     pub async fn get_notifications_by_type(
         &self,
         notification_type: &str,
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Notification>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
         let query = queries::notification::GET_NOTIFICATIONS_BY_TYPE;
 
         let mut variables = HashMap::new();
         variables.insert("type".to_string(), json!([notification_type]));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["notifications"].clone();
-        let notifications: Vec<Notification> = serde_json::from_value(data)?;
+        let notifications: Vec<Notification> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "notifications"])
+            .await?;
         Ok(notifications)
     }
 
     /// Mark notifications as read (requires authentication)
+    ///
+    /// Returns `Ok(true)` if every ID was marked. IDs are sent in chunks; see
+    /// [`Self::mark_notifications_as_read_chunked`] for per-chunk results. If
+    /// no chunk succeeds the first error is returned.
     pub async fn mark_notifications_as_read(
         &self,
         notification_ids: Vec<i32>,
     ) -> Result<bool, AniListError> {
+        let mut report = self
+            .mark_notifications_as_read_chunked(&notification_ids)
+            .await;
+        if report.marked.is_empty() && !report.failed.is_empty() {
+            let (_, error) = report.failed.swap_remove(0);
+            return Err(error);
+        }
+        Ok(report.is_complete())
+    }
+
+    /// Mark notifications as read in chunks, reporting which IDs failed (requires authentication)
+    ///
+    /// AniList rejects very large ID arrays, so IDs are sent
+    /// [`MAX_NOTIFICATION_IDS_PER_MUTATION`] at a time. Chunks failing with an
+    /// error that [`AniListError::is_retryable`] accepts are retried twice,
+    /// waiting [`AniListError::retry_delay`] after a rate limit; an empty list
+    /// sends nothing.
+    pub async fn mark_notifications_as_read_chunked(
+        &self,
+        notification_ids: &[i32],
+    ) -> MarkReadReport {
+        mark_read_in_chunks(
+            notification_ids,
+            MAX_NOTIFICATION_IDS_PER_MUTATION,
+            MARK_READ_CHUNK_RETRIES,
+            |chunk| self.send_mark_read(chunk),
+        )
+        .await
+    }
+
+    async fn send_mark_read(&self, notification_ids: Vec<i32>) -> Result<(), AniListError> {
         let query = queries::notification::MARK_NOTIFICATIONS_AS_READ;
 
         let mut variables = HashMap::new();
         variables.insert("notificationIds".to_string(), json!(notification_ids));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        // If we get a response without errors, consider it successful
-        Ok(response["data"]["SaveNotificationSettings"].is_object())
+        self.client.query(query, Some(variables)).await?;
+        Ok(())
+    }
+}
+
+impl Notification {
+    /// Fetches the resource this notification refers to, as worked out by
+    /// [`Notification::target`].
+    ///
+    /// Activities are fetched with their type-specific fields; comment
+    /// notifications resolve to the containing thread. Media targets of unknown
+    /// type are looked up as anime.
+    pub async fn load_context(
+        &self,
+        client: &AniListClient,
+    ) -> Result<ResolvedResource, AniListError> {
+        let resource = match self.target() {
+            NotificationTarget::Activity(id) => {
+                ResolvedResource::Activity(client.activity().get_activity_detail(id).await?)
+            }
+            NotificationTarget::Thread(id) => {
+                ResolvedResource::Thread(client.forum().get_thread_by_id(id).await?)
+            }
+            NotificationTarget::ThreadComment {
+                thread_id,
+                comment_id,
+            } => ResolvedResource::ThreadComment {
+                thread: client.forum().get_thread_by_id(thread_id).await?,
+                comment_id,
+            },
+            NotificationTarget::Media {
+                id,
+                media_type: Some(MediaType::Manga),
+            } => ResolvedResource::Manga(Box::new(client.manga().get_by_id(id).await?)),
+            NotificationTarget::Media { id, .. } => {
+                ResolvedResource::Anime(Box::new(client.anime().get_by_id(id).await?))
+            }
+            NotificationTarget::User(id) => {
+                ResolvedResource::User(Box::new(client.user().get_by_id(id).await?))
+            }
+            NotificationTarget::None => ResolvedResource::None,
+        };
+        Ok(resource)
+    }
+}
+
+/// Sends `ids` through `send` in chunks of `chunk_size`, retrying each failed
+/// chunk up to `max_retries` times on errors that
+/// [`AniListError::is_retryable`] accepts.
+///
+/// Retries wait [`AniListError::retry_delay`] for rate limits and back off
+/// exponentially from `MARK_READ_RETRY_DELAY` otherwise.
+pub(crate) async fn mark_read_in_chunks<F, Fut>(
+    ids: &[i32],
+    chunk_size: usize,
+    max_retries: u32,
+    mut send: F,
+) -> MarkReadReport
+where
+    F: FnMut(Vec<i32>) -> Fut,
+    Fut: Future<Output = Result<(), AniListError>>,
+{
+    let mut report = MarkReadReport::default();
+
+    for chunk in ids.chunks(chunk_size.max(1)) {
+        let mut attempt = 0;
+        loop {
+            report.mutations += 1;
+            match send(chunk.to_vec()).await {
+                Ok(()) => {
+                    report.marked.extend_from_slice(chunk);
+                    break;
+                }
+                Err(error) if attempt < max_retries && error.is_retryable() => {
+                    let delay = error.retry_delay().unwrap_or_else(|| {
+                        MARK_READ_RETRY_DELAY.saturating_mul(2u32.saturating_pow(attempt))
+                    });
+                    attempt += 1;
+                    crate::runtime::sleep(delay).await;
+                }
+                Err(error) => {
+                    report.failed.push((chunk.to_vec(), error));
+                    break;
+                }
+            }
+        }
     }
+
+    report
 }
@@ -1,9 +1,20 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
-use crate::models::social::Recommendation;
+use crate::limits;
+use crate::models::personalized::{
+    PersonalizedRecommendation, RecommendationCandidate, pick_sources, rank_personalized,
+};
+use crate::models::relation::MediaRef;
+use crate::models::social::{Recommendation, RecommendationRating, RecommendationUpsert};
 use crate::queries;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Completed titles used as sources by `get_personalized`
+const PERSONALIZED_SOURCES: usize = 5;
+
+/// Community recommendations fetched per source by `get_personalized`
+const PERSONALIZED_PER_SOURCE: i32 = 10;
 
 pub struct RecommendationEndpoint {
     client: AniListClient,
@@ -22,13 +33,12 @@ impl RecommendationEndpoint {
     ) -> Result<Vec<Recommendation>, AniListError> {
         let query = queries::recommendation::GET_RECENT_RECOMMENDATIONS;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["recommendations"].clone();
-        let recommendations: Vec<Recommendation> = serde_json::from_value(data)?;
+        let recommendations: Vec<Recommendation> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "recommendations"])
+            .await?;
         Ok(recommendations)
     }
 
@@ -43,12 +53,12 @@ impl RecommendationEndpoint {
 
         let mut variables = HashMap::new();
         variables.insert("mediaId".to_string(), json!(media_id));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["recommendations"].clone();
-        let recommendations: Vec<Recommendation> = serde_json::from_value(data)?;
+        let recommendations: Vec<Recommendation> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "recommendations"])
+            .await?;
         Ok(recommendations)
     }
 
@@ -60,13 +70,12 @@ impl RecommendationEndpoint {
     ) -> Result<Vec<Recommendation>, AniListError> {
         let query = queries::recommendation::GET_TOP_RATED_RECOMMENDATIONS;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["recommendations"].clone();
-        let recommendations: Vec<Recommendation> = serde_json::from_value(data)?;
+        let recommendations: Vec<Recommendation> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "recommendations"])
+            .await?;
         Ok(recommendations)
     }
 
@@ -77,9 +86,10 @@ impl RecommendationEndpoint {
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Recommendation"].clone();
-        let recommendation: Recommendation = serde_json::from_value(data)?;
+        let recommendation: Recommendation = self
+            .client
+            .query_as(query, Some(variables), &["data", "Recommendation"])
+            .await?;
         Ok(recommendation)
     }
 
@@ -107,9 +117,10 @@ impl RecommendationEndpoint {
             variables.insert("rating".to_string(), json!(rating_str));
         }
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["SaveRecommendation"].clone();
-        let recommendation: Recommendation = serde_json::from_value(data)?;
+        let recommendation: Recommendation = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveRecommendation"])
+            .await?;
         Ok(recommendation)
     }
 
@@ -131,9 +142,150 @@ impl RecommendationEndpoint {
         variables.insert("recommendationId".to_string(), json!(recommendation_id));
         variables.insert("rating".to_string(), json!(rating_str));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["SaveRecommendation"].clone();
-        let recommendation: Recommendation = serde_json::from_value(data)?;
+        let recommendation: Recommendation = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveRecommendation"])
+            .await?;
         Ok(recommendation)
     }
+
+    /// Get the recommendation for a media pair, including the viewer's rating (requires authentication)
+    ///
+    /// Returns `Ok(None)` when nobody has recommended `media_recommendation_id` for `media_id` yet.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_my_recommendation(
+        &self,
+        media_id: i32,
+        media_recommendation_id: i32,
+    ) -> Result<Option<Recommendation>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
+        let query = queries::recommendation::GET_RECOMMENDATION_FOR_PAIR;
+
+        let mut variables = HashMap::new();
+        variables.insert("mediaId".to_string(), json!(media_id));
+        variables.insert(
+            "mediaRecommendationId".to_string(),
+            json!(media_recommendation_id),
+        );
+
+        let mut recommendations: Vec<Recommendation> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "recommendations"])
+            .await?;
+        if recommendations.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(recommendations.swap_remove(0)))
+        }
+    }
+
+    /// Recommend `pair.1` for `pair.0` with the given rating, only mutating when needed (requires authentication)
+    ///
+    /// Looks up the existing recommendation first and skips the `SaveRecommendation`
+    /// mutation when the viewer's rating already matches, so repeated calls don't
+    /// create duplicates or churn the rating.
+    pub async fn recommend_or_update(
+        &self,
+        pair: (i32, i32),
+        rating: RecommendationRating,
+    ) -> Result<(RecommendationUpsert, Recommendation), AniListError> {
+        let (media_id, media_recommendation_id) = pair;
+        let existing = self
+            .get_my_recommendation(media_id, media_recommendation_id)
+            .await?;
+
+        let outcome = RecommendationUpsert::plan(existing.as_ref(), rating);
+        if let Some(recommendation) = existing
+            && !outcome.needs_mutation()
+        {
+            return Ok((outcome, recommendation));
+        }
+
+        let query = queries::recommendation::SAVE_RECOMMENDATION;
+
+        let mut variables = HashMap::new();
+        variables.insert("mediaId".to_string(), json!(media_id));
+        variables.insert(
+            "mediaRecommendationId".to_string(),
+            json!(media_recommendation_id),
+        );
+        variables.insert("rating".to_string(), json!(rating));
+
+        let recommendation: Recommendation = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveRecommendation"])
+            .await?;
+        Ok((outcome, recommendation))
+    }
+
+    /// Get "because you watched X" anime suggestions for the viewer (requires authentication)
+    ///
+    /// Uses the viewer's five highest scored completed anime as sources, takes
+    /// the top ten community recommendations for each, drops titles already on
+    /// the viewer's list (in any status) and returns up to `limit` suggestions,
+    /// best first. See [`rank_personalized`] for the ranking.
+    ///
+    /// Always makes exactly three requests: the viewer, their anime list, and
+    /// one batched query for all sources' recommendations.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_personalized(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<PersonalizedRecommendation>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
+        let entries = self.client.user().get_current_user_anime_list(None).await?;
+        let on_list: HashSet<i32> = entries.iter().map(|entry| entry.media_id).collect();
+        let sources = pick_sources(&entries, PERSONALIZED_SOURCES);
+        if sources.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = queries::recommendation::GET_RECOMMENDATIONS_FOR_SOURCES;
+        let ids: Vec<i32> = sources.iter().map(|(id, _)| *id).collect();
+
+        let mut variables = HashMap::new();
+        variables.insert("ids".to_string(), json!(ids));
+        variables.insert("perSource".to_string(), json!(PERSONALIZED_PER_SOURCE));
+
+        let response = self.client.query(query, Some(variables)).await?;
+        let weights: HashMap<i32, f64> = sources.into_iter().collect();
+
+        let mut candidates = Vec::new();
+        for media in response["data"]["Page"]["media"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            let source: MediaRef = serde_json::from_value(media.clone())?;
+            let source_weight = weights.get(&source.id).copied().unwrap_or(1.0);
+
+            for node in media["recommendations"]["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+            {
+                if node["mediaRecommendation"].is_null() {
+                    continue;
+                }
+                candidates.push(RecommendationCandidate {
+                    source: source.clone(),
+                    source_weight,
+                    suggestion: serde_json::from_value(node["mediaRecommendation"].clone())?,
+                    rating: node["rating"].as_i64().unwrap_or(0) as i32,
+                });
+            }
+        }
+
+        Ok(rank_personalized(candidates, &on_list, limit))
+    }
 }
@@ -1,5 +1,6 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
+use crate::limits;
 use crate::models::social::Review;
 use crate::queries;
 use serde_json::json;
@@ -22,13 +23,12 @@ impl ReviewEndpoint {
     ) -> Result<Vec<Review>, AniListError> {
         let query = queries::review::GET_RECENT_REVIEWS;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["reviews"].clone();
-        let reviews: Vec<Review> = serde_json::from_value(data)?;
+        let reviews: Vec<Review> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "reviews"])
+            .await?;
         Ok(reviews)
     }
 
@@ -43,12 +43,42 @@ impl ReviewEndpoint {
 
         let mut variables = HashMap::new();
         variables.insert("mediaId".to_string(), json!(media_id));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["reviews"].clone();
-        let reviews: Vec<Review> = serde_json::from_value(data)?;
+        let reviews: Vec<Review> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "reviews"])
+            .await?;
+        Ok(reviews)
+    }
+
+    /// Get reviews by media ID along with the viewer's list status for the media
+    ///
+    /// With an authenticated client each review's [`ReviewMedia::viewer_entry`]
+    /// is populated when the media is on the viewer's list; without a token
+    /// the status is not requested and `viewer_entry` is always `None`.
+    ///
+    /// [`ReviewMedia::viewer_entry`]: crate::models::social::ReviewMedia::viewer_entry
+    pub async fn get_reviews_for_media_with_viewer_status(
+        &self,
+        media_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<Vec<Review>, AniListError> {
+        let query = queries::review::GET_REVIEWS_FOR_MEDIA;
+
+        let mut variables = HashMap::new();
+        variables.insert("mediaId".to_string(), json!(media_id));
+        variables.extend(limits::page_variables(page, per_page)?);
+        variables.insert(
+            "withViewerStatus".to_string(),
+            json!(self.client.has_token()),
+        );
+
+        let reviews: Vec<Review> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "reviews"])
+            .await?;
         Ok(reviews)
     }
 
@@ -63,12 +93,12 @@ impl ReviewEndpoint {
 
         let mut variables = HashMap::new();
         variables.insert("userId".to_string(), json!(user_id));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["reviews"].clone();
-        let reviews: Vec<Review> = serde_json::from_value(data)?;
+        let reviews: Vec<Review> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "reviews"])
+            .await?;
         Ok(reviews)
     }
 
@@ -79,13 +109,17 @@ impl ReviewEndpoint {
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Review"].clone();
-        let review: Review = serde_json::from_value(data)?;
+        let review: Review = self
+            .client
+            .query_as(query, Some(variables), &["data", "Review"])
+            .await?;
         Ok(review)
     }
 
     /// Create or update a review (requires authentication)
+    ///
+    /// The body, summary and score are checked against the limits in
+    /// [`crate::limits`] before the request is sent.
     pub async fn save_review(
         &self,
         media_id: i32,
@@ -94,6 +128,35 @@ impl ReviewEndpoint {
         score: Option<i32>,
         private: Option<bool>,
     ) -> Result<Review, AniListError> {
+        limits::check_min_chars(
+            "Review body",
+            body,
+            limits::REVIEW_BODY_MIN_CHARS,
+            "REVIEW_BODY_MIN_CHARS",
+        )?;
+        if let Some(summary) = summary {
+            limits::check_min_chars(
+                "Review summary",
+                summary,
+                limits::SUMMARY_MIN_CHARS,
+                "SUMMARY_MIN_CHARS",
+            )?;
+            limits::check_max_chars(
+                "Review summary",
+                summary,
+                limits::SUMMARY_MAX_CHARS,
+                "SUMMARY_MAX_CHARS",
+            )?;
+        }
+        if let Some(score) = score {
+            limits::check_range(
+                "Review score",
+                score,
+                (limits::REVIEW_SCORE_MIN, "REVIEW_SCORE_MIN"),
+                (limits::REVIEW_SCORE_MAX, "REVIEW_SCORE_MAX"),
+            )?;
+        }
+
         let query = queries::review::SAVE_REVIEW;
 
         let mut variables = HashMap::new();
@@ -109,9 +172,10 @@ impl ReviewEndpoint {
             variables.insert("private".to_string(), json!(p));
         }
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["SaveReview"].clone();
-        let review: Review = serde_json::from_value(data)?;
+        let review: Review = self
+            .client
+            .query_as(query, Some(variables), &["data", "SaveReview"])
+            .await?;
         Ok(review)
     }
 
@@ -123,9 +187,10 @@ impl ReviewEndpoint {
         variables.insert("reviewId".to_string(), json!(review_id));
         variables.insert("rating".to_string(), json!(rating));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["RateReview"].clone();
-        let review: Review = serde_json::from_value(data)?;
+        let review: Review = self
+            .client
+            .query_as(query, Some(variables), &["data", "RateReview"])
+            .await?;
         Ok(review)
     }
 
@@ -151,13 +216,12 @@ impl ReviewEndpoint {
     ) -> Result<Vec<Review>, AniListError> {
         let query = queries::review::GET_TOP_RATED_REVIEWS;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["reviews"].clone();
-        let reviews: Vec<Review> = serde_json::from_value(data)?;
+        let reviews: Vec<Review> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "reviews"])
+            .await?;
         Ok(reviews)
     }
 }
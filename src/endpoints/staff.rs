@@ -1,5 +1,6 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
+use crate::limits;
 use crate::models::staff::Staff;
 use crate::queries;
 use serde_json::json;
@@ -18,13 +19,12 @@ impl StaffEndpoint {
     pub async fn get_popular(&self, page: i32, per_page: i32) -> Result<Vec<Staff>, AniListError> {
         let query = queries::staff::GET_POPULAR;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["staff"].clone();
-        let staff_list: Vec<Staff> = serde_json::from_value(data)?;
+        let staff_list: Vec<Staff> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "staff"])
+            .await?;
         Ok(staff_list)
     }
 
@@ -35,9 +35,10 @@ impl StaffEndpoint {
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Staff"].clone();
-        let staff: Staff = serde_json::from_value(data)?;
+        let staff: Staff = self
+            .client
+            .query_as(query, Some(variables), &["data", "Staff"])
+            .await?;
         Ok(staff)
     }
 
@@ -48,16 +49,19 @@ impl StaffEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Staff>, AniListError> {
+        let Some(search) = self.client.prepare_search(search)? else {
+            return Ok(Vec::new());
+        };
         let query = queries::staff::SEARCH;
 
         let mut variables = HashMap::new();
         variables.insert("search".to_string(), json!(search));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["staff"].clone();
-        let staff_list: Vec<Staff> = serde_json::from_value(data)?;
+        let staff_list: Vec<Staff> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "staff"])
+            .await?;
         Ok(staff_list)
     }
 
@@ -69,13 +73,12 @@ impl StaffEndpoint {
     ) -> Result<Vec<Staff>, AniListError> {
         let query = queries::staff::GET_TODAY_BIRTHDAY;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["staff"].clone();
-        let staff_list: Vec<Staff> = serde_json::from_value(data)?;
+        let staff_list: Vec<Staff> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "staff"])
+            .await?;
 
         Ok(staff_list)
     }
@@ -88,13 +91,12 @@ impl StaffEndpoint {
     ) -> Result<Vec<Staff>, AniListError> {
         let query = queries::staff::GET_MOST_FAVORITED;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["staff"].clone();
-        let staff_list: Vec<Staff> = serde_json::from_value(data)?;
+        let staff_list: Vec<Staff> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "staff"])
+            .await?;
         Ok(staff_list)
     }
 }
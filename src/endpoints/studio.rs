@@ -1,5 +1,6 @@
 use crate::client::AniListClient;
 use crate::error::AniListError;
+use crate::limits;
 use crate::models::social::Studio;
 use crate::queries;
 use serde_json::json;
@@ -18,13 +19,12 @@ impl StudioEndpoint {
     pub async fn get_popular(&self, page: i32, per_page: i32) -> Result<Vec<Studio>, AniListError> {
         let query = queries::studio::GET_POPULAR;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["studios"].clone();
-        let studios: Vec<Studio> = serde_json::from_value(data)?;
+        let studios: Vec<Studio> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "studios"])
+            .await?;
         Ok(studios)
     }
 
@@ -35,9 +35,10 @@ impl StudioEndpoint {
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Studio"].clone();
-        let studio: Studio = serde_json::from_value(data)?;
+        let studio: Studio = self
+            .client
+            .query_as(query, Some(variables), &["data", "Studio"])
+            .await?;
         Ok(studio)
     }
 
@@ -48,16 +49,19 @@ impl StudioEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<Studio>, AniListError> {
+        let Some(search) = self.client.prepare_search(search)? else {
+            return Ok(Vec::new());
+        };
         let query = queries::studio::SEARCH;
 
         let mut variables = HashMap::new();
         variables.insert("search".to_string(), json!(search));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["studios"].clone();
-        let studios: Vec<Studio> = serde_json::from_value(data)?;
+        let studios: Vec<Studio> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "studios"])
+            .await?;
         Ok(studios)
     }
 
@@ -69,13 +73,12 @@ impl StudioEndpoint {
     ) -> Result<Vec<Studio>, AniListError> {
         let query = queries::studio::GET_MOST_FAVORITED;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["studios"].clone();
-        let studios: Vec<Studio> = serde_json::from_value(data)?;
+        let studios: Vec<Studio> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "studios"])
+            .await?;
         Ok(studios)
     }
 
@@ -1,10 +1,21 @@
-use crate::client::AniListClient;
+use crate::client::{AniListClient, extract};
+use crate::envelope::ResponseEnvelope;
 use crate::error::AniListError;
-use crate::models::FuzzyDate;
-use crate::models::media_list::{MediaList, MediaListStatus};
-use crate::models::user::User;
+use crate::limits::{self, DEFAULT_MAX_CHUNKS, MAX_PER_CHUNK, MAX_PER_PAGE};
+use crate::models::media_list::{
+    ListCollection, MediaList, MediaListCollection, MediaListStatus, NextUpEntry, SaveListEntry,
+    UpdatedSince, next_up,
+};
+use crate::models::social::MediaType;
+use crate::models::user::{
+    FavouriteIdsPage, FavouriteItem, FavouriteSet, FavouriteSyncOptions, ProfileUpdateOptions,
+    SyncReport, User,
+};
+use crate::models::{FuzzyDate, Page};
 use crate::queries;
-use serde_json::json;
+use crate::utils::pagination::{AllPages, collect_all};
+use crate::utils::rate_limit_delay;
+use serde_json::{Value, json};
 use std::collections::HashMap;
 
 pub struct UserEndpoint {
@@ -17,75 +28,281 @@ impl UserEndpoint {
     }
 
     /// Get the currently authenticated user (requires token)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
     pub async fn get_current_user(&self) -> Result<User, AniListError> {
+        Ok(self.get_current_user_with_meta().await?.value)
+    }
+
+    /// Get the currently authenticated user along with the response status,
+    /// timing and rate-limit/request-ID headers, for debugging (requires token)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_current_user_with_meta(&self) -> Result<ResponseEnvelope<User>, AniListError> {
+        self.fetch_current_user(false).await
+    }
+
+    /// Get the currently authenticated user with their About text rendered as
+    /// HTML (requires token)
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_current_user_as_html(&self) -> Result<User, AniListError> {
+        Ok(self.fetch_current_user(true).await?.value)
+    }
+
+    async fn fetch_current_user(
+        &self,
+        as_html: bool,
+    ) -> Result<ResponseEnvelope<User>, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
         let query = queries::user::GET_CURRENT_USER;
 
-        let response = self.client.query(query, None).await?;
-        let data = response["data"]["Viewer"].clone();
-        let user: User = serde_json::from_value(data)?;
-        Ok(user)
+        let mut variables = HashMap::new();
+        variables.insert("asHtml".to_string(), json!(as_html));
+
+        let response = self.client.query_with_meta(query, Some(variables)).await?;
+        response.try_map(|json| extract(json, &["data", "Viewer"]))
     }
 
     /// Get the current user's anime list (requires token)
+    ///
+    /// Fetches every chunk of the list, up to [`DEFAULT_MAX_CHUNKS`]; use
+    /// [`Self::get_list_collection`] to set the limit and find out whether it
+    /// was hit.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
     pub async fn get_current_user_anime_list(
         &self,
         status: Option<&str>,
     ) -> Result<Vec<MediaList>, AniListError> {
-        let query = queries::user::GET_CURRENT_USER_ANIME_LIST;
+        let user_id = self.client.user().get_current_user().await?.id;
+        let status = status.map(|status| json!(status.to_uppercase()));
+        let collection = self
+            .fetch_list_collection(user_id, MediaType::Anime, status, DEFAULT_MAX_CHUNKS)
+            .await?;
+        Ok(collection.entries)
+    }
 
-        let mut variables = HashMap::new();
-        variables.insert("type".to_string(), json!("ANIME"));
-        variables.insert(
-            "userId".to_string(),
-            json!(self.client.user().get_current_user().await?.id),
-        );
+    /// Get the current user's anime list a page at a time (requires token)
+    ///
+    /// Unlike [`Self::get_current_user_anime_list`], entries are fetched in
+    /// pages of [`MAX_PER_PAGE`], waiting [`AniListClient::page_delay`]
+    /// between them and stopping after [`AniListClient::max_pages`]. An error
+    /// part way through is returned with the entries fetched before it; see
+    /// [`AllPages`].
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_current_user_anime_list_all(
+        &self,
+        status: Option<&str>,
+    ) -> AllPages<MediaList> {
+        let user_id = match self.client.user().get_current_user().await {
+            Ok(user) => user.id,
+            Err(error) => return AllPages::failed(error),
+        };
+        let status = status.map(|status| json!(status.to_uppercase()));
+        collect_all(self.client.clone(), move |client, page| {
+            let status = status.clone();
+            async move {
+                let mut variables = HashMap::new();
+                variables.insert("userId".to_string(), json!(user_id));
+                variables.insert("type".to_string(), json!(MediaType::Anime));
+                variables.insert("status".to_string(), json!(status));
+                variables.extend(limits::page_variables(page, MAX_PER_PAGE)?);
+
+                let response = client
+                    .query(queries::user::GET_MEDIA_LIST_PAGE, Some(variables))
+                    .await?;
+                Page::extract(response, "mediaList")
+            }
+        })
+        .await
+    }
 
-        if let Some(status) = status {
-            variables.insert("status".to_string(), json!(status.to_uppercase()));
+    /// Get a user's whole anime or manga list, optionally limited to one status
+    ///
+    /// Large lists are fetched in chunks of [`MAX_PER_CHUNK`] entries, one
+    /// request each, stopping after `max_chunks` chunks; check
+    /// [`ListCollection::truncated`] to see whether entries were left out.
+    /// Private lists require the owner's token.
+    ///
+    /// # Errors
+    ///
+    /// This method can return:
+    /// - [`AniListError::InvalidInput`] without sending a request when
+    ///   `max_chunks` is `0`
+    /// - [`AniListError::Private`] when the user's profile, and so their list,
+    ///   is private to the client's token
+    /// - [`AniListError::Json`] if any entry does not decode, rather than
+    ///   returning the list without it
+    pub async fn get_list_collection(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: Option<MediaListStatus>,
+        max_chunks: u32,
+    ) -> Result<ListCollection, AniListError> {
+        let status = status.map(|status| json!(status));
+        self.fetch_list_collection(user_id, media_type, status, max_chunks)
+            .await
+    }
+
+    async fn fetch_list_collection(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: Option<Value>,
+        max_chunks: u32,
+    ) -> Result<ListCollection, AniListError> {
+        let query = queries::user::GET_CURRENT_USER_ANIME_LIST;
+        let mut collection = ListCollection::new(max_chunks)?;
+        let mut chunk = 1;
+
+        while !collection.is_done() {
+            let mut variables = HashMap::new();
+            variables.insert("userId".to_string(), json!(user_id));
+            variables.insert("type".to_string(), json!(media_type));
+            variables.insert("status".to_string(), json!(status));
+            variables.insert("chunk".to_string(), json!(chunk));
+            variables.insert("perChunk".to_string(), json!(MAX_PER_CHUNK));
+
+            let lists: MediaListCollection = self
+                .client
+                .query_as(query, Some(variables), &["data", "MediaListCollection"])
+                .await?;
+            collection.push_collection_chunk(lists);
+            chunk += 1;
         }
 
-        let response = self.client.query(query, Some(variables)).await?;
+        Ok(collection)
+    }
 
-        // Extract entries from all lists
-        let mut all_entries = Vec::new();
-        if let Some(lists) = response["data"]["MediaListCollection"]["lists"].as_array() {
-            for list in lists {
-                if let Some(entries) = list["entries"].as_array() {
-                    for entry in entries {
-                        if let Ok(media_list) = serde_json::from_value::<MediaList>(entry.clone()) {
-                            all_entries.push(media_list);
-                        }
-                    }
-                }
+    /// Get the shows the current user is watching that have unwatched aired
+    /// episodes, most behind first (requires token)
+    ///
+    /// Airing information is part of the list query, so this needs no extra
+    /// request per show.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token.
+    pub async fn get_next_up(&self) -> Result<Vec<NextUpEntry>, AniListError> {
+        let entries = self.get_current_user_anime_list(Some("CURRENT")).await?;
+        Ok(next_up(entries))
+    }
+
+    /// Get a user's list entries changed after `since_unix`, for incremental sync
+    ///
+    /// Pages through the list sorted by last update, newest first, and stops
+    /// as soon as an entry at or before the checkpoint appears. Store
+    /// [`UpdatedSince::next_checkpoint`] and pass it as `since_unix` next time.
+    /// Private lists require the owner's token.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AniListError::Private`] when the user's profile, and so their
+    /// list, is private to the client's token.
+    pub async fn get_list_entries_updated_since(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        since_unix: i64,
+    ) -> Result<UpdatedSince, AniListError> {
+        let query = queries::user::GET_LIST_ENTRIES_UPDATED;
+        let mut result = UpdatedSince::new(since_unix);
+        let mut page = 1;
+
+        loop {
+            let mut variables = HashMap::new();
+            variables.insert("userId".to_string(), json!(user_id));
+            variables.insert("type".to_string(), json!(media_type));
+            variables.insert("page".to_string(), json!(page));
+            variables.insert("perPage".to_string(), json!(MAX_PER_PAGE));
+
+            let response = self.client.query(query, Some(variables)).await?;
+            let data = response["data"]["Page"]["mediaList"].clone();
+            let entries: Vec<MediaList> = serde_json::from_value(data)?;
+            let has_next_page = response["data"]["Page"]["pageInfo"]["hasNextPage"]
+                .as_bool()
+                .unwrap_or(false);
+
+            if !result.push_page(entries, has_next_page) {
+                return Ok(result);
             }
+            page += 1;
         }
-
-        Ok(all_entries)
     }
 
     /// Get user by ID
+    ///
+    /// Returns [`AniListError::Private`] if the user's profile is private.
     pub async fn get_by_id(&self, id: i32) -> Result<User, AniListError> {
+        Ok(self.get_by_id_with_meta(id).await?.value)
+    }
+
+    /// Get user by ID along with the response status, timing and
+    /// rate-limit/request-ID headers, for debugging
+    pub async fn get_by_id_with_meta(
+        &self,
+        id: i32,
+    ) -> Result<ResponseEnvelope<User>, AniListError> {
+        self.fetch_by_id(id, false).await
+    }
+
+    /// Get user by ID with their About text rendered as HTML
+    ///
+    /// Returns [`AniListError::Private`] if the user's profile is private.
+    pub async fn get_by_id_as_html(&self, id: i32) -> Result<User, AniListError> {
+        Ok(self.fetch_by_id(id, true).await?.value)
+    }
+
+    async fn fetch_by_id(
+        &self,
+        id: i32,
+        as_html: bool,
+    ) -> Result<ResponseEnvelope<User>, AniListError> {
         let query = queries::user::GET_BY_ID;
 
         let mut variables = HashMap::new();
         variables.insert("id".to_string(), json!(id));
+        variables.insert("asHtml".to_string(), json!(as_html));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["User"].clone();
-        let user: User = serde_json::from_value(data)?;
-        Ok(user)
+        let response = self.client.query_with_meta(query, Some(variables)).await?;
+        response.try_map(|json| extract(json, &["data", "User"]))
     }
 
     /// Get user by name
+    ///
+    /// Returns [`AniListError::Private`] if the user's profile is private.
     pub async fn get_by_name(&self, name: &str) -> Result<User, AniListError> {
+        self.fetch_by_name(name, false).await
+    }
+
+    /// Get user by name with their About text rendered as HTML
+    ///
+    /// Returns [`AniListError::Private`] if the user's profile is private.
+    pub async fn get_by_name_as_html(&self, name: &str) -> Result<User, AniListError> {
+        self.fetch_by_name(name, true).await
+    }
+
+    async fn fetch_by_name(&self, name: &str, as_html: bool) -> Result<User, AniListError> {
         let query = queries::user::GET_BY_NAME;
 
         let mut variables = HashMap::new();
         variables.insert("name".to_string(), json!(name));
+        variables.insert("asHtml".to_string(), json!(as_html));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["User"].clone();
-        let user: User = serde_json::from_value(data)?;
+        let user: User = self
+            .client
+            .query_as(query, Some(variables), &["data", "User"])
+            .await?;
         Ok(user)
     }
 
@@ -96,16 +313,19 @@ impl UserEndpoint {
         page: i32,
         per_page: i32,
     ) -> Result<Vec<User>, AniListError> {
+        let Some(search) = self.client.prepare_search(search)? else {
+            return Ok(Vec::new());
+        };
         let query = queries::user::SEARCH;
 
         let mut variables = HashMap::new();
         variables.insert("search".to_string(), json!(search));
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        variables.extend(limits::page_variables(page, per_page)?);
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["users"].clone();
-        let users: Vec<User> = serde_json::from_value(data)?;
+        let users: Vec<User> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "users"])
+            .await?;
         Ok(users)
     }
 
@@ -117,13 +337,12 @@ impl UserEndpoint {
     ) -> Result<Vec<User>, AniListError> {
         let query = queries::user::GET_MOST_ANIME_WATCHED;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["users"].clone();
-        let users: Vec<User> = serde_json::from_value(data)?;
+        let users: Vec<User> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "users"])
+            .await?;
         Ok(users)
     }
 
@@ -135,13 +354,12 @@ impl UserEndpoint {
     ) -> Result<Vec<User>, AniListError> {
         let query = queries::user::GET_MOST_MANGA_READ;
 
-        let mut variables = HashMap::new();
-        variables.insert("page".to_string(), json!(page));
-        variables.insert("perPage".to_string(), json!(per_page));
+        let variables = limits::page_variables(page, per_page)?;
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["Page"]["users"].clone();
-        let users: Vec<User> = serde_json::from_value(data)?;
+        let users: Vec<User> = self
+            .client
+            .query_as(query, Some(variables), &["data", "Page", "users"])
+            .await?;
         Ok(users)
     }
 
@@ -169,12 +387,61 @@ impl UserEndpoint {
         let mut variables = HashMap::new();
         variables.insert("userId".to_string(), json!(user_id));
 
-        let response = self.client.query(query, Some(variables)).await?;
-        let data = response["data"]["ToggleFollow"].clone();
-        let user: User = serde_json::from_value(data)?;
+        let user: User = self
+            .client
+            .query_as(query, Some(variables), &["data", "ToggleFollow"])
+            .await?;
         Ok(user)
     }
 
+    /// Replace the viewer's About text (requires authentication)
+    ///
+    /// `markdown` may use AniList's markdown extensions (see
+    /// [`crate::utils::anilist_markdown`]); an empty string clears the
+    /// section. Text longer than [`limits::ABOUT_MAX_CHARS`] is rejected with
+    /// [`AniListError::BadRequest`] before anything is sent.
+    ///
+    /// Returns the About text as saved by AniList.
+    pub async fn update_about(&self, markdown: &str) -> Result<String, AniListError> {
+        self.update_about_with_options(markdown, ProfileUpdateOptions::default())
+            .await
+    }
+
+    /// Replace the viewer's About text, see [`Self::update_about`]
+    ///
+    /// In dry-run mode the text is validated and returned unchanged without
+    /// sending the mutation.
+    pub async fn update_about_with_options(
+        &self,
+        markdown: &str,
+        options: ProfileUpdateOptions,
+    ) -> Result<String, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+        limits::check_max_chars(
+            "About text",
+            markdown,
+            limits::ABOUT_MAX_CHARS,
+            "ABOUT_MAX_CHARS",
+        )?;
+
+        if options.dry_run {
+            return Ok(markdown.to_string());
+        }
+
+        let query = queries::user::UPDATE_ABOUT;
+
+        let mut variables = HashMap::new();
+        variables.insert("about".to_string(), json!(markdown));
+
+        let about: Option<String> = self
+            .client
+            .query_as(query, Some(variables), &["data", "UpdateUser", "about"])
+            .await?;
+        Ok(about.unwrap_or_default())
+    }
+
     /// Toggle favorite anime/manga for the authenticated user
     ///
     /// # Arguments
@@ -224,6 +491,48 @@ impl UserEndpoint {
         Ok(response["data"]["ToggleFavourite"].is_object())
     }
 
+    /// Create or update a media list entry (requires authentication)
+    ///
+    /// Sends only the fields set on `entry`. AniList posts a list activity for
+    /// the change unless the entry is private; use
+    /// [`ActivityEndpoint::get_my_latest_list_activity`](crate::endpoints::activity::ActivityEndpoint::get_my_latest_list_activity)
+    /// to find it afterwards.
+    ///
+    /// # Errors
+    /// * `AniListError::AuthenticationRequired` - If no authentication token is provided
+    /// * `AniListError::BadRequest` - If `entry` has neither an entry ID nor a media ID
+    ///
+    /// # Example
+    /// ```rust
+    /// use anilist_sdk::models::{MediaListStatus, SaveListEntry};
+    ///
+    /// // Quietly catch up without announcing it
+    /// let entry = SaveListEntry::for_media(21).progress(1100).private(true);
+    /// let saved = client.user().save_list_entry(&entry).await?;
+    /// ```
+    pub async fn save_list_entry(&self, entry: &SaveListEntry) -> Result<MediaList, AniListError> {
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+        if entry.id.is_none() && entry.media_id.is_none() {
+            return Err(AniListError::BadRequest {
+                message: "A list entry needs an entry ID or a media ID".to_string(),
+            });
+        }
+
+        let query = queries::user::SAVE_LIST_ENTRY;
+
+        let saved: MediaList = self
+            .client
+            .query_as(
+                query,
+                Some(entry.variables()),
+                &["data", "SaveMediaListEntry"],
+            )
+            .await?;
+        Ok(saved)
+    }
+
     /// Update the progress of a media list entry (requires authentication)
     ///
     /// # Arguments
@@ -314,4 +623,123 @@ impl UserEndpoint {
         self.client.query(query, Some(variables)).await?;
         Ok(())
     }
+
+    /// Get every favourite of the authenticated user as a [`FavouriteSet`] (requires token)
+    ///
+    /// Walks all pages of each favourite connection (anime, manga, characters,
+    /// staff and studios) until none of them reports a next page.
+    ///
+    /// # Errors
+    ///
+    /// This method can return:
+    /// - [`AniListError::AuthenticationRequired`] without sending a request
+    ///   when the client has no token
+    /// - [`AniListError::PageLimitReached`] if a connection still reports a
+    ///   next page after 200 pages, rather than returning part of the set
+    /// - [`AniListError::Json`] if a page does not decode
+    pub async fn get_favourite_set(&self) -> Result<FavouriteSet, AniListError> {
+        const PER_PAGE: i32 = 25;
+        const MAX_PAGES: u32 = 200;
+
+        if !self.client.has_token() {
+            return Err(AniListError::AuthenticationRequired);
+        }
+
+        let query = queries::user::GET_VIEWER_FAVOURITES;
+        let mut set = FavouriteSet::default();
+
+        for page in 1..=MAX_PAGES {
+            let mut variables = HashMap::new();
+            variables.insert("page".to_string(), json!(page));
+            variables.insert("perPage".to_string(), json!(PER_PAGE));
+
+            let favourites: FavouriteIdsPage = self
+                .client
+                .query_as(query, Some(variables), &["data", "Viewer", "favourites"])
+                .await?;
+            if !favourites.add_to(&mut set) {
+                return Ok(set);
+            }
+        }
+
+        Err(AniListError::PageLimitReached { pages: MAX_PAGES })
+    }
+
+    /// Toggle the favourite state of a single item of any kind (requires authentication)
+    ///
+    /// AniList only exposes a toggle, so calling this on an item that is already
+    /// a favourite removes it.
+    pub async fn toggle_favourite_item(&self, item: FavouriteItem) -> Result<(), AniListError> {
+        let query = queries::user::TOGGLE_FAVOURITE_ITEM;
+
+        let mut variables = HashMap::new();
+        variables.insert(item.kind.variable_name().to_string(), json!(item.id));
+
+        self.client.query(query, Some(variables)).await?;
+        Ok(())
+    }
+
+    /// Synchronize the authenticated user's favourites with a desired set (requires authentication)
+    ///
+    /// Reads the current favourites, computes the difference with
+    /// [`FavouriteSet::plan_sync`] and only toggles the items that need to
+    /// change, waiting `options.delay_ms` between mutations. A failed toggle is
+    /// recorded in [`SyncReport::failed`] and the sync carries on with the
+    /// remaining items.
+    ///
+    /// Fails with [`AniListError::AuthenticationRequired`] without sending a
+    /// request when the client has no token, and with any error of
+    /// [`Self::get_favourite_set`] before toggling anything, so a partly read
+    /// set is never synced against.
+    ///
+    /// # Example
+    /// ```rust
+    /// use anilist_sdk::models::user::{FavouriteSet, FavouriteSyncOptions};
+    ///
+    /// let mut desired = FavouriteSet::default();
+    /// desired.anime.insert(16498);
+    ///
+    /// let options = FavouriteSyncOptions { dry_run: true, ..Default::default() };
+    /// let report = client.user().sync_favourites(&desired, options).await?;
+    /// println!("Would add {} and remove {}", report.added.len(), report.removed.len());
+    /// ```
+    pub async fn sync_favourites(
+        &self,
+        desired: &FavouriteSet,
+        options: FavouriteSyncOptions,
+    ) -> Result<SyncReport, AniListError> {
+        let current = self.get_favourite_set().await?;
+        let plan = current.plan_sync(desired);
+
+        let mut report = SyncReport {
+            skipped: plan.unchanged,
+            ..Default::default()
+        };
+
+        if options.dry_run {
+            report.added = plan.to_add;
+            report.removed = plan.to_remove;
+            return Ok(report);
+        }
+
+        let toggles = plan
+            .to_add
+            .into_iter()
+            .map(|item| (item, true))
+            .chain(plan.to_remove.into_iter().map(|item| (item, false)));
+
+        for (index, (item, adding)) in toggles.enumerate() {
+            if index > 0 {
+                rate_limit_delay(options.delay_ms).await;
+            }
+
+            match self.toggle_favourite_item(item).await {
+                Ok(()) if adding => report.added.push(item),
+                Ok(()) => report.removed.push(item),
+                Err(e) => report.failed.push((item, e)),
+            }
+        }
+
+        Ok(report)
+    }
 }
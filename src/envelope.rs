@@ -0,0 +1,93 @@
+//! Response metadata for debugging individual calls.
+//!
+//! The `_with_meta` endpoint variants (for example
+//! [`AnimeEndpoint::get_by_id_with_meta`](crate::endpoints::AnimeEndpoint::get_by_id_with_meta))
+//! return a [`ResponseEnvelope`] instead of the bare value, carrying the HTTP
+//! status, timing and the headers AniList support asks about when a call
+//! misbehaves.
+
+use crate::error::GraphQLErrorDetail;
+use crate::health::RateLimitStatus;
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Response headers that identify a request, when present.
+///
+/// AniList is served through Cloudflare, so `cf-ray` is usually the only
+/// identifier available; the others are kept in case a proxy adds them.
+pub const REQUEST_ID_HEADERS: [&str; 3] = ["x-request-id", "request-id", "cf-ray"];
+
+/// A successful result together with the metadata of the response it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseEnvelope<T> {
+    /// The decoded result
+    pub value: T,
+    /// HTTP status code of the response
+    pub status: u16,
+    /// Rate limit, `Retry-After` and request ID headers, keyed by lowercase name
+    pub headers_of_interest: HashMap<String, String>,
+    /// Time from sending the request until the body was read, excluding any
+    /// wait for the client's rate limiter
+    pub elapsed: Duration,
+    /// Errors that came with the data, when the client does not fail on them
+    /// (see [`AniListClient::set_fail_on_partial_errors`](crate::AniListClient::set_fail_on_partial_errors))
+    pub errors: Vec<GraphQLErrorDetail>,
+}
+
+impl<T> ResponseEnvelope<T> {
+    /// Replaces the value while keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ResponseEnvelope<U> {
+        ResponseEnvelope {
+            value: f(self.value),
+            status: self.status,
+            headers_of_interest: self.headers_of_interest,
+            elapsed: self.elapsed,
+            errors: self.errors,
+        }
+    }
+
+    /// Like [`Self::map`], for decoding steps that can fail.
+    pub fn try_map<U, E>(
+        self,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> Result<ResponseEnvelope<U>, E> {
+        Ok(ResponseEnvelope {
+            value: f(self.value)?,
+            status: self.status,
+            headers_of_interest: self.headers_of_interest,
+            elapsed: self.elapsed,
+            errors: self.errors,
+        })
+    }
+
+    /// The first request identifier found, see [`REQUEST_ID_HEADERS`].
+    pub fn request_id(&self) -> Option<&str> {
+        REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| self.headers_of_interest.get(*name))
+            .map(String::as_str)
+    }
+
+    /// The rate limit budget reported with this response.
+    pub fn rate_limit(&self) -> Option<RateLimitStatus> {
+        RateLimitStatus::from_header_values(&self.headers_of_interest)
+    }
+}
+
+/// Picks the headers worth keeping from a response: everything starting with
+/// `x-ratelimit-`, `retry-after`, and the [`REQUEST_ID_HEADERS`].
+pub fn headers_of_interest(headers: &HeaderMap) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| {
+            let name = name.as_str();
+            name.starts_with("x-ratelimit-")
+                || name == "retry-after"
+                || REQUEST_ID_HEADERS.contains(&name)
+        })
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect()
+}
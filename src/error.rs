@@ -6,6 +6,10 @@
 //! interacting with the AniList API, from network issues to rate limiting
 //! and authentication problems.
 
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Comprehensive error type for all AniList API interactions.
@@ -26,15 +30,24 @@ use thiserror::Error;
 /// - [`AniListError::GraphQL`] - GraphQL query errors from the API
 /// - [`AniListError::NotFound`] - Resource not found (404)
 /// - [`AniListError::BadRequest`] - Invalid request parameters (400)
+/// - [`AniListError::InvalidInput`] - An argument rejected before sending the request
+/// - [`AniListError::RequestAborted`] - A request hook stopped the request
+/// - [`AniListError::PageLimitReached`] - A result needed more pages than allowed
 ///
 /// ## Authentication Errors
 /// - [`AniListError::AuthenticationRequired`] - Missing or invalid token (401)
 /// - [`AniListError::AccessDenied`] - Insufficient permissions (403)
+/// - [`AniListError::Private`] - The requested user or list is private
+/// - [`AniListError::AdultContentBlocked`] - Adult content requires a logged-in viewer who opted in
 ///
 /// ## Rate Limiting Errors
 /// - [`AniListError::RateLimit`] - Rate limit exceeded with detailed info
 /// - [`AniListError::RateLimitSimple`] - Simple rate limit without details
 /// - [`AniListError::BurstLimit`] - Burst request limit exceeded
+/// - [`AniListError::BudgetUnavailable`] - Budget reservation timed out
+///
+/// ## Configuration Errors
+/// - [`AniListError::Config`] - Invalid or conflicting client configuration
 ///
 /// # Examples
 ///
@@ -55,12 +68,14 @@ use thiserror::Error;
 ///     Err(e) => println!("Other error: {}", e),
 /// }
 /// ```
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AniListError {
     /// Network-related errors such as connection failures, timeouts, or DNS issues.
     ///
     /// This error wraps underlying [`reqwest::Error`] types and indicates problems
-    /// with the HTTP request itself rather than the API response.
+    /// with the HTTP request itself rather than the API response. The error is
+    /// shared behind an [`Arc`] so it can be handed to every caller that
+    /// waited for the same request.
     ///
     /// # Common Causes
     /// - No internet connection
@@ -74,13 +89,32 @@ pub enum AniListError {
     /// Network errors are typically transient and may resolve with retry attempts.
     /// Consider implementing exponential backoff for automatic retry logic.
     #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
+    Network(#[source] Arc<reqwest::Error>),
+
+    /// The request did not complete within the client's timeout.
+    ///
+    /// Covers both the total request timeout (see
+    /// [`AniListClientBuilder::timeout`](crate::client::AniListClientBuilder::timeout)
+    /// and [`AniListClient::with_timeout`](crate::client::AniListClient::with_timeout))
+    /// and the connect timeout, so a slow or hung server can be told apart
+    /// from a refused connection, which stays a [`AniListError::Network`].
+    ///
+    /// # Handling
+    ///
+    /// Usually transient. Retry with backoff, or raise the timeout for calls
+    /// that are known to be slow.
+    #[error("Request timed out after {elapsed:?}")]
+    Timeout {
+        /// How long the request ran before it was abandoned
+        elapsed: Duration,
+    },
 
     /// JSON parsing errors when deserializing API responses.
     ///
     /// This error occurs when the API returns a response that cannot be parsed
     /// into the expected data structures, usually indicating API schema changes
-    /// or unexpected response formats.
+    /// or unexpected response formats. Like [`AniListError::Network`], the
+    /// error is shared behind an [`Arc`].
     ///
     /// # Common Causes
     /// - API schema changes not reflected in the wrapper
@@ -93,7 +127,7 @@ pub enum AniListError {
     /// JSON errors typically indicate either API changes or bugs in the wrapper.
     /// These should be reported as issues for investigation.
     #[error("JSON parsing error: {0}")]
-    Json(#[from] serde_json::Error),
+    Json(#[source] Arc<serde_json::Error>),
 
     /// GraphQL-specific errors returned by the AniList API.
     ///
@@ -110,23 +144,38 @@ pub enum AniListError {
     /// # Handling
     ///
     /// GraphQL errors usually require fixing the query or parameters being sent.
-    /// The error message provides specific details about what went wrong.
+    /// The error message provides specific details about what went wrong, and
+    /// `details` keeps each error's status, locations and, for rejected
+    /// mutation arguments, the `validation` messages per field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// if let Err(AniListError::GraphQL { details, .. }) = result {
+    ///     for (field, problems) in details.iter().flat_map(|d| d.validation.iter().flatten()) {
+    ///         println!("{field}: {}", problems.join("; "));
+    ///     }
+    /// }
+    /// ```
     #[error("GraphQL error: {message}")]
     GraphQL {
         /// Detailed error message from the GraphQL API
         message: String,
+        /// The individual errors the message was joined from
+        details: Vec<GraphQLErrorDetail>,
     },
 
     /// Detailed rate limit error with comprehensive rate limiting information.
     ///
-    /// AniList enforces a rate limit of 90 requests per minute. This error provides
+    /// AniList enforces a per-minute rate limit (90 requests, or 30 in degraded
+    /// mode). This error provides
     /// detailed information about the current rate limit state, allowing for
     /// intelligent retry logic and user feedback.
     ///
     /// # Rate Limit Details
     ///
     /// AniList's rate limiting works as follows:
-    /// - 90 requests per minute per IP address
+    /// - 90 requests per minute per IP address, 30 while the API is degraded
     /// - Additional burst limiting for rapid consecutive requests
     /// - Rate limit headers provided in all responses
     /// - 429 status code when limit is exceeded
@@ -151,7 +200,8 @@ pub enum AniListError {
         "Rate limit exceeded. Limit: {limit}, Remaining: {remaining}, Reset at: {reset_at}, Retry after: {retry_after} seconds"
     )]
     RateLimit {
-        /// The total rate limit (typically 90 requests per minute)
+        /// The total rate limit, as reported by AniList (normally 90 requests
+        /// per minute, 30 in degraded mode)
         limit: u32,
         /// Number of requests remaining in the current window
         remaining: u32,
@@ -261,6 +311,76 @@ pub enum AniListError {
     #[error("Access denied. Check your token permissions.")]
     AccessDenied,
 
+    /// The client was configured incorrectly.
+    ///
+    /// # Common Causes
+    /// - Calling [`crate::init`] again with a different configuration
+    ///
+    /// # Handling
+    ///
+    /// This indicates a programming error; fix the configuration code.
+    #[error("Invalid client configuration: {message}")]
+    Config {
+        /// Description of the configuration problem
+        message: String,
+    },
+
+    /// Not enough rate limit budget could be reserved in time.
+    ///
+    /// Returned by [`AniListClient::reserve_budget`](crate::client::AniListClient::reserve_budget)
+    /// when the requested number of requests did not become available before
+    /// the timeout, or exceeds what a single rate limit window allows.
+    ///
+    /// # Handling
+    ///
+    /// Retry later, or split the workflow into smaller reservations.
+    #[error("Could not reserve {requested} requests ({available} available)")]
+    BudgetUnavailable {
+        /// Number of requests asked for
+        requested: u32,
+        /// Number of unreserved requests available when giving up
+        available: u32,
+    },
+
+    /// The requested resource exists but its owner has made it private.
+    ///
+    /// AniList answers requests for private profiles and the lists of private
+    /// users with a `"Private User"` GraphQL error and a `404` status, which
+    /// this variant surfaces separately from a genuine [`AniListError::NotFound`].
+    ///
+    /// # Common Causes
+    /// - Fetching the media list collection of a user with a private list
+    /// - Fetching the profile or activities of a private user
+    ///
+    /// # Handling
+    ///
+    /// This is not a transient failure and retrying will not help. Show the
+    /// user a "this profile is private" state instead of an error.
+    #[error("The requested {resource} is private")]
+    Private {
+        /// The kind of resource that is private; `"user"` for AniList's
+        /// private-profile error
+        resource: String,
+    },
+
+    /// The requested media is adult content and the viewer cannot see it.
+    ///
+    /// AniList hides `isAdult` media from clients without a token, and from
+    /// accounts that have not enabled adult content in their settings. The
+    /// response carries AniList's `"Adult content is disabled for this user"`
+    /// error with a `403` status alongside null data, which this variant
+    /// surfaces instead of a generic [`AniListError::AccessDenied`].
+    ///
+    /// # Handling
+    ///
+    /// Retrying will not help. Prompt the user to log in (and enable adult
+    /// content in their AniList settings), or show an age gate.
+    #[error("Adult content is blocked for this viewer: {message}")]
+    AdultContentBlocked {
+        /// The error message returned by AniList
+        message: String,
+    },
+
     /// Bad request with detailed error information (HTTP 400).
     ///
     /// This error indicates that the request was malformed or contained invalid
@@ -284,6 +404,45 @@ pub enum AniListError {
         message: String,
     },
 
+    /// An argument was rejected before any request was sent.
+    ///
+    /// Paginated endpoint methods check `page` and `per_page` up front (a
+    /// page must be at least 1, and a page size between 1 and
+    /// [`MAX_PER_PAGE`](crate::limits::MAX_PER_PAGE)), since AniList would
+    /// otherwise clamp the value silently or answer with an unclear GraphQL
+    /// error. No rate limit budget is spent on the rejected call.
+    #[error("Invalid {field}: {message}")]
+    InvalidInput {
+        /// The name of the rejected argument, e.g. `per_page`
+        field: String,
+        /// What the argument must satisfy, and the value given
+        message: String,
+    },
+
+    /// A request hook stopped the request before it was sent.
+    ///
+    /// Returned when a hook registered with
+    /// [`AniListClient::add_request_hook`](crate::AniListClient::add_request_hook)
+    /// fails; `message` is the hook's error. No rate limit budget is spent,
+    /// and the request is not retried.
+    #[error("Request aborted by hook: {message}")]
+    RequestAborted {
+        /// The error returned by the hook
+        message: String,
+    },
+
+    /// A result that must be read in full had more pages than allowed.
+    ///
+    /// Returned instead of a partial result where acting on part of the data
+    /// would be wrong, such as reading the viewer's favourites before
+    /// [`UserEndpoint::sync_favourites`](crate::endpoints::UserEndpoint::sync_favourites)
+    /// toggles the difference.
+    #[error("Gave up after {pages} pages with more still reported")]
+    PageLimitReached {
+        /// Number of pages fetched before giving up
+        pages: u32,
+    },
+
     /// Server-side errors from the AniList API (HTTP 5xx).
     ///
     /// These errors indicate problems on the AniList server side rather than
@@ -309,3 +468,282 @@ pub enum AniListError {
         message: String,
     },
 }
+
+/// Wait suggested by [`AniListError::retry_delay`] for a minute-window rate
+/// limit that did not say when it resets.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Wait suggested by [`AniListError::retry_delay`] after a burst limit.
+const BURST_LIMIT_DELAY: Duration = Duration::from_secs(5);
+
+impl AniListError {
+    /// Returns `true` for the rate limit errors: [`AniListError::RateLimit`],
+    /// [`AniListError::RateLimitSimple`] and [`AniListError::BurstLimit`].
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(
+            self,
+            AniListError::RateLimit { .. }
+                | AniListError::RateLimitSimple
+                | AniListError::BurstLimit
+        )
+    }
+
+    /// Returns `true` if the same request may succeed when sent again.
+    ///
+    /// Rate limits, timeouts and transport-level network failures (refused
+    /// or dropped connections, but not errors reqwest raised for an HTTP
+    /// status or while building the request) are retryable, as are server
+    /// errors with a 5xx status other than 501 and 505, which will not
+    /// change on retry. Every other error is permanent.
+    ///
+    /// Note that a server error may arrive after a mutation was already
+    /// applied; only retry mutations on rate limit errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AniListError::Network(error) => !(error.is_status() || error.is_builder()),
+            AniListError::Timeout { .. } => true,
+            AniListError::ServerError { status, .. } => {
+                (500..=599).contains(status) && !matches!(status, 501 | 505)
+            }
+            _ => self.is_rate_limited(),
+        }
+    }
+
+    /// How long to wait before retrying a rate limited request, or `None`
+    /// if the error is not a rate limit.
+    ///
+    /// This is the server's `Retry-After` for [`AniListError::RateLimit`];
+    /// without one, a full minute window for the rate limits and five
+    /// seconds for a [`AniListError::BurstLimit`].
+    pub fn retry_delay(&self) -> Option<Duration> {
+        match self {
+            AniListError::RateLimit { .. } => {
+                Some(self.server_retry_after().unwrap_or(RATE_LIMIT_WINDOW))
+            }
+            AniListError::RateLimitSimple => Some(RATE_LIMIT_WINDOW),
+            AniListError::BurstLimit => Some(BURST_LIMIT_DELAY),
+            _ => None,
+        }
+    }
+
+    /// The wait the server asked for with `Retry-After`, if any.
+    pub(crate) fn server_retry_after(&self) -> Option<Duration> {
+        match self {
+            AniListError::RateLimit { retry_after, .. } if *retry_after > 0 => {
+                Some(Duration::from_secs(u64::from(*retry_after)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`AniListError::Network`] errors caused by the
+    /// connection itself (refused, reset or dropped connections, failed
+    /// handshakes) rather than by a timeout or an HTTP-level problem.
+    ///
+    /// These usually mean a pooled connection went stale; see the pool
+    /// settings on [`ClientConfig`](crate::client::ClientConfig).
+    pub fn is_connection_error(&self) -> bool {
+        match self {
+            AniListError::Network(error) => crate::metrics::is_connection_error(error),
+            _ => false,
+        }
+    }
+
+    /// Builds an error from the `errors` array of a GraphQL response.
+    ///
+    /// Known error messages are mapped to dedicated variants (burst limiting,
+    /// private resources, adult content gating). A `429` status becomes
+    /// [`AniListError::BurstLimit`], and errors that all carry a `403` or all
+    /// a `404` status become [`AniListError::AccessDenied`] or
+    /// [`AniListError::NotFound`]; anything else becomes
+    /// [`AniListError::GraphQL`] with all messages joined by `", "` and each
+    /// error kept in `details`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anilist_sdk::AniListError;
+    /// use serde_json::json;
+    ///
+    /// let errors = json!([{ "message": "Private User", "status": 404 }]);
+    /// let error = AniListError::from_graphql_errors(&errors);
+    /// assert!(matches!(error, AniListError::Private { .. }));
+    /// ```
+    pub fn from_graphql_errors(errors: &serde_json::Value) -> Self {
+        let error_message = match errors.as_array() {
+            Some(errors) => errors
+                .iter()
+                .map(|e| {
+                    e.get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or("Unknown error")
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => errors.to_string(),
+        };
+
+        let lowercase = error_message.to_lowercase();
+
+        // Check if it's a rate limit error in GraphQL response
+        if lowercase.contains("rate limit") || lowercase.contains("too many requests") {
+            return AniListError::BurstLimit;
+        }
+
+        if errors
+            .as_array()
+            .is_some_and(|errors| !errors.is_empty() && errors.iter().all(is_private_user))
+        {
+            return AniListError::Private {
+                resource: "user".to_string(),
+            };
+        }
+
+        if errors
+            .as_array()
+            .is_some_and(|errors| !errors.is_empty() && errors.iter().all(is_adult_content_error))
+        {
+            return AniListError::AdultContentBlocked {
+                message: error_message,
+            };
+        }
+
+        // AniList answers with a 200 and reports the real status inside the
+        // payload, e.g. 404 for `Media(id: 999999999)`
+        let statuses: Vec<u64> = errors
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.get("status").and_then(|s| s.as_u64()))
+            .collect();
+        if statuses.contains(&429) {
+            return AniListError::BurstLimit;
+        }
+        if !statuses.is_empty() {
+            if statuses.iter().all(|&status| status == 403) {
+                return AniListError::AccessDenied;
+            }
+            if statuses.iter().all(|&status| status == 404) {
+                return AniListError::NotFound;
+            }
+        }
+
+        AniListError::GraphQL {
+            message: error_message,
+            details: GraphQLErrorDetail::parse_all(errors),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AniListError {
+    fn from(error: reqwest::Error) -> Self {
+        AniListError::Network(Arc::new(error))
+    }
+}
+
+impl From<serde_json::Error> for AniListError {
+    fn from(error: serde_json::Error) -> Self {
+        AniListError::Json(Arc::new(error))
+    }
+}
+
+/// One entry of the `errors` array of a GraphQL response.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::error::GraphQLErrorDetail;
+/// use serde_json::json;
+///
+/// let detail: GraphQLErrorDetail = serde_json::from_value(json!({
+///     "message": "validation",
+///     "status": 400,
+///     "locations": [{ "line": 2, "column": 3 }],
+///     "validation": { "score": ["The score may not be greater than 100."] }
+/// }))?;
+/// assert_eq!(detail.locations, [(2, 3)]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct GraphQLErrorDetail {
+    /// The error message
+    #[serde(default)]
+    pub message: String,
+    /// The HTTP-like status AniList attaches to most errors
+    #[serde(default)]
+    pub status: Option<u16>,
+    /// `(line, column)` positions in the query the error refers to
+    #[serde(default, deserialize_with = "deserialize_locations")]
+    pub locations: Vec<(u32, u32)>,
+    /// Messages for each rejected input field, e.g. `score` when
+    /// `SaveMediaListEntry` is sent an out-of-range score
+    #[serde(default)]
+    pub validation: Option<HashMap<String, Vec<String>>>,
+}
+
+impl GraphQLErrorDetail {
+    /// Parses every entry of an `errors` array, falling back to the message
+    /// alone for entries that do not match the usual shape.
+    pub(crate) fn parse_all(errors: &serde_json::Value) -> Vec<Self> {
+        let Some(errors) = errors.as_array() else {
+            return vec![Self {
+                message: errors.to_string(),
+                ..Self::default()
+            }];
+        };
+        errors
+            .iter()
+            .map(|error| {
+                Self::deserialize(error).unwrap_or_else(|_| Self {
+                    message: error
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or("Unknown error")
+                        .to_string(),
+                    ..Self::default()
+                })
+            })
+            .collect()
+    }
+}
+
+fn deserialize_locations<'de, D>(deserializer: D) -> Result<Vec<(u32, u32)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Location {
+        line: u32,
+        column: u32,
+    }
+
+    let locations = Option::<Vec<Location>>::deserialize(deserializer)?;
+    Ok(locations
+        .into_iter()
+        .flatten()
+        .map(|location| (location.line, location.column))
+        .collect())
+}
+
+/// AniList's error for adult media requested by a viewer who cannot see it.
+const ADULT_CONTENT_DISABLED: &str = "Adult content is disabled for this user";
+
+/// Whether `error` is AniList's adult-content error: exactly
+/// [`ADULT_CONTENT_DISABLED`], with a `403` status when it carries one.
+fn is_adult_content_error(error: &serde_json::Value) -> bool {
+    error.get("message").and_then(|m| m.as_str()) == Some(ADULT_CONTENT_DISABLED)
+        && error
+            .get("status")
+            .is_none_or(|status| status.as_u64() == Some(403))
+}
+
+/// AniList's error for a private profile, also sent for that user's lists.
+const PRIVATE_USER: &str = "Private User";
+
+/// Whether `error` is AniList's private-profile error: exactly
+/// [`PRIVATE_USER`], with a `404` status when it carries one.
+fn is_private_user(error: &serde_json::Value) -> bool {
+    error.get("message").and_then(|m| m.as_str()) == Some(PRIVATE_USER)
+        && error
+            .get("status")
+            .is_none_or(|status| status.as_u64() == Some(404))
+}
@@ -0,0 +1,73 @@
+//! # Global Client
+//!
+//! A lazily-initialized, process-wide [`AniListClient`] for scripts and
+//! applications that would rather not pass a client through every function.
+//! All users of the global client share its rate limiter.
+
+use crate::client::{AniListClient, ClientConfig};
+use crate::error::AniListError;
+use std::sync::OnceLock;
+
+static GLOBAL: OnceLock<(ClientConfig, AniListClient)> = OnceLock::new();
+
+/// Initializes the global client with `config`.
+///
+/// Calling `init` again with the same configuration is a no-op that returns
+/// the existing client, so it is safe to call from several entry points.
+///
+/// # Errors
+///
+/// Returns [`AniListError::Config`] if `config` is invalid (see
+/// [`ClientConfig::validate`]), if the HTTP client cannot be initialized, or
+/// if the global client was already initialized (by `init` or [`global`])
+/// with a different configuration.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use anilist_sdk::client::ClientConfig;
+///
+/// anilist_sdk::init(ClientConfig::default().token("your_token"))?;
+///
+/// // Anywhere else in the program
+/// let client = anilist_sdk::global()?;
+/// # Ok::<(), anilist_sdk::AniListError>(())
+/// ```
+pub fn init(config: ClientConfig) -> Result<&'static AniListClient, AniListError> {
+    config.validate()?;
+    let (existing, client) = match GLOBAL.get() {
+        Some(global) => global,
+        None => {
+            let client = AniListClient::from_config(&config)?;
+            // A client set up by another thread in the meantime is kept
+            GLOBAL.get_or_init(|| (config.clone(), client))
+        }
+    };
+
+    if *existing != config {
+        return Err(AniListError::Config {
+            message: format!(
+                "the global client is already initialized with a different configuration ({existing:?})"
+            ),
+        });
+    }
+    Ok(client)
+}
+
+/// Returns the global client, initializing it with
+/// [`ClientConfig::default`] (which picks up `ANILIST_TOKEN`) if [`init`]
+/// has not been called.
+///
+/// # Errors
+///
+/// Returns [`AniListError::Config`] if the global client has not been
+/// initialized and cannot be built from the default configuration.
+pub fn global() -> Result<&'static AniListClient, AniListError> {
+    if let Some((_, client)) = GLOBAL.get() {
+        return Ok(client);
+    }
+    let config = ClientConfig::default();
+    let client = AniListClient::from_config(&config)?;
+    let (_, client) = GLOBAL.get_or_init(|| (config, client));
+    Ok(client)
+}
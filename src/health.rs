@@ -0,0 +1,69 @@
+//! Connectivity and token health checks.
+//!
+//! [`AniListClient::health_check`](crate::client::AniListClient::health_check)
+//! issues the cheapest possible query against the API and summarizes the
+//! result in a [`HealthReport`], so applications can verify connectivity and
+//! token validity before they start serving traffic.
+
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Rate limit budget reported by AniList in the response headers.
+///
+/// AniList sends `X-RateLimit-Limit` and `X-RateLimit-Remaining` on every
+/// response; `X-RateLimit-Reset` is only present once the limit has been hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Maximum number of requests allowed per minute
+    pub limit: u32,
+    /// Requests remaining in the current window
+    pub remaining: u32,
+    /// Unix timestamp when the window resets, if reported
+    pub reset_at: Option<u64>,
+}
+
+impl RateLimitStatus {
+    /// Reads the rate limit headers from a response, if present.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        Self::from_lookup(|name| headers.get(name).and_then(|value| value.to_str().ok()))
+    }
+
+    /// Same as [`Self::from_headers`], for headers already copied into a map
+    /// keyed by lowercase name.
+    pub(crate) fn from_header_values(headers: &HashMap<String, String>) -> Option<Self> {
+        Self::from_lookup(|name| headers.get(&name.to_ascii_lowercase()).map(String::as_str))
+    }
+
+    fn from_lookup<'a>(lookup: impl Fn(&str) -> Option<&'a str>) -> Option<Self> {
+        let header = |name: &str| lookup(name).and_then(|value| value.trim().parse::<u64>().ok());
+
+        Some(Self {
+            limit: header("X-RateLimit-Limit")? as u32,
+            remaining: header("X-RateLimit-Remaining")? as u32,
+            reset_at: header("X-RateLimit-Reset"),
+        })
+    }
+}
+
+/// Result of [`AniListClient::health_check`](crate::client::AniListClient::health_check).
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// Whether the configured token was accepted by the API
+    pub authenticated: bool,
+    /// Whether a token was configured but rejected by the API
+    pub token_rejected: bool,
+    /// ID of the authenticated user, when `authenticated` is `true`
+    pub viewer_id: Option<i32>,
+    /// Round-trip time of the probe request
+    pub latency: Duration,
+    /// Rate limit budget reported with the probe response
+    pub rate_limit: Option<RateLimitStatus>,
+}
+
+impl HealthReport {
+    /// Returns `true` if the API is reachable and no configured token was rejected.
+    pub fn is_healthy(&self) -> bool {
+        !self.token_rejected
+    }
+}
@@ -0,0 +1,142 @@
+//! # Hooks
+//!
+//! Callbacks that see every HTTP request the client sends and every response
+//! it receives, for logging, auditing, custom metrics or extra headers such
+//! as correlation IDs. Register them with
+//! [`AniListClient::add_request_hook`] and
+//! [`AniListClient::add_response_hook`].
+//!
+//! Hooks run once per attempt, so a retried request passes through them
+//! again. Requests answered without sending anything, from the response
+//! cache or by waiting for an identical request, do not run them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::AniListClient;
+//! use reqwest::header::HeaderValue;
+//!
+//! let mut client = AniListClient::new();
+//! client.add_request_hook(|request| {
+//!     request
+//!         .headers_mut()
+//!         .insert("x-correlation-id", HeaderValue::from_static("checkout-42"));
+//!     Ok::<_, std::convert::Infallible>(())
+//! });
+//! client.add_response_hook(|response| {
+//!     println!("{} after {:?}", response.status(), response.elapsed());
+//! });
+//! ```
+//!
+//! [`AniListClient::add_request_hook`]: crate::AniListClient::add_request_hook
+//! [`AniListClient::add_response_hook`]: crate::AniListClient::add_response_hook
+
+use crate::error::AniListError;
+use crate::transport::{TransportRequest, TransportResponse};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A request about to be sent, as seen by a request hook.
+pub struct RequestContext<'a> {
+    request: &'a mut TransportRequest,
+}
+
+impl RequestContext<'_> {
+    /// The GraphQL document being sent.
+    pub fn query(&self) -> &str {
+        self.request.query()
+    }
+
+    /// The variables sent with the document, `Null` when there are none.
+    pub fn variables(&self) -> &Value {
+        self.request.variables()
+    }
+
+    /// The request headers, including `Authorization` when the client has a
+    /// token.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.request.headers
+    }
+
+    /// The request headers, for adding or replacing headers.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.request.headers
+    }
+}
+
+/// A response that was just received, as seen by a response hook.
+pub struct ResponseContext<'a> {
+    query: &'a str,
+    response: &'a TransportResponse,
+    elapsed: Duration,
+}
+
+impl ResponseContext<'_> {
+    /// The GraphQL document the response answers.
+    pub fn query(&self) -> &str {
+        self.query
+    }
+
+    /// The HTTP status code.
+    pub fn status(&self) -> u16 {
+        self.response.status
+    }
+
+    /// The response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.response.headers
+    }
+
+    /// Time from sending the request until the response was read.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+type RequestHook = Arc<dyn Fn(&mut RequestContext<'_>) -> Result<(), AniListError> + Send + Sync>;
+type ResponseHook = Arc<dyn Fn(&ResponseContext<'_>) + Send + Sync>;
+
+/// The hooks registered on a client, in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    request: Vec<RequestHook>,
+    response: Vec<ResponseHook>,
+}
+
+impl Hooks {
+    pub(crate) fn add_request(&mut self, hook: RequestHook) {
+        self.request.push(hook);
+    }
+
+    pub(crate) fn add_response(&mut self, hook: ResponseHook) {
+        self.response.push(hook);
+    }
+
+    /// Runs the request hooks, stopping at the first that fails.
+    pub(crate) fn before_request(
+        &self,
+        request: &mut TransportRequest,
+    ) -> Result<(), AniListError> {
+        let mut context = RequestContext { request };
+        self.request.iter().try_for_each(|hook| hook(&mut context))
+    }
+
+    /// Runs the response hooks.
+    pub(crate) fn after_response(
+        &self,
+        query: &str,
+        response: &TransportResponse,
+        elapsed: Duration,
+    ) {
+        let context = ResponseContext {
+            query,
+            response,
+            elapsed,
+        };
+        for hook in &self.response {
+            hook(&context);
+        }
+    }
+}
@@ -15,7 +15,7 @@
 //! - **Type Safety**: Strongly typed responses with serde serialization/deserialization
 //! - **Modular Design**: Separate endpoint modules for clean code organization
 //! - **Authentication**: Full support for authenticated requests with Bearer tokens
-//! - **Rate Limiting**: Automatic handling of AniList's 90 requests/minute rate limit
+//! - **Rate Limiting**: Automatic handling of AniList's rate limit, adapting to the limit the API reports
 //! - **Error Handling**: Comprehensive error types with detailed error messages
 //! - **Retry Logic**: Built-in retry mechanisms for transient failures
 //! - **GraphQL Integration**: Direct GraphQL query execution with proper field mapping
@@ -53,9 +53,10 @@
 //!
 //! ## Rate Limiting
 //!
-//! AniList enforces a rate limit of 90 requests per minute. This wrapper automatically
-//! handles rate limiting with:
-//! - Automatic detection of rate limit headers
+//! AniList enforces a rate limit of 90 requests per minute, lowered to 30 while the
+//! API runs in degraded mode. This wrapper automatically handles rate limiting with:
+//! - Automatic detection of rate limit headers, following whatever limit they report
+//! - Waiting for the reported reset time once the budget is used up
 //! - Proper 429 error handling with retry-after support
 //! - Built-in retry logic with exponential backoff
 //! - Burst request protection
@@ -134,12 +135,28 @@
 //! }
 //! ```
 
+pub mod auth;
+pub mod batch;
+pub mod cache;
 pub mod client;
 pub mod endpoints;
+pub mod envelope;
 pub mod error;
+pub mod global;
+pub mod health;
+pub mod hooks;
+pub mod limits;
+pub mod metrics;
 pub mod models;
 pub mod queries;
+pub mod rate_limit;
+pub mod runtime;
+pub mod transport;
 pub mod utils;
 
-pub use client::AniListClient;
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building for wasm32 requires the `wasm` feature");
+
+pub use client::{AniListClient, AniListClientBuilder};
 pub use error::AniListError;
+pub use global::{global, init};
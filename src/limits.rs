@@ -0,0 +1,176 @@
+//! # AniList Limits
+//!
+//! Named constants for the limits AniList enforces on query variables and
+//! user-submitted content, plus the sort values used by this crate.
+//!
+//! Endpoints validate content against these limits before sending a request,
+//! returning [`AniListError::BadRequest`] with a message that names the
+//! violated constant, so invalid input fails fast without using up rate limit
+//! budget. Paginated methods check their `page` and `per_page` arguments the
+//! same way, returning [`AniListError::InvalidInput`]. A `per_page` above
+//! [`MAX_PER_PAGE`] is split into several requests instead, unless the client
+//! is strict (see
+//! [`AniListClient::set_strict_page_size`](crate::AniListClient::set_strict_page_size)).
+
+use crate::error::AniListError;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Maximum value AniList accepts for `perPage` on paginated queries
+pub const MAX_PER_PAGE: i32 = 50;
+
+/// Maximum value AniList accepts for `perChunk` on `MediaListCollection`
+pub const MAX_PER_CHUNK: i32 = 500;
+
+/// Default cap on the chunks fetched for one list collection, i.e. up to
+/// 50,000 entries
+pub const DEFAULT_MAX_CHUNKS: u32 = 100;
+
+/// Minimum length of a review body, in characters
+pub const REVIEW_BODY_MIN_CHARS: usize = 2200;
+
+/// Minimum length of a review summary, in characters
+pub const SUMMARY_MIN_CHARS: usize = 20;
+
+/// Maximum length of a review summary, in characters
+pub const SUMMARY_MAX_CHARS: usize = 120;
+
+/// Lowest score that can be given in a review
+pub const REVIEW_SCORE_MIN: i32 = 0;
+
+/// Highest score that can be given in a review
+pub const REVIEW_SCORE_MAX: i32 = 100;
+
+/// Maximum number of notification IDs sent in a single mark-as-read mutation
+pub const MAX_NOTIFICATION_IDS_PER_MUTATION: usize = 50;
+
+/// Maximum length of a user's About text, in characters
+pub const ABOUT_MAX_CHARS: usize = 20_000;
+
+/// Minimum length of activity text, replies, thread titles, bodies and comments
+pub const TEXT_MIN_CHARS: usize = 1;
+
+/// Sort values accepted by AniList's `sort` arguments.
+pub mod sort {
+    /// Sort media by popularity, most popular first
+    pub const POPULARITY_DESC: &str = "POPULARITY_DESC";
+
+    /// Sort media by trending score, highest first
+    pub const TRENDING_DESC: &str = "TRENDING_DESC";
+
+    /// Sort media by average score, highest first
+    pub const SCORE_DESC: &str = "SCORE_DESC";
+
+    /// Sort by number of favourites, highest first
+    pub const FAVOURITES_DESC: &str = "FAVOURITES_DESC";
+
+    /// Sort search results by relevance
+    pub const SEARCH_MATCH: &str = "SEARCH_MATCH";
+
+    /// Sort by ID, newest first
+    pub const ID_DESC: &str = "ID_DESC";
+
+    /// Sort by last update, most recent first
+    pub const UPDATED_AT_DESC: &str = "UPDATED_AT_DESC";
+
+    /// Sort airing schedules by airing time, soonest first
+    pub const AIRING_TIME: &str = "TIME";
+
+    /// Sort airing schedules by airing time, latest first
+    pub const AIRING_TIME_DESC: &str = "TIME_DESC";
+}
+
+/// Fails if `value` is shorter than `min` characters (ignoring surrounding whitespace).
+pub(crate) fn check_min_chars(
+    field: &str,
+    value: &str,
+    min: usize,
+    limit_name: &str,
+) -> Result<(), AniListError> {
+    let length = value.trim().chars().count();
+    if length < min {
+        return Err(AniListError::BadRequest {
+            message: format!(
+                "{field} must be at least {min} characters ({limit_name}), got {length}"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Fails if `value` is longer than `max` characters (ignoring surrounding whitespace).
+pub(crate) fn check_max_chars(
+    field: &str,
+    value: &str,
+    max: usize,
+    limit_name: &str,
+) -> Result<(), AniListError> {
+    let length = value.trim().chars().count();
+    if length > max {
+        return Err(AniListError::BadRequest {
+            message: format!(
+                "{field} must be at most {max} characters ({limit_name}), got {length}"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Fails if `value` lies outside `min..=max`.
+pub(crate) fn check_range(
+    field: &str,
+    value: i32,
+    (min, min_name): (i32, &str),
+    (max, max_name): (i32, &str),
+) -> Result<(), AniListError> {
+    if value < min || value > max {
+        return Err(AniListError::BadRequest {
+            message: format!(
+                "{field} must be between {min} ({min_name}) and {max} ({max_name}), got {value}"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Fails unless `page` and `per_page` are at least 1.
+///
+/// A `per_page` above [`MAX_PER_PAGE`] passes: the client splits it into
+/// several requests, or rejects it with [`per_page_too_large`] in strict mode.
+pub(crate) fn check_page(page: i32, per_page: i32) -> Result<(), AniListError> {
+    if page < 1 {
+        return Err(AniListError::InvalidInput {
+            field: "page".to_string(),
+            message: format!("must be at least 1, got {page}"),
+        });
+    }
+    if per_page < 1 {
+        return Err(AniListError::InvalidInput {
+            field: "per_page".to_string(),
+            message: format!("must be at least 1, got {per_page}"),
+        });
+    }
+    Ok(())
+}
+
+/// The error for a `per_page` above [`MAX_PER_PAGE`] on a strict client, or
+/// for a nested connection, which cannot be split into several requests.
+pub(crate) fn per_page_too_large(per_page: i64) -> AniListError {
+    AniListError::InvalidInput {
+        field: "per_page".to_string(),
+        message: format!("must be between 1 and {MAX_PER_PAGE} (MAX_PER_PAGE), got {per_page}"),
+    }
+}
+
+/// The `page` and `perPage` variables of a paginated query, once
+/// [`check_page`] accepts them.
+pub(crate) fn page_variables(
+    page: i32,
+    per_page: i32,
+) -> Result<HashMap<String, Value>, AniListError> {
+    check_page(page, per_page)?;
+    Ok(HashMap::from([
+        ("page".to_string(), json!(page)),
+        ("perPage".to_string(), json!(per_page)),
+    ]))
+}
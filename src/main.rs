@@ -1,5 +1,6 @@
 use anilist_sdk::client::AniListClient;
 
+#[cfg(not(target_arch = "wasm32"))]
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AniListClient::new();
@@ -10,3 +11,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Browser builds run the SDK from a page instead; see `examples/wasm_trending.rs`.
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,344 @@
+//! Request counters for operators.
+//!
+//! Every [`AniListClient`](crate::AniListClient) counts the requests it sends
+//! and how they failed, shared between its clones. Read them with
+//! [`AniListClient::metrics`](crate::AniListClient::metrics).
+//!
+//! Connection-level failures (refused or reset connections, TLS handshakes
+//! that fail) are counted separately from HTTP errors: a rising
+//! [`MetricsSnapshot::connection_errors`] with few HTTP errors points at
+//! connection reuse problems rather than at the API, and is the signal to
+//! tune the pool settings on [`ClientConfig`](crate::client::ClientConfig).
+//!
+//! Failed attempts are also counted by [`AniListError`] variant, rate limited
+//! attempts and retries made by the client have their own counters, and each
+//! counter is broken down by GraphQL operation in
+//! [`MetricsSnapshot::operations`]. An operation is named after the document's
+//! operation name when it has one, and otherwise after its root field and the
+//! field below it, such as `Media` or `Page.media`.
+
+use crate::error::AniListError;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Live counters, updated by the client as requests complete.
+#[derive(Debug, Default)]
+pub(crate) struct ClientMetrics {
+    requests: AtomicU64,
+    http_errors: AtomicU64,
+    network_errors: AtomicU64,
+    connection_errors: AtomicU64,
+    rate_limited: AtomicU64,
+    retries: AtomicU64,
+    responses: AtomicU64,
+    latency_micros: AtomicU64,
+    breakdown: Mutex<Breakdown>,
+}
+
+/// Counters keyed by error variant and by operation.
+#[derive(Debug, Default)]
+struct Breakdown {
+    errors: BTreeMap<&'static str, u64>,
+    operations: BTreeMap<String, OperationMetrics>,
+}
+
+impl ClientMetrics {
+    pub(crate) fn record_request(&self, query: &str) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.operation(query, |operation| operation.requests += 1);
+    }
+
+    /// Records a response received `elapsed` after its request was sent.
+    pub(crate) fn record_response(&self, elapsed: Duration) {
+        self.responses.fetch_add(1, Ordering::Relaxed);
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.latency_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    /// Records an attempt of `query` that failed with `error`.
+    pub(crate) fn record_error(&self, query: &str, error: &AniListError) {
+        let rate_limited = error.is_rate_limited();
+        if rate_limited {
+            self.rate_limited.fetch_add(1, Ordering::Relaxed);
+        }
+        *self
+            .lock()
+            .errors
+            .entry(error_kind(error))
+            .or_default() += 1;
+        self.operation(query, |operation| {
+            operation.errors += 1;
+            operation.rate_limited += u64::from(rate_limited);
+        });
+    }
+
+    pub(crate) fn record_retry(&self, query: &str) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+        self.operation(query, |operation| operation.retries += 1);
+    }
+
+    pub(crate) fn record_http_error(&self) {
+        self.http_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_timeout(&self) {
+        self.network_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_network_error(&self, error: &reqwest::Error) {
+        self.network_errors.fetch_add(1, Ordering::Relaxed);
+        if is_connection_error(error) {
+            self.connection_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+        let breakdown = self.lock();
+        MetricsSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            http_errors: self.http_errors.load(Ordering::Relaxed),
+            network_errors: self.network_errors.load(Ordering::Relaxed),
+            connection_errors: self.connection_errors.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            responses: self.responses.load(Ordering::Relaxed),
+            total_latency: Duration::from_micros(self.latency_micros.load(Ordering::Relaxed)),
+            errors: breakdown.errors.clone(),
+            operations: breakdown.operations.clone(),
+        }
+    }
+
+    fn operation(&self, query: &str, update: impl FnOnce(&mut OperationMetrics)) {
+        let name = operation_name(query);
+        update(self.lock().operations.entry(name).or_default());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Breakdown> {
+        self.breakdown
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// The client's counters at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// Requests sent, successful or not
+    pub requests: u64,
+    /// Responses with a non-2xx status
+    pub http_errors: u64,
+    /// Requests that failed before a complete response arrived, including
+    /// timeouts and the connection errors below
+    pub network_errors: u64,
+    /// Network errors caused by the connection itself: refused, reset or
+    /// aborted connections and failed handshakes
+    pub connection_errors: u64,
+    /// Attempts answered with a rate limit or burst limit error
+    pub rate_limited: u64,
+    /// Attempts repeated by the client's own retry policy
+    pub retries: u64,
+    /// Requests that got a response, whatever its status
+    pub responses: u64,
+    /// Time spent waiting for those responses, summed
+    pub total_latency: Duration,
+    /// Failed attempts by [`AniListError`] variant name, such as `"NotFound"`
+    pub errors: BTreeMap<&'static str, u64>,
+    /// The same counters for each GraphQL operation
+    pub operations: BTreeMap<String, OperationMetrics>,
+}
+
+/// Counters for one GraphQL operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// Requests sent
+    pub requests: u64,
+    /// Attempts that failed, for any reason
+    pub errors: u64,
+    /// Attempts answered with a rate limit or burst limit error
+    pub rate_limited: u64,
+    /// Attempts repeated by the client's own retry policy
+    pub retries: u64,
+}
+
+impl MetricsSnapshot {
+    /// Share of requests that failed at the connection level, from 0.0 to 1.0.
+    pub fn connection_error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.connection_errors as f64 / self.requests as f64
+    }
+
+    /// Average time from sending a request to receiving its response, or
+    /// zero before the first response.
+    pub fn avg_latency(&self) -> Duration {
+        match u32::try_from(self.responses) {
+            Ok(0) => Duration::ZERO,
+            Ok(responses) => self.total_latency / responses,
+            Err(_) => self.total_latency.div_f64(self.responses as f64),
+        }
+    }
+
+    /// Failed attempts of any kind.
+    pub fn errors_total(&self) -> u64 {
+        self.errors.values().sum()
+    }
+}
+
+/// The variant name of `error`, used as its metrics key.
+fn error_kind(error: &AniListError) -> &'static str {
+    match error {
+        AniListError::Network(_) => "Network",
+        AniListError::Timeout { .. } => "Timeout",
+        AniListError::Json(_) => "Json",
+        AniListError::GraphQL { .. } => "GraphQL",
+        AniListError::RateLimit { .. } => "RateLimit",
+        AniListError::RateLimitSimple => "RateLimitSimple",
+        AniListError::BurstLimit => "BurstLimit",
+        AniListError::NotFound => "NotFound",
+        AniListError::AuthenticationRequired => "AuthenticationRequired",
+        AniListError::AccessDenied => "AccessDenied",
+        AniListError::Config { .. } => "Config",
+        AniListError::BudgetUnavailable { .. } => "BudgetUnavailable",
+        AniListError::Private { .. } => "Private",
+        AniListError::AdultContentBlocked { .. } => "AdultContentBlocked",
+        AniListError::BadRequest { .. } => "BadRequest",
+        AniListError::InvalidInput { .. } => "InvalidInput",
+        AniListError::RequestAborted { .. } => "RequestAborted",
+        AniListError::PageLimitReached { .. } => "PageLimitReached",
+        AniListError::ServerError { .. } => "ServerError",
+    }
+}
+
+/// The label a GraphQL document is counted under: its operation name, or
+/// else its first root field and, unless it is a plain scalar, the first
+/// field selected below that.
+pub(crate) fn operation_name(document: &str) -> String {
+    let tokens = tokens(document);
+    let mut tokens = tokens.iter().copied().peekable();
+    if tokens
+        .next_if(|token| matches!(*token, "query" | "mutation" | "subscription"))
+        .is_some()
+        && let Some(name) = tokens.next_if(|token| is_name(token))
+    {
+        return name.to_string();
+    }
+
+    // Skip variable definitions and directives up to the selection set
+    if tokens.find(|token| *token == "{").is_none() {
+        return "unknown".to_string();
+    }
+    let mut path = Vec::new();
+    while path.len() < 2 {
+        let Some(mut field) = tokens.next_if(|token| is_name(token)) else {
+            break;
+        };
+        if tokens.next_if_eq(&":").is_some() {
+            match tokens.next_if(|token| is_name(token)) {
+                Some(aliased) => field = aliased,
+                None => break,
+            }
+        }
+        let arguments = tokens.next_if_eq(&"(").is_some();
+        if arguments {
+            let mut depth = 1;
+            for token in tokens.by_ref() {
+                match token {
+                    "(" => depth += 1,
+                    ")" => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
+        }
+        let selection = tokens.next_if_eq(&"{").is_some();
+        // Leaf fields such as `id` say nothing about the operation
+        if path.is_empty() || arguments || selection {
+            path.push(field);
+        }
+        if !selection {
+            break;
+        }
+    }
+    if path.is_empty() {
+        return "unknown".to_string();
+    }
+    path.join(".")
+}
+
+fn is_name(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_alphabetic() || c == '_')
+}
+
+/// Splits a document into names and punctuation, dropping comments, string
+/// literals and whitespace.
+fn tokens(document: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = document;
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '#' {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if c == '"' {
+            let mut escaped = false;
+            rest[1..]
+                .find(|c| {
+                    let end = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    end
+                })
+                .map_or(rest.len(), |end| end + 2)
+        } else if c.is_alphanumeric() || c == '_' {
+            rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len())
+        } else {
+            c.len_utf8()
+        };
+        let (token, next) = rest.split_at(len);
+        if !(c == '#' || c == '"' || c == ',' || c.is_whitespace() || c == '\u{feff}') {
+            tokens.push(token);
+        }
+        rest = next;
+    }
+    tokens
+}
+
+/// Whether a transport error comes from the connection rather than from a
+/// timeout or a malformed request.
+///
+/// Besides errors while connecting, this covers connections the server or CDN
+/// dropped while a pooled connection was being reused, which reqwest reports
+/// as request or body errors wrapping an I/O error.
+pub(crate) fn is_connection_error(error: &reqwest::Error) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    if error.is_connect() {
+        return true;
+    }
+    if error.is_timeout() {
+        return false;
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            use std::io::ErrorKind::*;
+            return matches!(
+                io.kind(),
+                ConnectionReset
+                    | ConnectionAborted
+                    | ConnectionRefused
+                    | BrokenPipe
+                    | UnexpectedEof
+            );
+        }
+        // hyper reports a connection closed mid-response without an I/O error
+        let message = cause.to_string();
+        if message.contains("connection closed") || message.contains("IncompleteMessage") {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
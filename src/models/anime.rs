@@ -3,18 +3,25 @@
 //! This module contains data structures representing anime information
 //! as returned by the AniList API.
 
+use super::color::Color;
+use super::external_link::{MediaExternalLink, SiteLink, StreamingEpisode, watch_sites};
+use super::ranking::{MediaRank, best_ranking};
+use super::tag::{MediaTag, visible_tags};
 use serde::{Deserialize, Serialize};
 
 /// Represents a complete anime entry from AniList.
 ///
 /// This struct contains comprehensive information about an anime series or movie,
-/// including metadata, statistics, dates, and relationships. All fields are optional
-/// as different API endpoints may return varying levels of detail.
+/// including metadata, statistics, dates, and relationships. `id` and `title` are
+/// selected by every anime query in this crate and are therefore always present;
+/// all other fields are optional as different API endpoints may return varying
+/// levels of detail.
 ///
 /// # Field Descriptions
 ///
 /// ## Identification
 /// - `id`: Unique AniList identifier for this anime
+/// - `id_mal`: MyAnimeList identifier, when the anime is on MyAnimeList
 /// - `title`: Multi-language title information (romaji, english, native)
 /// - `hashtag`: Official hashtag used for social media
 ///
@@ -55,7 +62,7 @@ use serde::{Deserialize, Serialize};
 /// let client = AniListClient::new();
 /// let anime = client.anime().get_by_id(16498).await?;
 ///
-/// println!("Title: {}", anime.title.as_ref().unwrap().romaji);
+/// println!("Title: {}", anime.title.romaji.as_deref().unwrap_or("?"));
 /// println!("Episodes: {}", anime.episodes.unwrap_or(0));
 /// println!("Score: {}/100", anime.average_score.unwrap_or(0));
 ///
@@ -74,8 +81,14 @@ use serde::{Deserialize, Serialize};
 pub struct Anime {
     /// Unique identifier for this anime on AniList
     pub id: i32,
-    /// Multi-language title information including romaji, english, and native titles
-    pub title: Option<MediaTitle>,
+    /// ID of the same anime on MyAnimeList, if it has one
+    pub id_mal: Option<i32>,
+    /// Multi-language title information including romaji, english, and native titles.
+    ///
+    /// Always present: every anime query selects `title`, and AniList returns the
+    /// object even when individual languages are missing, so those stay optional
+    /// on [`MediaTitle`].
+    pub title: MediaTitle,
     /// Synopsis or description of the anime (may contain HTML formatting)
     pub description: Option<String>,
     /// Format/type of the anime (TV series, movie, OVA, etc.)
@@ -118,6 +131,47 @@ pub struct Anime {
     pub trailer: Option<MediaTrailer>,
     pub updated_at: Option<i32>,
     pub site_url: Option<String>,
+    /// Links to external sites; only selected by [`get_by_id`](crate::endpoints::AnimeEndpoint::get_by_id)
+    pub external_links: Option<Vec<MediaExternalLink>>,
+    /// Episodes on legal streaming sites; only selected by
+    /// [`get_by_id`](crate::endpoints::AnimeEndpoint::get_by_id), see also
+    /// [`get_streaming_episodes`](crate::endpoints::AnimeEndpoint::get_streaming_episodes)
+    pub streaming_episodes: Option<Vec<StreamingEpisode>>,
+    /// Tags with their rank and spoiler flags for this anime; selected by
+    /// [`get_by_id`](crate::endpoints::AnimeEndpoint::get_by_id) and when
+    /// searching by tag, e.g. by [`get_by_tags`](crate::endpoints::AnimeEndpoint::get_by_tags).
+    /// See [`visible_tags`](Self::visible_tags) for the ones safe to show.
+    pub tags: Option<Vec<MediaTag>>,
+    /// Places in AniList's rankings; only selected by
+    /// [`get_by_id`](crate::endpoints::AnimeEndpoint::get_by_id)
+    pub rankings: Option<Vec<MediaRank>>,
+}
+
+impl Anime {
+    /// Where to watch this anime: links to known streaming sites, official
+    /// first, with spelling variants merged (see [`watch_sites`]).
+    ///
+    /// Empty unless the anime was fetched with external links.
+    pub fn streaming_sites(&self) -> Vec<SiteLink> {
+        watch_sites(self.external_links.as_deref().unwrap_or_default())
+    }
+
+    /// The tags that are not spoilers, in AniList's order.
+    ///
+    /// Leaves out tags flagged as a spoiler in general or for this anime, so
+    /// they are not shown by accident; `tags` still has every tag. Empty
+    /// unless the anime was fetched with tags.
+    pub fn visible_tags(&self) -> Vec<&MediaTag> {
+        visible_tags(self.tags.as_deref().unwrap_or_default())
+    }
+
+    /// The most specific all-time rank by score, e.g. "#12 Highest Rated
+    /// All Time" for TV series rather than across every format.
+    ///
+    /// `None` unless the anime was fetched with rankings and has such a rank.
+    pub fn best_ranking(&self) -> Option<&MediaRank> {
+        best_ranking(self.rankings.as_deref().unwrap_or_default())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -136,7 +190,12 @@ pub struct FuzzyDate {
     pub day: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+/// The format of a media entry, shared by anime, manga and every social model.
+///
+/// Serialized exactly as AniList's `MediaFormat` enum values
+/// (`TV`, `TV_SHORT`, `MOVIE`, `SPECIAL`, `OVA`, `ONA`, `MUSIC`, `MANGA`,
+/// `NOVEL`, `ONE_SHOT`).
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MediaFormat {
     Tv,
@@ -151,7 +210,53 @@ pub enum MediaFormat {
     OneShot,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+/// Orders accepted by AniList's `sort` argument on media queries.
+///
+/// Serialized exactly as AniList's `MediaSort` enum values, e.g.
+/// `SCORE_DESC` or `TITLE_ROMAJI`.
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MediaSort {
+    Id,
+    IdDesc,
+    TitleRomaji,
+    TitleRomajiDesc,
+    TitleEnglish,
+    TitleEnglishDesc,
+    TitleNative,
+    TitleNativeDesc,
+    Type,
+    TypeDesc,
+    Format,
+    FormatDesc,
+    StartDate,
+    StartDateDesc,
+    EndDate,
+    EndDateDesc,
+    Score,
+    ScoreDesc,
+    Popularity,
+    PopularityDesc,
+    Trending,
+    TrendingDesc,
+    Episodes,
+    EpisodesDesc,
+    Duration,
+    DurationDesc,
+    Status,
+    StatusDesc,
+    Chapters,
+    ChaptersDesc,
+    Volumes,
+    VolumesDesc,
+    UpdatedAt,
+    UpdatedAtDesc,
+    SearchMatch,
+    Favourites,
+    FavouritesDesc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MediaStatus {
     Finished,
@@ -161,7 +266,7 @@ pub enum MediaStatus {
     Hiatus,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MediaSeason {
     Winter,
@@ -170,6 +275,19 @@ pub enum MediaSeason {
     Fall,
 }
 
+impl MediaSeason {
+    /// The value AniList expects for this season, as accepted by
+    /// [`AnimeEndpoint::get_by_season`](crate::endpoints::anime::AnimeEndpoint::get_by_season).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaSeason::Winter => "WINTER",
+            MediaSeason::Spring => "SPRING",
+            MediaSeason::Summer => "SUMMER",
+            MediaSeason::Fall => "FALL",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Copy)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MediaSource {
@@ -206,9 +324,17 @@ pub struct MediaCoverImage {
     pub extra_large: Option<String>,
     pub large: Option<String>,
     pub medium: Option<String>,
+    /// Average color of the cover as a hex string, e.g. `"#e4a15d"`
     pub color: Option<String>,
 }
 
+impl MediaCoverImage {
+    /// The cover's average color, if present and valid.
+    pub fn parsed_color(&self) -> Option<Color> {
+        self.color.as_deref().and_then(Color::parse)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaTrailer {
     pub id: Option<String>,
@@ -216,6 +342,96 @@ pub struct MediaTrailer {
     pub thumbnail: Option<String>,
 }
 
+/// Video sites AniList hosts trailers on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrailerSite {
+    YouTube,
+    Dailymotion,
+}
+
+impl MediaTrailer {
+    fn site_and_id(&self) -> Option<(TrailerSite, &str)> {
+        let id = self.id.as_deref().filter(|id| !id.is_empty())?;
+        let site = match self.site.as_deref()?.to_ascii_lowercase().as_str() {
+            "youtube" => TrailerSite::YouTube,
+            "dailymotion" => TrailerSite::Dailymotion,
+            _ => return None,
+        };
+        Some((site, id))
+    }
+
+    /// Watch page URL for the trailer, or `None` for unknown sites.
+    ///
+    /// ```rust
+    /// # use anilist_sdk::models::MediaTrailer;
+    /// let trailer = MediaTrailer {
+    ///     id: Some("dQw4w9WgXcQ".to_string()),
+    ///     site: Some("youtube".to_string()),
+    ///     thumbnail: None,
+    /// };
+    /// assert_eq!(
+    ///     trailer.url().as_deref(),
+    ///     Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ")
+    /// );
+    /// ```
+    pub fn url(&self) -> Option<String> {
+        let (site, id) = self.site_and_id()?;
+        Some(match site {
+            TrailerSite::YouTube => format!("https://www.youtube.com/watch?v={id}"),
+            TrailerSite::Dailymotion => format!("https://www.dailymotion.com/video/{id}"),
+        })
+    }
+
+    /// Embeddable player URL for the trailer (for an `<iframe>`), or `None`
+    /// for unknown sites.
+    pub fn embed_url(&self) -> Option<String> {
+        let (site, id) = self.site_and_id()?;
+        Some(match site {
+            TrailerSite::YouTube => format!("https://www.youtube.com/embed/{id}"),
+            TrailerSite::Dailymotion => format!("https://www.dailymotion.com/embed/video/{id}"),
+        })
+    }
+
+    /// The thumbnail AniList provides, falling back to the site's default
+    /// thumbnail for the video.
+    pub fn thumbnail_or_default(&self) -> Option<String> {
+        if let Some(thumbnail) = self.thumbnail.as_ref().filter(|t| !t.is_empty()) {
+            return Some(thumbnail.clone());
+        }
+        let (site, id) = self.site_and_id()?;
+        Some(match site {
+            TrailerSite::YouTube => format!("https://i.ytimg.com/vi/{id}/hqdefault.jpg"),
+            TrailerSite::Dailymotion => {
+                format!("https://www.dailymotion.com/thumbnail/video/{id}")
+            }
+        })
+    }
+}
+
+/// A lightweight search result for typeahead/autocomplete UIs.
+///
+/// Only the fields needed to render a suggestion are requested; the title is
+/// the viewer's preferred rendering (romaji, English or native, depending on
+/// their settings), so results differ between authenticated users.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaSuggestion {
+    pub id: i32,
+    #[serde(rename = "type")]
+    pub media_type: Option<crate::models::social::MediaType>,
+    pub title: Option<MediaTitle>,
+    pub format: Option<MediaFormat>,
+    pub season_year: Option<i32>,
+    pub cover_image: Option<MediaCoverImage>,
+}
+
+impl MediaSuggestion {
+    /// The title to display, as preferred by the viewer.
+    pub fn display_title(&self) -> Option<&str> {
+        self.title.as_ref()?.user_preferred.as_deref()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StudioConnection {
     pub edges: Option<Vec<StudioEdge>>,
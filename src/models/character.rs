@@ -3,8 +3,10 @@
 //! This module contains data structures representing character information
 //! as returned by the AniList API, including character details, names, and images.
 
-use super::FuzzyDate;
+use super::staff::Staff;
+use super::{FuzzyDate, MediaTitle};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Represents a character entry from AniList.
 ///
@@ -29,6 +31,9 @@ use serde::{Deserialize, Serialize};
 /// - `age`: Character's age (can be a range or specific age)
 /// - `blood_type`: Character's blood type (common in anime/manga)
 ///
+/// ## Context
+/// - `media`: The character's most popular appearance, when selected (search results)
+///
 /// ## User Interaction
 /// - `is_favourite`: Whether the authenticated user has favorited this character
 /// - `is_favourite_blocked`: Whether favoriting is blocked for this character
@@ -45,14 +50,12 @@ use serde::{Deserialize, Serialize};
 /// let client = AniListClient::new();
 /// let character = client.character().get_by_id(40882).await?;
 ///
-/// if let Some(name) = &character.name {
-///     println!("Character: {} {}",
-///         name.first.as_deref().unwrap_or(""),
-///         name.last.as_deref().unwrap_or("")
-///     );
-/// }
+/// println!("Character: {} {}",
+///     character.name.first.as_deref().unwrap_or(""),
+///     character.name.last.as_deref().unwrap_or("")
+/// );
 ///
-/// println!("Favorites: {}", character.favourites.unwrap_or(0));
+/// println!("Favorites: {}", character.favourites);
 ///
 /// if let Some(description) = &character.description {
 ///     println!("Description: {}", description);
@@ -61,23 +64,33 @@ use serde::{Deserialize, Serialize};
 ///
 /// # Note
 ///
-/// Many fields may be `None` depending on the completeness of character data
-/// and whether the information is available or disclosed.
+/// `id`, `name`, `image`, `site_url` and `favourites` are selected by every
+/// character query and always returned. The other fields are `None` when the
+/// query did not select them, when the information is not known, or, for
+/// `is_favourite`, when the client has no token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Character {
     /// Unique identifier for this character on AniList
     pub id: i32,
 
-    /// Multi-part name information for the character
-    pub name: Option<CharacterName>,
+    /// Multi-part name information for the character.
+    ///
+    /// Always present: every character query selects `name`, and AniList
+    /// returns the object even when individual parts are missing, so those stay
+    /// optional on [`CharacterName`].
+    pub name: CharacterName,
 
-    /// Character portrait images in various sizes
-    pub image: Option<CharacterImage>,
+    /// Character portrait images in various sizes.
+    ///
+    /// Always present: every character query selects `image`, and AniList
+    /// falls back to a placeholder for characters without a portrait.
+    pub image: CharacterImage,
 
-    /// Character biography and background information (may contain HTML)
+    /// Character biography and background information (may contain HTML);
+    /// not selected on media edges
     pub description: Option<String>,
 
-    /// Character's gender (if specified)
+    /// Character's gender; `None` when AniList has none on record
     pub gender: Option<String>,
 
     /// Character's date of birth (if known)
@@ -91,24 +104,85 @@ pub struct Character {
     #[serde(rename = "bloodType")]
     pub blood_type: Option<String>,
 
-    /// Whether the authenticated user has favorited this character
+    /// Whether the authenticated user has favorited this character; `None`
+    /// without a token or when not selected
     #[serde(rename = "isFavourite")]
     pub is_favourite: Option<bool>,
 
-    /// Whether favoriting is blocked for this character
+    /// Whether favoriting is blocked for this character; not selected by
+    /// every query
     #[serde(rename = "isFavouriteBlocked")]
     pub is_favourite_blocked: Option<bool>,
 
-    /// Direct URL to this character's page on AniList
+    /// Direct URL to this character's page on AniList (always selected)
     #[serde(rename = "siteUrl")]
-    pub site_url: Option<String>,
+    pub site_url: String,
 
-    /// Total number of users who have favorited this character
-    pub favourites: Option<i32>,
+    /// Total number of users who have favorited this character (always
+    /// selected, `0` when nobody has)
+    pub favourites: i32,
 
-    /// Moderator notes (only visible to moderators)
+    /// Moderator notes (only visible to moderators, so usually `None`)
     #[serde(rename = "modNotes")]
     pub mod_notes: Option<String>,
+
+    /// Media the character appears in (search selects only the most popular one)
+    pub media: Option<CharacterMediaConnection>,
+}
+
+impl Character {
+    /// Preferred title of the character's most popular media, if it was selected.
+    ///
+    /// Useful to tell same-named characters apart in search results, e.g.
+    /// "Ichigo Kurosaki (Bleach)".
+    pub fn primary_media_title(&self) -> Option<&str> {
+        self.media
+            .as_ref()?
+            .nodes
+            .as_ref()?
+            .first()?
+            .title
+            .as_ref()?
+            .user_preferred
+            .as_deref()
+    }
+}
+
+/// Media a character appears in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterMediaConnection {
+    /// The media, in the order requested by the query
+    pub nodes: Option<Vec<CharacterMedia>>,
+}
+
+/// A media entry a character appears in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterMedia {
+    /// AniList ID of the media
+    pub id: Option<i32>,
+
+    /// Titles of the media
+    pub title: Option<MediaTitle>,
+}
+
+/// A character's role in a media entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CharacterRole {
+    Main,
+    Supporting,
+    Background,
+}
+
+/// An edge of a media entry's `characters` connection: a character, their
+/// role in that entry and who voices them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterEdge {
+    pub role: Option<CharacterRole>,
+    pub node: Option<Character>,
+    /// Voice actors in the requested language, most relevant first
+    pub voice_actors: Option<Vec<Staff>>,
 }
 
 /// Represents the name information for a character.
@@ -151,6 +225,7 @@ pub struct CharacterName {
     pub alternative: Option<Vec<String>>,
 
     /// Alternative names that may contain spoilers
+    #[serde(rename = "alternativeSpoiler")]
     pub alternative_spoiler: Option<Vec<String>>,
 
     /// User's preferred name format (based on user settings)
@@ -158,6 +233,58 @@ pub struct CharacterName {
     pub user_preferred: Option<String>,
 }
 
+impl CharacterName {
+    /// Every known name of the character, for matching against search input.
+    ///
+    /// Names are listed in order of preference (user preferred, full, native,
+    /// then the individual parts and alternatives), trimmed, with blanks
+    /// dropped. Names that only differ by case or by full-width/half-width
+    /// forms are listed once, in the form first seen.
+    ///
+    /// Alternative names that AniList marks as spoilers are only included when
+    /// `include_spoilers` is set, so they never leak into UI by accident.
+    pub fn all_names(&self, include_spoilers: bool) -> Vec<String> {
+        let single = [
+            &self.user_preferred,
+            &self.full,
+            &self.native,
+            &self.first,
+            &self.middle,
+            &self.last,
+        ];
+        let alternatives = self.alternative.iter().flatten();
+        let spoilers = self
+            .alternative_spoiler
+            .iter()
+            .flatten()
+            .filter(|_| include_spoilers);
+
+        let mut seen = HashSet::new();
+        single
+            .into_iter()
+            .flatten()
+            .chain(alternatives)
+            .chain(spoilers)
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty() && seen.insert(name_key(name)))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Folds full-width forms to ASCII and lowercases, so "ＥＤ" and "ed" compare
+/// equal.
+fn name_key(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 /// Represents character image URLs in different sizes.
 ///
 /// Provides character portrait images optimized for different display contexts.
@@ -166,16 +293,14 @@ pub struct CharacterName {
 /// # Examples
 ///
 /// ```rust
-/// if let Some(image) = &character.image {
-///     // Use large image for detailed view
-///     if let Some(large_url) = &image.large {
-///         println!("High-res image: {}", large_url);
-///     }
-///     
-///     // Use medium image for list views
-///     if let Some(medium_url) = &image.medium {
-///         println!("Medium image: {}", medium_url);
-///     }
+/// // Use large image for detailed view
+/// if let Some(large_url) = &character.image.large {
+///     println!("High-res image: {}", large_url);
+/// }
+///
+/// // Use medium image for list views
+/// if let Some(medium_url) = &character.image.medium {
+///     println!("Medium image: {}", medium_url);
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,100 @@
+//! # Colors
+//!
+//! AniList reports colors in two forms: cover images carry a hex string such
+//! as `"#e4a15d"`, while a user's `profileColor` is usually one of the site's
+//! named theme colors (`"blue"`, `"purple"`, ...) and a hex string only for
+//! supporters who picked a custom one. [`Color::parse`] accepts both.
+
+use std::fmt;
+
+/// An RGB color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// AniList's named profile colors and the RGB values the site renders them with.
+pub const NAMED_COLORS: [(&str, Color); 7] = [
+    ("blue", Color::rgb(61, 180, 242)),
+    ("purple", Color::rgb(192, 99, 255)),
+    ("pink", Color::rgb(252, 157, 214)),
+    ("orange", Color::rgb(239, 136, 26)),
+    ("red", Color::rgb(225, 51, 51)),
+    ("green", Color::rgb(76, 202, 81)),
+    ("gray", Color::rgb(103, 123, 148)),
+];
+
+impl Color {
+    /// Creates a color from its components.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Parses an AniList color: a named profile color (case-insensitive) or a
+    /// hex color in `#rgb` or `#rrggbb` form, with or without the `#`.
+    ///
+    /// Returns `None` for anything else.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anilist_sdk::models::Color;
+    ///
+    /// assert_eq!(Color::parse("#e4a15d"), Some(Color::rgb(228, 161, 93)));
+    /// assert_eq!(Color::parse("blue").unwrap().to_hex(), "#3db4f2");
+    /// assert_eq!(Color::parse("not a color"), None);
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some((_, color)) = NAMED_COLORS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(value))
+        {
+            return Some(*color);
+        }
+
+        let hex = value.strip_prefix('#').unwrap_or(value);
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let component = |digits: &str| u8::from_str_radix(digits, 16).ok();
+        match hex.len() {
+            3 => {
+                let mut digits = hex.chars().map(|c| c.to_digit(16).map(|d| d as u8 * 17));
+                Some(Self::rgb(digits.next()??, digits.next()??, digits.next()??))
+            }
+            6 => Some(Self::rgb(
+                component(&hex[0..2])?,
+                component(&hex[2..4])?,
+                component(&hex[4..6])?,
+            )),
+            _ => None,
+        }
+    }
+
+    /// The color as a lowercase `#rrggbb` string.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// The color as an `(r, g, b)` tuple.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        (self.r, self.g, self.b)
+    }
+
+    /// The AniList name of this color, if it is one of the named profile colors.
+    pub fn name(&self) -> Option<&'static str> {
+        NAMED_COLORS
+            .iter()
+            .find(|(_, color)| color == self)
+            .map(|(name, _)| *name)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
@@ -0,0 +1,207 @@
+//! External links and "where to watch/read" aggregation.
+//!
+//! AniList attaches free-form external links to each media entry. The
+//! [`watch_sites`] and [`read_sites`] helpers (exposed as
+//! [`Anime::streaming_sites`](super::Anime::streaming_sites) and
+//! [`Manga::reading_sites`](super::Manga::reading_sites)) keep the links to
+//! known legal sites, merge spelling variants into one canonical name, and
+//! order them by a built-in priority.
+
+use super::color::Color;
+use serde::{Deserialize, Serialize};
+
+/// A link from a media entry to an external site.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaExternalLink {
+    pub id: i32,
+    pub url: Option<String>,
+    /// Site name as entered on AniList, e.g. `"Crunchyroll"`
+    pub site: String,
+    /// AniList's ID for the site, shared by every link to it
+    pub site_id: Option<i32>,
+    #[serde(rename = "type")]
+    pub link_type: Option<ExternalLinkType>,
+    /// Language of the site, e.g. `"English"`; `None` for multi-language sites
+    pub language: Option<String>,
+    /// The site's brand color as a hex string, e.g. `"#F88B24"`
+    pub color: Option<String>,
+    /// URL of the site's icon
+    pub icon: Option<String>,
+    pub is_disabled: Option<bool>,
+}
+
+impl MediaExternalLink {
+    /// The site's brand color, if present and valid.
+    pub fn parsed_color(&self) -> Option<Color> {
+        self.color.as_deref().and_then(Color::parse)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExternalLinkType {
+    Info,
+    Streaming,
+    Social,
+    /// A type AniList added after this version of the crate
+    #[serde(other)]
+    Unknown,
+}
+
+/// An episode on a streaming site, as listed under a media entry's
+/// `streamingEpisodes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamingEpisode {
+    /// Episode title as shown by the site, usually prefixed with its number,
+    /// e.g. `"Episode 1 - Asteroid Blues"`
+    pub title: Option<String>,
+    pub thumbnail: Option<String>,
+    pub url: Option<String>,
+    /// Site name, e.g. `"Crunchyroll"`, `"Hidive"` or `"Youtube"`
+    pub site: Option<String>,
+}
+
+/// A normalized link to a site where a title can be watched or read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteLink {
+    /// Canonical site name, e.g. `"Crunchyroll"` for both Crunchyroll and VRV links
+    pub site: &'static str,
+    pub url: String,
+    pub language: Option<String>,
+}
+
+/// What a known site is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SiteKind {
+    Watch,
+    Read,
+}
+
+/// A known site: canonical name, use, and the lowercase spellings AniList
+/// uses for it (including the name itself).
+type KnownSite = (&'static str, SiteKind, &'static [&'static str]);
+
+/// Known sites in priority order: the title's official site first, then
+/// first-party and large licensed services, then smaller ones.
+const KNOWN_SITES: &[KnownSite] = &[
+    ("Official Site", SiteKind::Watch, &["official site"]),
+    ("Official Site", SiteKind::Read, &["official site"]),
+    (
+        "Crunchyroll",
+        SiteKind::Watch,
+        &["crunchyroll", "vrv", "funimation"],
+    ),
+    ("Netflix", SiteKind::Watch, &["netflix"]),
+    (
+        "Amazon Prime Video",
+        SiteKind::Watch,
+        &["amazon prime video", "prime video", "amazon"],
+    ),
+    ("Disney Plus", SiteKind::Watch, &["disney plus", "disney+"]),
+    ("Hulu", SiteKind::Watch, &["hulu"]),
+    ("HIDIVE", SiteKind::Watch, &["hidive"]),
+    ("Max", SiteKind::Watch, &["max", "hbo max"]),
+    ("Bilibili TV", SiteKind::Watch, &["bilibili tv", "bilibili"]),
+    ("Tubi TV", SiteKind::Watch, &["tubi tv", "tubi"]),
+    ("YouTube", SiteKind::Watch, &["youtube"]),
+    (
+        "MANGA Plus",
+        SiteKind::Read,
+        &["manga plus", "manga plus by shueisha", "mangaplus"],
+    ),
+    ("VIZ", SiteKind::Read, &["viz", "viz media", "shonen jump"]),
+    ("K MANGA", SiteKind::Read, &["k manga", "kmanga"]),
+    ("Manga UP!", SiteKind::Read, &["manga up!", "manga up"]),
+    ("Comikey", SiteKind::Read, &["comikey"]),
+    ("Azuki", SiteKind::Read, &["azuki"]),
+    ("INKR", SiteKind::Read, &["inkr"]),
+    (
+        "WEBTOON",
+        SiteKind::Read,
+        &["webtoon", "webtoons", "line webtoon"],
+    ),
+    ("Tapas", SiteKind::Read, &["tapas"]),
+    ("Tappytoon", SiteKind::Read, &["tappytoon"]),
+    ("Pocket Comics", SiteKind::Read, &["pocket comics"]),
+    (
+        "BookWalker",
+        SiteKind::Read,
+        &["bookwalker", "book walker", "book☆walker"],
+    ),
+    (
+        "Amazon Kindle",
+        SiteKind::Read,
+        &["amazon kindle", "kindle", "amazon"],
+    ),
+];
+
+/// Looks up a site name, returning its canonical name and priority (lower
+/// sorts first) for the given use.
+fn known_site(kind: SiteKind, site: &str) -> Option<(usize, &'static str)> {
+    let site = site.trim().to_lowercase();
+    KNOWN_SITES
+        .iter()
+        .enumerate()
+        .find(|(_, (_, known_kind, aliases))| {
+            *known_kind == kind && aliases.contains(&site.as_str())
+        })
+        .map(|(priority, (name, _, _))| (priority, *name))
+}
+
+/// Canonical name of a streaming site, e.g. `"VRV"` becomes `"Crunchyroll"`.
+///
+/// Returns `None` for sites that are not in the built-in table.
+pub fn normalize_watch_site(site: &str) -> Option<&'static str> {
+    known_site(SiteKind::Watch, site).map(|(_, name)| name)
+}
+
+/// Canonical name of a reading site, e.g. `"MANGA Plus by SHUEISHA"` becomes
+/// `"MANGA Plus"`.
+///
+/// Returns `None` for sites that are not in the built-in table.
+pub fn normalize_read_site(site: &str) -> Option<&'static str> {
+    known_site(SiteKind::Read, site).map(|(_, name)| name)
+}
+
+fn collect_sites(kind: SiteKind, links: &[MediaExternalLink]) -> Vec<SiteLink> {
+    let mut sites: Vec<(usize, SiteLink)> = Vec::new();
+    for link in links {
+        if link.is_disabled == Some(true)
+            || !matches!(
+                link.link_type,
+                Some(ExternalLinkType::Streaming | ExternalLinkType::Info)
+            )
+        {
+            continue;
+        }
+        let (Some(url), Some((priority, site))) = (link.url.as_ref(), known_site(kind, &link.site))
+        else {
+            continue;
+        };
+        if sites.iter().any(|(_, existing)| existing.url == *url) {
+            continue;
+        }
+        sites.push((
+            priority,
+            SiteLink {
+                site,
+                url: url.clone(),
+                language: link.language.clone(),
+            },
+        ));
+    }
+    // Stable, so links to the same site keep AniList's order
+    sites.sort_by_key(|(priority, _)| *priority);
+    sites.into_iter().map(|(_, site)| site).collect()
+}
+
+/// Streaming and info links to known watch sites, official first.
+pub fn watch_sites(links: &[MediaExternalLink]) -> Vec<SiteLink> {
+    collect_sites(SiteKind::Watch, links)
+}
+
+/// Streaming and info links to known reading sites, official first.
+pub fn read_sites(links: &[MediaExternalLink]) -> Vec<SiteLink> {
+    collect_sites(SiteKind::Read, links)
+}
@@ -1,10 +1,17 @@
-use super::{FuzzyDate, MediaCoverImage, MediaFormat, MediaSource, MediaStatus, MediaTitle};
+use super::external_link::{MediaExternalLink, SiteLink, read_sites};
+use super::ranking::{MediaRank, best_ranking};
+use super::tag::{MediaTag, visible_tags};
+use super::{
+    FuzzyDate, MediaCoverImage, MediaFormat, MediaSource, MediaStatus, MediaTitle, MediaTrailer,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manga {
     pub id: i32,
-    pub title: Option<MediaTitle>,
+    /// Always present: every manga query selects `title`. Individual languages
+    /// may still be missing, see [`MediaTitle`].
+    pub title: MediaTitle,
     pub description: Option<String>,
     pub format: Option<MediaFormat>,
     pub status: Option<MediaStatus>,
@@ -31,8 +38,38 @@ pub struct Manga {
     #[serde(rename = "bannerImage")]
     pub banner_image: Option<String>,
     pub source: Option<MediaSource>,
+    pub trailer: Option<MediaTrailer>,
     #[serde(rename = "updatedAt")]
     pub updated_at: Option<i32>,
     #[serde(rename = "siteUrl")]
     pub site_url: Option<String>,
+    /// Links to external sites; only selected by [`get_by_id`](crate::endpoints::MangaEndpoint::get_by_id)
+    #[serde(rename = "externalLinks")]
+    pub external_links: Option<Vec<MediaExternalLink>>,
+    /// Tags with their rank and spoiler flags; only selected by [`get_by_id`](crate::endpoints::MangaEndpoint::get_by_id)
+    pub tags: Option<Vec<MediaTag>>,
+    /// Places in AniList's rankings; only selected by [`get_by_id`](crate::endpoints::MangaEndpoint::get_by_id)
+    pub rankings: Option<Vec<MediaRank>>,
+}
+
+impl Manga {
+    /// Where to read this manga: links to known reading sites, official
+    /// first, with spelling variants merged (see [`read_sites`]).
+    ///
+    /// Empty unless the manga was fetched with external links.
+    pub fn reading_sites(&self) -> Vec<SiteLink> {
+        read_sites(self.external_links.as_deref().unwrap_or_default())
+    }
+
+    /// The tags that are not spoilers, see
+    /// [`Anime::visible_tags`](super::Anime::visible_tags).
+    pub fn visible_tags(&self) -> Vec<&MediaTag> {
+        visible_tags(self.tags.as_deref().unwrap_or_default())
+    }
+
+    /// The most specific all-time rank by score, see
+    /// [`Anime::best_ranking`](super::Anime::best_ranking).
+    pub fn best_ranking(&self) -> Option<&MediaRank> {
+        best_ranking(self.rankings.as_deref().unwrap_or_default())
+    }
 }
@@ -1,33 +1,410 @@
+use crate::error::AniListError;
+use crate::models::score::{ScoreDisplay, ScoreFormat, format_score};
 use crate::models::{AiringSchedule, anime::MediaStatus};
 
 use super::{FuzzyDate, MediaCoverImage, MediaTitle};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
+/// An entry on a user's anime or manga list.
+///
+/// Every list query selects the same entry fields, so the ones AniList always
+/// fills in are required; see each field for why the rest stay optional.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaList {
     pub id: i32,
     pub user_id: i32,
     pub media_id: i32,
+    pub status: MediaListStatus,
+    /// `0` when the entry is unscored
+    pub score: f64,
+    pub progress: i32,
+    /// Only tracked for manga, so it is not relied on for anime entries
+    pub progress_volumes: Option<i32>,
+    pub repeat: i32,
+    pub priority: i32,
+    pub private: bool,
+    /// `None` when the user has written no notes
+    pub notes: Option<String>,
+    pub hidden_from_status_lists: bool,
+    /// Not selected by any list query
+    pub custom_lists: Option<serde_json::Value>,
+    /// Not selected by any list query
+    pub advanced_scores: Option<serde_json::Value>,
+    /// Always returned; unset parts of the date are `None`
+    pub started_at: FuzzyDate,
+    /// Always returned; unset parts of the date are `None`
+    pub completed_at: FuzzyDate,
+    /// Unix timestamp of the last change to this entry.
+    ///
+    /// Stays optional so entries returned without a timestamp are still
+    /// listed; [`UpdatedSince`] treats them as older than any checkpoint.
+    pub updated_at: Option<i64>,
+    /// Unix timestamp of when this entry was added; optional for the same
+    /// reason as `updated_at`
+    pub created_at: Option<i64>,
+    /// Not selected when saving an entry
+    pub media: Option<MediaListMedia>,
+}
+
+impl MediaList {
+    /// Number of episodes of this entry's media that have aired so far.
+    ///
+    /// For airing shows this is one less than the next airing episode; for
+    /// finished shows (whose `nextAiringEpisode` is null) it is the total
+    /// episode count. Returns `None` when it cannot be determined (e.g. the
+    /// media was not selected, or the show has not started airing).
+    pub fn aired_episodes(&self) -> Option<i32> {
+        let media = self.media.as_ref()?;
+        match (&media.next_airing_episode, media.status) {
+            (Some(next), _) => Some(next.episode - 1),
+            (None, Some(MediaStatus::Finished)) => media.episodes,
+            _ => None,
+        }
+    }
+
+    /// The entry's score formatted for display in the viewer's score format.
+    ///
+    /// Returns `None` when the entry is unscored (AniList reports a score of
+    /// `0`). See [`format_score`] for the rounding rules.
+    pub fn score_display(&self, viewer_format: ScoreFormat) -> Option<ScoreDisplay> {
+        (self.score > 0.0).then(|| format_score(self.score, viewer_format))
+    }
+
+    /// Number of aired episodes the user has not watched yet.
+    ///
+    /// See [`MediaList::aired_episodes`] for how the aired count is derived.
+    pub fn episodes_behind(&self) -> Option<i32> {
+        let aired = self.aired_episodes()?;
+        Some((aired - self.progress).max(0))
+    }
+}
+
+/// Changes to a list entry, sent with
+/// [`UserEndpoint::save_list_entry`](crate::endpoints::user::UserEndpoint::save_list_entry).
+///
+/// Start from [`SaveListEntry::for_media`] to add or update the viewer's entry
+/// for a title, or [`SaveListEntry::for_entry`] to update an entry by its own
+/// ID, then set only the fields to change; unset fields keep their current
+/// values on AniList.
+///
+/// Saving an entry normally posts a list activity on the viewer's profile.
+/// Entries saved with [`SaveListEntry::private`] set to `true` are hidden from
+/// other users and do not generate an activity.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::models::{MediaListStatus, SaveListEntry};
+///
+/// let entry = SaveListEntry::for_media(21)
+///     .status(MediaListStatus::Current)
+///     .progress(12)
+///     .private(true);
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveListEntry {
+    /// ID of an existing list entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    /// ID of the media; creates the entry if it is not on the list yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<MediaListStatus>,
+    /// Score in the viewer's score format
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub score: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub progress: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub progress_volumes: Option<i32>,
-    pub repeat: Option<i32>,
-    pub priority: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
-    pub hidden_from_status_lists: Option<bool>,
-    pub custom_lists: Option<serde_json::Value>,
-    pub advanced_scores: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub started_at: Option<FuzzyDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<FuzzyDate>,
-    pub updated_at: Option<i32>,
-    pub created_at: Option<i32>,
-    pub media: Option<MediaListMedia>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+impl SaveListEntry {
+    /// Changes to the viewer's entry for a media, creating it if needed.
+    pub fn for_media(media_id: i32) -> Self {
+        Self {
+            media_id: Some(media_id),
+            ..Default::default()
+        }
+    }
+
+    /// Changes to an existing list entry, by the entry's ID.
+    pub fn for_entry(id: i32) -> Self {
+        Self {
+            id: Some(id),
+            ..Default::default()
+        }
+    }
+
+    pub fn status(mut self, status: MediaListStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn score(mut self, score: f64) -> Self {
+        self.score = Some(score);
+        self
+    }
+
+    pub fn progress(mut self, progress: i32) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub fn progress_volumes(mut self, progress_volumes: i32) -> Self {
+        self.progress_volumes = Some(progress_volumes);
+        self
+    }
+
+    /// Hides the entry from other users; private updates post no activity.
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = Some(private);
+        self
+    }
+
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    pub fn started_at(mut self, started_at: FuzzyDate) -> Self {
+        self.started_at = Some(started_at);
+        self
+    }
+
+    pub fn completed_at(mut self, completed_at: FuzzyDate) -> Self {
+        self.completed_at = Some(completed_at);
+        self
+    }
+
+    /// The GraphQL variables sent for these changes. Unset fields are left
+    /// out, so AniList keeps their current values.
+    pub fn variables(&self) -> HashMap<String, serde_json::Value> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(map)) => map.into_iter().collect(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+/// A list entry with unwatched episodes available, as returned by
+/// [`UserEndpoint::get_next_up`](crate::endpoints::user::UserEndpoint::get_next_up).
+#[derive(Debug, Clone)]
+pub struct NextUpEntry {
+    /// The list entry, including its media and airing information
+    pub entry: MediaList,
+    /// Aired episodes the user has not watched yet (always at least 1)
+    pub available_episodes: i32,
+}
+
+/// Keeps the entries with unwatched aired episodes, most behind first.
+///
+/// Entries whose aired episode count is unknown are dropped. Entries that are
+/// equally far behind keep their original order.
+pub fn next_up(entries: Vec<MediaList>) -> Vec<NextUpEntry> {
+    let mut next_up: Vec<NextUpEntry> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let available_episodes = entry.episodes_behind()?;
+            (available_episodes > 0).then_some(NextUpEntry {
+                entry,
+                available_episodes,
+            })
+        })
+        .collect();
+    next_up.sort_by_key(|item| std::cmp::Reverse(item.available_episodes));
+    next_up
+}
+
+/// List entries changed after a sync checkpoint, as returned by
+/// [`UserEndpoint::get_list_entries_updated_since`](crate::endpoints::user::UserEndpoint::get_list_entries_updated_since).
+///
+/// Pages of entries sorted by `updatedAt`, newest first, are fed in with
+/// [`UpdatedSince::push_page`] until it reports that older entries have been
+/// reached, so unchanged parts of a large list are never downloaded.
+#[derive(Debug, Clone)]
+pub struct UpdatedSince {
+    /// The checkpoint entries are compared against (Unix seconds)
+    pub since: i64,
+    /// Entries updated strictly after `since`, newest first
+    pub entries: Vec<MediaList>,
+    /// The newest `updatedAt` seen, or `None` if nothing changed
+    pub newest_updated_at: Option<i64>,
+    /// Number of pages consumed
+    pub pages_fetched: u32,
+    done: bool,
+}
+
+impl UpdatedSince {
+    /// Starts collecting entries updated after `since`.
+    pub fn new(since: i64) -> Self {
+        Self {
+            since,
+            entries: Vec::new(),
+            newest_updated_at: None,
+            pages_fetched: 0,
+            done: false,
+        }
+    }
+
+    /// Adds one page of entries sorted by `updatedAt` descending.
+    ///
+    /// Returns `true` if the next page should be fetched. Collection stops at
+    /// the first entry not newer than `since` (entries without a timestamp
+    /// count as old), or when `has_next_page` is `false`.
+    pub fn push_page(&mut self, page: Vec<MediaList>, has_next_page: bool) -> bool {
+        if self.done {
+            return false;
+        }
+        self.pages_fetched += 1;
+
+        for entry in page {
+            match entry.updated_at {
+                Some(updated_at) if updated_at > self.since => {
+                    self.newest_updated_at = Some(
+                        self.newest_updated_at
+                            .map_or(updated_at, |newest| newest.max(updated_at)),
+                    );
+                    self.entries.push(entry);
+                }
+                _ => {
+                    self.done = true;
+                    return false;
+                }
+            }
+        }
+
+        self.done = !has_next_page;
+        has_next_page
+    }
+
+    /// Whether collection has finished.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// The checkpoint to pass as `since` on the next sync.
+    ///
+    /// Falls back to the current checkpoint when nothing changed.
+    pub fn next_checkpoint(&self) -> i64 {
+        self.newest_updated_at.unwrap_or(self.since)
+    }
+}
+
+/// One chunk of a user's lists, the `MediaListCollection` node of a response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaListCollection {
+    /// `None` in unchunked responses, which hold the whole list
+    pub has_next_chunk: Option<bool>,
+    /// `None` when the user has no lists of this type
+    pub lists: Option<Vec<MediaListGroup>>,
+}
+
+/// One status or custom list inside a [`MediaListCollection`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaListGroup {
+    /// `None` for an empty list
+    pub entries: Option<Vec<MediaList>>,
+}
+
+/// A media list collection assembled from chunks, as returned by
+/// [`UserEndpoint::get_list_collection`](crate::endpoints::user::UserEndpoint::get_list_collection).
+///
+/// AniList returns large lists in chunks of at most
+/// [`MAX_PER_CHUNK`](crate::limits::MAX_PER_CHUNK) entries. Chunks are fed in
+/// with [`ListCollection::push_chunk`] until AniList reports the last one or
+/// the `max_chunks` guard is hit, in which case [`ListCollection::truncated`]
+/// is set instead of the missing entries going unnoticed.
+#[derive(Debug, Clone)]
+pub struct ListCollection {
+    /// Entries of all lists in all chunks fetched, in AniList's order, each
+    /// entry once even when it is on a custom list as well as a status list
+    pub entries: Vec<MediaList>,
+    /// Number of chunks consumed
+    pub chunks_fetched: u32,
+    /// `true` if AniList had more chunks when `max_chunks` was reached
+    pub truncated: bool,
+    max_chunks: u32,
+    done: bool,
+    seen: HashSet<i32>,
+}
+
+impl ListCollection {
+    /// Starts collecting, stopping after at most `max_chunks` chunks.
+    ///
+    /// Fails with [`AniListError::InvalidInput`] when `max_chunks` is `0`,
+    /// which could never return an entry.
+    pub fn new(max_chunks: u32) -> Result<Self, AniListError> {
+        if max_chunks == 0 {
+            return Err(AniListError::InvalidInput {
+                field: "max_chunks".to_string(),
+                message: "must be at least 1, got 0".to_string(),
+            });
+        }
+        Ok(Self {
+            entries: Vec::new(),
+            chunks_fetched: 0,
+            truncated: false,
+            max_chunks,
+            done: false,
+            seen: HashSet::new(),
+        })
+    }
+
+    /// Adds the entries of one chunk, skipping entries already collected.
+    ///
+    /// Returns `true` if the next chunk should be fetched: AniList reported
+    /// `has_next_chunk` and the `max_chunks` guard has not been reached.
+    pub fn push_chunk(&mut self, chunk: Vec<MediaList>, has_next_chunk: bool) -> bool {
+        if self.done {
+            return false;
+        }
+        self.chunks_fetched += 1;
+        let seen = &mut self.seen;
+        self.entries
+            .extend(chunk.into_iter().filter(|entry| seen.insert(entry.id)));
+
+        if has_next_chunk && self.chunks_fetched >= self.max_chunks {
+            self.truncated = true;
+        }
+        self.done = !has_next_chunk || self.truncated;
+        !self.done
+    }
+
+    /// Adds one chunk as returned by AniList.
+    ///
+    /// Entries of all lists are added in order. Returns `true` if the next
+    /// chunk should be fetched, see [`Self::push_chunk`].
+    pub fn push_collection_chunk(&mut self, collection: MediaListCollection) -> bool {
+        let entries = collection
+            .lists
+            .into_iter()
+            .flatten()
+            .flat_map(|list| list.entries.into_iter().flatten())
+            .collect();
+        self.push_chunk(entries, collection.has_next_chunk.unwrap_or(false))
+    }
+
+    /// Whether collection has finished.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MediaListStatus {
     Current,
@@ -0,0 +1,289 @@
+//! # Media Search Filters
+//!
+//! [`MediaSearchFilter`] collects the arguments AniList's `media` query can
+//! filter and sort by, for
+//! [`AnimeEndpoint::search_advanced`](crate::endpoints::anime::AnimeEndpoint::search_advanced).
+//! Only the fields that were set are sent, so an empty filter browses
+//! everything.
+
+use super::anime::{MediaFormat, MediaSeason, MediaSort, MediaStatus};
+use crate::error::AniListError;
+use crate::utils::normalize_search;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Filtering and sorting options for an advanced media search.
+///
+/// Every field is optional; unset fields are left out of the request rather
+/// than sent as `null`. Ranges are inclusive.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::models::{MediaFormat, MediaSearchFilter, MediaSort};
+///
+/// let filter = MediaSearchFilter::new()
+///     .search("fate")
+///     .genres(["Action"])
+///     .year_range(2015, 2020)
+///     .formats([MediaFormat::Tv])
+///     .sort(MediaSort::ScoreDesc);
+/// let anime = client.anime().search_advanced(&filter, 1, 25).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MediaSearchFilter {
+    /// Title to search for; blank terms are rejected the same way
+    /// [`AnimeEndpoint::search`](crate::endpoints::anime::AnimeEndpoint::search)
+    /// rejects them
+    pub search: Option<String>,
+    /// Only include media with all of these genres (`genre_in`)
+    pub genres: Option<Vec<String>>,
+    /// Leave out media with any of these genres (`genre_not_in`)
+    pub excluded_genres: Option<Vec<String>>,
+    /// Only include media with all of these tags (`tag_in`)
+    pub tags: Option<Vec<String>>,
+    /// Only count tags ranked at least this high for `tags` and
+    /// `tag_categories` (`minimumTagRank`)
+    pub minimum_tag_rank: Option<i32>,
+    /// Only include media with a tag in one of these categories
+    /// (`tagCategory_in`)
+    pub tag_categories: Option<Vec<String>>,
+    /// Only include media of this season year (`seasonYear`)
+    pub year: Option<i32>,
+    /// Only include media that started within these years, inclusive
+    pub year_range: Option<(i32, i32)>,
+    /// Only include media of this season
+    pub season: Option<MediaSeason>,
+    /// Only include media in one of these formats (`format_in`)
+    pub formats: Option<Vec<MediaFormat>>,
+    /// Only include media with one of these statuses (`status_in`)
+    pub statuses: Option<Vec<MediaStatus>>,
+    /// Only include media whose average score is above this
+    /// (`averageScore_greater`)
+    pub average_score_greater: Option<i32>,
+    /// Only include media with at least this many episodes
+    pub min_episodes: Option<i32>,
+    /// Only include media with at most this many episodes
+    pub max_episodes: Option<i32>,
+    /// Only include media on (`Some(true)`) or off (`Some(false)`) the
+    /// viewer's list; requires a token
+    pub on_list: Option<bool>,
+    /// Only include adult (`Some(true)`) or non-adult (`Some(false)`) media
+    pub is_adult: Option<bool>,
+    /// Order of the results; AniList's default order when unset
+    pub sort: Option<Vec<MediaSort>>,
+}
+
+impl MediaSearchFilter {
+    /// Creates a filter that matches every media entry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Searches by title.
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    /// Restricts the results to media with all of `genres`.
+    pub fn genres<I, S>(mut self, genres: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.genres = Some(genres.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Leaves out media with any of `genres`.
+    pub fn exclude_genres<I, S>(mut self, genres: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.excluded_genres = Some(genres.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Restricts the results to media with all of `tags`.
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Ignores tags ranked below `rank` (0 to 100) when matching tags and
+    /// tag categories.
+    pub fn minimum_tag_rank(mut self, rank: i32) -> Self {
+        self.minimum_tag_rank = Some(rank);
+        self
+    }
+
+    /// Restricts the results to media with a tag in one of `categories`,
+    /// e.g. `"Theme-Fantasy"`.
+    pub fn tag_categories<I, S>(mut self, categories: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tag_categories = Some(categories.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Restricts the results to one season year.
+    pub fn year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Restricts the results to media that started between `from` and `to`,
+    /// inclusive.
+    pub fn year_range(mut self, from: i32, to: i32) -> Self {
+        self.year_range = Some((from, to));
+        self
+    }
+
+    /// Restricts the results to one season.
+    pub fn season(mut self, season: MediaSeason) -> Self {
+        self.season = Some(season);
+        self
+    }
+
+    /// Restricts the results to the given formats.
+    pub fn formats(mut self, formats: impl IntoIterator<Item = MediaFormat>) -> Self {
+        self.formats = Some(formats.into_iter().collect());
+        self
+    }
+
+    /// Restricts the results to the given statuses.
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = MediaStatus>) -> Self {
+        self.statuses = Some(statuses.into_iter().collect());
+        self
+    }
+
+    /// Restricts the results to media with an average score above `score`.
+    pub fn average_score_greater(mut self, score: i32) -> Self {
+        self.average_score_greater = Some(score);
+        self
+    }
+
+    /// Restricts the results to media with `min` to `max` episodes,
+    /// inclusive.
+    pub fn episodes_range(mut self, min: i32, max: i32) -> Self {
+        self.min_episodes = Some(min);
+        self.max_episodes = Some(max);
+        self
+    }
+
+    /// Restricts the results to media on or off the viewer's list.
+    pub fn on_list(mut self, on_list: bool) -> Self {
+        self.on_list = Some(on_list);
+        self
+    }
+
+    /// Restricts the results to adult or non-adult media.
+    pub fn is_adult(mut self, is_adult: bool) -> Self {
+        self.is_adult = Some(is_adult);
+        self
+    }
+
+    /// Orders the results by `sort`.
+    pub fn sort(mut self, sort: MediaSort) -> Self {
+        self.sort = Some(vec![sort]);
+        self
+    }
+
+    /// The GraphQL variables for the fields that are set.
+    ///
+    /// Fails with [`AniListError::BadRequest`] for a blank search term and
+    /// with [`AniListError::InvalidInput`] for a year or episode bound that
+    /// does not fit AniList's arguments.
+    pub(crate) fn variables(&self) -> Result<HashMap<String, Value>, AniListError> {
+        let mut variables = HashMap::new();
+        let mut set = |name: &str, value: Value| {
+            variables.insert(name.to_string(), value);
+        };
+
+        if let Some(search) = &self.search {
+            set("search", json!(normalize_search(search)?));
+        }
+        if let Some(genres) = &self.genres {
+            set("genres", json!(genres));
+        }
+        if let Some(genres) = &self.excluded_genres {
+            set("excludedGenres", json!(genres));
+        }
+        if let Some(tags) = &self.tags {
+            set("tags", json!(tags));
+        }
+        if let Some(rank) = self.minimum_tag_rank {
+            set("minimumTagRank", json!(rank));
+        }
+        if let Some(categories) = &self.tag_categories {
+            set("tagCategories", json!(categories));
+        }
+        if self.tags.is_some() || self.tag_categories.is_some() {
+            // Tag searches also select each result's tags and their ranks
+            set("withTags", json!(true));
+        }
+        if let Some(year) = self.year {
+            set("year", json!(year));
+        }
+        if let Some((from, to)) = self.year_range {
+            // FuzzyDateInt is YYYYMMDD; these bounds are exclusive
+            let greater = from.checked_mul(10_000);
+            let lesser = to.checked_add(1).and_then(|to| to.checked_mul(10_000));
+            let (Some(greater), Some(lesser)) = (greater, lesser) else {
+                return Err(out_of_range("year_range", format!("{from} to {to}")));
+            };
+            set("startDateGreater", json!(greater));
+            set("startDateLesser", json!(lesser));
+        }
+        if let Some(season) = self.season {
+            set("season", json!(season));
+        }
+        if let Some(formats) = &self.formats {
+            set("formats", json!(formats));
+        }
+        if let Some(statuses) = &self.statuses {
+            set("statuses", json!(statuses));
+        }
+        if let Some(score) = self.average_score_greater {
+            set("averageScoreGreater", json!(score));
+        }
+        if let Some(min) = self.min_episodes {
+            let greater = min
+                .checked_sub(1)
+                .ok_or_else(|| out_of_range("min_episodes", min.to_string()))?;
+            set("episodesGreater", json!(greater));
+        }
+        if let Some(max) = self.max_episodes {
+            let lesser = max
+                .checked_add(1)
+                .ok_or_else(|| out_of_range("max_episodes", max.to_string()))?;
+            set("episodesLesser", json!(lesser));
+        }
+        if let Some(on_list) = self.on_list {
+            set("onList", json!(on_list));
+        }
+        if let Some(is_adult) = self.is_adult {
+            set("isAdult", json!(is_adult));
+        }
+        if let Some(sort) = &self.sort {
+            set("sort", json!(sort));
+        }
+        Ok(variables)
+    }
+}
+
+/// The error for a bound whose exclusive counterpart does not fit an `i32`.
+fn out_of_range(field: &str, value: String) -> AniListError {
+    AniListError::InvalidInput {
+        field: field.to_string(),
+        message: format!("{value} is out of range"),
+    }
+}
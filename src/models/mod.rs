@@ -1,28 +1,59 @@
 pub mod anime;
 pub mod character;
+pub mod color;
+pub mod external_link;
 pub mod manga;
 pub mod media_list;
+pub mod media_search;
+pub mod page;
+pub mod personalized;
+pub mod ranking;
+pub mod relation;
+pub mod score;
 pub mod social;
 pub mod staff;
+pub mod stats;
+pub mod tag;
 pub mod user;
 
 // Re-export specific types to avoid ambiguity
 pub use anime::{
-    AiringSchedule, Anime, FuzzyDate, MediaCoverImage, MediaFormat, MediaSeason, MediaSource,
-    MediaStatus, MediaTitle, MediaTrailer, Studio, StudioConnection, StudioEdge,
+    AiringSchedule, Anime, FuzzyDate, MediaCoverImage, MediaFormat, MediaSeason, MediaSort,
+    MediaSource, MediaStatus, MediaSuggestion, MediaTitle, MediaTrailer, Studio, StudioConnection,
+    StudioEdge,
 };
-pub use character::{Character, CharacterImage, CharacterName};
+pub use character::{
+    Character, CharacterEdge, CharacterImage, CharacterMedia, CharacterMediaConnection,
+    CharacterName, CharacterRole,
+};
+pub use color::Color;
+pub use external_link::{ExternalLinkType, MediaExternalLink, SiteLink, StreamingEpisode};
 pub use manga::Manga;
-pub use media_list::{MediaList, MediaListMedia, MediaListStatus};
+pub use media_list::{
+    ListCollection, MediaList, MediaListCollection, MediaListGroup, MediaListMedia,
+    MediaListStatus, NextUpEntry, SaveListEntry, UpdatedSince,
+};
+pub use media_search::MediaSearchFilter;
+pub use page::{Page, PageInfo};
+pub use personalized::{PersonalizedRecommendation, RecommendationCandidate};
+pub use ranking::{MediaRank, MediaRankType};
+pub use relation::{MediaRef, MediaRelation, MediaRelationEdge, RelatedMedia};
+pub use score::{ScoreDisplay, ScoreFormat, Smiley, format_score};
 pub use social::{
-    Activity, ActivityReply, ActivityType, AiringMedia, AiringSchedule as SocialAiringSchedule,
-    ListActivity, MediaType, MessageActivity, Notification, NotificationMedia, NotificationType,
-    NotificationUser, Recommendation, RecommendationMedia, RecommendationRating,
-    RecommendationUser, Review, ReviewMedia, ReviewRating, ReviewUser, Studio as SocialStudio,
-    TextActivity, Thread, ThreadCategory, ThreadComment, ThreadUser,
+    Activity, ActivityFeedFilter, ActivityMedia, ActivityReply, ActivityType, ActivityUnion,
+    AiringMedia, AiringSchedule as SocialAiringSchedule, LikeableType, ListActivity,
+    MarkReadReport, MediaType, MessageActivity, Notification, NotificationMedia,
+    NotificationTarget, NotificationThread, NotificationType, NotificationUser, Recommendation,
+    RecommendationMedia, RecommendationRating, RecommendationUpsert, RecommendationUser,
+    ResolvedResource, Review, ReviewMedia, ReviewRating, ReviewUser, Studio as SocialStudio,
+    TextActivity, Thread, ThreadCategory, ThreadComment, ThreadFilter, ThreadModeration,
+    ThreadUser, UserPreview, ViewerListEntry,
 };
-pub use staff::{Staff, StaffImage, StaffName};
+pub use staff::{Staff, StaffEdge, StaffImage, StaffLanguage, StaffName};
+pub use stats::{MediaStats, ScoreDistribution, StatusDistribution};
+pub use tag::MediaTag;
 pub use user::{
-    Favourites, MediaListOptions, MediaListTypeOptions, NotificationOption, User, UserAvatar,
-    UserOptions, UserStatistics, UserStatisticsType,
+    FavouriteItem, FavouriteKind, FavouriteSet, FavouriteSyncOptions, FavouriteSyncPlan,
+    Favourites, MediaListOptions, MediaListTypeOptions, NotificationOption, ProfileUpdateOptions,
+    SyncReport, User, UserAvatar, UserOptions, UserStatistics, UserStatisticsType,
 };
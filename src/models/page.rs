@@ -0,0 +1,95 @@
+//! # Pagination Models
+//!
+//! Generic containers for paginated results returned by AniList's `Page` query.
+
+use crate::client::extract;
+use crate::error::AniListError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Pagination metadata returned alongside a page of results.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    /// Total number of items across all pages (may be approximate for large sets)
+    pub total: Option<i32>,
+    /// Number of items per page
+    pub per_page: Option<i32>,
+    /// The current page number (1-based)
+    pub current_page: Option<i32>,
+    /// The last page number
+    pub last_page: Option<i32>,
+    /// Whether another page is available after this one
+    pub has_next_page: Option<bool>,
+}
+
+/// A single page of results together with its pagination metadata.
+///
+/// # Examples
+///
+/// ```rust
+/// let likers = client.activity().get_likers(123456, 1, 25).await?;
+/// for user in &likers.items {
+///     println!("Liked by {}", user.name);
+/// }
+/// if likers.has_next_page() {
+///     // fetch page 2
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    /// Pagination metadata for this page
+    pub page_info: PageInfo,
+    /// The items on this page
+    pub items: Vec<T>,
+}
+
+impl<T> Default for Page<T> {
+    /// An empty last page.
+    fn default() -> Self {
+        Self {
+            page_info: PageInfo::default(),
+            items: Vec::new(),
+        }
+    }
+}
+
+impl<T> Page<T> {
+    /// Returns `true` if AniList reports another page after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.page_info.has_next_page.unwrap_or(false)
+    }
+}
+
+impl<T: DeserializeOwned> Page<T> {
+    /// Builds a page from a `Page { pageInfo ... <field> }` JSON node.
+    ///
+    /// A missing or null item list is treated as an empty page.
+    pub(crate) fn from_page_value(page: &Value, field: &str) -> Result<Self, serde_json::Error> {
+        let page_info = match &page["pageInfo"] {
+            Value::Null => PageInfo::default(),
+            info => serde_json::from_value(info.clone())?,
+        };
+        let items = match &page[field] {
+            Value::Null => Vec::new(),
+            items => serde_json::from_value(items.clone())?,
+        };
+        Ok(Self { page_info, items })
+    }
+
+    /// Takes the page out of a `data.Page` response.
+    ///
+    /// Unlike [`Page::from_page_value`], a missing or null item list is an
+    /// error naming its path, as for unpaged list methods; missing page
+    /// info is still treated as unknown.
+    pub(crate) fn extract(mut response: Value, field: &str) -> Result<Self, AniListError> {
+        let page_info = match response.pointer_mut("/data/Page/pageInfo").map(Value::take) {
+            None | Some(Value::Null) => PageInfo::default(),
+            Some(info) => serde_json::from_value(info)?,
+        };
+        let items = extract(response, &["data", "Page", field])?;
+        Ok(Self { page_info, items })
+    }
+}
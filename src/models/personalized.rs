@@ -0,0 +1,116 @@
+//! # Personalized Recommendation Models
+//!
+//! Data structures and ranking logic for "because you watched X"
+//! recommendations built from the viewer's completed list.
+
+use super::media_list::{MediaList, MediaListStatus};
+use super::relation::MediaRef;
+use crate::utils::merge::{HasId, stable_rank_merge};
+use std::collections::HashSet;
+
+/// A suggestion for the viewer, with the completed title it was derived from.
+#[derive(Debug, Clone)]
+pub struct PersonalizedRecommendation {
+    /// The completed title this suggestion is recommended for
+    pub source: MediaRef,
+    /// The suggested title, not on the viewer's list
+    pub suggestion: MediaRef,
+    /// Ranking score: community recommendation ratings weighted by how highly
+    /// the viewer scored each source, summed over every source suggesting it
+    pub score: f64,
+}
+
+/// One community recommendation of `suggestion` for a source title.
+#[derive(Debug, Clone)]
+pub struct RecommendationCandidate {
+    /// The completed title the recommendation was made for
+    pub source: MediaRef,
+    /// Weight of the source, from [`pick_sources`]
+    pub source_weight: f64,
+    /// The recommended title
+    pub suggestion: MediaRef,
+    /// Community rating of the recommendation
+    pub rating: i32,
+}
+
+/// Picks the viewer's `count` highest scored completed entries as sources.
+///
+/// Returns `(media_id, weight)` pairs, best first. Weights are the entry's
+/// score relative to the best picked score, so they do not depend on the
+/// viewer's score format; unscored lists weigh every source `1.0`. Entries
+/// with equal scores keep their list order.
+pub fn pick_sources(entries: &[MediaList], count: usize) -> Vec<(i32, f64)> {
+    let mut completed: Vec<(i32, f64)> = entries
+        .iter()
+        .filter(|entry| entry.status == MediaListStatus::Completed)
+        .map(|entry| (entry.media_id, entry.score))
+        .collect();
+    completed.sort_by(|a, b| b.1.total_cmp(&a.1));
+    completed.truncate(count);
+
+    let best = completed.first().map_or(0.0, |(_, score)| *score);
+    completed
+        .into_iter()
+        .map(|(id, score)| {
+            let weight = if best > 0.0 { score / best } else { 1.0 };
+            (id, weight)
+        })
+        .collect()
+}
+
+/// Deduplicates and ranks recommendation candidates.
+///
+/// Suggestions already on the viewer's list (`on_list`) and candidates with
+/// a non-positive rating are dropped. A title suggested for several sources
+/// is returned once, with the contributions summed and the source that
+/// contributed most. Results are sorted by score, highest first (ties by
+/// media ID), and truncated to `limit`.
+pub fn rank_personalized(
+    candidates: Vec<RecommendationCandidate>,
+    on_list: &HashSet<i32>,
+    limit: usize,
+) -> Vec<PersonalizedRecommendation> {
+    let scored = candidates
+        .into_iter()
+        .filter(|candidate| candidate.rating > 0 && !on_list.contains(&candidate.suggestion.id))
+        .map(|candidate| {
+            let contribution = f64::from(candidate.rating) * candidate.source_weight;
+            Scored {
+                recommendation: PersonalizedRecommendation {
+                    source: candidate.source,
+                    suggestion: candidate.suggestion,
+                    score: contribution,
+                },
+                best: contribution,
+            }
+        });
+
+    let mut ranked: Vec<PersonalizedRecommendation> = stable_rank_merge(
+        scored,
+        |merged, other| {
+            merged.recommendation.score += other.best;
+            if other.best > merged.best {
+                merged.best = other.best;
+                merged.recommendation.source = other.recommendation.source;
+            }
+        },
+        |scored| scored.recommendation.score,
+    )
+    .into_iter()
+    .map(|scored| scored.recommendation)
+    .collect();
+    ranked.truncate(limit);
+    ranked
+}
+
+/// A recommendation being ranked, with its best single contribution.
+struct Scored {
+    recommendation: PersonalizedRecommendation,
+    best: f64,
+}
+
+impl HasId for Scored {
+    fn id(&self) -> i32 {
+        self.recommendation.suggestion.id
+    }
+}
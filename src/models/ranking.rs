@@ -0,0 +1,54 @@
+//! # Media Ranking Models
+//!
+//! Data structures for the rankings AniList shows on a media page, such as
+//! "#3 Most Popular 2013" or "#12 Highest Rated All Time".
+
+use super::anime::{MediaFormat, MediaSeason};
+use serde::{Deserialize, Serialize};
+
+/// What a [`MediaRank`] ranks by.
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MediaRankType {
+    /// Ranked by score
+    Rated,
+    /// Ranked by the number of users with the media on their list
+    Popular,
+}
+
+/// A place the media holds in one of AniList's rankings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaRank {
+    pub id: i32,
+    /// Position in the ranking, 1 being the best
+    pub rank: i32,
+    #[serde(rename = "type")]
+    pub rank_type: MediaRankType,
+    /// Format the ranking is limited to, e.g. only TV series
+    pub format: Option<MediaFormat>,
+    /// Year the ranking is limited to; `None` for all-time ranks
+    pub year: Option<i32>,
+    /// Season the ranking is limited to; `None` unless the rank is seasonal
+    pub season: Option<MediaSeason>,
+    /// Whether the ranking covers every year
+    pub all_time: Option<bool>,
+    /// How AniList describes the ranking, e.g. `"highest rated all time"`
+    pub context: String,
+}
+
+impl MediaRank {
+    /// Whether this is an all-time rank by score.
+    pub fn is_all_time_rated(&self) -> bool {
+        self.rank_type == MediaRankType::Rated && self.all_time == Some(true)
+    }
+}
+
+/// The most specific all-time rank by score in `rankings`: ranks limited to a
+/// format win over ranks across every format, then the best position wins.
+pub(crate) fn best_ranking(rankings: &[MediaRank]) -> Option<&MediaRank> {
+    rankings
+        .iter()
+        .filter(|rank| rank.is_all_time_rated())
+        .min_by_key(|rank| (rank.format.is_none(), rank.rank))
+}
@@ -0,0 +1,88 @@
+//! # Media Relation Models
+//!
+//! Data structures for the `relations` connection between media, such as the
+//! anime adaptation of a manga or the sequel of a series.
+
+use super::anime::{AiringSchedule, MediaCoverImage, MediaFormat, MediaStatus, MediaTitle};
+use super::social::MediaType;
+use serde::{Deserialize, Serialize};
+
+/// How two media entries are related.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MediaRelation {
+    Adaptation,
+    Prequel,
+    Sequel,
+    Parent,
+    SideStory,
+    Character,
+    Summary,
+    Alternative,
+    SpinOff,
+    Other,
+    Source,
+    Compilation,
+    Contains,
+}
+
+/// A minimal reference to a media entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaRef {
+    pub id: i32,
+    #[serde(rename = "type")]
+    pub media_type: Option<MediaType>,
+    pub title: Option<MediaTitle>,
+    pub format: Option<MediaFormat>,
+    pub status: Option<MediaStatus>,
+}
+
+/// A related media entry, with its next airing episode when it is an anime.
+///
+/// `media.media_type` tells anime and manga apart, since relations cross
+/// types (an anime's `SOURCE` is usually a manga).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedMedia {
+    #[serde(flatten)]
+    pub media: MediaRef,
+    pub next_airing_episode: Option<AiringSchedule>,
+    /// Only selected by [`get_relations`](crate::endpoints::AnimeEndpoint::get_relations)
+    pub cover_image: Option<MediaCoverImage>,
+}
+
+/// An edge of the `relations` connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaRelationEdge {
+    pub relation_type: Option<MediaRelation>,
+    pub node: Option<RelatedMedia>,
+}
+
+/// Picks the anime adaptation whose next episode should be shown.
+///
+/// Only `ADAPTATION` edges to anime with a scheduled next episode are
+/// considered. When a manga has several adaptations, currently `RELEASING`
+/// ones are preferred over `NOT_YET_RELEASED` ones, then over anything else;
+/// ties keep the order returned by the API.
+pub fn select_airing_adaptation(edges: &[MediaRelationEdge]) -> Option<(MediaRef, AiringSchedule)> {
+    let status_rank = |status: Option<MediaStatus>| match status {
+        Some(MediaStatus::Releasing) => 0,
+        Some(MediaStatus::NotYetReleased) => 1,
+        _ => 2,
+    };
+
+    edges
+        .iter()
+        .filter(|edge| edge.relation_type == Some(MediaRelation::Adaptation))
+        .filter_map(|edge| edge.node.as_ref())
+        .filter(|node| matches!(node.media.media_type, Some(MediaType::Anime)))
+        .filter_map(|node| {
+            node.next_airing_episode
+                .as_ref()
+                .map(|airing| (&node.media, airing))
+        })
+        .min_by_key(|(media, _)| status_rank(media.status))
+        .map(|(media, airing)| (media.clone(), airing.clone()))
+}
@@ -0,0 +1,104 @@
+//! # Score Formats
+//!
+//! AniList lets each user pick how scores are displayed. List entry scores
+//! are returned in the viewer's format, and these helpers turn them into
+//! something ready to render.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A user's preferred score format (`mediaListOptions.scoreFormat`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScoreFormat {
+    /// Integer scores from 1 to 100
+    #[serde(rename = "POINT_100")]
+    Point100,
+    /// Scores from 0.1 to 10.0 with one decimal
+    #[serde(rename = "POINT_10_DECIMAL")]
+    Point10Decimal,
+    /// Integer scores from 1 to 10
+    #[serde(rename = "POINT_10")]
+    Point10,
+    /// Stars from 1 to 5
+    #[serde(rename = "POINT_5")]
+    Point5,
+    /// Smileys: 1 = 🙁, 2 = 😐, 3 = 🙂
+    #[serde(rename = "POINT_3")]
+    Point3,
+}
+
+/// The three faces used by the [`ScoreFormat::Point3`] format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Smiley {
+    Sad,
+    Neutral,
+    Happy,
+}
+
+impl Smiley {
+    /// The emoji AniList shows for this face.
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Smiley::Sad => "🙁",
+            Smiley::Neutral => "😐",
+            Smiley::Happy => "🙂",
+        }
+    }
+}
+
+/// A score ready for display in a particular [`ScoreFormat`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreDisplay {
+    /// A number formatted for the point-based formats (e.g. `"85"` or `"8.5"`)
+    Numeric(String),
+    /// A number of stars from 1 to 5
+    Stars(u8),
+    /// A smiley face
+    Smiley(Smiley),
+}
+
+impl fmt::Display for ScoreDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreDisplay::Numeric(score) => f.write_str(score),
+            ScoreDisplay::Stars(stars) => {
+                let stars = usize::from(*stars);
+                write!(f, "{}{}", "★".repeat(stars), "☆".repeat(5 - stars))
+            }
+            ScoreDisplay::Smiley(smiley) => f.write_str(smiley.emoji()),
+        }
+    }
+}
+
+/// Formats a score that is already expressed in `format`.
+///
+/// # Rounding
+///
+/// - `POINT_100` and `POINT_10` round to the nearest integer
+/// - `POINT_10_DECIMAL` rounds to one decimal place
+/// - `POINT_5` rounds to the nearest whole star, clamped to 1–5
+/// - `POINT_3` rounds to the nearest face, clamped to 1–3
+///
+/// Halves round away from zero (e.g. `2.5` stars becomes 3).
+pub fn format_score(score: f64, format: ScoreFormat) -> ScoreDisplay {
+    match format {
+        ScoreFormat::Point100 | ScoreFormat::Point10 => {
+            ScoreDisplay::Numeric(format!("{}", score.round() as i64))
+        }
+        ScoreFormat::Point10Decimal => {
+            ScoreDisplay::Numeric(format!("{:.1}", round_to_tenth(score)))
+        }
+        ScoreFormat::Point5 => ScoreDisplay::Stars(score.round().clamp(1.0, 5.0) as u8),
+        ScoreFormat::Point3 => ScoreDisplay::Smiley(match score.round().clamp(1.0, 3.0) as u8 {
+            1 => Smiley::Sad,
+            2 => Smiley::Neutral,
+            _ => Smiley::Happy,
+        }),
+    }
+}
+
+/// Rounds half away from zero at one decimal, avoiding `format!`'s
+/// round-half-to-even behaviour on values like `8.25`.
+fn round_to_tenth(score: f64) -> f64 {
+    (score * 10.0).round() / 10.0
+}
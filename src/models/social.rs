@@ -1,4 +1,11 @@
 use super::MediaCoverImage;
+use super::anime::Anime;
+use super::anime::MediaFormat as CanonicalMediaFormat;
+use super::manga::Manga;
+use super::media_list::MediaListStatus;
+use super::user::User;
+use crate::error::AniListError;
+use crate::utils::anilist_markdown::{embed_at, find_seq};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +52,8 @@ pub struct Thread {
     pub created_at: i32,
     #[serde(rename = "updatedAt")]
     pub updated_at: i32,
+    /// Stays optional even though every thread query selects it: AniList
+    /// returns `null` once the author's account has been deleted.
     pub user: Option<ThreadUser>,
     #[serde(rename = "replyUser")]
     pub reply_user: Option<ThreadUser>,
@@ -56,6 +65,137 @@ pub struct Thread {
     pub site_url: Option<String>,
 }
 
+impl Thread {
+    /// Returns a plain-text preview of the thread body for list views.
+    ///
+    /// Markdown, HTML tags and AniList-specific markup (images, videos,
+    /// spoilers) are stripped, whitespace is collapsed, and the text is cut on
+    /// a word boundary so that the result, including the trailing `…`, is at
+    /// most `max_chars` characters long.
+    pub fn excerpt(&self, max_chars: usize) -> String {
+        let text = strip_markup(self.body.as_deref().unwrap_or_default());
+        truncate_on_word(&text, max_chars)
+    }
+}
+
+/// The moderation state of a thread, as returned by the moderation mutations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadModeration {
+    pub id: i32,
+    #[serde(rename = "isLocked")]
+    pub is_locked: Option<bool>,
+    #[serde(rename = "isSticky")]
+    pub is_sticky: Option<bool>,
+}
+
+/// Removes markdown, HTML and AniList markup, collapsing whitespace.
+fn strip_markup(body: &str) -> String {
+    let chars: Vec<char> = body.chars().collect();
+    let mut out = String::with_capacity(body.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let rest = &chars[i..];
+
+        // Spoilers: ~!hidden!~ are dropped entirely
+        if rest.starts_with(&['~', '!']) {
+            i += find_seq(rest, &['!', '~']).map_or(rest.len(), |end| end + 2);
+            continue;
+        }
+
+        // Embedded media: img220(url), youtube(id), webm(url)
+        if let Some((_, len)) = embed_at(rest) {
+            i += len;
+            out.push(' ');
+            continue;
+        }
+
+        match rest[0] {
+            '<' => {
+                // HTML tag: skip to the closing bracket
+                i += rest.iter().position(|&c| c == '>').map_or(1, |end| end + 1);
+                out.push(' ');
+            }
+            '[' => {
+                // Link: keep the label, drop the target
+                if let Some(close) = rest.iter().position(|&c| c == ']')
+                    && rest.get(close + 1) == Some(&'(')
+                {
+                    let target_end = rest[close..].iter().position(|&c| c == ')');
+                    out.extend(&rest[1..close]);
+                    i += target_end.map_or(close + 1, |end| close + end + 1);
+                    continue;
+                }
+                i += 1;
+            }
+            '&' => {
+                let (decoded, len) = decode_entity(rest);
+                out.push(decoded);
+                i += len;
+            }
+            '*' | '~' | '#' | '`' => i += 1,
+            '_' if rest.get(1) == Some(&'_') => i += 2,
+            '!' if rest.get(1) == Some(&'[') => i += 1,
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decodes a common HTML entity, returning the character and consumed length.
+fn decode_entity(text: &[char]) -> (char, usize) {
+    const ENTITIES: [(&str, char); 7] = [
+        ("&amp;", '&'),
+        ("&lt;", '<'),
+        ("&gt;", '>'),
+        ("&quot;", '"'),
+        ("&#039;", '\''),
+        ("&#39;", '\''),
+        ("&nbsp;", ' '),
+    ];
+
+    for (entity, decoded) in ENTITIES {
+        let entity: Vec<char> = entity.chars().collect();
+        if text.starts_with(&entity) {
+            return (decoded, entity.len());
+        }
+    }
+    ('&', 1)
+}
+
+/// Truncates `text` to at most `max_chars` characters on a word boundary.
+fn truncate_on_word(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+
+    // Reserve one character for the ellipsis
+    let cut: String = text.chars().take(max_chars - 1).collect();
+    let next_is_space = text
+        .chars()
+        .nth(max_chars - 1)
+        .is_some_and(char::is_whitespace);
+
+    let kept = if next_is_space {
+        cut.as_str()
+    } else {
+        match cut.rfind(char::is_whitespace) {
+            Some(end) => &cut[..end],
+            None => cut.as_str(),
+        }
+    };
+
+    let kept = kept.trim_end_matches(|c: char| c.is_whitespace() || ",;:.-".contains(c));
+    format!("{kept}…")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreadCategory {
     pub id: i32,
@@ -75,12 +215,34 @@ pub struct ThreadUser {
     pub moderator_roles: Option<Vec<String>>,
 }
 
+/// Minimal user information, as returned by like lists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserPreview {
+    pub id: i32,
+    pub name: String,
+    pub avatar: Option<UserAvatar>,
+}
+
+/// The kinds of content that can be liked on AniList.
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LikeableType {
+    Thread,
+    ThreadComment,
+    Activity,
+    ActivityReply,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserAvatar {
     pub large: Option<String>,
     pub medium: Option<String>,
 }
 
+/// A comment on a forum thread.
+///
+/// The IDs, the text, the like count and both timestamps are selected by
+/// every comment query and always returned.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreadComment {
     pub id: i32,
@@ -91,17 +253,27 @@ pub struct ThreadComment {
     pub comment: String,
     #[serde(rename = "likeCount")]
     pub like_count: i32,
+    /// Whether the viewer liked the comment; `None` without a token
     #[serde(rename = "isLiked")]
     pub is_liked: Option<bool>,
     #[serde(rename = "createdAt")]
     pub created_at: i32,
     #[serde(rename = "updatedAt")]
     pub updated_at: i32,
+    /// Stays optional even though every comment query selects it: AniList
+    /// returns `null` once the author's account has been deleted, as for
+    /// [`Thread::user`].
     pub user: Option<ThreadUser>,
+    /// Only selected by the comment list
     #[serde(rename = "siteUrl")]
     pub site_url: Option<String>,
 }
 
+/// A user's review of an anime or manga.
+///
+/// The IDs, body, rating counts, link and timestamps are selected by every
+/// review query and always returned. The other fields are left out of the
+/// `RateReview` mutation's selection, so they stay optional.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Review {
     pub id: i32,
@@ -109,29 +281,39 @@ pub struct Review {
     pub user_id: i32,
     #[serde(rename = "mediaId")]
     pub media_id: i32,
+    /// Not selected by `RateReview`
     #[serde(rename = "mediaType")]
     pub media_type: Option<MediaType>,
+    /// Not selected by `RateReview`
     pub summary: Option<String>,
     pub body: String,
-    pub rating: Option<i32>,
+    /// Upvotes the review received (always selected)
+    pub rating: i32,
+    /// Total votes the review received (always selected)
     #[serde(rename = "ratingAmount")]
-    pub rating_amount: Option<i32>,
+    pub rating_amount: i32,
+    /// The viewer's vote; `None` without a token
     #[serde(rename = "userRating")]
     pub user_rating: Option<ReviewRating>,
+    /// The reviewer's score for the media; not selected by `RateReview`
     pub score: Option<i32>,
+    /// Not selected by `RateReview`
     #[serde(rename = "private")]
     pub is_private: Option<bool>,
+    /// Always selected
     #[serde(rename = "siteUrl")]
-    pub site_url: Option<String>,
+    pub site_url: String,
     #[serde(rename = "createdAt")]
     pub created_at: i32,
     #[serde(rename = "updatedAt")]
     pub updated_at: i32,
+    /// Not selected by `RateReview`
     pub user: Option<ReviewUser>,
+    /// Not selected by `RateReview`
     pub media: Option<ReviewMedia>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MediaType {
     Anime,
@@ -161,6 +343,18 @@ pub struct ReviewMedia {
     pub cover_image: Option<MediaCoverImage>,
     #[serde(rename = "bannerImage")]
     pub banner_image: Option<String>,
+    /// The viewer's list entry for this media, when requested by an
+    /// authenticated client and the media is on the viewer's list
+    #[serde(rename = "mediaListEntry")]
+    pub viewer_entry: Option<ViewerListEntry>,
+}
+
+/// The authenticated viewer's list status for a media.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewerListEntry {
+    pub status: Option<MediaListStatus>,
+    pub progress: Option<i32>,
+    pub score: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -172,19 +366,27 @@ pub struct MediaTitle {
     pub user_preferred: Option<String>,
 }
 
+/// A user's suggestion of one media to fans of another.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Recommendation {
     pub id: i32,
-    pub rating: Option<i32>,
+    /// Net votes (upvotes minus downvotes); selected by every recommendation
+    /// query and always returned
+    pub rating: i32,
+    /// The viewer's vote; `None` without a token
     #[serde(rename = "userRating")]
     pub user_rating: Option<RecommendationRating>,
+    /// Stays optional even though every query selects it: AniList returns
+    /// `null` once the media has been deleted
     pub media: Option<RecommendationMedia>,
+    /// Optional for the same reason as `media`
     #[serde(rename = "mediaRecommendation")]
     pub media_recommendation: Option<RecommendationMedia>,
+    /// Not selected by the rating mutation
     pub user: Option<RecommendationUser>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RecommendationRating {
     NoRating,
@@ -192,31 +394,62 @@ pub enum RecommendationRating {
     RateDown,
 }
 
+/// What [`crate::endpoints::recommendation::RecommendationEndpoint::recommend_or_update`]
+/// did (or needs to do) for a media pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecommendationUpsert {
+    /// No recommendation existed for the pair, so one was created.
+    Created,
+    /// A recommendation existed but the viewer's rating differed.
+    Updated,
+    /// The viewer's rating already matched; no mutation was sent.
+    Unchanged,
+}
+
+impl RecommendationUpsert {
+    /// Decide the outcome from the existing recommendation (if any) and the desired rating.
+    ///
+    /// A missing `userRating` on an existing recommendation counts as
+    /// [`RecommendationRating::NoRating`].
+    pub fn plan(existing: Option<&Recommendation>, desired: RecommendationRating) -> Self {
+        match existing {
+            None => RecommendationUpsert::Created,
+            Some(recommendation) => {
+                let current = recommendation
+                    .user_rating
+                    .unwrap_or(RecommendationRating::NoRating);
+                if current == desired {
+                    RecommendationUpsert::Unchanged
+                } else {
+                    RecommendationUpsert::Updated
+                }
+            }
+        }
+    }
+
+    /// Whether this outcome requires a `SaveRecommendation` mutation.
+    pub fn needs_mutation(&self) -> bool {
+        !matches!(self, RecommendationUpsert::Unchanged)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecommendationMedia {
     pub id: i32,
     pub title: Option<MediaTitle>,
     #[serde(rename = "coverImage")]
     pub cover_image: Option<MediaCoverImage>,
-    pub format: Option<MediaFormat>,
+    pub format: Option<CanonicalMediaFormat>,
     #[serde(rename = "averageScore")]
     pub average_score: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum MediaFormat {
-    Tv,
-    TvShort,
-    Movie,
-    Special,
-    Ova,
-    Ona,
-    Music,
-    Manga,
-    Novel,
-    OneShot,
-}
+/// Deprecated alias kept for backwards compatibility.
+///
+/// Both the anime and social models now share a single format enum; use
+/// [`crate::models::MediaFormat`] instead.
+#[deprecated(note = "use `anilist_sdk::models::MediaFormat` instead")]
+pub type MediaFormat = CanonicalMediaFormat;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecommendationUser {
@@ -247,34 +480,59 @@ pub struct AiringMedia {
     #[serde(rename = "bannerImage")]
     pub banner_image: Option<String>,
     pub episodes: Option<i32>,
-    pub format: Option<MediaFormat>,
+    pub format: Option<CanonicalMediaFormat>,
     #[serde(rename = "siteUrl")]
     pub site_url: Option<String>,
 }
 
+/// An activity from a feed, with the fields shared by every kind.
+///
+/// Feeds mix text, list and message activities, so only `id`, the counters
+/// and `createdAt` are present on all of them. Use
+/// [`crate::endpoints::ActivityEndpoint::get_activity_detail`] for the
+/// kind-specific [`ActivityUnion`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Activity {
     pub id: i32,
+    /// `None` for message activities, which have a messenger instead
     #[serde(rename = "userId")]
     pub user_id: Option<i32>,
+    /// Not selected by the like toggle
     #[serde(rename = "type")]
     pub activity_type: Option<ActivityType>,
     #[serde(rename = "replyCount")]
     pub reply_count: i32,
     #[serde(rename = "likeCount")]
     pub like_count: i32,
+    /// `None` without a token
     #[serde(rename = "isLiked")]
     pub is_liked: Option<bool>,
+    /// `None` without a token or when not selected
     #[serde(rename = "isSubscribed")]
     pub is_subscribed: Option<bool>,
     #[serde(rename = "createdAt")]
     pub created_at: i32,
+    /// `None` for message activities and once the author's account has been
+    /// deleted
     pub user: Option<ActivityUser>,
+    /// Not selected by the like toggle
     #[serde(rename = "siteUrl")]
     pub site_url: Option<String>,
+    /// The media a list activity refers to (`None` for text and message activities)
+    pub media: Option<ActivityMedia>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+impl Activity {
+    /// Returns `true` if this is a list activity for an adult title.
+    pub fn is_adult(&self) -> bool {
+        self.media
+            .as_ref()
+            .and_then(|media| media.is_adult)
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ActivityType {
     Text,
@@ -291,49 +549,68 @@ pub struct ActivityUser {
     pub avatar: Option<UserAvatar>,
 }
 
+/// A status post on a user's profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextActivity {
     pub id: i32,
+    /// Always present: every text activity query selects `userId`
     #[serde(rename = "userId")]
-    pub user_id: Option<i32>,
-    pub text: Option<String>,
+    pub user_id: i32,
+    /// Always present: every text activity query selects `text`
+    pub text: String,
     #[serde(rename = "replyCount")]
     pub reply_count: i32,
     #[serde(rename = "likeCount")]
     pub like_count: i32,
+    /// `None` without a token
     #[serde(rename = "isLiked")]
     pub is_liked: Option<bool>,
+    /// Stays optional: the schema allows `null` and AniList does not say when
     #[serde(rename = "isPinned")]
     pub is_pinned: Option<bool>,
+    /// Always present: every text activity query selects `siteUrl`
     #[serde(rename = "siteUrl")]
-    pub site_url: Option<String>,
+    pub site_url: String,
     #[serde(rename = "createdAt")]
     pub created_at: i32,
+    /// Not selected by the pin toggle; `null` once the author's account has
+    /// been deleted
     pub user: Option<ActivityUser>,
 }
 
+/// A list update, such as "watched episode 3 of".
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListActivity {
     pub id: i32,
+    /// Always present: every list activity query selects `userId`
     #[serde(rename = "userId")]
-    pub user_id: Option<i32>,
+    pub user_id: i32,
+    /// Always present: every list activity query selects `type`
     #[serde(rename = "type")]
-    pub activity_type: Option<ActivityType>,
-    pub status: Option<String>,
+    pub activity_type: ActivityType,
+    /// Always present: the verb of the update, e.g. `"watched episode"`
+    pub status: String,
+    /// `None` for updates without a progress, such as completing a title
     pub progress: Option<String>,
     #[serde(rename = "replyCount")]
     pub reply_count: i32,
     #[serde(rename = "likeCount")]
     pub like_count: i32,
+    /// `None` without a token
     #[serde(rename = "isLiked")]
     pub is_liked: Option<bool>,
+    /// Stays optional: the schema allows `null` and AniList does not say when
     #[serde(rename = "isPinned")]
     pub is_pinned: Option<bool>,
+    /// Always present: every list activity query selects `siteUrl`
     #[serde(rename = "siteUrl")]
-    pub site_url: Option<String>,
+    pub site_url: String,
     #[serde(rename = "createdAt")]
     pub created_at: i32,
+    /// Not selected by the pin toggle; `null` once the author's account has
+    /// been deleted
     pub user: Option<ActivityUser>,
+    /// AniList returns `null` once the media has been deleted
     pub media: Option<ActivityMedia>,
 }
 
@@ -345,73 +622,399 @@ pub struct ActivityMedia {
     pub title: Option<MediaTitle>,
     #[serde(rename = "coverImage")]
     pub cover_image: Option<MediaCoverImage>,
+    #[serde(rename = "isAdult")]
+    pub is_adult: Option<bool>,
+}
+
+/// Filtering options for activity feeds.
+///
+/// `types` is sent to AniList as the `type_in` argument. `exclude_adult` is
+/// applied after the page has been fetched, so a filtered page may contain
+/// fewer than `per_page` activities even when more are available on later
+/// pages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivityFeedFilter {
+    /// Only include these activity types (`None` includes every type)
+    pub types: Option<Vec<ActivityType>>,
+    /// Drop list activities for adult titles
+    pub exclude_adult: bool,
+}
+
+impl ActivityFeedFilter {
+    /// Creates a filter that keeps every activity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the feed to the given activity types, e.g. text and list
+    /// activities without messages.
+    pub fn types(mut self, types: &[ActivityType]) -> Self {
+        self.types = Some(types.to_vec());
+        self
+    }
+
+    /// Sets whether list activities for adult titles are dropped.
+    pub fn exclude_adult(mut self, exclude_adult: bool) -> Self {
+        self.exclude_adult = exclude_adult;
+        self
+    }
+
+    /// Returns `true` if the activity passes this filter.
+    pub fn matches(&self, activity: &Activity) -> bool {
+        if let (Some(types), Some(activity_type)) = (&self.types, activity.activity_type)
+            && !types.contains(&activity_type)
+        {
+            return false;
+        }
+        !(self.exclude_adult && activity.is_adult())
+    }
+
+    /// Removes the activities that do not pass this filter.
+    pub fn apply(&self, activities: Vec<Activity>) -> Vec<Activity> {
+        activities
+            .into_iter()
+            .filter(|activity| self.matches(activity))
+            .collect()
+    }
+}
+
+/// Filtering options for browsing and searching forum threads.
+///
+/// AniList's `threads` query has no sticky or locked arguments, so these are
+/// applied after the page has been fetched: a filtered page may contain fewer
+/// than `per_page` threads even when more are available on later pages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadFilter {
+    /// Only include sticky (`Some(true)`) or non-sticky (`Some(false)`) threads
+    pub is_sticky: Option<bool>,
+    /// Drop locked threads
+    pub exclude_locked: bool,
+}
+
+impl ThreadFilter {
+    /// Creates a filter that keeps every thread.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the results to sticky or non-sticky threads.
+    pub fn is_sticky(mut self, is_sticky: bool) -> Self {
+        self.is_sticky = Some(is_sticky);
+        self
+    }
+
+    /// Sets whether locked threads are dropped.
+    pub fn exclude_locked(mut self, exclude_locked: bool) -> Self {
+        self.exclude_locked = exclude_locked;
+        self
+    }
+
+    /// Returns `true` if the thread passes this filter.
+    ///
+    /// Missing `isSticky`/`isLocked` values count as `false`.
+    pub fn matches(&self, thread: &Thread) -> bool {
+        if let Some(is_sticky) = self.is_sticky
+            && thread.is_sticky.unwrap_or(false) != is_sticky
+        {
+            return false;
+        }
+        !(self.exclude_locked && thread.is_locked.unwrap_or(false))
+    }
+
+    /// Removes the threads that do not pass this filter.
+    pub fn apply(&self, threads: Vec<Thread>) -> Vec<Thread> {
+        threads
+            .into_iter()
+            .filter(|thread| self.matches(thread))
+            .collect()
+    }
 }
 
+/// A message left on a user's profile.
+///
+/// The pin toggle selects only the shared fields, so the message itself and
+/// both parties stay optional.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageActivity {
     pub id: i32,
+    /// Not selected by the pin toggle
     #[serde(rename = "recipientId")]
     pub recipient_id: Option<i32>,
+    /// Not selected by the pin toggle
     #[serde(rename = "messengerId")]
     pub messenger_id: Option<i32>,
+    /// Always present: every message activity query selects `type`
     #[serde(rename = "type")]
-    pub activity_type: Option<ActivityType>,
+    pub activity_type: ActivityType,
     #[serde(rename = "replyCount")]
     pub reply_count: i32,
+    /// Not selected by the pin toggle
     pub message: Option<String>,
+    /// Not selected by the pin toggle
     #[serde(rename = "isLocked")]
     pub is_locked: Option<bool>,
+    /// `None` without a token or when not selected
     #[serde(rename = "isSubscribed")]
     pub is_subscribed: Option<bool>,
     #[serde(rename = "likeCount")]
     pub like_count: i32,
+    /// `None` without a token
     #[serde(rename = "isLiked")]
     pub is_liked: Option<bool>,
+    /// Not selected by the pin toggle
     #[serde(rename = "isPrivate")]
     pub is_private: Option<bool>,
+    /// Always present: every message activity query selects `siteUrl`
     #[serde(rename = "siteUrl")]
-    pub site_url: Option<String>,
+    pub site_url: String,
     #[serde(rename = "createdAt")]
     pub created_at: i32,
+    /// Not selected by the pin toggle; `null` once the account has been deleted
     pub recipient: Option<ActivityUser>,
+    /// Not selected by the pin toggle; `null` once the account has been deleted
     pub messenger: Option<ActivityUser>,
 }
 
+/// An activity with the fields specific to its kind.
+///
+/// Returned by [`crate::endpoints::ActivityEndpoint::get_activity_detail`];
+/// the variant is chosen from the GraphQL `__typename`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "__typename")]
+pub enum ActivityUnion {
+    #[serde(rename = "TextActivity")]
+    Text(TextActivity),
+    #[serde(rename = "ListActivity")]
+    List(ListActivity),
+    #[serde(rename = "MessageActivity")]
+    Message(MessageActivity),
+}
+
+impl ActivityUnion {
+    /// The activity ID, whichever kind it is.
+    pub fn id(&self) -> i32 {
+        match self {
+            Self::Text(activity) => activity.id,
+            Self::List(activity) => activity.id,
+            Self::Message(activity) => activity.id,
+        }
+    }
+
+    /// When the activity was created, as a Unix timestamp.
+    pub fn created_at(&self) -> i32 {
+        match self {
+            Self::Text(activity) => activity.created_at,
+            Self::List(activity) => activity.created_at,
+            Self::Message(activity) => activity.created_at,
+        }
+    }
+
+    /// Link to the activity on AniList.
+    pub fn site_url(&self) -> &str {
+        match self {
+            Self::Text(activity) => &activity.site_url,
+            Self::List(activity) => &activity.site_url,
+            Self::Message(activity) => &activity.site_url,
+        }
+    }
+
+    /// The user who posted the activity (the messenger for messages).
+    pub fn author(&self) -> Option<&ActivityUser> {
+        match self {
+            Self::Text(activity) => activity.user.as_ref(),
+            Self::List(activity) => activity.user.as_ref(),
+            Self::Message(activity) => activity.messenger.as_ref(),
+        }
+    }
+}
+
+/// A reply in an activity's thread.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActivityReply {
     pub id: i32,
+    /// Only selected by the reply list
     #[serde(rename = "userId")]
     pub user_id: Option<i32>,
+    /// Not selected by the reply like toggle
     #[serde(rename = "activityId")]
     pub activity_id: Option<i32>,
-    pub text: Option<String>,
+    /// Always present: every reply query selects `text`
+    pub text: String,
     #[serde(rename = "likeCount")]
     pub like_count: i32,
+    /// `None` without a token
     #[serde(rename = "isLiked")]
     pub is_liked: Option<bool>,
     #[serde(rename = "createdAt")]
     pub created_at: i32,
+    /// Not selected by the reply like toggle; `null` once the author's account
+    /// has been deleted
     pub user: Option<ActivityUser>,
 }
 
+/// A notification from the viewer's inbox.
+///
+/// Every fragment of both notification queries selects `id`, `userId`,
+/// `type` and `createdAt`, so those are required. The rest depends on the
+/// notification's kind and is `None` for kinds that do not carry it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Notification {
     pub id: i32,
+    /// Always present: the viewer the notification was sent to
     #[serde(rename = "userId")]
-    pub user_id: Option<i32>,
+    pub user_id: i32,
+    /// Always present: every fragment selects `type`
     #[serde(rename = "type")]
-    pub notification_type: Option<NotificationType>,
+    pub notification_type: NotificationType,
+    /// Only set on airing notifications
     #[serde(rename = "animeId")]
     pub anime_id: Option<i32>,
+    /// Only set on airing notifications
     pub episode: Option<i32>,
+    /// Stays optional: media notifications carry a single `context` instead,
+    /// so AniList can return `null` here
     pub contexts: Option<Vec<String>>,
+    /// Always present: every fragment selects `createdAt`
     #[serde(rename = "createdAt")]
-    pub created_at: Option<i32>,
+    pub created_at: i32,
+    /// Set on airing and media notifications; `null` once the media is deleted
     pub media: Option<NotificationMedia>,
+    /// Set on notifications triggered by another user; `null` once their
+    /// account has been deleted
     pub user: Option<NotificationUser>,
+    /// Set on activity notifications (messages, replies, mentions, likes)
+    #[serde(rename = "activityId")]
+    pub activity_id: Option<i32>,
+    /// Set on thread like notifications
+    #[serde(rename = "threadId")]
+    pub thread_id: Option<i32>,
+    /// Set on thread comment notifications
+    #[serde(rename = "commentId")]
+    pub comment_id: Option<i32>,
+    /// Set on media addition, change and merge notifications
+    #[serde(rename = "mediaId")]
+    pub media_id: Option<i32>,
+    /// The thread a comment notification belongs to
+    pub thread: Option<NotificationThread>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+impl Notification {
+    /// Works out which resource this notification refers to.
+    ///
+    /// Returns [`NotificationTarget::None`] for media deletions and
+    /// notifications missing the ID their type should carry.
+    pub fn target(&self) -> NotificationTarget {
+        use NotificationType::*;
+
+        let target = match self.notification_type {
+            ActivityMessage
+            | ActivityReply
+            | ActivityMention
+            | ActivityLike
+            | ActivityReplyLike
+            | ActivityReplySubscribed => self.activity_id.map(NotificationTarget::Activity),
+            ThreadLike => self
+                .thread_id
+                .or(self.thread.as_ref().map(|thread| thread.id))
+                .map(NotificationTarget::Thread),
+            ThreadCommentMention | ThreadSubscribed | ThreadCommentReply | ThreadCommentLike => {
+                match (self.thread.as_ref(), self.comment_id) {
+                    (Some(thread), Some(comment_id)) => Some(NotificationTarget::ThreadComment {
+                        thread_id: thread.id,
+                        comment_id,
+                    }),
+                    _ => None,
+                }
+            }
+            Airing => self
+                .anime_id
+                .or(self.media.as_ref().map(|media| media.id))
+                .map(|id| NotificationTarget::Media {
+                    id,
+                    media_type: Some(MediaType::Anime),
+                }),
+            RelatedMediaAddition | MediaDataChange | MediaMerge => self
+                .media_id
+                .or(self.media.as_ref().map(|media| media.id))
+                .map(|id| NotificationTarget::Media {
+                    id,
+                    media_type: self.media.as_ref().and_then(|media| media.media_type),
+                }),
+            Following => self
+                .user
+                .as_ref()
+                .map(|user| NotificationTarget::User(user.id)),
+            MediaDeletion => None,
+        };
+        target.unwrap_or(NotificationTarget::None)
+    }
+}
+
+/// The resource a [`Notification`] points at, as returned by
+/// [`Notification::target`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationTarget {
+    /// A text, list or message activity
+    Activity(i32),
+    /// A forum thread
+    Thread(i32),
+    /// A comment inside a forum thread
+    ThreadComment { thread_id: i32, comment_id: i32 },
+    /// An anime or manga; `media_type` is `None` when AniList did not say which
+    Media {
+        id: i32,
+        media_type: Option<MediaType>,
+    },
+    /// A user, e.g. a new follower
+    User(i32),
+    /// Nothing that can still be fetched
+    None,
+}
+
+/// A notification's target after it has been fetched.
+#[derive(Debug, Clone)]
+pub enum ResolvedResource {
+    Activity(ActivityUnion),
+    Thread(Thread),
+    /// The thread containing the comment; AniList has no single-comment lookup
+    /// that returns thread context, so the comment is identified by ID.
+    ThreadComment {
+        thread: Thread,
+        comment_id: i32,
+    },
+    Anime(Box<Anime>),
+    Manga(Box<Manga>),
+    User(Box<User>),
+    None,
+}
+
+/// Outcome of marking notifications as read in chunks.
+#[derive(Debug, Default)]
+pub struct MarkReadReport {
+    /// IDs whose chunk was accepted
+    pub marked: Vec<i32>,
+    /// Chunks that still failed after retrying, with the last error returned
+    pub failed: Vec<(Vec<i32>, AniListError)>,
+    /// Number of mutations sent, including retries
+    pub mutations: u32,
+}
+
+impl MarkReadReport {
+    /// All IDs whose chunk failed.
+    pub fn failed_ids(&self) -> Vec<i32> {
+        self.failed
+            .iter()
+            .flat_map(|(ids, _)| ids.iter().copied())
+            .collect()
+    }
+
+    /// Whether every chunk was accepted.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum NotificationType {
     ActivityMessage,
@@ -443,6 +1046,12 @@ pub struct NotificationMedia {
     pub cover_image: Option<MediaCoverImage>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationThread {
+    pub id: i32,
+    pub title: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationUser {
     pub id: i32,
@@ -1,35 +1,60 @@
 use super::FuzzyDate;
 use serde::{Deserialize, Serialize};
 
+/// A voice actor, writer, animator or other person credited on AniList.
+///
+/// `id`, `name`, `image` and `site_url` are selected by every staff query,
+/// including the staff and voice actor edges of a media entry, and are always
+/// returned. The other fields are `None` when the query did not select them,
+/// when AniList has nothing on record, or, for `is_favourite`, when the client
+/// has no token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Staff {
     pub id: i32,
-    pub name: Option<StaffName>,
+    /// Always present; individual parts stay optional on [`StaffName`]
+    pub name: StaffName,
+    /// Primary dub language, e.g. `"Japanese"`; not selected by every query
     #[serde(rename = "languageV2")]
     pub language_v2: Option<String>,
-    pub image: Option<StaffImage>,
+    /// Always present; AniList falls back to a placeholder without a photo
+    pub image: StaffImage,
+    /// Biography (may contain markup); not selected on media edges
     pub description: Option<String>,
+    /// Not selected on voice actor edges; empty when none are on record
     #[serde(rename = "primaryOccupations")]
     pub primary_occupations: Option<Vec<String>>,
+    /// `None` when AniList has none on record or the query did not select it
     pub gender: Option<String>,
+    /// `None` when unknown or not selected
     #[serde(rename = "dateOfBirth")]
     pub date_of_birth: Option<FuzzyDate>,
+    /// `None` for living people, and when not selected
     #[serde(rename = "dateOfDeath")]
     pub date_of_death: Option<FuzzyDate>,
+    /// `None` when unknown or not selected
     pub age: Option<i32>,
+    /// `None` when unknown or not selected
     #[serde(rename = "yearsActive")]
     pub years_active: Option<Vec<i32>>,
+    /// `None` when unknown or not selected
     #[serde(rename = "homeTown")]
     pub home_town: Option<String>,
+    /// `None` when unknown or not selected
     #[serde(rename = "bloodType")]
     pub blood_type: Option<String>,
+    /// Whether the viewer has favourited this person; `None` without a token
+    /// or when not selected
     #[serde(rename = "isFavourite")]
     pub is_favourite: Option<bool>,
+    /// Not selected by every query
     #[serde(rename = "isFavouriteBlocked")]
     pub is_favourite_blocked: Option<bool>,
+    /// Always present
     #[serde(rename = "siteUrl")]
-    pub site_url: Option<String>,
+    pub site_url: String,
+    /// Not selected on media edges
     pub favourites: Option<i32>,
+    /// Only visible to moderators, so usually `None`
     #[serde(rename = "modNotes")]
     pub mod_notes: Option<String>,
 }
@@ -51,3 +76,30 @@ pub struct StaffImage {
     pub large: Option<String>,
     pub medium: Option<String>,
 }
+
+/// An edge of a media entry's `staff` connection: a person and what they did
+/// on that entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffEdge {
+    /// The credit as written on AniList, e.g. `"Director"`, `"Original
+    /// Creator"`, `"Series Composition"`, `"Music"`, `"Character Design"` or
+    /// `"Storyboard (ep 1)"`; episode ranges and notes are part of the text
+    pub role: Option<String>,
+    pub node: Option<Staff>,
+}
+
+/// Language of a voice actor's dub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StaffLanguage {
+    Japanese,
+    English,
+    Korean,
+    Italian,
+    Spanish,
+    Portuguese,
+    French,
+    German,
+    Hebrew,
+    Hungarian,
+}
@@ -0,0 +1,54 @@
+//! # Media Statistics Models
+//!
+//! Data structures for the score and list status distributions AniList
+//! shows on a media page, as returned by
+//! [`AnimeEndpoint::get_stats`](crate::endpoints::AnimeEndpoint::get_stats)
+//! and [`MangaEndpoint::get_stats`](crate::endpoints::MangaEndpoint::get_stats).
+
+use super::media_list::MediaListStatus;
+use serde::{Deserialize, Serialize};
+
+/// How users have scored and listed a media entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaStats {
+    /// Number of users per score, in steps of 10 from 10 to 100
+    #[serde(default)]
+    pub score_distribution: Vec<ScoreDistribution>,
+    /// Number of users per list status
+    #[serde(default)]
+    pub status_distribution: Vec<StatusDistribution>,
+}
+
+impl MediaStats {
+    /// Number of users who scored the entry.
+    pub fn total_scored(&self) -> i64 {
+        self.score_distribution
+            .iter()
+            .map(|bucket| i64::from(bucket.amount))
+            .sum()
+    }
+
+    /// Number of users with the entry on their list, in any status.
+    pub fn total_listed(&self) -> i64 {
+        self.status_distribution
+            .iter()
+            .map(|bucket| i64::from(bucket.amount))
+            .sum()
+    }
+}
+
+/// Number of users who gave a media entry one score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreDistribution {
+    /// Score on AniList's 100-point scale
+    pub score: i32,
+    pub amount: i32,
+}
+
+/// Number of users with a media entry in one list status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusDistribution {
+    pub status: MediaListStatus,
+    pub amount: i32,
+}
@@ -0,0 +1,38 @@
+//! # Media Tag Models
+//!
+//! Data structures for the tags AniList attaches to media entries. Unlike
+//! genres, tags are ranked per entry: `rank` is how strongly the tag applies
+//! to that particular title.
+
+use serde::{Deserialize, Serialize};
+
+/// A tag on a media entry, e.g. `"Time Travel"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaTag {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    /// Category the tag belongs to, e.g. `"Theme-Fantasy"` or `"Setting-Scene"`
+    pub category: Option<String>,
+    /// How relevant the tag is to this entry, from 0 to 100
+    pub rank: Option<i32>,
+    /// Whether the tag is a spoiler for every entry it is on
+    pub is_general_spoiler: Option<bool>,
+    /// Whether the tag is a spoiler for this entry
+    pub is_media_spoiler: Option<bool>,
+    pub is_adult: Option<bool>,
+}
+
+impl MediaTag {
+    /// Whether the tag gives away something about the story, either for
+    /// every entry it is on or for this one.
+    pub fn is_spoiler(&self) -> bool {
+        self.is_general_spoiler == Some(true) || self.is_media_spoiler == Some(true)
+    }
+}
+
+/// The tags in `tags` that are safe to show, in the same order.
+pub(crate) fn visible_tags(tags: &[MediaTag]) -> Vec<&MediaTag> {
+    tags.iter().filter(|tag| !tag.is_spoiler()).collect()
+}
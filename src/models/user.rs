@@ -1,36 +1,66 @@
+use crate::error::AniListError;
+use crate::models::color::Color;
+use crate::models::page::PageInfo;
+use crate::models::score::ScoreFormat;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
+/// An AniList user profile.
+///
+/// Only `id` and `name` are selected by every user query: the follow toggle
+/// returns little more than the relationship flags, and the leaderboards and
+/// search skip the settings. Everything else stays optional.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: i32,
     pub name: String,
+    /// `None` when the user has not written one or when not selected
     pub about: Option<String>,
+    /// Not selected by the follow toggle
     pub avatar: Option<UserAvatar>,
+    /// `None` when the user has not set one or when not selected
     #[serde(rename = "bannerImage")]
     pub banner_image: Option<String>,
+    /// `None` without a token; only selected by profile lookups and the
+    /// follow toggle
     #[serde(rename = "isFollowing")]
     pub is_following: Option<bool>,
+    /// `None` without a token; only selected by profile lookups and the
+    /// follow toggle
     #[serde(rename = "isFollower")]
     pub is_follower: Option<bool>,
+    /// `None` without a token; only selected by profile lookups and the
+    /// follow toggle
     #[serde(rename = "isBlocked")]
     pub is_blocked: Option<bool>,
+    /// Only selected by the viewer and profile lookups
     pub options: Option<UserOptions>,
+    /// Only selected by the viewer and profile lookups
     #[serde(rename = "mediaListOptions")]
     pub media_list_options: Option<MediaListOptions>,
+    /// Only selected by the viewer and profile lookups
     pub favourites: Option<Favourites>,
+    /// Not selected by the follow toggle
     pub statistics: Option<UserStatistics>,
+    /// Only filled in for the viewer's own profile
     #[serde(rename = "unreadNotificationCount")]
     pub unread_notification_count: Option<i32>,
+    /// Not selected by the follow toggle
     #[serde(rename = "siteUrl")]
     pub site_url: Option<String>,
+    /// Not selected by the follow toggle
     #[serde(rename = "donatorTier")]
     pub donator_tier: Option<i32>,
+    /// Not selected by the follow toggle
     #[serde(rename = "donatorBadge")]
     pub donator_badge: Option<String>,
+    /// `None` for users without a moderator role or when not selected
     #[serde(rename = "moderatorRoles")]
     pub moderator_roles: Option<Vec<String>>,
+    /// Not selected by the follow toggle
     #[serde(rename = "createdAt")]
     pub created_at: Option<i32>,
+    /// Not selected by the leaderboards or the follow toggle
     #[serde(rename = "updatedAt")]
     pub updated_at: Option<i32>,
 }
@@ -49,6 +79,7 @@ pub struct UserOptions {
     pub display_adult_content: Option<bool>,
     #[serde(rename = "airingNotifications")]
     pub airing_notifications: Option<bool>,
+    /// Profile theme color: a named color such as `"blue"`, or a hex string
     #[serde(rename = "profileColor")]
     pub profile_color: Option<String>,
     #[serde(rename = "notificationOptions")]
@@ -60,6 +91,13 @@ pub struct UserOptions {
     pub staff_name_language: Option<String>,
 }
 
+impl UserOptions {
+    /// The profile theme color, if present and valid.
+    pub fn parsed_profile_color(&self) -> Option<Color> {
+        self.profile_color.as_deref().and_then(Color::parse)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationOption {
     pub r#type: Option<String>,
@@ -78,6 +116,14 @@ pub struct MediaListOptions {
     pub manga_list: Option<MediaListTypeOptions>,
 }
 
+impl MediaListOptions {
+    /// The score format as a typed value, if it is one AniList documents.
+    pub fn parsed_score_format(&self) -> Option<ScoreFormat> {
+        let format = self.score_format.as_deref()?;
+        serde_json::from_value(serde_json::Value::String(format.to_string())).ok()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaListTypeOptions {
     #[serde(rename = "sectionOrder")]
@@ -101,26 +147,89 @@ pub struct Favourites {
     pub studios: Option<StudioConnection>,
 }
 
+impl Favourites {
+    /// Returns `true` if any connection holds only the first page of favourites.
+    ///
+    /// Profile fetches include a single page of each kind; use
+    /// [`UserEndpoint::get_favourite_set`](crate::endpoints::user::UserEndpoint::get_favourite_set)
+    /// to walk the rest.
+    pub fn is_truncated(&self) -> bool {
+        self.anime
+            .as_ref()
+            .is_some_and(MediaConnection::has_next_page)
+            || self
+                .manga
+                .as_ref()
+                .is_some_and(MediaConnection::has_next_page)
+            || self
+                .characters
+                .as_ref()
+                .is_some_and(CharacterConnection::has_next_page)
+            || self
+                .staff
+                .as_ref()
+                .is_some_and(StaffConnection::has_next_page)
+            || self
+                .studios
+                .as_ref()
+                .is_some_and(StudioConnection::has_next_page)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaConnection {
+    #[serde(rename = "pageInfo")]
+    pub page_info: Option<PageInfo>,
     pub nodes: Option<Vec<Media>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterConnection {
+    #[serde(rename = "pageInfo")]
+    pub page_info: Option<PageInfo>,
     pub nodes: Option<Vec<Character>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StaffConnection {
+    #[serde(rename = "pageInfo")]
+    pub page_info: Option<PageInfo>,
     pub nodes: Option<Vec<Staff>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StudioConnection {
+    #[serde(rename = "pageInfo")]
+    pub page_info: Option<PageInfo>,
     pub nodes: Option<Vec<Studio>>,
 }
 
+macro_rules! impl_favourite_connection {
+    ($($connection:ty),* $(,)?) => {
+        $(impl $connection {
+            /// Returns `true` if AniList reports more favourites than were included.
+            pub fn has_next_page(&self) -> bool {
+                self.page_info
+                    .as_ref()
+                    .and_then(|info| info.has_next_page)
+                    .unwrap_or(false)
+            }
+
+            /// Total number of favourites of this kind, if selected.
+            pub fn total(&self) -> Option<i32> {
+                self.page_info.as_ref()?.total
+            }
+        })*
+    };
+}
+
+impl_favourite_connection!(
+    MediaConnection,
+    CharacterConnection,
+    StaffConnection,
+    StudioConnection,
+);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Media {
     pub id: i32,
@@ -185,3 +294,246 @@ pub struct UserStatisticsType {
     #[serde(rename = "volumesRead")]
     pub volumes_read: Option<i32>,
 }
+
+/// The kind of entity that can be marked as a favourite on AniList.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FavouriteKind {
+    Anime,
+    Manga,
+    Character,
+    Staff,
+    Studio,
+}
+
+impl FavouriteKind {
+    /// Every favourite kind, in the order used for planning and reporting.
+    pub const ALL: [FavouriteKind; 5] = [
+        FavouriteKind::Anime,
+        FavouriteKind::Manga,
+        FavouriteKind::Character,
+        FavouriteKind::Staff,
+        FavouriteKind::Studio,
+    ];
+
+    /// Name of the `ToggleFavourite` mutation argument for this kind.
+    pub(crate) fn variable_name(self) -> &'static str {
+        match self {
+            FavouriteKind::Anime => "animeId",
+            FavouriteKind::Manga => "mangaId",
+            FavouriteKind::Character => "characterId",
+            FavouriteKind::Staff => "staffId",
+            FavouriteKind::Studio => "studioId",
+        }
+    }
+}
+
+/// A single favourite entry identified by its kind and AniList ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FavouriteItem {
+    pub kind: FavouriteKind,
+    pub id: i32,
+}
+
+/// A set of favourites across all favourite kinds.
+///
+/// Used both to describe the viewer's current favourites and the desired
+/// state passed to [`crate::endpoints::UserEndpoint::sync_favourites`].
+/// Entries are kept ordered so that sync plans are deterministic.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::models::user::FavouriteSet;
+///
+/// let mut desired = FavouriteSet::default();
+/// desired.anime.insert(16498);
+/// desired.characters.insert(40882);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FavouriteSet {
+    pub anime: BTreeSet<i32>,
+    pub manga: BTreeSet<i32>,
+    pub characters: BTreeSet<i32>,
+    pub staff: BTreeSet<i32>,
+    pub studios: BTreeSet<i32>,
+}
+
+impl FavouriteSet {
+    /// Returns the set of IDs for the given kind.
+    pub fn ids(&self, kind: FavouriteKind) -> &BTreeSet<i32> {
+        match kind {
+            FavouriteKind::Anime => &self.anime,
+            FavouriteKind::Manga => &self.manga,
+            FavouriteKind::Character => &self.characters,
+            FavouriteKind::Staff => &self.staff,
+            FavouriteKind::Studio => &self.studios,
+        }
+    }
+
+    /// Returns a mutable reference to the set of IDs for the given kind.
+    pub fn ids_mut(&mut self, kind: FavouriteKind) -> &mut BTreeSet<i32> {
+        match kind {
+            FavouriteKind::Anime => &mut self.anime,
+            FavouriteKind::Manga => &mut self.manga,
+            FavouriteKind::Character => &mut self.characters,
+            FavouriteKind::Staff => &mut self.staff,
+            FavouriteKind::Studio => &mut self.studios,
+        }
+    }
+
+    /// Adds an item to the set, returning `true` if it was not already present.
+    pub fn insert(&mut self, item: FavouriteItem) -> bool {
+        self.ids_mut(item.kind).insert(item.id)
+    }
+
+    /// Returns `true` if the set contains the given item.
+    pub fn contains(&self, item: FavouriteItem) -> bool {
+        self.ids(item.kind).contains(&item.id)
+    }
+
+    /// Total number of items across all kinds.
+    pub fn len(&self) -> usize {
+        FavouriteKind::ALL.iter().map(|k| self.ids(*k).len()).sum()
+    }
+
+    /// Returns `true` if no favourites of any kind are present.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over every item in the set, grouped by kind in a stable order.
+    pub fn iter(&self) -> impl Iterator<Item = FavouriteItem> + '_ {
+        FavouriteKind::ALL.iter().flat_map(move |kind| {
+            self.ids(*kind).iter().map(move |id| FavouriteItem {
+                kind: *kind,
+                id: *id,
+            })
+        })
+    }
+
+    /// Computes the toggles needed to turn `self` (the current state) into `desired`.
+    ///
+    /// This is pure planning logic: no requests are made. Items present in both
+    /// sets end up in [`FavouriteSyncPlan::unchanged`].
+    pub fn plan_sync(&self, desired: &FavouriteSet) -> FavouriteSyncPlan {
+        let mut plan = FavouriteSyncPlan::default();
+        for item in desired.iter() {
+            if self.contains(item) {
+                plan.unchanged.push(item);
+            } else {
+                plan.to_add.push(item);
+            }
+        }
+        for item in self.iter() {
+            if !desired.contains(item) {
+                plan.to_remove.push(item);
+            }
+        }
+        plan
+    }
+}
+
+/// One page of the viewer's favourite IDs, as selected by
+/// [`GET_VIEWER_FAVOURITES`](crate::queries::user::GET_VIEWER_FAVOURITES).
+#[derive(Debug, Deserialize)]
+pub(crate) struct FavouriteIdsPage {
+    anime: FavouriteIds,
+    manga: FavouriteIds,
+    characters: FavouriteIds,
+    staff: FavouriteIds,
+    studios: FavouriteIds,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FavouriteIds {
+    page_info: PageInfo,
+    nodes: Vec<FavouriteId>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FavouriteId {
+    id: i32,
+}
+
+impl FavouriteIdsPage {
+    /// Adds the page's IDs to `set`, returning `true` if any kind has
+    /// another page.
+    pub(crate) fn add_to(self, set: &mut FavouriteSet) -> bool {
+        let connections = [
+            (FavouriteKind::Anime, self.anime),
+            (FavouriteKind::Manga, self.manga),
+            (FavouriteKind::Character, self.characters),
+            (FavouriteKind::Staff, self.staff),
+            (FavouriteKind::Studio, self.studios),
+        ];
+        let mut has_next_page = false;
+        for (kind, connection) in connections {
+            set.ids_mut(kind)
+                .extend(connection.nodes.into_iter().map(|node| node.id));
+            has_next_page |= connection.page_info.has_next_page.unwrap_or(false);
+        }
+        has_next_page
+    }
+}
+
+/// The toggles required to bring the viewer's favourites in line with a desired set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FavouriteSyncPlan {
+    /// Items that are desired but not currently favourited
+    pub to_add: Vec<FavouriteItem>,
+    /// Items that are currently favourited but not desired
+    pub to_remove: Vec<FavouriteItem>,
+    /// Items already in the desired state
+    pub unchanged: Vec<FavouriteItem>,
+}
+
+impl FavouriteSyncPlan {
+    /// Returns `true` if no toggles are required.
+    pub fn is_empty(&self) -> bool {
+        self.to_add.is_empty() && self.to_remove.is_empty()
+    }
+}
+
+/// Options controlling how [`crate::endpoints::UserEndpoint::sync_favourites`] executes.
+#[derive(Debug, Clone)]
+pub struct FavouriteSyncOptions {
+    /// When `true`, the plan is computed and reported but no mutations are sent.
+    pub dry_run: bool,
+    /// Delay in milliseconds between consecutive toggle mutations.
+    pub delay_ms: u64,
+}
+
+impl Default for FavouriteSyncOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            delay_ms: 1000,
+        }
+    }
+}
+
+/// Options controlling how [`crate::endpoints::UserEndpoint::update_about_with_options`]
+/// executes.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileUpdateOptions {
+    /// When `true`, the input is validated and returned but no mutation is sent.
+    pub dry_run: bool,
+}
+
+/// Outcome of a favourites synchronization.
+///
+/// In dry-run mode `added` and `removed` list the toggles that *would* have
+/// been made; nothing is sent to the API.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// Items that were favourited
+    pub added: Vec<FavouriteItem>,
+    /// Items that were unfavourited
+    pub removed: Vec<FavouriteItem>,
+    /// Items that were already in the desired state
+    pub skipped: Vec<FavouriteItem>,
+    /// Items whose toggle failed, with the error returned by the API
+    pub failed: Vec<(FavouriteItem, AniListError)>,
+}
@@ -5,26 +5,44 @@
 
 /// Anime-related GraphQL queries
 pub mod anime {
-    /// Get popular anime query
-    pub const GET_POPULAR: &str = include_str!("anime/get_popular.graphql");
-
-    /// Get trending anime query
-    pub const GET_TRENDING: &str = include_str!("anime/get_trending.graphql");
-
-    /// Search anime query
-    pub const SEARCH: &str = include_str!("anime/search.graphql");
+    /// Browse or search a page of anime query
+    ///
+    /// Shared by every list method on the anime endpoint; `$sort`, `$status`,
+    /// `$season`/`$year`, `$search`, `$popularityGreater`, the
+    /// `$startDateGreater`/`$startDateLesser` window and `$endDateGreater`
+    /// are all optional.
+    pub const GET_MEDIA_PAGE: &str = include_str!("anime/get_media_page.graphql");
 
     /// Get anime by ID query
+    ///
+    /// Looks up by AniList `$id` or MyAnimeList `$idMal`; send one of them.
     pub const GET_BY_ID: &str = include_str!("anime/get_by_id.graphql");
 
-    /// Get anime by season query
-    pub const GET_BY_SEASON: &str = include_str!("anime/get_by_season.graphql");
+    /// Get an anime's related media (sequels, prequels, source material, ...) query
+    pub const GET_RELATIONS: &str = include_str!("anime/get_relations.graphql");
+
+    /// Get one page of an anime's characters with their voice actors query
+    ///
+    /// Paged through the `characters` connection's own `page`/`perPage`.
+    pub const GET_CHARACTERS: &str = include_str!("anime/get_characters.graphql");
+
+    /// Get one page of an anime's staff credits query
+    ///
+    /// Paged through the `staff` connection's own `page`/`perPage`.
+    pub const GET_STAFF: &str = include_str!("anime/get_staff.graphql");
 
-    /// Get top rated anime query
-    pub const GET_TOP_RATED: &str = include_str!("anime/get_top_rated.graphql");
+    /// Get an anime's streaming episode links query
+    pub const GET_STREAMING_EPISODES: &str = include_str!("anime/get_streaming_episodes.graphql");
 
-    /// Get currently airing anime query
-    pub const GET_AIRING: &str = include_str!("anime/get_airing.graphql");
+    /// Get an anime's external links query
+    pub const GET_EXTERNAL_LINKS: &str = include_str!("anime/get_external_links.graphql");
+
+    /// Get a media entry's score and list status distributions query (shared
+    /// by anime and manga)
+    pub const GET_STATS: &str = include_str!("anime/get_stats.graphql");
+
+    /// Slim media autocomplete query (shared by anime and manga)
+    pub const AUTOCOMPLETE: &str = include_str!("anime/autocomplete.graphql");
 }
 
 /// User-related GraphQL queries
@@ -32,10 +50,16 @@ pub mod user {
     /// Get current user (Viewer) query
     pub const GET_CURRENT_USER: &str = include_str!("user/get_current_user.graphql");
 
-    /// Get current user's anime list query
+    /// Minimal Viewer query for checking a token
+    pub const VALIDATE_TOKEN: &str = include_str!("user/validate_token.graphql");
+
+    /// Get one chunk of a user's anime or manga list collection query
     pub const GET_CURRENT_USER_ANIME_LIST: &str =
         include_str!("user/get_current_user_anime_list.graphql");
 
+    /// Get one page of a user's anime or manga list entries query
+    pub const GET_MEDIA_LIST_PAGE: &str = include_str!("user/get_media_list_page.graphql");
+
     /// Get user by ID query
     pub const GET_BY_ID: &str = include_str!("user/get_by_id.graphql");
 
@@ -57,6 +81,12 @@ pub mod user {
     /// Toggle favorite anime/manga mutation
     pub const TOGGLE_FAVORITE: &str = include_str!("user/toggle_favorite.graphql");
 
+    /// Get the viewer's favourite IDs (paginated) query
+    pub const GET_VIEWER_FAVOURITES: &str = include_str!("user/get_viewer_favourites.graphql");
+
+    /// Toggle favourite for any favourite kind mutation
+    pub const TOGGLE_FAVOURITE_ITEM: &str = include_str!("user/toggle_favourite_item.graphql");
+
     /// Update media list progress mutation
     pub const UPDATE_MEDIA_LIST_PROGRESS: &str =
         include_str!("user/update_media_list_progress.graphql");
@@ -64,6 +94,16 @@ pub mod user {
     /// Update media list status mutation
     pub const UPDATE_MEDIA_LIST_STATUS: &str =
         include_str!("user/update_media_list_status.graphql");
+
+    /// Get list entries sorted by last update query
+    pub const GET_LIST_ENTRIES_UPDATED: &str =
+        include_str!("user/get_list_entries_updated.graphql");
+
+    /// Create or update a media list entry mutation
+    pub const SAVE_LIST_ENTRY: &str = include_str!("user/save_list_entry.graphql");
+
+    /// Update the viewer's About text mutation
+    pub const UPDATE_ABOUT: &str = include_str!("user/update_about.graphql");
 }
 
 /// Manga-related GraphQL queries
@@ -88,6 +128,9 @@ pub mod manga {
 
     /// Get completed manga query
     pub const GET_COMPLETED: &str = include_str!("manga/get_completed.graphql");
+
+    /// Get a manga's anime adaptations with their next airing episode query
+    pub const GET_ADAPTATION_AIRING: &str = include_str!("manga/get_adaptation_airing.graphql");
 }
 
 /// Character-related GraphQL queries
@@ -162,6 +205,9 @@ pub mod activity {
     /// Get activity by ID query
     pub const GET_ACTIVITY_BY_ID: &str = include_str!("activity/get_activity_by_id.graphql");
 
+    /// Get activity by ID with type-specific fields query
+    pub const GET_ACTIVITY_DETAIL: &str = include_str!("activity/get_activity_detail.graphql");
+
     /// Get activity replies query
     pub const GET_ACTIVITY_REPLIES: &str = include_str!("activity/get_activity_replies.graphql");
 
@@ -180,6 +226,24 @@ pub mod activity {
 
     /// Reply to activity mutation
     pub const REPLY_TO_ACTIVITY: &str = include_str!("activity/reply_to_activity.graphql");
+
+    /// Get users who liked an activity, activity reply, thread or thread comment query
+    pub const GET_LIKERS: &str = include_str!("activity/get_likers.graphql");
+
+    /// Get activities the viewer is subscribed to query
+    pub const GET_SUBSCRIBED_ACTIVITIES: &str =
+        include_str!("activity/get_subscribed_activities.graphql");
+
+    /// Subscribe to or unsubscribe from an activity mutation
+    pub const TOGGLE_ACTIVITY_SUBSCRIPTION: &str =
+        include_str!("activity/toggle_activity_subscription.graphql");
+
+    /// Get a user's latest list activity for a media query
+    pub const GET_LATEST_LIST_ACTIVITY: &str =
+        include_str!("activity/get_latest_list_activity.graphql");
+
+    /// Pin or unpin an activity on the viewer's profile mutation
+    pub const TOGGLE_ACTIVITY_PIN: &str = include_str!("activity/toggle_activity_pin.graphql");
 }
 
 /// Forum-related GraphQL queries
@@ -193,6 +257,9 @@ pub mod forum {
     /// Search threads query
     pub const SEARCH_THREADS: &str = include_str!("forum/search_threads.graphql");
 
+    /// Get threads authored or last replied to by a user, or subscribed to by the viewer query
+    pub const GET_THREADS_BY_USER: &str = include_str!("forum/get_threads_by_user.graphql");
+
     /// Get thread comments query
     pub const GET_THREAD_COMMENTS: &str = include_str!("forum/get_thread_comments.graphql");
 
@@ -207,6 +274,13 @@ pub mod forum {
 
     /// Like thread comment mutation
     pub const LIKE_THREAD_COMMENT: &str = include_str!("forum/like_thread_comment.graphql");
+
+    /// Lock or sticky a thread mutation (moderators only)
+    pub const MODERATE_THREAD: &str = include_str!("forum/moderate_thread.graphql");
+
+    /// Subscribe to or unsubscribe from a thread mutation
+    pub const TOGGLE_THREAD_SUBSCRIPTION: &str =
+        include_str!("forum/toggle_thread_subscription.graphql");
 }
 
 /// Recommendation-related GraphQL queries
@@ -227,6 +301,14 @@ pub mod recommendation {
     pub const GET_RECOMMENDATION_BY_ID: &str =
         include_str!("recommendation/get_recommendation_by_id.graphql");
 
+    /// Get recommendation for a media pair query
+    pub const GET_RECOMMENDATION_FOR_PAIR: &str =
+        include_str!("recommendation/get_recommendation_for_pair.graphql");
+
+    /// Get recommendations for several source media query
+    pub const GET_RECOMMENDATIONS_FOR_SOURCES: &str =
+        include_str!("recommendation/get_recommendations_for_sources.graphql");
+
     /// Save recommendation mutation
     pub const SAVE_RECOMMENDATION: &str =
         include_str!("recommendation/save_recommendation.graphql");
@@ -303,3 +385,12 @@ pub mod airing {
     /// Get next episode query
     pub const GET_NEXT_EPISODE: &str = include_str!("airing/get_next_episode.graphql");
 }
+
+/// Health check GraphQL queries
+pub mod health {
+    /// Authenticated probe query
+    pub const VIEWER_PROBE: &str = include_str!("health/viewer_probe.graphql");
+
+    /// Unauthenticated probe query
+    pub const PUBLIC_PROBE: &str = include_str!("health/public_probe.graphql");
+}
@@ -0,0 +1,506 @@
+//! # Rate Limiting
+//!
+//! AniList allows a fixed number of requests per minute (normally 90, lowered
+//! to 30 while the API is in degraded mode). The client tracks how many
+//! requests it has sent in the current window, keeps that count and the limit
+//! itself in sync with the `X-RateLimit-*` response headers, and waits for the
+//! next window instead of sending requests that would be rejected. Once the
+//! server reports no requests remaining, the next request waits until the
+//! `X-RateLimit-Reset` time, when one was sent. [`AniListClient::rate_limit_state`]
+//! shows what the client currently believes.
+//!
+//! Clients built with
+//! [`AniListClientBuilder::requests_per_minute`](crate::client::AniListClientBuilder::requests_per_minute)
+//! additionally pace their requests with a token bucket, so a loop spreads its
+//! requests evenly over the minute instead of spending the whole window in one
+//! burst and then stalling. The pace drops to the limit AniList reports when
+//! that is lower than the one configured.
+//!
+//! Multi-step workflows can reserve part of the budget up front with
+//! [`AniListClient::reserve_budget`](crate::client::AniListClient::reserve_budget)
+//! so they do not stall midway when other tasks share the same client.
+
+use crate::client::AniListClient;
+use crate::error::AniListError;
+use crate::health::RateLimitStatus;
+use crate::runtime::{Instant, sleep, sleep_until};
+use chrono::Utc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Default number of requests AniList allows per window
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 90;
+
+/// Length of an AniList rate limit window
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Default time [`AniListClient::reserve_budget`] waits for budget to free up
+pub const DEFAULT_RESERVATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Requests a paced client may send back to back before pacing kicks in
+pub const DEFAULT_BURST: u32 = 5;
+
+/// The rate limit budget as the client currently sees it, from
+/// [`AniListClient::rate_limit_state`].
+///
+/// `limit` starts at [`DEFAULT_REQUESTS_PER_MINUTE`] and follows the
+/// `X-RateLimit-Limit` header once a response carried one; `remaining` counts
+/// down with every request sent and is corrected by `X-RateLimit-Remaining`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitState {
+    /// Requests allowed per window
+    pub limit: u32,
+    /// Requests left in the current window, including reserved ones
+    pub remaining: u32,
+    /// Time until the current window ends; `None` before the first request
+    /// of a window
+    pub resets_in: Option<Duration>,
+    /// Whether `limit` was reported by AniList rather than assumed
+    pub reported: bool,
+}
+
+impl RateLimitState {
+    /// Time to wait before each request so the remaining budget lasts until
+    /// the window resets.
+    ///
+    /// Zero outside a window, the whole time until the reset once nothing
+    /// remains, and the time until the reset shared out evenly otherwise.
+    pub fn suggested_delay(&self) -> Duration {
+        spread(self.remaining, self.resets_in.unwrap_or_default())
+    }
+}
+
+/// `until_reset` shared out over `remaining` requests, or all of it when none
+/// remain.
+pub(crate) fn spread(remaining: u32, until_reset: Duration) -> Duration {
+    match remaining {
+        0 => until_reset,
+        remaining => until_reset / remaining,
+    }
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    limit: u32,
+    reported: bool,
+    window_start: Option<Instant>,
+    /// When the server said the exhausted window resets, if it did
+    reset_at: Option<Instant>,
+    used: u32,
+    reserved: u32,
+}
+
+impl LimiterState {
+    /// Starts a new window if the current one has elapsed.
+    fn roll_window(&mut self, now: Instant) {
+        let ended = match (self.reset_at, self.window_start) {
+            (Some(reset_at), _) => now >= reset_at,
+            (None, Some(start)) => now.duration_since(start) >= RATE_LIMIT_WINDOW,
+            (None, None) => false,
+        };
+        if ended {
+            self.window_start = None;
+            self.reset_at = None;
+            self.used = 0;
+        }
+    }
+
+    /// Requests that can be sent without touching any reservation.
+    fn unreserved(&self) -> u32 {
+        self.limit.saturating_sub(self.used + self.reserved)
+    }
+
+    fn window_end(&self) -> Option<Instant> {
+        self.reset_at
+            .or(self.window_start.map(|start| start + RATE_LIMIT_WINDOW))
+    }
+
+    fn record_request(&mut self, now: Instant) {
+        self.window_start.get_or_insert(now);
+        self.used += 1;
+    }
+}
+
+/// Tracks the request budget shared by all clones of a client.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    state: Mutex<LimiterState>,
+    released: Notify,
+}
+
+impl RateLimiter {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                limit: DEFAULT_REQUESTS_PER_MINUTE,
+                reported: false,
+                window_start: None,
+                reset_at: None,
+                used: 0,
+                reserved: 0,
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Waits until a request may be sent, drawing from `reservation` first.
+    pub(crate) async fn acquire(&self, reservation: Option<&Reservation>) {
+        loop {
+            // Register for release notifications before checking the state so
+            // a reservation dropped in between cannot be missed.
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            let wake_at = {
+                let now = Instant::now();
+                let mut state = self.lock();
+                state.roll_window(now);
+
+                if let Some(reservation) = reservation
+                    && reservation.take_one()
+                {
+                    state.reserved -= 1;
+                    state.record_request(now);
+                    return;
+                }
+
+                if state.unreserved() > 0 {
+                    state.record_request(now);
+                    return;
+                }
+
+                state.window_end()
+            };
+
+            match wake_at {
+                Some(wake_at) => {
+                    tokio::select! {
+                        _ = sleep_until(wake_at) => {}
+                        _ = released => {}
+                    }
+                }
+                None => released.await,
+            }
+        }
+    }
+
+    /// Brings the local limit and count in line with what the server
+    /// reported. The server's count wins either way, so requests counted
+    /// here but never charged by AniList are given back.
+    pub(crate) fn observe(&self, status: RateLimitStatus) {
+        let now = Instant::now();
+        let mut state = self.lock();
+        state.roll_window(now);
+        let used = status.limit.saturating_sub(status.remaining);
+        let freed = used < state.used;
+        state.limit = status.limit;
+        state.reported = true;
+        state.used = used;
+        if freed {
+            self.released.notify_waiters();
+        }
+        if state.used > 0 {
+            state.window_start.get_or_insert(now);
+        }
+        if status.remaining == 0
+            && let Some(reset_at) = status.reset_at
+        {
+            let until_reset = reset_at.saturating_sub(Utc::now().timestamp().max(0) as u64);
+            state.reset_at = Some(now + Duration::from_secs(until_reset));
+        }
+    }
+
+    /// The budget as currently tracked.
+    pub(crate) fn state(&self) -> RateLimitState {
+        let now = Instant::now();
+        let mut state = self.lock();
+        state.roll_window(now);
+        RateLimitState {
+            limit: state.limit,
+            remaining: state.limit.saturating_sub(state.used),
+            resets_in: state
+                .window_end()
+                .map(|end| end.saturating_duration_since(now)),
+            reported: state.reported,
+        }
+    }
+
+    /// Reserves `requests` units, waiting up to `timeout` for them to free up.
+    async fn reserve(&self, requests: u32, timeout: Duration) -> Result<(), AniListError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            let (wake_at, available) = {
+                let now = Instant::now();
+                let mut state = self.lock();
+                state.roll_window(now);
+
+                if requests > state.limit {
+                    return Err(AniListError::BudgetUnavailable {
+                        requested: requests,
+                        available: state.unreserved(),
+                    });
+                }
+
+                if state.unreserved() >= requests {
+                    state.reserved += requests;
+                    return Ok(());
+                }
+
+                (state.window_end(), state.unreserved())
+            };
+
+            if Instant::now() >= deadline {
+                return Err(AniListError::BudgetUnavailable {
+                    requested: requests,
+                    available,
+                });
+            }
+
+            let wake_at = wake_at.map_or(deadline, |end| end.min(deadline));
+            tokio::select! {
+                _ = sleep_until(wake_at) => {}
+                _ = released => {}
+            }
+        }
+    }
+
+    fn release(&self, requests: u32) {
+        if requests == 0 {
+            return;
+        }
+        let mut state = self.lock();
+        state.reserved = state.reserved.saturating_sub(requests);
+        drop(state);
+        self.released.notify_waiters();
+    }
+
+    /// Requests currently available to callers without a reservation.
+    fn available(&self) -> u32 {
+        let mut state = self.lock();
+        state.roll_window(Instant::now());
+        state.unreserved()
+    }
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Tokens left; negative while callers are queued for future tokens
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Spreads requests evenly over the minute, shared by all clones of a client.
+///
+/// Each caller takes a token straight away, even when that drives the count
+/// negative, and then sleeps until its token would have been refilled. Tasks
+/// arriving together are therefore queued one refill interval apart instead
+/// of all waking at once.
+///
+/// The configured pace is lowered to the limit the server reports, if that is
+/// smaller, and restored once the server reports a higher one again.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    state: Mutex<BucketState>,
+    requests_per_minute: u32,
+    /// Limit last reported by the server; zero until one is
+    reported_limit: AtomicU32,
+}
+
+impl TokenBucket {
+    /// A full bucket refilling at `requests_per_minute`, holding at most
+    /// [`DEFAULT_BURST`] tokens.
+    pub(crate) fn new(requests_per_minute: u32) -> Self {
+        Self {
+            state: Mutex::new(BucketState {
+                tokens: Self::capacity(requests_per_minute),
+                last_refill: Instant::now(),
+            }),
+            requests_per_minute,
+            reported_limit: AtomicU32::new(0),
+        }
+    }
+
+    fn capacity(requests_per_minute: u32) -> f64 {
+        f64::from(DEFAULT_BURST.min(requests_per_minute).max(1))
+    }
+
+    /// The pace requests are currently spread at.
+    pub(crate) fn requests_per_minute(&self) -> u32 {
+        match self.reported_limit.load(Ordering::Relaxed) {
+            0 => self.requests_per_minute,
+            reported => self.requests_per_minute.min(reported),
+        }
+    }
+
+    /// Adapts the pace to the limit the server reported.
+    pub(crate) fn observe_limit(&self, limit: u32) {
+        self.reported_limit.store(limit, Ordering::Relaxed);
+    }
+
+    /// Waits until a request may be sent.
+    pub(crate) async fn acquire(&self) {
+        let requests_per_minute = self.requests_per_minute();
+        let rate = f64::from(requests_per_minute) / RATE_LIMIT_WINDOW.as_secs_f64();
+        let capacity = Self::capacity(requests_per_minute);
+        let wait = {
+            let now = Instant::now();
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * rate).min(capacity);
+            state.last_refill = now;
+            state.tokens -= 1.0;
+            (state.tokens < 0.0).then(|| Duration::from_secs_f64(-state.tokens / rate))
+        };
+
+        if let Some(wait) = wait {
+            sleep(wait).await;
+        }
+    }
+}
+
+/// Requests set aside for a [`BudgetGuard`].
+#[derive(Debug)]
+pub(crate) struct Reservation {
+    remaining: AtomicU32,
+}
+
+impl Reservation {
+    fn take_one(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1))
+            .is_ok()
+    }
+}
+
+/// A reservation of rate limit budget for a multi-step workflow.
+///
+/// Requests made through [`BudgetGuard::client`] draw from the reserved
+/// budget before anything else, so other tasks sharing the client cannot use
+/// it up. Dropping the guard returns any unused requests to the shared pool.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use anilist_sdk::client::AniListClient;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = AniListClient::new();
+///
+/// // Wait until 25 requests can be made in the current window
+/// let guard = client.reserve_budget(25).await?;
+/// let reserved = guard.client();
+///
+/// for id in [1, 5, 20] {
+///     let anime = reserved.anime().get_by_id(id).await?;
+///     println!("{:?}", anime.title);
+/// }
+/// // Unused requests are released here
+/// drop(guard);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BudgetGuard {
+    limiter: Arc<RateLimiter>,
+    reservation: Arc<Reservation>,
+    client: AniListClient,
+}
+
+impl BudgetGuard {
+    pub(crate) fn new(client: &AniListClient, limiter: Arc<RateLimiter>, requests: u32) -> Self {
+        let reservation = Arc::new(Reservation {
+            remaining: AtomicU32::new(requests),
+        });
+        Self {
+            client: client.with_reservation(reservation.clone()),
+            limiter,
+            reservation,
+        }
+    }
+
+    /// A client whose requests draw from this reservation.
+    pub fn client(&self) -> AniListClient {
+        self.client.clone()
+    }
+
+    /// Reserved requests that have not been used yet.
+    pub fn remaining(&self) -> u32 {
+        self.reservation.remaining.load(Ordering::Acquire)
+    }
+}
+
+impl std::fmt::Debug for BudgetGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BudgetGuard")
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+impl Drop for BudgetGuard {
+    fn drop(&mut self) {
+        let unused = self.reservation.remaining.swap(0, Ordering::AcqRel);
+        self.limiter.release(unused);
+    }
+}
+
+impl AniListClient {
+    /// Reserves `requests` units of rate limit budget for a multi-step workflow.
+    ///
+    /// Waits up to [`DEFAULT_RESERVATION_TIMEOUT`] for enough budget to become
+    /// available. See [`AniListClient::reserve_budget_with_timeout`].
+    pub async fn reserve_budget(&self, requests: u32) -> Result<BudgetGuard, AniListError> {
+        self.reserve_budget_with_timeout(requests, DEFAULT_RESERVATION_TIMEOUT)
+            .await
+    }
+
+    /// Reserves `requests` units of rate limit budget, waiting up to `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AniListError::BudgetUnavailable`] if the budget does not free
+    /// up before the timeout, or if more requests are asked for than a single
+    /// window allows.
+    pub async fn reserve_budget_with_timeout(
+        &self,
+        requests: u32,
+        timeout: Duration,
+    ) -> Result<BudgetGuard, AniListError> {
+        let limiter = self.rate_limiter();
+        limiter.reserve(requests, timeout).await?;
+        Ok(BudgetGuard::new(self, limiter, requests))
+    }
+
+    /// Requests that can currently be sent without waiting or touching a
+    /// reservation.
+    pub fn available_budget(&self) -> u32 {
+        self.rate_limiter().available()
+    }
+
+    /// The rate limit budget as tracked from the requests sent and the
+    /// `X-RateLimit-*` headers received, shared by all clones of the client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let state = client.rate_limit_state();
+    /// if state.reported && state.limit < 90 {
+    ///     println!("AniList is in degraded mode: {} requests/minute", state.limit);
+    /// }
+    /// tokio::time::sleep(state.suggested_delay()).await;
+    /// ```
+    pub fn rate_limit_state(&self) -> RateLimitState {
+        self.rate_limiter().state()
+    }
+}
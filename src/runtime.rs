@@ -0,0 +1,141 @@
+//! # Runtime
+//!
+//! The few things the crate needs from an async runtime: sleeping, reading
+//! the clock and spawning background requests. Native builds use Tokio;
+//! `wasm32` builds (with the `wasm` feature) use the browser's timers and
+//! event loop, so the rest of the crate reads the same on both.
+//!
+//! Futures returned by the client are `Send` on native targets only, since
+//! browser requests cannot move between threads. [`MaybeSend`] expresses
+//! that in bounds that apply to both.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use tokio::time::{Instant, sleep, sleep_until};
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
+
+/// `Send` on native targets; implemented by every type on `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// `Send` on native targets; implemented by every type on `wasm32`.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// Waits for `duration`.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: std::time::Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Waits until `deadline`.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep_until(deadline: Instant) {
+    sleep(deadline.saturating_duration_since(Instant::now())).await;
+}
+
+/// Runs `future` for at most `duration`, returning `None` if it took longer.
+pub(crate) async fn timeout<F: Future>(
+    duration: std::time::Duration,
+    future: F,
+) -> Option<F::Output> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        tokio::time::timeout(duration, future).await.ok()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        tokio::select! {
+            output = future => Some(output),
+            _ = sleep(duration) => None,
+        }
+    }
+}
+
+/// A spawned future, resolving to its output.
+///
+/// Dropping the task lets it run to completion; [`Task::abort`] stops it.
+pub(crate) struct Task<T> {
+    #[cfg(not(target_arch = "wasm32"))]
+    handle: tokio::task::JoinHandle<T>,
+    #[cfg(target_arch = "wasm32")]
+    output: tokio::sync::oneshot::Receiver<T>,
+    #[cfg(target_arch = "wasm32")]
+    cancel: tokio::sync::oneshot::Sender<()>,
+}
+
+/// Runs `future` in the background.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn spawn<F>(future: F) -> Task<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    Task {
+        handle: tokio::spawn(future),
+    }
+}
+
+/// Runs `future` in the background.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn spawn<F>(future: F) -> Task<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    let (sender, output) = tokio::sync::oneshot::channel();
+    let (cancel, cancelled) = tokio::sync::oneshot::channel();
+    wasm_bindgen_futures::spawn_local(async move {
+        tokio::select! {
+            value = future => {
+                let _ = sender.send(value);
+            }
+            Ok(()) = cancelled => {}
+        }
+    });
+    Task { output, cancel }
+}
+
+impl<T> Task<T> {
+    /// Stops the task if it has not completed yet.
+    pub(crate) fn abort(self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle.abort();
+        #[cfg(target_arch = "wasm32")]
+        let _ = self.cancel.send(());
+    }
+}
+
+impl<T> Future for Task<T> {
+    type Output = T;
+
+    /// Resumes a panic raised by the task.
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Pin::new(&mut self.handle)
+                .poll(cx)
+                .map(|outcome| match outcome {
+                    Ok(value) => value,
+                    Err(error) => std::panic::resume_unwind(error.into_panic()),
+                })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Pin::new(&mut self.output)
+                .poll(cx)
+                .map(|outcome| outcome.expect("background task panicked"))
+        }
+    }
+}
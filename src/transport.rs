@@ -0,0 +1,163 @@
+//! # Transport
+//!
+//! The HTTP layer requests go through. Clients send over [`HttpTransport`]
+//! (reqwest) by default; [`AniListClient::set_transport`] swaps in any other
+//! [`Transport`], e.g. one serving canned responses in tests or one recording
+//! live responses as fixtures.
+//!
+//! Everything above the transport still runs as usual: the mutation auth
+//! check, rate limiting, metrics, and the mapping of statuses and GraphQL
+//! errors to [`AniListError`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::transport::{Transport, TransportFuture, TransportRequest, TransportResponse};
+//!
+//! struct Canned(&'static str);
+//!
+//! impl Transport for Canned {
+//!     fn send(&self, _request: TransportRequest) -> TransportFuture<'_> {
+//!         let body = self.0;
+//!         Box::pin(async move { Ok(TransportResponse::new(200, body)) })
+//!     }
+//! }
+//!
+//! let mut client = AniListClient::new();
+//! client.set_transport(Canned(r#"{"data":{"Media":{"id":1}}}"#));
+//! ```
+//!
+//! [`AniListClient::set_transport`]: crate::AniListClient::set_transport
+
+use crate::error::AniListError;
+use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// A GraphQL request ready to be sent.
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    /// The endpoint to POST to
+    pub url: String,
+    /// Request headers, including `Authorization` when the client has a token
+    pub headers: HeaderMap,
+    /// The JSON body: `query` and, when given, `variables`
+    pub body: Value,
+}
+
+impl TransportRequest {
+    /// The GraphQL document being sent.
+    pub fn query(&self) -> &str {
+        self.body["query"].as_str().unwrap_or_default()
+    }
+
+    /// The variables sent with the document, `Null` when there are none.
+    pub fn variables(&self) -> &Value {
+        &self.body["variables"]
+    }
+}
+
+/// A complete HTTP response, body included.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransportResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// The raw body; expected to be JSON on success, but may be anything
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    /// A response with the given status and body and no headers.
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Adds a header. Names and values that are not valid HTTP are ignored.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            self.headers.append(name, value);
+        }
+        self
+    }
+
+    /// The body as text, with invalid UTF-8 replaced.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// The future returned by [`Transport::send`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<TransportResponse, AniListError>> + Send + 'a>>;
+
+/// The future returned by [`Transport::send`]; browser requests are not `Send`.
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<TransportResponse, AniListError>> + 'a>>;
+
+/// Sends requests to AniList and returns the raw responses.
+///
+/// Implementations report any complete response as `Ok`, whatever its status;
+/// the client turns error statuses into [`AniListError`]s. Only failures to get
+/// a response at all should be returned as errors, normally
+/// [`AniListError::Network`].
+pub trait Transport: Send + Sync {
+    /// Sends one request.
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        (**self).send(request)
+    }
+}
+
+/// The default transport, sending requests with reqwest (the browser's
+/// `fetch` on `wasm32`).
+#[derive(Debug, Clone, Default)]
+pub struct HttpTransport {
+    client: Client,
+}
+
+impl HttpTransport {
+    /// Sends requests with the given reqwest client, keeping its connection
+    /// pool and settings.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for HttpTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(&request.url)
+                .headers(request.headers)
+                .json(&request.body)
+                .send()
+                .await?;
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?.to_vec();
+            Ok(TransportResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
@@ -4,8 +4,15 @@
 //! and other common operations when working with the AniList API.
 
 use crate::error::AniListError;
+use crate::runtime::sleep;
 use std::time::Duration;
-use tokio::time::sleep;
+
+pub mod anilist_markdown;
+pub mod batch_runner;
+pub mod merge;
+pub mod pagination;
+pub mod prefetch;
+pub mod seasons;
 
 /// Configuration for retry behavior when handling API failures.
 ///
@@ -17,27 +24,39 @@ use tokio::time::sleep;
 ///
 /// ```rust
 /// use anilist_sdk::utils::RetryConfig;
+/// use std::time::Duration;
 ///
 /// // Default configuration (3 retries, exponential backoff)
 /// let config = RetryConfig::default();
 ///
 /// // Custom configuration for aggressive retrying
-/// let aggressive = RetryConfig {
-///     max_retries: 5,
-///     base_delay_ms: 500,
-///     exponential_backoff: true,
-///     max_delay_ms: 60000,
-/// };
+/// let aggressive = RetryConfig::builder()
+///     .max_retries(5)
+///     .base_delay(Duration::from_millis(500))
+///     .max_delay(Duration::from_secs(60))
+///     .jitter(true)
+///     .max_total_delay(Duration::from_secs(120))
+///     .build();
+///
+/// // Wait out rate limits patiently, but give up quickly on server errors
+/// let patient = RetryConfig::builder()
+///     .max_rate_limit_retries(5)
+///     .max_server_error_retries(1)
+///     .build();
 ///
 /// // Configuration for quick retries without backoff
-/// let quick = RetryConfig {
-///     max_retries: 2,
-///     base_delay_ms: 100,
-///     exponential_backoff: false,
-///     max_delay_ms: 1000,
-/// };
+/// let quick = RetryConfig::builder()
+///     .max_retries(2)
+///     .base_delay(Duration::from_millis(100))
+///     .exponential(false)
+///     .build();
 /// ```
-#[derive(Debug, Clone)]
+///
+/// New settings may be added in later versions, so build a configuration
+/// with [`RetryConfig::builder`] or start from [`RetryConfig::default`]
+/// rather than with a struct literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct RetryConfig {
     /// Maximum number of retry attempts before giving up.
     ///
@@ -89,6 +108,38 @@ pub struct RetryConfig {
     /// - 1-2 minutes: For non-interactive or batch operations
     /// - 5+ minutes: Only for very long-running processes
     pub max_delay_ms: u64,
+
+    /// Whether to randomize backoff delays.
+    ///
+    /// With "full jitter" each delay is drawn uniformly from zero up to the
+    /// computed backoff, so workers that were rate limited together do not
+    /// all retry at the same instant. Waits requested by the server through
+    /// `Retry-After` are not randomized.
+    pub jitter: bool,
+
+    /// Upper bound on the total time spent sleeping between retries.
+    ///
+    /// Once the next backoff would take the total past this budget, the last
+    /// error is returned even if `max_retries` is not exhausted. A
+    /// `Retry-After` wait is shortened to whatever budget is left instead.
+    /// `None` means no limit.
+    pub max_total_delay: Option<Duration>,
+
+    /// Which kinds of failure are retried.
+    ///
+    /// Defaults to [`RetryOn::ALL`]; drop [`RetryOn::SERVER_ERROR`] to fail
+    /// fast on 5xx responses while still waiting out rate limits.
+    pub retry_on: RetryOn,
+
+    /// Retries allowed for rate limit errors; `None` uses `max_retries`.
+    pub max_rate_limit_retries: Option<u32>,
+
+    /// Retries allowed for server errors; `None` uses `max_retries`.
+    pub max_server_error_retries: Option<u32>,
+
+    /// Retries allowed for network failures and timeouts; `None` uses
+    /// `max_retries`.
+    pub max_network_retries: Option<u32>,
 }
 
 impl Default for RetryConfig {
@@ -99,6 +150,10 @@ impl Default for RetryConfig {
     /// - `base_delay_ms`: 1000ms (1 second)
     /// - `exponential_backoff`: true
     /// - `max_delay_ms`: 30000ms (30 seconds)
+    /// - `jitter`: false
+    /// - `max_total_delay`: no limit
+    /// - `retry_on`: [`RetryOn::ALL`]
+    /// - no per-kind limits, so every kind gets `max_retries`
     ///
     /// These defaults provide a good balance between resilience and response time,
     /// with appropriate handling for AniList's rate limiting.
@@ -108,10 +163,327 @@ impl Default for RetryConfig {
             base_delay_ms: 1000,
             exponential_backoff: true,
             max_delay_ms: 30000,
+            jitter: false,
+            max_total_delay: None,
+            retry_on: RetryOn::ALL,
+            max_rate_limit_retries: None,
+            max_server_error_retries: None,
+            max_network_retries: None,
+        }
+    }
+}
+
+/// Builds a [`RetryConfig`], starting from [`RetryConfig::default`].
+///
+/// Created with [`RetryConfig::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct RetryConfigBuilder {
+    config: RetryConfig,
+}
+
+impl RetryConfigBuilder {
+    /// Sets how many times a failure of any kind is retried, unless a
+    /// per-kind limit says otherwise.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.config.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.config.base_delay_ms = duration_ms(delay);
+        self
+    }
+
+    /// Sets whether the delay doubles after every retry.
+    pub fn exponential(mut self, exponential: bool) -> Self {
+        self.config.exponential_backoff = exponential;
+        self
+    }
+
+    /// Caps the delay between two attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.config.max_delay_ms = duration_ms(delay);
+        self
+    }
+
+    /// Sets whether backoff delays are randomized (see [`RetryConfig::jitter`]).
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.config.jitter = jitter;
+        self
+    }
+
+    /// Caps the total time spent waiting between retries (see
+    /// [`RetryConfig::max_total_delay`]).
+    pub fn max_total_delay(mut self, budget: Duration) -> Self {
+        self.config.max_total_delay = Some(budget);
+        self
+    }
+
+    /// Sets which kinds of failure are retried.
+    pub fn retry_on(mut self, retry_on: RetryOn) -> Self {
+        self.config.retry_on = retry_on;
+        self
+    }
+
+    /// Sets how many times rate limit errors are retried.
+    pub fn max_rate_limit_retries(mut self, max_retries: u32) -> Self {
+        self.config.max_rate_limit_retries = Some(max_retries);
+        self
+    }
+
+    /// Sets how many times server errors are retried.
+    pub fn max_server_error_retries(mut self, max_retries: u32) -> Self {
+        self.config.max_server_error_retries = Some(max_retries);
+        self
+    }
+
+    /// Sets how many times network failures and timeouts are retried.
+    pub fn max_network_retries(mut self, max_retries: u32) -> Self {
+        self.config.max_network_retries = Some(max_retries);
+        self
+    }
+
+    /// Returns the configuration.
+    pub fn build(self) -> RetryConfig {
+        self.config
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Retries made so far for each kind of failure.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct RetryCounts {
+    rate_limit: u32,
+    server_error: u32,
+    network: u32,
+}
+
+impl RetryCounts {
+    fn get_mut(&mut self, kind: RetryOn) -> &mut u32 {
+        match kind {
+            RetryOn::RATE_LIMIT => &mut self.rate_limit,
+            RetryOn::SERVER_ERROR => &mut self.server_error,
+            _ => &mut self.network,
+        }
+    }
+
+    /// Retries made so far for `kind`.
+    pub(crate) fn of(mut self, kind: RetryOn) -> u32 {
+        *self.get_mut(kind)
+    }
+
+    /// Counts a retry after a failure of `kind`.
+    pub(crate) fn record(&mut self, kind: RetryOn) {
+        *self.get_mut(kind) += 1;
+    }
+}
+
+/// The kinds of failure a [`RetryConfig`] retries, combined with `|`.
+///
+/// Errors that are not retryable at all (see
+/// [`AniListError::is_retryable`]), such as authentication failures, bad
+/// requests or JSON errors, are never retried whatever the set.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::utils::{RetryConfig, RetryOn};
+///
+/// // Wait out rate limits and dropped connections, but not server errors
+/// let config = RetryConfig::builder()
+///     .retry_on(RetryOn::RATE_LIMIT | RetryOn::NETWORK)
+///     .build();
+/// assert!(!config.retry_on.contains(RetryOn::SERVER_ERROR));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetryOn(u8);
+
+impl RetryOn {
+    /// Retry nothing.
+    pub const NONE: Self = Self(0);
+    /// [`AniListError::RateLimit`], [`AniListError::RateLimitSimple`] and
+    /// [`AniListError::BurstLimit`].
+    pub const RATE_LIMIT: Self = Self(1);
+    /// Transport-level [`AniListError::Network`] failures and
+    /// [`AniListError::Timeout`].
+    pub const NETWORK: Self = Self(1 << 1);
+    /// Transient [`AniListError::ServerError`]s.
+    pub const SERVER_ERROR: Self = Self(1 << 2);
+    /// Every kind above.
+    pub const ALL: Self = Self(Self::RATE_LIMIT.0 | Self::NETWORK.0 | Self::SERVER_ERROR.0);
+
+    /// Whether every kind in `other` is in this set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The kind `error` belongs to, or `None` if it is never retried.
+    pub(crate) fn of(error: &AniListError) -> Option<Self> {
+        if error.is_rate_limited() {
+            Some(Self::RATE_LIMIT)
+        } else if !error.is_retryable() {
+            None
+        } else if matches!(error, AniListError::ServerError { .. }) {
+            Some(Self::SERVER_ERROR)
+        } else {
+            Some(Self::NETWORK)
         }
     }
 }
 
+impl Default for RetryOn {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl std::ops::BitOr for RetryOn {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl std::ops::BitOrAssign for RetryOn {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl RetryConfig {
+    /// Starts building a configuration from the defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anilist_sdk::utils::RetryConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = RetryConfig::builder()
+    ///     .max_retries(4)
+    ///     .base_delay(Duration::from_millis(250))
+    ///     .max_server_error_retries(1)
+    ///     .build();
+    /// assert_eq!(config.base_delay_ms, 250);
+    /// ```
+    pub fn builder() -> RetryConfigBuilder {
+        RetryConfigBuilder::default()
+    }
+
+    /// How many times failures of `kind` are retried: its own limit if set,
+    /// otherwise `max_retries`.
+    ///
+    /// `kind` is one of [`RetryOn::RATE_LIMIT`], [`RetryOn::SERVER_ERROR`]
+    /// or [`RetryOn::NETWORK`].
+    pub fn max_retries_for(&self, kind: RetryOn) -> u32 {
+        let limit = match kind {
+            RetryOn::RATE_LIMIT => self.max_rate_limit_retries,
+            RetryOn::SERVER_ERROR => self.max_server_error_retries,
+            RetryOn::NETWORK => self.max_network_retries,
+            _ => None,
+        };
+        limit.unwrap_or(self.max_retries)
+    }
+
+    /// Which kind `error` counts as for retrying, or `None` if this
+    /// configuration does not retry it.
+    pub(crate) fn retried_kind(&self, error: &AniListError) -> Option<RetryOn> {
+        RetryOn::of(error).filter(|kind| self.retry_on.contains(*kind))
+    }
+
+    /// How long to wait before retry number `attempt + 1` after `error`, or
+    /// `None` if the request should not be retried.
+    ///
+    /// `counts` holds the retries made so far per kind, checked against
+    /// [`RetryConfig::max_retries_for`]; `attempt` counts all of them and
+    /// sets the backoff.
+    ///
+    /// Rate limit errors honour `Retry-After` when the server sent one;
+    /// transient server errors back off from `base_delay_ms`. Only rate limit
+    /// errors are retried for requests that are not `idempotent`, since a
+    /// 5xx may arrive after the server already applied a mutation. Kinds
+    /// left out of [`RetryConfig::retry_on`] are not retried.
+    ///
+    /// `slept` is the time already spent waiting on earlier retries, counted
+    /// against [`RetryConfig::max_total_delay`].
+    pub(crate) fn delay_for(
+        &self,
+        attempt: u32,
+        counts: RetryCounts,
+        error: &AniListError,
+        idempotent: bool,
+        slept: Duration,
+    ) -> Option<Duration> {
+        let kind = self.retried_kind(error)?;
+        if counts.of(kind) >= self.max_retries_for(kind) {
+            return None;
+        }
+        let backoff = if self.exponential_backoff {
+            self.base_delay_ms
+                .saturating_mul(2u64.saturating_pow(attempt))
+                .min(self.max_delay_ms)
+        } else {
+            self.base_delay_ms
+        };
+        let retryable = match kind {
+            RetryOn::RATE_LIMIT => true,
+            RetryOn::SERVER_ERROR => idempotent,
+            _ => false,
+        };
+        if !retryable {
+            return None;
+        }
+        if let Some(wait) = error.server_retry_after() {
+            return self.budgeted(wait, true, slept);
+        }
+        self.budgeted(Duration::from_millis(backoff), false, slept)
+    }
+
+    /// Applies jitter and the total delay budget to a planned wait, or
+    /// returns `None` if the budget does not allow another retry.
+    ///
+    /// Waits `from_server` (`Retry-After`) are neither randomized nor
+    /// refused; they are only shortened to the budget left.
+    pub(crate) fn budgeted(
+        &self,
+        planned: Duration,
+        from_server: bool,
+        slept: Duration,
+    ) -> Option<Duration> {
+        let delay = if self.jitter && !from_server {
+            full_jitter(planned)
+        } else {
+            planned
+        };
+        let Some(max_total_delay) = self.max_total_delay else {
+            return Some(delay);
+        };
+        let remaining = max_total_delay.saturating_sub(slept);
+        if from_server {
+            (!remaining.is_zero()).then(|| delay.min(remaining))
+        } else {
+            (delay <= remaining).then_some(delay)
+        }
+    }
+}
+
+/// A random duration between zero and `delay`, inclusive.
+fn full_jitter(delay: Duration) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    // Each `RandomState` is seeded differently, which is all the randomness
+    // spreading out retries needs
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(delay.as_nanos());
+    let fraction = hasher.finish() as f64 / u64::MAX as f64;
+    delay.mul_f64(fraction)
+}
+
 /// Executes a future with automatic retry logic for handling transient failures.
 ///
 /// This function wraps API calls with intelligent retry behavior, automatically
@@ -133,16 +505,20 @@ impl Default for RetryConfig {
 /// - [`AniListError::RateLimit`] - Respects retry-after timing when available
 /// - [`AniListError::RateLimitSimple`] - Uses exponential backoff
 /// - [`AniListError::BurstLimit`] - Uses exponential backoff
-/// - [`AniListError::Network`] - For transient network issues
-/// - [`AniListError::ServerError`] - For 5xx server errors
+/// - [`AniListError::Network`] - For transport-level failures such as refused
+///   or dropped connections, and [`AniListError::Timeout`]
+/// - [`AniListError::ServerError`] - For transient 5xx server errors
 ///
-/// Other errors (authentication, not found, bad request) are not retried as they
-/// typically indicate permanent issues that won't resolve with retries.
+/// Other errors (authentication, not found, bad request, JSON errors) are not
+/// retried as they typically indicate permanent issues that won't resolve
+/// with retries; see [`AniListError::is_retryable`]. Narrow the set with
+/// [`RetryConfig::retry_on`].
 ///
 /// # Examples
 ///
 /// ```rust
 /// use anilist_sdk::{AniListClient, utils::{retry_with_backoff, RetryConfig}};
+/// use std::time::Duration;
 ///
 /// let client = AniListClient::new();
 /// let config = RetryConfig::default();
@@ -154,12 +530,13 @@ impl Default for RetryConfig {
 /// ).await?;
 ///
 /// // Custom retry configuration for critical operations
-/// let aggressive_config = RetryConfig {
-///     max_retries: 5,
-///     base_delay_ms: 2000,
-///     exponential_backoff: true,
-///     max_delay_ms: 60000,
-/// };
+/// let aggressive_config = RetryConfig::builder()
+///     .max_retries(5)
+///     .base_delay(Duration::from_secs(2))
+///     .max_delay(Duration::from_secs(60))
+///     .jitter(true)
+///     .max_total_delay(Duration::from_secs(300))
+///     .build();
 ///
 /// let important_result = retry_with_backoff(
 ///     || client.user().get_current_user(),
@@ -175,6 +552,14 @@ impl Default for RetryConfig {
 /// 3. Fall back to exponential backoff for simple rate limit errors
 /// 4. Continue with remaining retry attempts
 ///
+/// Each kind of failure (rate limits, server errors, network failures) is
+/// retried up to its own limit, see [`RetryConfig::max_retries_for`].
+///
+/// With [`RetryConfig::jitter`] set, backoff delays are randomized, and
+/// [`RetryConfig::max_total_delay`] stops retrying early once the time spent
+/// waiting would exceed the budget. A `retry_after` wait is capped by the
+/// budget left rather than abandoned.
+///
 /// # Performance Considerations
 ///
 /// - Higher `max_retries` values increase resilience but may cause longer delays
@@ -195,100 +580,127 @@ where
     Fut: std::future::Future<Output = Result<T, AniListError>>,
 {
     let mut attempts = 0;
+    let mut counts = RetryCounts::default();
     let mut delay = config.base_delay_ms;
+    let mut slept = Duration::ZERO;
 
     loop {
-        match operation().await {
+        let error = match instrument_attempt(operation(), attempts).await {
             Ok(result) => return Ok(result),
-            Err(AniListError::RateLimit { retry_after, .. }) => {
-                if attempts >= config.max_retries {
-                    return Err(AniListError::RateLimit {
-                        limit: 90,
-                        remaining: 0,
-                        reset_at: 0,
-                        retry_after,
-                    });
-                }
-
-                // Use the Retry-After header if available, otherwise use exponential backoff
-                let sleep_duration = if retry_after > 0 {
-                    Duration::from_secs(retry_after as u64)
-                } else {
-                    Duration::from_millis(delay.min(config.max_delay_ms))
-                };
-
-                println!(
-                    "Rate limited. Retrying in {} seconds... (attempt {}/{})",
-                    sleep_duration.as_secs(),
-                    attempts + 1,
-                    config.max_retries
-                );
-
-                sleep(sleep_duration).await;
-
-                attempts += 1;
-                if config.exponential_backoff {
-                    delay = (delay * 2).min(config.max_delay_ms);
-                }
-            }
-            Err(AniListError::RateLimitSimple) => {
-                if attempts >= config.max_retries {
-                    return Err(AniListError::RateLimitSimple);
-                }
-
-                let sleep_duration = Duration::from_millis(delay.min(config.max_delay_ms));
-                println!(
-                    "Rate limited. Retrying in {} seconds... (attempt {}/{})",
-                    sleep_duration.as_secs(),
-                    attempts + 1,
-                    config.max_retries
-                );
-
-                sleep(sleep_duration).await;
-
-                attempts += 1;
-                if config.exponential_backoff {
-                    delay = (delay * 2).min(config.max_delay_ms);
-                }
-            }
-            Err(AniListError::BurstLimit) => {
-                if attempts >= config.max_retries {
-                    return Err(AniListError::BurstLimit);
-                }
-
-                // For burst limits, wait a bit longer
-                let sleep_duration = Duration::from_millis((delay * 2).min(config.max_delay_ms));
-                println!(
-                    "Burst limit exceeded. Retrying in {} seconds... (attempt {}/{})",
-                    sleep_duration.as_secs(),
-                    attempts + 1,
-                    config.max_retries
-                );
-
-                sleep(sleep_duration).await;
-
-                attempts += 1;
-                delay = (delay * 2).min(config.max_delay_ms);
-            }
-            Err(other_error) => return Err(other_error),
+            Err(error) => error,
+        };
+
+        let Some(kind) = config.retried_kind(&error) else {
+            return Err(error);
+        };
+        let max_retries = config.max_retries_for(kind);
+        if counts.of(kind) >= max_retries {
+            return Err(error);
+        }
+        let burst = matches!(error, AniListError::BurstLimit);
+        let reason = match kind {
+            _ if burst => "burst limit exceeded",
+            RetryOn::RATE_LIMIT => "rate limited",
+            RetryOn::SERVER_ERROR => "server error",
+            _ => "network error",
+        };
+        // Use the Retry-After header if available, otherwise use exponential
+        // backoff; burst limits wait a bit longer
+        let (planned, from_server) = match error.server_retry_after() {
+            Some(wait) => (wait, true),
+            None if burst => (
+                Duration::from_millis((delay * 2).min(config.max_delay_ms)),
+                false,
+            ),
+            None => (Duration::from_millis(delay.min(config.max_delay_ms)), false),
+        };
+        let Some(sleep_duration) = config.budgeted(planned, from_server, slept) else {
+            return Err(error);
+        };
+
+        log_retry(reason, counts.of(kind) + 1, max_retries, sleep_duration);
+        sleep(sleep_duration).await;
+        slept += sleep_duration;
+
+        attempts += 1;
+        counts.record(kind);
+        if config.exponential_backoff || burst {
+            delay = (delay * 2).min(config.max_delay_ms);
         }
     }
 }
 
+/// Runs one attempt of a retried request, inside an `anilist_attempt` span
+/// when the `tracing` feature is enabled.
+pub(crate) async fn instrument_attempt<Fut: std::future::Future>(
+    future: Fut,
+    attempt: u32,
+) -> Fut::Output {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+        future
+            .instrument(tracing::debug_span!("anilist_attempt", attempt))
+            .await
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = attempt;
+        future.await
+    }
+}
+
+/// Reports an upcoming retry as a `tracing` warning; silent without the
+/// `tracing` feature.
+pub(crate) fn log_retry(reason: &str, attempt: u32, max_retries: u32, sleep: Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        reason,
+        attempt,
+        max_retries,
+        sleep_secs = sleep.as_secs_f64(),
+        "retrying AniList request"
+    );
+    #[cfg(not(feature = "tracing"))]
+    let _ = (reason, attempt, max_retries, sleep);
+}
+
 /// Helper to add delay between requests to avoid rate limiting
 pub async fn rate_limit_delay(delay_ms: u64) {
     sleep(Duration::from_millis(delay_ms)).await;
 }
 
-/// Calculate appropriate delay based on remaining rate limit
+/// Calculate a delay that spreads the `remaining` requests evenly over the
+/// `reset_in_seconds` until the window resets, or waits for the reset once
+/// nothing remains.
+///
+/// [`RateLimitState::suggested_delay`](crate::rate_limit::RateLimitState::suggested_delay)
+/// does the same with the figures the client tracked from the response
+/// headers.
 pub fn calculate_delay(remaining: u32, reset_in_seconds: u64) -> Duration {
-    if remaining == 0 {
-        Duration::from_secs(reset_in_seconds)
-    } else if remaining < 10 {
-        Duration::from_millis(2000) // 2 seconds when getting low
-    } else if remaining < 30 {
-        Duration::from_millis(1000) // 1 second when moderate
-    } else {
-        Duration::from_millis(500) // 500ms when plenty remaining
+    crate::rate_limit::spread(remaining, Duration::from_secs(reset_in_seconds))
+}
+
+/// Trim a search term and collapse internal whitespace to single spaces.
+///
+/// Empty or whitespace-only terms are rejected with
+/// [`AniListError::BadRequest`]: AniList treats them as "no filter" and
+/// returns unrelated popular media instead of an empty result.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::utils::normalize_search;
+///
+/// assert_eq!(normalize_search("  attack \t on\ntitan ").unwrap(), "attack on titan");
+/// assert!(normalize_search("   ").is_err());
+/// ```
+pub fn normalize_search(search: &str) -> Result<String, AniListError> {
+    let normalized = search.split_whitespace().collect::<Vec<_>>().join(" ");
+    if normalized.is_empty() {
+        return Err(AniListError::BadRequest {
+            message: "search term must not be empty or whitespace-only".to_string(),
+        });
     }
+    Ok(normalized)
 }
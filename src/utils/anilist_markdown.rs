@@ -0,0 +1,342 @@
+//! # AniList Markdown
+//!
+//! AniList extends markdown with a few constructs of its own, used heavily in
+//! activities, forum posts and user About sections:
+//!
+//! - `img220(url)` / `img50%(url)` / `img(url)`: an image, optionally sized
+//! - `youtube(url or id)` and `webm(url)`: embedded videos
+//! - `~~~text~~~` (or `<center>text</center>`): centered content
+//! - `~!text!~`: a spoiler
+//!
+//! [`parse`] turns a text into a small tree of [`MarkdownNode`]s covering
+//! these extensions plus links and markdown images. Everything else,
+//! including standard markdown such as `**bold**` or headings, is left
+//! untouched in [`MarkdownNode::Text`] so a regular markdown renderer can
+//! handle it. Nothing is rendered here.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::utils::anilist_markdown::{ImageWidth, MarkdownNode, parse};
+//!
+//! let nodes = parse("~~~img220(https://i.imgur.com/a.png)~~~ Hi!");
+//! assert_eq!(
+//!     nodes,
+//!     vec![
+//!         MarkdownNode::Centered(vec![MarkdownNode::Image {
+//!             width: Some(ImageWidth::Pixels(220)),
+//!             url: "https://i.imgur.com/a.png".to_string(),
+//!         }]),
+//!         MarkdownNode::Text(" Hi!".to_string()),
+//!     ]
+//! );
+//! ```
+
+/// A node of parsed AniList markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownNode {
+    /// Source text outside any AniList construct, standard markdown included
+    Text(String),
+    /// `img(url)`, `img220(url)`, `img50%(url)` or `![alt](url)`
+    Image {
+        width: Option<ImageWidth>,
+        url: String,
+    },
+    /// `youtube(...)`, with the video ID extracted from whatever URL form was used
+    YouTube { id: String },
+    /// `webm(url)`
+    Video { url: String },
+    /// `~~~...~~~` or `<center>...</center>`
+    Centered(Vec<MarkdownNode>),
+    /// `~!...!~`
+    Spoiler(Vec<MarkdownNode>),
+    /// `[label](url)`; the label may itself contain images
+    Link {
+        url: String,
+        children: Vec<MarkdownNode>,
+    },
+}
+
+/// The size given to an `img` tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageWidth {
+    /// `img220(url)`
+    Pixels(u32),
+    /// `img50%(url)`
+    Percent(u32),
+}
+
+/// An embedded media token such as `img220(url)`.
+pub(crate) enum Embed {
+    Image(Option<ImageWidth>, String),
+    YouTube(String),
+    Video(String),
+}
+
+/// Parses AniList markdown into a tree of nodes.
+///
+/// Unterminated constructs, such as a `~!` without its closing `!~`, are kept
+/// as text rather than swallowing the rest of the input. Containers and links
+/// nested more than [`MAX_DEPTH`] levels deep are kept as text too, so hostile
+/// input cannot exhaust the stack.
+pub fn parse(text: &str) -> Vec<MarkdownNode> {
+    parse_nested(text, 0)
+}
+
+/// How deeply spoilers, centered blocks and links may nest before further
+/// openers are treated as plain text.
+pub const MAX_DEPTH: usize = 32;
+
+fn parse_nested(text: &str, depth: usize) -> Vec<MarkdownNode> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut pos = 0;
+    parse_until(&chars, &mut pos, None, depth)
+}
+
+impl MarkdownNode {
+    /// Returns `true` if this node is or contains a spoiler.
+    pub fn contains_spoiler(&self) -> bool {
+        match self {
+            MarkdownNode::Spoiler(_) => true,
+            MarkdownNode::Centered(children) | MarkdownNode::Link { children, .. } => {
+                children.iter().any(MarkdownNode::contains_spoiler)
+            }
+            _ => false,
+        }
+    }
+}
+
+const CENTER_OPEN: &str = "<center>";
+const CENTER_CLOSE: &str = "</center>";
+
+fn parse_until(
+    chars: &[char],
+    pos: &mut usize,
+    terminator: Option<&[char]>,
+    depth: usize,
+) -> Vec<MarkdownNode> {
+    let mut nodes = Vec::new();
+    let mut text = String::new();
+    let can_nest = depth < MAX_DEPTH;
+
+    while *pos < chars.len() {
+        let rest = &chars[*pos..];
+
+        if let Some(terminator) = terminator
+            && rest.starts_with(terminator)
+        {
+            *pos += terminator.len();
+            break;
+        }
+
+        let node = if can_nest && rest.starts_with(&['~', '~', '~']) {
+            container(
+                chars,
+                pos,
+                3,
+                &['~', '~', '~'],
+                depth,
+                MarkdownNode::Centered,
+            )
+        } else if can_nest && rest.starts_with(&['~', '!']) {
+            container(chars, pos, 2, &['!', '~'], depth, MarkdownNode::Spoiler)
+        } else if can_nest && starts_with_ignore_case(rest, CENTER_OPEN) {
+            let close: Vec<char> = CENTER_CLOSE.chars().collect();
+            container_ignore_case(chars, pos, &close, depth)
+        } else if word_start(chars, *pos)
+            && let Some((embed, len)) = embed_at(rest)
+        {
+            *pos += len;
+            Some(match embed {
+                Embed::Image(width, url) => MarkdownNode::Image { width, url },
+                Embed::YouTube(source) => MarkdownNode::YouTube {
+                    id: youtube_id(&source),
+                },
+                Embed::Video(url) => MarkdownNode::Video { url },
+            })
+        } else if rest.starts_with(&['!', '[']) {
+            bracketed(rest, 1).map(|(_, url, len)| {
+                *pos += len;
+                MarkdownNode::Image { width: None, url }
+            })
+        } else if can_nest && rest[0] == '[' {
+            bracketed(rest, 0).map(|(label, url, len)| {
+                *pos += len;
+                MarkdownNode::Link {
+                    url,
+                    children: parse_nested(&label, depth + 1),
+                }
+            })
+        } else {
+            None
+        };
+
+        match node {
+            Some(node) => {
+                if !text.is_empty() {
+                    nodes.push(MarkdownNode::Text(std::mem::take(&mut text)));
+                }
+                nodes.push(node);
+            }
+            None => {
+                text.push(chars[*pos]);
+                *pos += 1;
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        nodes.push(MarkdownNode::Text(text));
+    }
+    nodes
+}
+
+/// Parses a container opened by `open_len` characters at `pos`, if its
+/// closing sequence appears later on.
+fn container(
+    chars: &[char],
+    pos: &mut usize,
+    open_len: usize,
+    close: &[char],
+    depth: usize,
+    wrap: fn(Vec<MarkdownNode>) -> MarkdownNode,
+) -> Option<MarkdownNode> {
+    find_seq(&chars[*pos + open_len..], close)?;
+    *pos += open_len;
+    Some(wrap(parse_until(chars, pos, Some(close), depth + 1)))
+}
+
+/// Like [`container`] for `<center>`, whose tags are case-insensitive.
+fn container_ignore_case(
+    chars: &[char],
+    pos: &mut usize,
+    close: &[char],
+    depth: usize,
+) -> Option<MarkdownNode> {
+    let open_len = CENTER_OPEN.chars().count();
+    let lower: Vec<char> = chars[*pos + open_len..]
+        .iter()
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let end = find_seq(&lower, close)?;
+    let inner: String = chars[*pos + open_len..*pos + open_len + end]
+        .iter()
+        .collect();
+    *pos += open_len + end + close.len();
+    Some(MarkdownNode::Centered(parse_nested(&inner, depth + 1)))
+}
+
+fn starts_with_ignore_case(text: &[char], prefix: &str) -> bool {
+    let len = prefix.chars().count();
+    text.len() >= len
+        && text[..len]
+            .iter()
+            .zip(prefix.chars())
+            .all(|(a, b)| a.to_ascii_lowercase() == b)
+}
+
+/// `true` unless `pos` continues a word, so `ximg(...)` is not an image.
+fn word_start(chars: &[char], pos: usize) -> bool {
+    pos == 0 || !chars[pos - 1].is_alphanumeric()
+}
+
+/// Parses `[label](url)` starting `offset` characters into `text`, returning
+/// the label, the URL and the total length. Brackets in the label may nest.
+fn bracketed(text: &[char], offset: usize) -> Option<(String, String, usize)> {
+    let mut depth = 0;
+    let mut close = None;
+    for (i, c) in text.iter().enumerate().skip(offset) {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i);
+                    break;
+                }
+            }
+            '\n' => return None,
+            _ => {}
+        }
+    }
+    let close = close?;
+    if text.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let url_len = text[close + 2..].iter().position(|&c| c == ')')?;
+    let label: String = text[offset + 1..close].iter().collect();
+    let url: String = text[close + 2..close + 2 + url_len].iter().collect();
+    Some((label, url.trim().to_string(), close + 2 + url_len + 1))
+}
+
+/// Recognizes an embed token such as `img220(url)` at the start of `text`,
+/// returning it with its length.
+///
+/// Also used by [`Thread::excerpt`](crate::models::social::Thread::excerpt)
+/// to drop embeds from previews.
+pub(crate) fn embed_at(text: &[char]) -> Option<(Embed, usize)> {
+    for keyword in ["img", "youtube", "webm"] {
+        let keyword_chars: Vec<char> = keyword.chars().collect();
+        if !starts_with_ignore_case(text, keyword) {
+            continue;
+        }
+        let after = &text[keyword_chars.len()..];
+        let digits = after.iter().take_while(|c| c.is_ascii_digit()).count();
+        let percent = after.get(digits) == Some(&'%');
+        let size_len = digits + usize::from(percent);
+        if after.get(size_len) != Some(&'(') {
+            continue;
+        }
+        let close = after[size_len..].iter().position(|&c| c == ')')?;
+        let source: String = after[size_len + 1..size_len + close]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_string();
+        let len = keyword_chars.len() + size_len + close + 1;
+        let embed = match keyword {
+            "img" => {
+                let size: String = after[..digits].iter().collect();
+                let width = size.parse().ok().map(|size| {
+                    if percent {
+                        ImageWidth::Percent(size)
+                    } else {
+                        ImageWidth::Pixels(size)
+                    }
+                });
+                Embed::Image(width, source)
+            }
+            "youtube" => Embed::YouTube(source),
+            _ => Embed::Video(source),
+        };
+        return Some((embed, len));
+    }
+    None
+}
+
+/// Extracts the video ID from any of the forms `youtube(...)` accepts: a
+/// bare ID, a `watch?v=` URL, a `youtu.be` short link, or an embed or
+/// shorts URL.
+fn youtube_id(source: &str) -> String {
+    let source = source.trim();
+    let id = if let Some((_, query)) = source.split_once("v=") {
+        query
+    } else if let Some((_, path)) = source
+        .split_once("youtu.be/")
+        .or_else(|| source.split_once("/embed/"))
+        .or_else(|| source.split_once("/shorts/"))
+    {
+        path
+    } else {
+        source
+    };
+    id.split(['&', '?', '#', '/'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Position of `seq` in `text`.
+pub(crate) fn find_seq(text: &[char], seq: &[char]) -> Option<usize> {
+    text.windows(seq.len()).position(|window| window == seq)
+}
@@ -0,0 +1,139 @@
+//! # Concurrency-Limited Batches
+//!
+//! [`BatchRunner`] runs many independent calls, such as hydrating a list of
+//! IDs one request at a time, with a cap on how many are in flight at once
+//! and a minimum gap between starting them, so a large batch does not trip
+//! AniList's burst limit.
+//!
+//! Unlike [`batch`](crate::batch), which merges lookups into one request,
+//! every call here is its own request and goes through the client's rate
+//! limiter as usual; the runner only decides when each call starts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::utils::batch_runner::BatchRunner;
+//! use std::time::Duration;
+//!
+//! let anime = client.anime();
+//! let ids = vec![1, 20, 21, 5114, 16498];
+//! let results = BatchRunner::new(3)
+//!     .min_gap(Duration::from_millis(250))
+//!     .run(ids.iter().map(|&id| anime.get_by_id(id)))
+//!     .await;
+//!
+//! for (id, result) in ids.iter().zip(results) {
+//!     match result {
+//!         Ok(anime) => println!("{id}: {:?}", anime.title),
+//!         Err(error) => println!("{id} failed: {error}"),
+//!     }
+//! }
+//! ```
+
+use crate::error::AniListError;
+use crate::runtime::{Instant, sleep_until};
+use std::future::{Future, poll_fn};
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+
+/// Runs calls with bounded concurrency, returning their results in order.
+///
+/// A failing call does not stop the batch; its error takes its place in the
+/// output. When a call fails with [`AniListError::RateLimit`], no further
+/// calls are started until its `retry_after` has passed.
+///
+/// Calls run concurrently on the current task rather than being spawned, so
+/// they may borrow from the caller and need not be `Send` or `'static`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchRunner {
+    max_concurrency: usize,
+    min_gap: Duration,
+}
+
+impl BatchRunner {
+    /// Creates a runner keeping at most `max_concurrency` calls in flight
+    /// (at least one), with no gap between starts.
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            max_concurrency: max_concurrency.max(1),
+            min_gap: Duration::ZERO,
+        }
+    }
+
+    /// Sets the minimum time between starting two calls.
+    pub fn min_gap(mut self, min_gap: Duration) -> Self {
+        self.min_gap = min_gap;
+        self
+    }
+
+    /// The most calls kept in flight at once.
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Runs every call in `ops`, returning one result per call in input
+    /// order.
+    ///
+    /// Calls are futures, which do nothing until polled, so each one starts
+    /// when the runner first polls it.
+    pub async fn run<I, Fut, T>(&self, ops: I) -> Vec<Result<T, AniListError>>
+    where
+        I: IntoIterator<Item = Fut>,
+        Fut: Future<Output = Result<T, AniListError>>,
+    {
+        let mut pending = ops.into_iter().enumerate().peekable();
+        let mut results: Vec<Option<Result<T, AniListError>>> = Vec::new();
+        let mut in_flight: Vec<(usize, Pin<Box<Fut>>)> = Vec::new();
+        let mut next_start = Instant::now();
+
+        loop {
+            while in_flight.len() < self.max_concurrency && Instant::now() >= next_start {
+                let Some((index, op)) = pending.next() else {
+                    break;
+                };
+                results.push(None);
+                in_flight.push((index, Box::pin(op)));
+                next_start = Instant::now() + self.min_gap;
+            }
+
+            let can_start = pending.peek().is_some() && in_flight.len() < self.max_concurrency;
+            if in_flight.is_empty() && !can_start {
+                break;
+            }
+
+            let next_finished = poll_fn(|cx| {
+                for slot in 0..in_flight.len() {
+                    if let Poll::Ready(result) = in_flight[slot].1.as_mut().poll(cx) {
+                        let (index, _) = in_flight.swap_remove(slot);
+                        return Poll::Ready((index, result));
+                    }
+                }
+                Poll::Pending
+            });
+            let start_due = async {
+                if can_start {
+                    sleep_until(next_start).await;
+                } else {
+                    std::future::pending::<()>().await;
+                }
+            };
+
+            tokio::select! {
+                (index, result) = next_finished => {
+                    if let Err(AniListError::RateLimit { retry_after, .. }) = &result {
+                        let resume = Instant::now() + Duration::from_secs(u64::from(*retry_after));
+                        next_start = next_start.max(resume);
+                    }
+                    results[index] = Some(result);
+                }
+                _ = start_due => {}
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every started call completes"))
+            .collect()
+    }
+}
@@ -0,0 +1,141 @@
+//! # Merging Results
+//!
+//! Helpers for combining results from several queries into one list, keyed by
+//! AniList ID. The output order depends only on the input order, never on hash
+//! iteration order, so the same inputs always produce the same list.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::utils::merge::{dedup_by_id, interleave};
+//!
+//! let popular = client.anime().get_popular(1, 10).await?;
+//! let trending = client.anime().get_trending(1, 10).await?;
+//!
+//! // Alternate between the lists, each title once
+//! let mixed = interleave(vec![popular.clone(), trending.clone()]);
+//!
+//! // Popular first, then trending titles not already listed
+//! let combined = dedup_by_id(popular.into_iter().chain(trending));
+//! ```
+
+use crate::models::personalized::PersonalizedRecommendation;
+use crate::models::{anime, social};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// A model identified by its AniList ID.
+pub trait HasId {
+    /// The AniList ID of this item.
+    fn id(&self) -> i32;
+}
+
+macro_rules! impl_has_id {
+    ($($model:ty),* $(,)?) => {
+        $(impl HasId for $model {
+            fn id(&self) -> i32 {
+                self.id
+            }
+        })*
+    };
+}
+
+impl_has_id!(
+    crate::models::Anime,
+    crate::models::Manga,
+    crate::models::Character,
+    crate::models::Staff,
+    crate::models::User,
+    crate::models::MediaRef,
+    crate::models::MediaList,
+    crate::models::Review,
+    crate::models::Recommendation,
+    crate::models::Thread,
+    crate::models::ThreadComment,
+    crate::models::Activity,
+    crate::models::TextActivity,
+    crate::models::Notification,
+    anime::Studio,
+    anime::AiringSchedule,
+    social::Studio,
+    social::AiringSchedule,
+);
+
+impl HasId for PersonalizedRecommendation {
+    /// The ID of the suggested title.
+    fn id(&self) -> i32 {
+        self.suggestion.id
+    }
+}
+
+/// Removes items whose ID was already seen, keeping the first occurrence of
+/// each ID in its original position.
+pub fn dedup_by_id<T: HasId>(items: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(item.id()))
+        .collect()
+}
+
+/// Round-robins over `sources`: the first item of each source in order, then
+/// the second of each, and so on.
+///
+/// An item whose ID already appeared earlier in the output is skipped. Shorter
+/// sources simply drop out once exhausted.
+pub fn interleave<T: HasId>(sources: Vec<Vec<T>>) -> Vec<T> {
+    let mut iters: Vec<_> = sources.into_iter().map(Vec::into_iter).collect();
+    let mut seen = HashSet::new();
+    let mut merged = Vec::new();
+
+    loop {
+        let mut exhausted = true;
+        for iter in &mut iters {
+            if let Some(item) = iter.next() {
+                exhausted = false;
+                if seen.insert(item.id()) {
+                    merged.push(item);
+                }
+            }
+        }
+        if exhausted {
+            return merged;
+        }
+    }
+}
+
+/// Merges items sharing an ID, then sorts by `score`, highest first.
+///
+/// For each ID, later items are folded into the first one with `combine`, in
+/// input order. Ties in score are broken by ID, lowest first, so the result is
+/// fully determined by the inputs. NaN scores sort last.
+pub fn stable_rank_merge<T: HasId>(
+    items: impl IntoIterator<Item = T>,
+    mut combine: impl FnMut(&mut T, T),
+    score: impl Fn(&T) -> f64,
+) -> Vec<T> {
+    let mut merged: Vec<T> = Vec::new();
+    let mut positions: HashMap<i32, usize> = HashMap::new();
+
+    for item in items {
+        match positions.get(&item.id()) {
+            Some(&position) => combine(&mut merged[position], item),
+            None => {
+                positions.insert(item.id(), merged.len());
+                merged.push(item);
+            }
+        }
+    }
+
+    merged.sort_by(|a, b| {
+        let (a_score, b_score) = (score(a), score(b));
+        match (a_score.is_nan(), b_score.is_nan()) {
+            (false, true) => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            _ => b_score.total_cmp(&a_score),
+        }
+        .then(a.id().cmp(&b.id()))
+    });
+    merged
+}
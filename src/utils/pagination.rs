@@ -0,0 +1,317 @@
+//! # Auto-Pagination
+//!
+//! [`paginate`] turns any method that fetches one [`Page`] into a stream of
+//! the items on every page. Pages are fetched lazily as the stream is
+//! polled, one at a time, waiting [`AniListClient::page_delay`] between
+//! them so a long crawl stays under AniList's rate limit. The stream ends
+//! after the last page, or after the first error.
+//!
+//! The endpoints expose ready-made streams such as
+//! [`AnimeEndpoint::search_stream`](crate::endpoints::anime::AnimeEndpoint::search_stream);
+//! any other paged method is one call away:
+//!
+//! ```rust
+//! use anilist_sdk::utils::pagination::paginate;
+//! use futures::StreamExt;
+//! use std::pin::pin;
+//!
+//! let mut likers = pin!(paginate(client.clone(), move |client, page| async move {
+//!     client.activity().get_likers(activity_id, page, 50).await
+//! }));
+//! while let Some(user) = likers.next().await {
+//!     println!("{}", user?.name);
+//! }
+//! ```
+//!
+//! Dropping a stream part way through a page simply cancels the request in
+//! flight; the client and its rate limit budget are unaffected.
+//!
+//! When the whole result set is wanted at once, [`collect_all`] and the
+//! `_all` endpoint methods built on it fetch page after page into an
+//! [`AllPages`], with the same delay, stopping after
+//! [`AniListClient::max_pages`] pages so a broad query cannot crawl the
+//! whole database by accident.
+//!
+//! The same pacing applies when a single page asks for more than
+//! [`MAX_PER_PAGE`] items: the client fetches it as consecutive API pages
+//! and hands back one concatenated page (see
+//! [`AniListClient::set_strict_page_size`]).
+
+use crate::client::AniListClient;
+use crate::envelope::ResponseEnvelope;
+use crate::error::AniListError;
+use crate::limits::MAX_PER_PAGE;
+use crate::models::Page;
+use crate::runtime::sleep;
+use futures_util::Stream;
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::time::Duration;
+
+/// Time waited between page fetches unless configured otherwise, keeping a
+/// crawl just under AniList's default limit of 90 requests per minute
+pub const DEFAULT_PAGE_DELAY: Duration = Duration::from_millis(700);
+
+/// Most pages the `_all` methods fetch unless configured otherwise, i.e.
+/// up to 2,000 items at 50 per page
+pub const DEFAULT_MAX_PAGES: u32 = 40;
+
+/// Every page of a result set, as returned by [`collect_all`] and the `_all`
+/// endpoint methods.
+///
+/// A failure part way through does not throw away the pages already
+/// fetched: they stay in `items` with the failure in `error`. Rate limit
+/// errors only end up there once the client's retries (see
+/// [`AniListClient::set_retry`]) have run out, or straight away on a client
+/// without retries.
+///
+/// # Examples
+///
+/// ```rust
+/// let lineup = client.anime().get_by_season_all("WINTER", 2024).await;
+/// if lineup.truncated {
+///     println!("stopped after {} pages", lineup.pages_fetched);
+/// }
+/// let anime = lineup.into_result()?;
+/// ```
+#[derive(Debug)]
+pub struct AllPages<T> {
+    /// Items of every page fetched, in order
+    pub items: Vec<T>,
+    /// Number of pages fetched successfully
+    pub pages_fetched: u32,
+    /// `true` if AniList had more pages when the page limit was reached
+    pub truncated: bool,
+    /// The error that stopped the fetch early, if any
+    pub error: Option<AniListError>,
+}
+
+impl<T> AllPages<T> {
+    /// A fetch that failed before its first page.
+    pub(crate) fn failed(error: AniListError) -> Self {
+        Self {
+            items: Vec::new(),
+            pages_fetched: 0,
+            truncated: false,
+            error: Some(error),
+        }
+    }
+
+    /// Returns `true` if every page was fetched.
+    pub fn is_complete(&self) -> bool {
+        !self.truncated && self.error.is_none()
+    }
+
+    /// The items, or the error if one stopped the fetch, discarding any
+    /// partial results. A truncated result still counts as success.
+    pub fn into_result(self) -> Result<Vec<T>, AniListError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.items),
+        }
+    }
+}
+
+struct State<T, F> {
+    client: AniListClient,
+    fetch: F,
+    /// Items fetched but not yet yielded
+    buffered: VecDeque<T>,
+    /// The page to fetch once `buffered` runs out; `None` after the last one
+    next_page: Option<i32>,
+}
+
+/// Streams the items of every page returned by `fetch`, starting at page 1.
+///
+/// `fetch` is called with a clone of `client` and the page number. The next
+/// page is only fetched once every item of the previous one has been
+/// yielded and AniList reported another page after it; a page without items
+/// also ends the stream. [`AniListClient::page_delay`] is waited before each
+/// page after the first.
+///
+/// An error is yielded as the stream's last item.
+pub fn paginate<T, F, Fut>(
+    client: AniListClient,
+    fetch: F,
+) -> impl Stream<Item = Result<T, AniListError>>
+where
+    F: FnMut(AniListClient, i32) -> Fut,
+    Fut: Future<Output = Result<Page<T>, AniListError>>,
+{
+    let state = State {
+        client,
+        fetch,
+        buffered: VecDeque::new(),
+        next_page: Some(1),
+    };
+    futures_util::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.buffered.pop_front() {
+                return Some((Ok(item), state));
+            }
+            let page = state.next_page?;
+            let delay = state.client.page_delay();
+            if page > 1 && !delay.is_zero() {
+                sleep(delay).await;
+            }
+            match (state.fetch)(state.client.clone(), page).await {
+                Ok(fetched) => {
+                    let more = fetched.has_next_page() && !fetched.items.is_empty();
+                    state.next_page = more.then_some(page + 1);
+                    state.buffered = fetched.items.into();
+                }
+                Err(error) => {
+                    state.next_page = None;
+                    return Some((Err(error), state));
+                }
+            }
+        }
+    })
+}
+
+/// Fetches every page returned by `fetch`, starting at page 1, into an
+/// [`AllPages`].
+///
+/// Pages are fetched one after another, waiting
+/// [`AniListClient::page_delay`] before each page after the first, until
+/// AniList reports no next page, a page comes back empty, a request fails,
+/// or [`AniListClient::max_pages`] pages have been fetched.
+pub async fn collect_all<T, F, Fut>(client: AniListClient, mut fetch: F) -> AllPages<T>
+where
+    F: FnMut(AniListClient, i32) -> Fut,
+    Fut: Future<Output = Result<Page<T>, AniListError>>,
+{
+    let mut all = AllPages {
+        items: Vec::new(),
+        pages_fetched: 0,
+        truncated: false,
+        error: None,
+    };
+    let max_pages = client.max_pages().max(1);
+    let delay = client.page_delay();
+    let mut page = 1;
+    loop {
+        if page > 1 && !delay.is_zero() {
+            sleep(delay).await;
+        }
+        let fetched = match fetch(client.clone(), page).await {
+            Ok(fetched) => fetched,
+            Err(error) => {
+                all.error = Some(error);
+                return all;
+            }
+        };
+        all.pages_fetched += 1;
+        let more = fetched.has_next_page() && !fetched.items.is_empty();
+        all.items.extend(fetched.items);
+        if !more {
+            return all;
+        }
+        if all.pages_fetched >= max_pages {
+            all.truncated = true;
+            return all;
+        }
+        page += 1;
+    }
+}
+
+/// Fetches page `page` of a paged query whose `perPage` variable is above
+/// [`MAX_PER_PAGE`], as consecutive API pages of [`MAX_PER_PAGE`] items.
+///
+/// The lists under `data.Page` are concatenated and cut to the items the
+/// requested page covers, and `pageInfo`, if selected, is rewritten to
+/// describe that page. [`AniListClient::page_delay`] is waited between the
+/// requests, and no further pages are fetched once AniList runs out of
+/// items. The metadata is that of the last response, with the time of every
+/// request added up and every partial error kept.
+pub(crate) async fn fetch_oversized_page(
+    client: &AniListClient,
+    query: &str,
+    mut variables: HashMap<String, Value>,
+) -> Result<ResponseEnvelope<Value>, AniListError> {
+    let chunk = i64::from(MAX_PER_PAGE);
+    let page = variables.get("page").and_then(Value::as_i64).unwrap_or(1);
+    let per_page = variables.get("perPage").and_then(Value::as_i64).unwrap_or(chunk);
+    let start = (page - 1) * per_page;
+    let first = start / chunk + 1;
+    let last = (start + per_page - 1) / chunk + 1;
+    let skip = (start - (first - 1) * chunk) as usize;
+
+    let delay = client.page_delay();
+    let mut lists: Vec<(String, Vec<Value>)> = Vec::new();
+    let mut page_info = None;
+    let mut combined: Option<ResponseEnvelope<Value>> = None;
+    for api_page in first..=last {
+        if api_page > first && !delay.is_zero() {
+            sleep(delay).await;
+        }
+        variables.insert("page".to_string(), json!(api_page));
+        variables.insert("perPage".to_string(), json!(chunk));
+        let mut envelope = client
+            .send_query(query, Some(variables.clone()), false)
+            .await?;
+
+        let fetched = match envelope.value.pointer_mut("/data/Page") {
+            Some(Value::Object(fetched)) => std::mem::take(fetched),
+            _ => Map::new(),
+        };
+        let mut full = true;
+        for (field, value) in fetched {
+            match value {
+                Value::Array(items) => {
+                    full &= items.len() as i64 == chunk;
+                    match lists.iter_mut().find(|(name, _)| *name == field) {
+                        Some((_, list)) => list.extend(items),
+                        None => lists.push((field, items)),
+                    }
+                }
+                info if field == "pageInfo" => page_info = Some(info),
+                _ => {}
+            }
+        }
+        let more = full
+            && page_info
+                .as_ref()
+                .is_none_or(|info| info["hasNextPage"] != Value::Bool(false));
+
+        if let Some(previous) = combined.take() {
+            envelope.elapsed += previous.elapsed;
+            envelope.errors.splice(0..0, previous.errors);
+        }
+        combined = Some(envelope);
+        if !more {
+            break;
+        }
+    }
+    let mut envelope = combined.expect("at least one page is fetched");
+
+    let mut merged = Map::new();
+    let mut beyond = false;
+    for (field, items) in lists {
+        beyond |= items.len() > skip + per_page as usize;
+        let items = items.into_iter().skip(skip).take(per_page as usize);
+        merged.insert(field, Value::Array(items.collect()));
+    }
+    if let Some(info) = page_info {
+        let total = info["total"].as_i64();
+        let has_next_page = beyond || info["hasNextPage"] == Value::Bool(true);
+        merged.insert(
+            "pageInfo".to_string(),
+            json!({
+                "total": total,
+                "perPage": per_page,
+                "currentPage": page,
+                "lastPage": total.map(|total| ((total + per_page - 1) / per_page).max(1)),
+                "hasNextPage": has_next_page,
+            }),
+        );
+    }
+    match envelope.value.pointer_mut("/data") {
+        Some(Value::Object(data)) => {
+            data.insert("Page".to_string(), Value::Object(merged));
+        }
+        _ => envelope.value["data"] = json!({ "Page": merged }),
+    }
+    Ok(envelope)
+}
@@ -0,0 +1,156 @@
+//! # Prefetching
+//!
+//! [`Prefetcher`] walks a list of IDs in order, fetching each item's details
+//! while warming up the next few, so a detail view that steps through search
+//! results has the next item ready when the user moves on.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::utils::prefetch::Prefetcher;
+//!
+//! let ids = vec![21, 16498, 1535, 5114];
+//! let mut details = Prefetcher::new(
+//!     client.clone(),
+//!     ids,
+//!     |client, id| async move { client.anime().get_by_id(id).await },
+//!     2,
+//! );
+//!
+//! while let Some(anime) = details.next().await {
+//!     show(anime?);
+//!     wait_for_arrow_key().await;
+//! }
+//! ```
+
+use crate::client::AniListClient;
+use crate::error::AniListError;
+use crate::runtime::{MaybeSend, Task, spawn};
+use std::collections::VecDeque;
+use std::future::Future;
+
+/// Fetches items by ID in order, keeping up to `lookahead` requests in flight.
+///
+/// The item [`Prefetcher::next`] is waiting for is always requested.
+/// Requests for the items after it are only started while the client has
+/// rate limit budget to spare for them, beyond what the requests already
+/// sent have used, so warming never eats into budget the rest of the
+/// application is about to use.
+///
+/// Requests run as Tokio tasks (browser tasks on `wasm32`). [`Prefetcher::next`] is cancel-safe: if the
+/// future it returns is dropped before completing, the request keeps running
+/// and its result is returned by the next call. Dropping the prefetcher, or
+/// moving to another list with [`Prefetcher::reset`], aborts requests that
+/// have not completed.
+pub struct Prefetcher<T, F> {
+    client: AniListClient,
+    pending: VecDeque<i32>,
+    in_flight: VecDeque<Task<Result<T, AniListError>>>,
+    fetch: F,
+    lookahead: usize,
+}
+
+impl<T, F, Fut> Prefetcher<T, F>
+where
+    T: MaybeSend + 'static,
+    F: Fn(AniListClient, i32) -> Fut,
+    Fut: Future<Output = Result<T, AniListError>> + MaybeSend + 'static,
+{
+    /// Creates a prefetcher over `ids`, fetching each with `fetch`.
+    ///
+    /// `lookahead` is the maximum number of requests in flight, including the
+    /// one for the next item; values below 1 are treated as 1. Nothing is
+    /// requested until the first call to [`Prefetcher::next`].
+    pub fn new(
+        client: AniListClient,
+        ids: impl IntoIterator<Item = i32>,
+        fetch: F,
+        lookahead: usize,
+    ) -> Self {
+        Self {
+            client,
+            pending: ids.into_iter().collect(),
+            in_flight: VecDeque::new(),
+            fetch,
+            lookahead: lookahead.max(1),
+        }
+    }
+
+    /// Returns the next item in ID order, or `None` once every ID has been
+    /// returned.
+    ///
+    /// After the item arrives, requests for the following items are started
+    /// so they are ready by the next call.
+    pub async fn next(&mut self) -> Option<Result<T, AniListError>> {
+        self.fill();
+        let head = self.in_flight.front_mut()?;
+        // The handle stays queued until it completes, so dropping this future
+        // mid-request loses nothing
+        let result = head.await;
+        self.in_flight.pop_front();
+        self.fill();
+        Some(result)
+    }
+
+    /// Replaces the remaining IDs, e.g. when the user jumps to another list.
+    ///
+    /// Requests still in flight for the old IDs are aborted.
+    pub fn reset(&mut self, ids: impl IntoIterator<Item = i32>) {
+        self.abort_in_flight();
+        self.pending = ids.into_iter().collect();
+    }
+
+    /// Number of requests started but not yet returned by [`Prefetcher::next`].
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Number of items not yet returned, including those in flight.
+    pub fn remaining(&self) -> usize {
+        self.in_flight.len() + self.pending.len()
+    }
+
+    /// Starts requests until `lookahead` are in flight or the budget check
+    /// fails.
+    fn fill(&mut self) {
+        // Requests already sent are counted by the budget; the ones started
+        // here have not been sent yet
+        let mut started = 0;
+        while self.in_flight.len() < self.lookahead {
+            let warming = !self.in_flight.is_empty();
+            if warming && self.client.available_budget() as usize <= started {
+                break;
+            }
+            let Some(id) = self.pending.pop_front() else {
+                break;
+            };
+            let request = (self.fetch)(self.client.clone(), id);
+            self.in_flight.push_back(spawn(request));
+            started += 1;
+        }
+    }
+}
+
+impl<T, F> Prefetcher<T, F> {
+    fn abort_in_flight(&mut self) {
+        for handle in self.in_flight.drain(..) {
+            handle.abort();
+        }
+    }
+}
+
+impl<T, F> Drop for Prefetcher<T, F> {
+    fn drop(&mut self) {
+        self.abort_in_flight();
+    }
+}
+
+impl<T, F> std::fmt::Debug for Prefetcher<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Prefetcher")
+            .field("pending", &self.pending)
+            .field("in_flight", &self.in_flight.len())
+            .field("lookahead", &self.lookahead)
+            .finish()
+    }
+}
@@ -0,0 +1,234 @@
+//! # Season Helpers
+//!
+//! Navigation helpers for seasonal archives. AniList splits each year into
+//! four seasons by quarter: Winter (January–March), Spring (April–June),
+//! Summer (July–September) and Fall (October–December).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anilist_sdk::models::MediaSeason;
+//! use anilist_sdk::utils::seasons::{SeasonLabel, next_season, previous_season};
+//!
+//! let fall = (MediaSeason::Fall, 2023);
+//! assert_eq!(fall.label(), "Fall 2023");
+//! assert_eq!(next_season(fall), (MediaSeason::Winter, 2024));
+//! assert_eq!(previous_season((MediaSeason::Winter, 2024)), fall);
+//! ```
+
+use crate::error::AniListError;
+use crate::models::{Anime, MediaFormat, MediaSeason};
+use chrono::{Datelike, Days, NaiveDate};
+
+/// A season of a particular year, e.g. `(MediaSeason::Fall, 2023)`
+pub type SeasonYear = (MediaSeason, i32);
+
+/// First year of AniList's seasonal archive
+pub const ARCHIVE_START_YEAR: i32 = 1940;
+
+/// All seasons in calendar order
+pub const SEASONS: [MediaSeason; 4] = [
+    MediaSeason::Winter,
+    MediaSeason::Spring,
+    MediaSeason::Summer,
+    MediaSeason::Fall,
+];
+
+/// Human-readable labels for `(season, year)` pairs.
+pub trait SeasonLabel {
+    /// The season and year as shown in archive navigation, e.g. `"Fall 2023"`.
+    fn label(&self) -> String;
+}
+
+impl SeasonLabel for SeasonYear {
+    fn label(&self) -> String {
+        let season = match self.0 {
+            MediaSeason::Winter => "Winter",
+            MediaSeason::Spring => "Spring",
+            MediaSeason::Summer => "Summer",
+            MediaSeason::Fall => "Fall",
+        };
+        format!("{season} {}", self.1)
+    }
+}
+
+/// The season a calendar month (1–12) falls in.
+pub fn season_for_month(month: u32) -> MediaSeason {
+    match month {
+        1..=3 => MediaSeason::Winter,
+        4..=6 => MediaSeason::Spring,
+        7..=9 => MediaSeason::Summer,
+        _ => MediaSeason::Fall,
+    }
+}
+
+/// The season airing on `date`. Winter starts in January, so December 31st
+/// is still Fall of that year and January 1st is Winter of the new one.
+///
+/// # Examples
+///
+/// ```rust
+/// use anilist_sdk::models::MediaSeason;
+/// use anilist_sdk::utils::seasons::{next_season, season_for_date};
+/// use chrono::NaiveDate;
+///
+/// let new_years_eve = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+/// assert_eq!(season_for_date(new_years_eve), (MediaSeason::Fall, 2024));
+/// assert_eq!(next_season(season_for_date(new_years_eve)), (MediaSeason::Winter, 2025));
+/// ```
+pub fn season_for_date(date: NaiveDate) -> SeasonYear {
+    (season_for_month(date.month()), date.year())
+}
+
+/// The season airing right now, in UTC.
+pub fn current_season() -> SeasonYear {
+    season_for_date(chrono::Utc::now().date_naive())
+}
+
+/// The season before `season`, wrapping Winter back to the previous year's Fall.
+pub fn previous_season((season, year): SeasonYear) -> SeasonYear {
+    match season {
+        MediaSeason::Winter => (MediaSeason::Fall, year - 1),
+        MediaSeason::Spring => (MediaSeason::Winter, year),
+        MediaSeason::Summer => (MediaSeason::Spring, year),
+        MediaSeason::Fall => (MediaSeason::Summer, year),
+    }
+}
+
+/// The season after `season`, wrapping Fall forward to the next year's Winter.
+pub fn next_season((season, year): SeasonYear) -> SeasonYear {
+    match season {
+        MediaSeason::Winter => (MediaSeason::Spring, year),
+        MediaSeason::Spring => (MediaSeason::Summer, year),
+        MediaSeason::Summer => (MediaSeason::Fall, year),
+        MediaSeason::Fall => (MediaSeason::Winter, year + 1),
+    }
+}
+
+/// Every season from Winter 1940 through Fall of next year, oldest first.
+pub fn archive_range() -> Vec<SeasonYear> {
+    archive_range_until(current_season().1 + 1)
+}
+
+/// Every season from Winter 1940 through Fall of `last_year`, oldest first.
+pub fn archive_range_until(last_year: i32) -> Vec<SeasonYear> {
+    (ARCHIVE_START_YEAR..=last_year)
+        .flat_map(|year| SEASONS.into_iter().map(move |season| (season, year)))
+        .collect()
+}
+
+/// The seasons before and after `season` within an archive ending in Fall of
+/// `last_year`; `None` at either end of the archive.
+pub fn neighbors_within(
+    season: SeasonYear,
+    last_year: i32,
+) -> (Option<SeasonYear>, Option<SeasonYear>) {
+    let previous = previous_season(season);
+    let next = next_season(season);
+    (
+        (previous.1 >= ARCHIVE_START_YEAR).then_some(previous),
+        (next.1 <= last_year).then_some(next),
+    )
+}
+
+/// The first day of a season, e.g. October 1st for Fall.
+pub fn season_start((season, year): SeasonYear) -> NaiveDate {
+    let month = season_index(season) as u32 * 3 + 1;
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(NaiveDate::MIN)
+}
+
+/// A date as AniList's `FuzzyDateInt`, e.g. 2023-10-07 becomes `20231007`.
+///
+/// `None` for years too far out to fit an `i32` in that form.
+pub fn fuzzy_date_int(date: NaiveDate) -> Option<i32> {
+    date.year()
+        .checked_mul(10_000)?
+        .checked_add(date.month() as i32 * 100 + date.day() as i32)
+}
+
+/// Exclusive `(startDate_greater, startDate_lesser)` bounds covering `today`
+/// through `today + days`, both inclusive.
+///
+/// Titles whose start date only has a year and month are stored as
+/// `YYYYMM00`, so they fall in the window only when the window reaches past
+/// the first of that month.
+///
+/// ```rust
+/// use anilist_sdk::utils::seasons::premiere_window;
+/// use chrono::NaiveDate;
+///
+/// let today = NaiveDate::from_ymd_opt(2023, 12, 20).unwrap();
+/// assert_eq!(premiere_window(today, 30)?, (20231219, 20240120));
+/// # Ok::<(), anilist_sdk::AniListError>(())
+/// ```
+///
+/// # Errors
+///
+/// Fails with [`AniListError::InvalidInput`] if the window ends past the
+/// dates a `FuzzyDateInt` can hold.
+pub fn premiere_window(today: NaiveDate, days: u32) -> Result<(i32, i32), AniListError> {
+    let first = today.pred_opt().unwrap_or(today);
+    let bounds = today
+        .checked_add_days(Days::new(u64::from(days) + 1))
+        .and_then(|last| Some((fuzzy_date_int(first)?, fuzzy_date_int(last)?)));
+    bounds.ok_or_else(|| AniListError::InvalidInput {
+        field: "days".to_string(),
+        message: format!("a window of {days} days from {today} is out of range"),
+    })
+}
+
+/// How an entry relates to the season chart it is listed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeasonEntryKind {
+    /// A series that started this season
+    New,
+    /// A series continuing from an earlier season
+    Leftover,
+    /// A movie, special, OVA or music video
+    MovieSpecial,
+}
+
+/// Position of a season in the calendar, Winter first.
+fn season_index(season: MediaSeason) -> usize {
+    SEASONS.iter().position(|s| *s == season).unwrap_or(0)
+}
+
+/// The season an entry started in, as `(year, season index)`.
+///
+/// AniList's own `season`/`seasonYear` take precedence over the start date,
+/// so a Fall show that premiered in late September still counts as Fall. A
+/// start date with only a year sorts as that year's Fall, so it only counts
+/// as earlier when the year itself is earlier.
+fn started_in(anime: &Anime) -> Option<(i32, usize)> {
+    if let (Some(season), Some(year)) = (anime.season, anime.season_year) {
+        return Some((year, season_index(season)));
+    }
+    let start = anime.start_date.as_ref()?;
+    let year = start.year?;
+    let season = match start.month {
+        Some(month) => season_for_month(month as u32),
+        None => MediaSeason::Fall,
+    };
+    Some((year, season_index(season)))
+}
+
+/// Classifies an entry of the `(season, year)` chart.
+///
+/// Movies, specials, OVAs and music videos are [`SeasonEntryKind::MovieSpecial`]
+/// regardless of when they started. A series is a
+/// [`SeasonEntryKind::Leftover`] when it started in an earlier season,
+/// including split-cour shows whose single entry resumes after a gap, and
+/// [`SeasonEntryKind::New`] otherwise; entries without any start information
+/// are assumed to be new. No request is made.
+pub fn classify_season_entry(anime: &Anime, season: SeasonYear) -> SeasonEntryKind {
+    if matches!(
+        anime.format,
+        Some(MediaFormat::Movie | MediaFormat::Special | MediaFormat::Ova | MediaFormat::Music)
+    ) {
+        return SeasonEntryKind::MovieSpecial;
+    }
+    match started_in(anime) {
+        Some(started) if started < (season.1, season_index(season.0)) => SeasonEntryKind::Leftover,
+        _ => SeasonEntryKind::New,
+    }
+}
@@ -81,3 +81,311 @@ async fn test_get_activity_replies() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_get_activity_likers() {
+    let client = AniListClient::new();
+    let result = crate::activity_api_call!(client, get_likers, 1, 1, 5);
+
+    // The activity might not exist, so we only check the shape of successful results
+    if let Ok(likers) = result {
+        assert!(likers.items.len() <= 5);
+        for user in &likers.items {
+            assert!(user.id > 0);
+        }
+    }
+}
+
+#[test]
+fn test_likers_fixture_deserializes() {
+    use anilist_sdk::models::{PageInfo, UserPreview};
+
+    let fixture = serde_json::json!({
+        "pageInfo": {
+            "total": 2,
+            "perPage": 25,
+            "currentPage": 1,
+            "lastPage": 1,
+            "hasNextPage": false
+        },
+        "likes": [
+            { "id": 1, "name": "Josh", "avatar": { "large": "https://s4.anilist.co/l.png", "medium": null } },
+            { "id": 2, "name": "Mirai", "avatar": null }
+        ]
+    });
+
+    let page_info: PageInfo = serde_json::from_value(fixture["pageInfo"].clone()).unwrap();
+    let likers: Vec<UserPreview> = serde_json::from_value(fixture["likes"].clone()).unwrap();
+
+    assert_eq!(page_info.has_next_page, Some(false));
+    assert_eq!(page_info.total, Some(2));
+    assert_eq!(likers.len(), 2);
+    assert_eq!(likers[0].name, "Josh");
+    assert!(likers[1].avatar.is_none());
+}
+
+#[test]
+fn test_feed_filter_excludes_adult_list_activities() {
+    use anilist_sdk::models::{Activity, ActivityFeedFilter};
+
+    let list_activity = |id: i32, is_adult: Option<bool>| {
+        serde_json::json!({
+            "id": id,
+            "userId": 1,
+            "type": "ANIME_LIST",
+            "replyCount": 0,
+            "likeCount": 0,
+            "createdAt": 1_700_000_000,
+            "media": { "id": id * 10, "type": "ANIME", "isAdult": is_adult }
+        })
+    };
+    let feed = serde_json::json!([
+        list_activity(1, Some(false)),
+        list_activity(2, Some(true)),
+        { "id": 3, "userId": 1, "type": "TEXT", "replyCount": 0, "likeCount": 0, "createdAt": 1_700_000_000 },
+        list_activity(4, None),
+    ]);
+    let activities: Vec<Activity> = serde_json::from_value(feed).unwrap();
+
+    assert!(activities[1].is_adult());
+    assert!(!activities[2].is_adult());
+
+    let unfiltered = ActivityFeedFilter::new().apply(activities.clone());
+    assert_eq!(unfiltered.len(), 4);
+
+    let filtered = ActivityFeedFilter::new()
+        .exclude_adult(true)
+        .apply(activities);
+    let ids: Vec<i32> = filtered.iter().map(|a| a.id).collect();
+    assert_eq!(ids, vec![1, 3, 4]);
+}
+
+#[tokio::test]
+async fn test_get_recent_activities_without_messages() {
+    use anilist_sdk::models::{ActivityFeedFilter, ActivityType};
+
+    let client = AniListClient::new();
+    let filter = ActivityFeedFilter::new().types(&[ActivityType::Text, ActivityType::AnimeList]);
+    test_utils::rate_limit().await;
+    let result = client
+        .activity()
+        .get_recent_activities_filtered(1, 10, &filter)
+        .await;
+
+    if let Ok(activities) = result {
+        for activity in &activities {
+            assert!(matches!(
+                activity.activity_type,
+                Some(ActivityType::Text | ActivityType::AnimeList)
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_feed_filter_types() {
+    use anilist_sdk::models::{Activity, ActivityFeedFilter, ActivityType};
+
+    // `types` is sent as the `type_in` variable, so it must use the API's names
+    assert_eq!(
+        serde_json::to_value([
+            ActivityType::Text,
+            ActivityType::AnimeList,
+            ActivityType::MangaList,
+            ActivityType::Message,
+            ActivityType::MediaList,
+        ])
+        .unwrap(),
+        serde_json::json!(["TEXT", "ANIME_LIST", "MANGA_LIST", "MESSAGE", "MEDIA_LIST"])
+    );
+
+    let activity = |id: i32, activity_type: &str| {
+        serde_json::json!({
+            "id": id,
+            "type": activity_type,
+            "replyCount": 0,
+            "likeCount": 0,
+            "createdAt": 1_700_000_000
+        })
+    };
+    let feed: Vec<Activity> = serde_json::from_value(serde_json::json!([
+        activity(1, "TEXT"),
+        activity(2, "MESSAGE"),
+        activity(3, "ANIME_LIST"),
+        activity(4, "MANGA_LIST"),
+    ]))
+    .unwrap();
+
+    // Default filter keeps current behaviour
+    assert_eq!(ActivityFeedFilter::default().apply(feed.clone()).len(), 4);
+
+    let text_and_lists = ActivityFeedFilter::new().types(&[
+        ActivityType::Text,
+        ActivityType::AnimeList,
+        ActivityType::MangaList,
+    ]);
+    let ids: Vec<i32> = text_and_lists.apply(feed).iter().map(|a| a.id).collect();
+    assert_eq!(ids, vec![1, 3, 4]);
+}
+
+#[tokio::test]
+async fn test_get_activity_detail() {
+    let client = AniListClient::new();
+    let result = crate::activity_api_call!(client, get_activity_detail, 1);
+
+    // The activity might not exist, so we only check successful results
+    if let Ok(activity) = result {
+        assert_eq!(activity.id(), 1);
+    }
+}
+
+#[test]
+fn test_activity_union_fixtures_deserialize() {
+    use anilist_sdk::models::ActivityUnion;
+
+    let text = serde_json::json!({
+        "__typename": "TextActivity",
+        "id": 1, "userId": 2, "text": "Hello", "replyCount": 0, "likeCount": 3,
+        "createdAt": 1_700_000_000, "siteUrl": "https://anilist.co/activity/1",
+        "user": { "id": 2, "name": "Josh", "avatar": null }
+    });
+    let list = serde_json::json!({
+        "__typename": "ListActivity",
+        "id": 2, "userId": 2, "type": "ANIME_LIST", "status": "watched episode",
+        "progress": "5", "replyCount": 1, "likeCount": 0, "createdAt": 1_700_000_000,
+        "siteUrl": "https://anilist.co/activity/2",
+        "media": { "id": 16498, "type": "ANIME", "title": { "userPreferred": "Shingeki no Kyojin" }, "isAdult": false }
+    });
+    let message = serde_json::json!({
+        "__typename": "MessageActivity",
+        "id": 3, "recipientId": 2, "messengerId": 4, "type": "MESSAGE", "message": "Hi!",
+        "replyCount": 0, "likeCount": 0, "isPrivate": false, "createdAt": 1_700_000_000,
+        "siteUrl": "https://anilist.co/activity/3",
+        "messenger": { "id": 4, "name": "Mirai", "avatar": null }
+    });
+
+    let ActivityUnion::Text(text) = serde_json::from_value(text).unwrap() else {
+        panic!("expected a text activity");
+    };
+    assert_eq!(text.text, "Hello");
+
+    let list: ActivityUnion = serde_json::from_value(list).unwrap();
+    assert_eq!(list.id(), 2);
+    let ActivityUnion::List(list) = list else {
+        panic!("expected a list activity");
+    };
+    assert_eq!(list.media.unwrap().id, 16498);
+
+    let message: ActivityUnion = serde_json::from_value(message).unwrap();
+    assert_eq!(
+        message.author().map(|user| user.name.as_str()),
+        Some("Mirai")
+    );
+    let ActivityUnion::Message(message) = message else {
+        panic!("expected a message activity");
+    };
+    assert_eq!(message.message.as_deref(), Some("Hi!"));
+}
+
+#[tokio::test]
+async fn test_subscribed_activities_require_token() {
+    use anilist_sdk::error::AniListError;
+
+    let client = AniListClient::new();
+    let result = client.activity().get_subscribed_activities(1, 10).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+}
+
+#[test]
+fn test_subscribed_activities_fixture() {
+    use anilist_sdk::models::{Activity, PageInfo};
+
+    let fixture = serde_json::json!({
+        "pageInfo": { "total": 3, "perPage": 10, "currentPage": 1, "lastPage": 1, "hasNextPage": false },
+        "activities": [
+            {
+                "id": 10, "userId": 1, "type": "TEXT", "replyCount": 4, "likeCount": 2,
+                "isSubscribed": true, "createdAt": 1700000000,
+                "user": { "id": 1, "name": "Josh" }
+            },
+            {
+                "id": 11, "userId": 2, "type": "ANIME_LIST", "replyCount": 1, "likeCount": 0,
+                "isSubscribed": true, "createdAt": 1700000100,
+                "media": { "id": 21, "type": "ANIME", "isAdult": false, "title": { "userPreferred": "ONE PIECE" } }
+            },
+            {
+                "id": 12, "type": "MESSAGE", "replyCount": 0, "likeCount": 0,
+                "isSubscribed": true, "createdAt": 1700000200
+            }
+        ]
+    });
+
+    let page_info: PageInfo = serde_json::from_value(fixture["pageInfo"].clone()).unwrap();
+    let activities: Vec<Activity> = serde_json::from_value(fixture["activities"].clone()).unwrap();
+
+    assert_eq!(page_info.total, Some(3));
+    assert_eq!(activities.len(), 3);
+    assert!(activities.iter().all(|a| a.is_subscribed == Some(true)));
+    // Message activities carry no author in this selection
+    assert!(activities[2].user.is_none());
+
+    // Unsubscribing returns the activity with the flag cleared
+    let toggled: Activity = serde_json::from_value(serde_json::json!({
+        "id": 10, "userId": 1, "type": "TEXT", "replyCount": 4, "likeCount": 2,
+        "isSubscribed": false, "createdAt": 1700000000
+    }))
+    .unwrap();
+    assert_eq!(toggled.is_subscribed, Some(false));
+}
+
+#[tokio::test]
+async fn test_latest_list_activity_and_pin_require_token() {
+    use anilist_sdk::error::AniListError;
+
+    let client = AniListClient::new();
+    let result = client.activity().get_my_latest_list_activity(21).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+    let result = client.activity().toggle_activity_pin(1, true).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+}
+
+#[test]
+fn test_latest_list_activity_fixture() {
+    use anilist_sdk::models::ListActivity;
+
+    let page = serde_json::json!({
+        "activities": [{
+            "id": 700, "userId": 5, "type": "ANIME_LIST", "status": "watched episode",
+            "progress": "1099 - 1100", "replyCount": 0, "likeCount": 1, "isLiked": false,
+            "isPinned": false, "siteUrl": "https://anilist.co/activity/700",
+            "createdAt": 1_700_000_000,
+            "media": { "id": 21, "type": "ANIME", "title": { "userPreferred": "ONE PIECE" }, "isAdult": false }
+        }]
+    });
+    let activities: Vec<ListActivity> = serde_json::from_value(page["activities"].clone()).unwrap();
+    let latest = activities.into_iter().next().unwrap();
+    assert_eq!(latest.progress.as_deref(), Some("1099 - 1100"));
+    assert_eq!(latest.media.unwrap().id, 21);
+    assert_eq!(latest.site_url, "https://anilist.co/activity/700");
+
+    // No list activity for the media: an empty page
+    let empty: Vec<ListActivity> = serde_json::from_value(serde_json::json!([])).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_pinned_activity_fixture() {
+    use anilist_sdk::models::ActivityUnion;
+
+    let pinned: ActivityUnion = serde_json::from_value(serde_json::json!({
+        "__typename": "TextActivity",
+        "id": 9, "userId": 5, "text": "Welcome to my profile", "replyCount": 0,
+        "likeCount": 0, "isPinned": true, "siteUrl": "https://anilist.co/activity/9",
+        "createdAt": 1_700_000_000
+    }))
+    .unwrap();
+    let ActivityUnion::Text(pinned) = pinned else {
+        panic!("expected a text activity");
+    };
+    assert_eq!(pinned.is_pinned, Some(true));
+}
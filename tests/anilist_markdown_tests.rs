@@ -0,0 +1,270 @@
+use anilist_sdk::utils::anilist_markdown::{ImageWidth, MAX_DEPTH, MarkdownNode, parse};
+
+fn text(s: &str) -> MarkdownNode {
+    MarkdownNode::Text(s.to_string())
+}
+
+fn image(width: Option<ImageWidth>, url: &str) -> MarkdownNode {
+    MarkdownNode::Image {
+        width,
+        url: url.to_string(),
+    }
+}
+
+#[test]
+fn test_plain_markdown_is_kept_as_text() {
+    let source = "# Hi there\n\n**Watching:** _Frieren_ & more";
+    assert_eq!(parse(source), vec![text(source)]);
+    assert!(parse("").is_empty());
+}
+
+#[test]
+fn test_image_sizes() {
+    assert_eq!(
+        parse("img(https://i.imgur.com/a.png)"),
+        vec![image(None, "https://i.imgur.com/a.png")]
+    );
+    assert_eq!(
+        parse("img220(https://i.imgur.com/a.png)"),
+        vec![image(
+            Some(ImageWidth::Pixels(220)),
+            "https://i.imgur.com/a.png"
+        )]
+    );
+    assert_eq!(
+        parse("img40%(https://i.imgur.com/a.png)"),
+        vec![image(
+            Some(ImageWidth::Percent(40)),
+            "https://i.imgur.com/a.png"
+        )]
+    );
+    // Tags are case-insensitive and the URL is trimmed
+    assert_eq!(
+        parse("IMG350( https://i.imgur.com/a.gif )"),
+        vec![image(
+            Some(ImageWidth::Pixels(350)),
+            "https://i.imgur.com/a.gif"
+        )]
+    );
+    // Markdown images carry no width
+    assert_eq!(
+        parse("![banner](https://i.imgur.com/b.jpg)"),
+        vec![image(None, "https://i.imgur.com/b.jpg")]
+    );
+}
+
+#[test]
+fn test_embeds_only_start_words() {
+    assert_eq!(parse("ximg(a.png)"), vec![text("ximg(a.png)")]);
+    assert_eq!(parse("img no parens"), vec![text("img no parens")]);
+    assert_eq!(parse("img(unclosed"), vec![text("img(unclosed")]);
+}
+
+#[test]
+fn test_youtube_id_forms() {
+    let forms = [
+        "youtube(dQw4w9WgXcQ)",
+        "youtube(https://www.youtube.com/watch?v=dQw4w9WgXcQ)",
+        "youtube(https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42s)",
+        "youtube(https://youtu.be/dQw4w9WgXcQ?si=abc)",
+        "youtube(https://www.youtube.com/embed/dQw4w9WgXcQ)",
+        "youtube(https://youtube.com/shorts/dQw4w9WgXcQ)",
+    ];
+    for form in forms {
+        assert_eq!(
+            parse(form),
+            vec![MarkdownNode::YouTube {
+                id: "dQw4w9WgXcQ".to_string()
+            }],
+            "{form}"
+        );
+    }
+}
+
+#[test]
+fn test_webm() {
+    assert_eq!(
+        parse("webm(https://files.catbox.moe/op.webm)"),
+        vec![MarkdownNode::Video {
+            url: "https://files.catbox.moe/op.webm".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_center_forms() {
+    let expected = vec![MarkdownNode::Centered(vec![text("hello")])];
+    assert_eq!(parse("~~~hello~~~"), expected);
+    assert_eq!(parse("<center>hello</center>"), expected);
+    assert_eq!(parse("<CENTER>hello</Center>"), expected);
+}
+
+#[test]
+fn test_spoilers() {
+    assert_eq!(
+        parse("Ending: ~!they win!~ :)"),
+        vec![
+            text("Ending: "),
+            MarkdownNode::Spoiler(vec![text("they win")]),
+            text(" :)"),
+        ]
+    );
+    assert!(parse("~!x!~")[0].contains_spoiler());
+    assert!(!parse("~~~x~~~")[0].contains_spoiler());
+}
+
+#[test]
+fn test_unterminated_constructs_stay_text() {
+    assert_eq!(parse("~!no end"), vec![text("~!no end")]);
+    assert_eq!(parse("~~~no end"), vec![text("~~~no end")]);
+    assert_eq!(parse("<center>no end"), vec![text("<center>no end")]);
+    assert_eq!(parse("[label] (url)"), vec![text("[label] (url)")]);
+    assert_eq!(parse("[a\nb](url)"), vec![text("[a\nb](url)")]);
+}
+
+#[test]
+fn test_links() {
+    assert_eq!(
+        parse("[My list](https://anilist.co/user/Josh/animelist)"),
+        vec![MarkdownNode::Link {
+            url: "https://anilist.co/user/Josh/animelist".to_string(),
+            children: vec![text("My list")],
+        }]
+    );
+}
+
+#[test]
+fn test_profile_banner_with_image_links() {
+    // A common About-section header: centered banner and image links
+    let source = "~~~img(https://i.imgur.com/banner.gif)\n\
+                  [img40(https://i.imgur.com/mal.png)](https://myanimelist.net/profile/x) \
+                  [img40(https://i.imgur.com/tw.png)](https://twitter.com/x)~~~\n\
+                  Hi! I mostly watch seinen.";
+
+    let nodes = parse(source);
+    assert_eq!(
+        nodes,
+        vec![
+            MarkdownNode::Centered(vec![
+                image(None, "https://i.imgur.com/banner.gif"),
+                text("\n"),
+                MarkdownNode::Link {
+                    url: "https://myanimelist.net/profile/x".to_string(),
+                    children: vec![image(
+                        Some(ImageWidth::Pixels(40)),
+                        "https://i.imgur.com/mal.png"
+                    )],
+                },
+                text(" "),
+                MarkdownNode::Link {
+                    url: "https://twitter.com/x".to_string(),
+                    children: vec![image(
+                        Some(ImageWidth::Pixels(40)),
+                        "https://i.imgur.com/tw.png"
+                    )],
+                },
+            ]),
+            text("\nHi! I mostly watch seinen."),
+        ]
+    );
+}
+
+#[test]
+fn test_activity_with_spoiler_inside_center() {
+    let source = "Finally finished it!\n~~~~!img220(https://i.imgur.com/last.png)!~~~~";
+
+    assert_eq!(
+        parse(source),
+        vec![
+            text("Finally finished it!\n"),
+            MarkdownNode::Centered(vec![MarkdownNode::Spoiler(vec![image(
+                Some(ImageWidth::Pixels(220)),
+                "https://i.imgur.com/last.png"
+            )])]),
+        ]
+    );
+    assert!(parse(source)[1].contains_spoiler());
+}
+
+#[test]
+fn test_mixed_about_section() {
+    let source = "<center>## Favourite OPs\nyoutube(https://youtu.be/abc123)\n\
+                  webm(https://files.catbox.moe/ed.webm)</center>\n\
+                  ~!Spoiler: [thread](https://anilist.co/forum/thread/1)!~";
+
+    assert_eq!(
+        parse(source),
+        vec![
+            MarkdownNode::Centered(vec![
+                text("## Favourite OPs\n"),
+                MarkdownNode::YouTube {
+                    id: "abc123".to_string()
+                },
+                text("\n"),
+                MarkdownNode::Video {
+                    url: "https://files.catbox.moe/ed.webm".to_string()
+                },
+            ]),
+            text("\n"),
+            MarkdownNode::Spoiler(vec![
+                text("Spoiler: "),
+                MarkdownNode::Link {
+                    url: "https://anilist.co/forum/thread/1".to_string(),
+                    children: vec![text("thread")],
+                },
+            ]),
+        ]
+    );
+}
+
+#[test]
+fn test_unicode_text() {
+    assert_eq!(
+        parse("ありがとう ~!ネタバレ!~ 🎉"),
+        vec![
+            text("ありがとう "),
+            MarkdownNode::Spoiler(vec![text("ネタバレ")]),
+            text(" 🎉"),
+        ]
+    );
+}
+
+#[test]
+fn test_deep_nesting_is_capped() {
+    fn depth(nodes: &[MarkdownNode]) -> usize {
+        nodes
+            .iter()
+            .map(|node| match node {
+                MarkdownNode::Spoiler(children)
+                | MarkdownNode::Centered(children)
+                | MarkdownNode::Link { children, .. } => 1 + depth(children),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    let levels = 10_000;
+    let spoilers = format!("{}bio{}", "~!".repeat(levels), "!~".repeat(levels));
+    assert_eq!(depth(&parse(&spoilers)), MAX_DEPTH);
+
+    let links = format!("{}x{}", "[".repeat(levels), "](u)".repeat(levels));
+    assert_eq!(depth(&parse(&links)), MAX_DEPTH);
+
+    // The opener past the limit stays as text, and so does its closer
+    let source = format!(
+        "{}bio{}",
+        "~!".repeat(MAX_DEPTH + 1),
+        "!~".repeat(MAX_DEPTH + 1)
+    );
+    let mut nodes = parse(&source);
+    assert_eq!(nodes.last(), Some(&text("!~")));
+    nodes.pop();
+    for _ in 0..MAX_DEPTH {
+        let [MarkdownNode::Spoiler(children)] = nodes.as_slice() else {
+            panic!("expected a single spoiler, got {nodes:?}");
+        };
+        nodes = children.clone();
+    }
+    assert_eq!(nodes, vec![text("~!bio")]);
+}
@@ -1,4 +1,5 @@
 use anilist_sdk::client::AniListClient;
+use anilist_sdk::models::MediaTrailer;
 
 mod test_utils;
 
@@ -15,7 +16,7 @@ async fn test_get_popular_anime() {
     // Check that all anime have required fields
     for anime in &anime_list {
         assert!(anime.id > 0);
-        assert!(anime.title.is_some());
+        assert!(anime.title.romaji.is_some());
     }
 }
 
@@ -39,7 +40,19 @@ async fn test_get_anime_by_id() {
         crate::anime_api_call!(client, get_by_id, 16498).expect("Failed to get anime by ID");
 
     assert_eq!(anime.id, 16498);
-    assert!(anime.title.is_some());
+    assert!(anime.title.romaji.is_some());
+}
+
+#[tokio::test]
+async fn test_get_anime_by_mal_id() {
+    let client = AniListClient::new();
+
+    // Attack on Titan has the same ID on both sites
+    let anime = crate::anime_api_call!(client, get_by_mal_id, 16498)
+        .expect("Failed to get anime by MAL ID");
+
+    assert_eq!(anime.id, 16498);
+    assert_eq!(anime.id_mal, Some(16498));
 }
 
 #[tokio::test]
@@ -53,18 +66,15 @@ async fn test_search_anime() {
 
     // Check that results contain "Naruto" in some form
     let has_naruto = anime_list.iter().any(|anime| {
-        if let Some(title) = &anime.title {
-            title
-                .romaji
+        let title = &anime.title;
+        title
+            .romaji
+            .as_ref()
+            .is_some_and(|t| t.to_lowercase().contains("naruto"))
+            || title
+                .english
                 .as_ref()
                 .is_some_and(|t| t.to_lowercase().contains("naruto"))
-                || title
-                    .english
-                    .as_ref()
-                    .is_some_and(|t| t.to_lowercase().contains("naruto"))
-        } else {
-            false
-        }
     });
     assert!(has_naruto);
 }
@@ -105,6 +115,53 @@ async fn test_get_top_rated_anime() {
     }
 }
 
+#[tokio::test]
+async fn test_get_top_rated_anime_with_min_popularity() {
+    let client = AniListClient::new();
+    let min_popularity = 50_000;
+
+    let anime_list = crate::anime_api_call!(
+        client,
+        get_top_rated_with_min_popularity,
+        1,
+        25,
+        Some(min_popularity)
+    )
+    .expect("Failed to get top rated anime with a popularity threshold");
+
+    assert!(!anime_list.is_empty());
+    // Tiny-sample outliers fall below the threshold and must be filtered out
+    for anime in &anime_list {
+        let popularity = anime.popularity.expect("popularity should be selected");
+        assert!(popularity >= min_popularity);
+    }
+}
+
+#[tokio::test]
+async fn test_get_anime_by_genre() {
+    use anilist_sdk::models::MediaSort;
+
+    let client = AniListClient::new();
+
+    let anime_list = crate::anime_api_call!(
+        client,
+        get_by_genre_excluding,
+        &["Action"],
+        &["Comedy"],
+        MediaSort::ScoreDesc,
+        1,
+        10
+    )
+    .expect("Failed to get anime by genre");
+
+    assert!(!anime_list.is_empty());
+    for anime in &anime_list {
+        let genres = anime.genres.as_ref().expect("genres should be selected");
+        assert!(genres.iter().any(|genre| genre == "Action"), "{genres:?}");
+        assert!(!genres.iter().any(|genre| genre == "Comedy"), "{genres:?}");
+    }
+}
+
 #[tokio::test]
 async fn test_get_airing_anime() {
     let client = AniListClient::new();
@@ -118,3 +175,385 @@ async fn test_get_airing_anime() {
         // Airing anime should have status RELEASING (though this might not always be set)
     }
 }
+
+#[test]
+fn test_media_format_round_trips_every_api_value() {
+    use anilist_sdk::models::MediaFormat;
+
+    let known = [
+        (MediaFormat::Tv, "TV"),
+        (MediaFormat::TvShort, "TV_SHORT"),
+        (MediaFormat::Movie, "MOVIE"),
+        (MediaFormat::Special, "SPECIAL"),
+        (MediaFormat::Ova, "OVA"),
+        (MediaFormat::Ona, "ONA"),
+        (MediaFormat::Music, "MUSIC"),
+        (MediaFormat::Manga, "MANGA"),
+        (MediaFormat::Novel, "NOVEL"),
+        (MediaFormat::OneShot, "ONE_SHOT"),
+    ];
+
+    for (format, api_value) in known {
+        let serialized = serde_json::to_value(format).unwrap();
+        assert_eq!(serialized, serde_json::json!(api_value));
+
+        let deserialized: MediaFormat = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, format);
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_social_media_format_is_canonical_alias() {
+    use anilist_sdk::models::MediaFormat;
+    use anilist_sdk::models::social::{MediaFormat as SocialMediaFormat, RecommendationMedia};
+
+    let social: SocialMediaFormat = MediaFormat::TvShort;
+    assert_eq!(social, MediaFormat::TvShort);
+
+    let media: RecommendationMedia = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "title": null,
+        "coverImage": null,
+        "format": "ONE_SHOT",
+        "averageScore": 70
+    }))
+    .unwrap();
+    assert_eq!(media.format, Some(MediaFormat::OneShot));
+}
+
+#[tokio::test]
+async fn test_autocomplete() {
+    let client = AniListClient::new();
+    let suggestions = crate::anime_api_call!(client, autocomplete, "naruto", 5)
+        .expect("Failed to get autocomplete suggestions");
+
+    assert!(!suggestions.is_empty());
+    assert!(suggestions.len() <= 5);
+    for suggestion in &suggestions {
+        assert!(suggestion.id > 0);
+        assert!(suggestion.display_title().is_some());
+    }
+
+    // A repeated search is served from the cache without another request
+    let cached = client.anime().autocomplete("Naruto ", 5).await.unwrap();
+    let ids: Vec<i32> = cached.iter().map(|s| s.id).collect();
+    assert_eq!(ids, suggestions.iter().map(|s| s.id).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_autocomplete_query_is_slim() {
+    let query = anilist_sdk::queries::anime::AUTOCOMPLETE;
+
+    assert!(query.contains("userPreferred"));
+    for field in ["romaji", "english", "native", "description", "extraLarge"] {
+        assert!(
+            !query.contains(field),
+            "autocomplete should not select {field}"
+        );
+    }
+}
+
+#[test]
+fn test_media_suggestion_fixture_deserializes() {
+    use anilist_sdk::models::{MediaFormat, MediaSuggestion};
+
+    let media = serde_json::json!([
+        {
+            "id": 154587,
+            "type": "ANIME",
+            "title": { "userPreferred": "Sousou no Frieren" },
+            "format": "TV",
+            "seasonYear": 2023,
+            "coverImage": { "medium": "https://s4.anilist.co/file/anilistcdn/media/anime/cover/small/bx154587.jpg" }
+        },
+        {
+            "id": 118586,
+            "type": "MANGA",
+            "title": { "userPreferred": "葬送のフリーレン" },
+            "format": "MANGA",
+            "seasonYear": null,
+            "coverImage": null
+        }
+    ]);
+
+    let suggestions: Vec<MediaSuggestion> = serde_json::from_value(media).unwrap();
+    assert_eq!(suggestions[0].display_title(), Some("Sousou no Frieren"));
+    assert_eq!(suggestions[0].format, Some(MediaFormat::Tv));
+    assert_eq!(suggestions[0].season_year, Some(2023));
+    assert_eq!(suggestions[1].display_title(), Some("葬送のフリーレン"));
+    assert!(suggestions[1].cover_image.is_none());
+}
+
+fn trailer(id: Option<&str>, site: Option<&str>, thumbnail: Option<&str>) -> MediaTrailer {
+    MediaTrailer {
+        id: id.map(str::to_string),
+        site: site.map(str::to_string),
+        thumbnail: thumbnail.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_trailer_urls_youtube() {
+    let youtube = trailer(Some("LHtdKWJdif4"), Some("youtube"), None);
+    assert_eq!(
+        youtube.url().as_deref(),
+        Some("https://www.youtube.com/watch?v=LHtdKWJdif4")
+    );
+    assert_eq!(
+        youtube.embed_url().as_deref(),
+        Some("https://www.youtube.com/embed/LHtdKWJdif4")
+    );
+    assert_eq!(
+        youtube.thumbnail_or_default().as_deref(),
+        Some("https://i.ytimg.com/vi/LHtdKWJdif4/hqdefault.jpg")
+    );
+}
+
+#[test]
+fn test_trailer_urls_dailymotion() {
+    let dailymotion = trailer(
+        Some("x7tgad0"),
+        Some("dailymotion"),
+        Some("https://s2.dmcdn.net/thumb.jpg"),
+    );
+    assert_eq!(
+        dailymotion.url().as_deref(),
+        Some("https://www.dailymotion.com/video/x7tgad0")
+    );
+    assert_eq!(
+        dailymotion.embed_url().as_deref(),
+        Some("https://www.dailymotion.com/embed/video/x7tgad0")
+    );
+    // AniList's own thumbnail wins over the default
+    assert_eq!(
+        dailymotion.thumbnail_or_default().as_deref(),
+        Some("https://s2.dmcdn.net/thumb.jpg")
+    );
+}
+
+#[test]
+fn test_trailer_urls_unknown_values() {
+    let unknown_site = trailer(Some("abc"), Some("vimeo"), None);
+    assert_eq!(unknown_site.url(), None);
+    assert_eq!(unknown_site.embed_url(), None);
+    assert_eq!(unknown_site.thumbnail_or_default(), None);
+
+    let missing_id = trailer(None, Some("youtube"), None);
+    assert_eq!(missing_id.url(), None);
+
+    let empty_id = trailer(Some(""), Some("youtube"), None);
+    assert_eq!(empty_id.embed_url(), None);
+
+    // Site names are matched case-insensitively
+    let capitalised = trailer(Some("abc"), Some("YouTube"), None);
+    assert_eq!(
+        capitalised.url().as_deref(),
+        Some("https://www.youtube.com/watch?v=abc")
+    );
+}
+
+#[tokio::test]
+async fn test_browse_methods_return_identically_shaped_anime() {
+    use anilist_sdk::models::Anime;
+
+    let client = AniListClient::new();
+
+    let results: [(&str, Vec<Anime>); 6] = [
+        (
+            "get_popular",
+            crate::anime_api_call!(client, get_popular, 1, 3).expect("get_popular failed"),
+        ),
+        (
+            "get_trending",
+            crate::anime_api_call!(client, get_trending, 1, 3).expect("get_trending failed"),
+        ),
+        (
+            "search",
+            crate::anime_api_call!(client, search, "Naruto", 1, 3).expect("search failed"),
+        ),
+        (
+            "get_by_season",
+            crate::anime_api_call!(client, get_by_season, "FALL", 2023, 1, 3)
+                .expect("get_by_season failed"),
+        ),
+        (
+            "get_top_rated",
+            crate::anime_api_call!(client, get_top_rated, 1, 3).expect("get_top_rated failed"),
+        ),
+        (
+            "get_airing",
+            crate::anime_api_call!(client, get_airing, 1, 3).expect("get_airing failed"),
+        ),
+    ];
+
+    // These used to be missing from some browse documents but not others
+    for (method, anime_list) in &results {
+        assert!(!anime_list.is_empty(), "{method} returned nothing");
+        for anime in anime_list {
+            assert!(anime.start_date.is_some(), "{method}: startDate");
+            assert!(
+                anime.country_of_origin.is_some(),
+                "{method}: countryOfOrigin"
+            );
+            assert!(anime.is_adult.is_some(), "{method}: isAdult");
+            assert!(anime.genres.is_some(), "{method}: genres");
+            assert!(anime.cover_image.is_some(), "{method}: coverImage");
+            assert!(anime.site_url.is_some(), "{method}: siteUrl");
+        }
+    }
+
+    let airing = &results[5].1;
+    assert!(
+        airing
+            .iter()
+            .any(|anime| anime.next_airing_episode.is_some()),
+        "get_airing should include the next episode"
+    );
+}
+
+#[test]
+fn test_browse_document_selects_former_drifted_fields() {
+    let query = anilist_sdk::queries::anime::GET_MEDIA_PAGE;
+
+    for field in [
+        "nextAiringEpisode",
+        "startDate",
+        "endDate",
+        "hashtag",
+        "countryOfOrigin",
+        "isAdult",
+    ] {
+        assert!(query.contains(field), "browse document lacks {field}");
+    }
+    for argument in ["sort: $sort", "status: $status", "search: $search"] {
+        assert!(query.contains(argument), "browse document lacks {argument}");
+    }
+}
+
+fn external_link(
+    id: i32,
+    site: &str,
+    link_type: &str,
+    language: Option<&str>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "url": format!("https://example.com/{id}"),
+        "site": site,
+        "type": link_type,
+        "language": language,
+        "isDisabled": false
+    })
+}
+
+#[test]
+fn test_streaming_sites_normalize_filter_and_sort() {
+    use anilist_sdk::models::Anime;
+
+    let anime: Anime = serde_json::from_value(serde_json::json!({
+        "id": 16498,
+        "title": { "romaji": "Shingeki no Kyojin" },
+        "externalLinks": [
+            external_link(1, "Twitter", "SOCIAL", None),
+            external_link(2, "Hulu", "STREAMING", Some("English")),
+            external_link(3, "VRV", "STREAMING", Some("English")),
+            external_link(4, "Official Site", "INFO", Some("Japanese")),
+            external_link(5, "Some Fan Wiki", "INFO", None),
+            external_link(6, "Crunchyroll", "STREAMING", Some("German")),
+            {
+                "id": 7, "url": "https://example.com/7", "site": "Netflix",
+                "type": "STREAMING", "isDisabled": true
+            }
+        ]
+    }))
+    .unwrap();
+
+    let sites = anime.streaming_sites();
+    let names: Vec<_> = sites.iter().map(|site| site.site).collect();
+    assert_eq!(
+        names,
+        ["Official Site", "Crunchyroll", "Crunchyroll", "Hulu"]
+    );
+    assert_eq!(sites[1].url, "https://example.com/3");
+    assert_eq!(sites[2].language.as_deref(), Some("German"));
+}
+
+#[test]
+fn test_streaming_sites_without_links_is_empty() {
+    use anilist_sdk::models::Anime;
+
+    let anime: Anime = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "title": { "romaji": "Cowboy Bebop" }
+    }))
+    .unwrap();
+    assert!(anime.streaming_sites().is_empty());
+}
+
+#[test]
+fn test_unknown_external_link_types_deserialize() {
+    use anilist_sdk::models::{ExternalLinkType, MediaExternalLink};
+
+    let link: MediaExternalLink =
+        serde_json::from_value(external_link(8, "Shop", "MERCHANDISE", None)).unwrap();
+    assert_eq!(link.link_type, Some(ExternalLinkType::Unknown));
+    assert!(link.site_id.is_none());
+}
+
+#[test]
+fn test_normalize_watch_site() {
+    use anilist_sdk::models::external_link::{normalize_read_site, normalize_watch_site};
+
+    assert_eq!(normalize_watch_site("VRV"), Some("Crunchyroll"));
+    assert_eq!(normalize_watch_site(" crunchyroll "), Some("Crunchyroll"));
+    assert_eq!(normalize_watch_site("Disney+"), Some("Disney Plus"));
+    assert_eq!(normalize_watch_site("Amazon"), Some("Amazon Prime Video"));
+    assert_eq!(normalize_read_site("Amazon"), Some("Amazon Kindle"));
+    assert_eq!(normalize_watch_site("MANGA Plus"), None);
+    assert_eq!(normalize_watch_site("Unknown Stream"), None);
+}
+
+#[tokio::test]
+async fn test_get_premiering_within() {
+    use anilist_sdk::models::MediaStatus;
+    use anilist_sdk::utils::seasons::premiere_window;
+
+    let client = AniListClient::new();
+    let (after, before) = premiere_window(chrono::Utc::now().date_naive(), 30).unwrap();
+
+    let anime_list = crate::anime_api_call!(client, get_premiering_within, 30, 1, 10)
+        .expect("Failed to get premiering anime");
+
+    for anime in &anime_list {
+        assert!(matches!(anime.status, Some(MediaStatus::NotYetReleased)));
+        if let Some(start) = &anime.start_date
+            && let (Some(year), Some(month)) = (start.year, start.month)
+        {
+            let date = year * 10_000 + month * 100 + start.day.unwrap_or(0);
+            assert!(
+                after < date && date < before,
+                "{date} outside {after}..{before}"
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_season_chart() {
+    use anilist_sdk::models::MediaSeason;
+    use anilist_sdk::utils::seasons::SeasonEntryKind;
+
+    let client = AniListClient::new();
+    let chart = crate::anime_api_call!(client, get_season_chart, MediaSeason::Fall, 2023, 1, 10)
+        .expect("Failed to get season chart");
+
+    assert!(!chart.is_empty());
+    for (anime, kind) in &chart {
+        if *kind == SeasonEntryKind::Leftover
+            && let Some(year) = anime.start_date.as_ref().and_then(|d| d.year)
+        {
+            assert!(year <= 2023, "leftover {} starts in {year}", anime.id);
+        }
+    }
+}
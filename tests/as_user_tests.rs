@@ -0,0 +1,66 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::transport::TransportResponse;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::{MockApi, authorization};
+
+mod test_utils;
+
+/// Answers every request with a viewer named after the bearer token it
+/// carried, after a short delay so concurrent requests overlap.
+fn viewer_api() -> Arc<MockApi> {
+    let api = MockApi::from_fn(|request| {
+        let name = authorization(request).trim_start_matches("Bearer ");
+        let body = format!(r#"{{"data":{{"Viewer":{{"id":1,"name":"{name}"}}}}}}"#);
+        TransportResponse::new(200, body)
+    });
+    Arc::new(api.delay(Duration::from_millis(20)))
+}
+
+fn client(transport: &Arc<MockApi>) -> AniListClient {
+    transport.attach(AniListClient::builder().token("service"))
+}
+
+#[tokio::test]
+async fn test_concurrent_calls_carry_their_own_token() {
+    let transport = viewer_api();
+    let client = client(&transport);
+
+    let (alice, bob) = (client.as_user("alice").user(), client.as_user("bob").user());
+    let (alice, bob) = tokio::join!(alice.get_current_user(), bob.get_current_user());
+
+    assert_eq!(alice.unwrap().name, "alice");
+    assert_eq!(bob.unwrap().name, "bob");
+    let mut seen = transport.authorizations();
+    seen.sort();
+    assert_eq!(seen, ["Bearer alice", "Bearer bob"]);
+}
+
+#[tokio::test]
+async fn test_override_does_not_leak_into_the_client() {
+    let transport = viewer_api();
+    let client = client(&transport);
+
+    let (own, other) = (client.user(), client.as_user("alice").user());
+    let (own, other) = tokio::join!(own.get_current_user(), other.get_current_user());
+
+    assert_eq!(own.unwrap().name, "service");
+    assert_eq!(other.unwrap().name, "alice");
+}
+
+#[tokio::test]
+async fn test_token_changes_stay_on_their_side() {
+    let transport = viewer_api();
+    let mut client = client(&transport);
+    let mut user = client.as_user("alice");
+
+    client.set_token("rotated".to_string());
+    assert_eq!(user.user().get_current_user().await.unwrap().name, "alice");
+
+    user.clear_token();
+    assert!(!user.has_token());
+    assert_eq!(
+        client.user().get_current_user().await.unwrap().name,
+        "rotated"
+    );
+}
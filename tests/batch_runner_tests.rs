@@ -0,0 +1,110 @@
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::{TransportRequest, TransportResponse};
+use anilist_sdk::utils::batch_runner::BatchRunner;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_utils::{MockApi, not_found};
+use tokio::time::Instant;
+
+mod test_utils;
+
+/// Answers `Media` lookups after a delay that varies by ID. ID 4 does not
+/// exist.
+fn slow_api() -> Arc<MockApi> {
+    let id = |request: &TransportRequest| request.variables()["id"].as_i64().unwrap();
+    let api = MockApi::from_fn(move |request| match id(request) {
+        4 => not_found(),
+        id => {
+            let body = json!({ "data": { "Media": { "id": id, "title": {} } } });
+            TransportResponse::new(200, body.to_string())
+        }
+    });
+    Arc::new(api.delay_with(move |request| {
+        Duration::from_millis([30, 5, 20, 1, 12][id(request) as usize % 5])
+    }))
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_run_limits_requests_in_flight_and_keeps_order() {
+    let api = slow_api();
+    let anime = api.client().anime();
+
+    let results = BatchRunner::new(3)
+        .run((1..=10).map(|id| anime.get_by_id(id)))
+        .await;
+
+    assert_eq!(api.max_in_flight(), 3);
+    assert_eq!(results.len(), 10);
+    for (id, result) in (1..=10).zip(results) {
+        match result {
+            Ok(anime) => assert_eq!(anime.id, id),
+            Err(error) => assert_eq!(id, 4, "unexpected error for {id}: {error}"),
+        }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_run_spaces_out_starts() {
+    let started = Mutex::new(Vec::new());
+    let begin = Instant::now();
+
+    let results = BatchRunner::new(10)
+        .min_gap(Duration::from_millis(100))
+        .run((0..4).map(|i| {
+            let started = &started;
+            async move {
+                started.lock().unwrap().push(begin.elapsed());
+                Ok::<_, AniListError>(i)
+            }
+        }))
+        .await;
+
+    let ids: Vec<i32> = results.into_iter().map(Result::unwrap).collect();
+    assert_eq!(ids, [0, 1, 2, 3]);
+    let started = started.into_inner().unwrap();
+    for pair in started.windows(2) {
+        assert!(
+            pair[1] - pair[0] >= Duration::from_millis(100),
+            "{started:?}"
+        );
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_run_waits_for_retry_after_before_starting_more() {
+    let started = Mutex::new(Vec::new());
+    let begin = Instant::now();
+
+    let results = BatchRunner::new(1)
+        .run((0..3).map(|i| {
+            let started = &started;
+            async move {
+                started.lock().unwrap().push(begin.elapsed());
+                if i == 0 {
+                    return Err(AniListError::RateLimit {
+                        limit: 90,
+                        remaining: 0,
+                        reset_at: 0,
+                        retry_after: 2,
+                    });
+                }
+                Ok(i)
+            }
+        }))
+        .await;
+
+    assert!(matches!(results[0], Err(AniListError::RateLimit { .. })));
+    assert_eq!(results[1].as_ref().unwrap(), &1);
+    assert_eq!(results[2].as_ref().unwrap(), &2);
+    let started = started.into_inner().unwrap();
+    assert!(started[1] >= Duration::from_secs(2), "{started:?}");
+}
+
+#[tokio::test]
+async fn test_run_with_no_calls_returns_nothing() {
+    let ops: Vec<std::future::Ready<Result<i32, AniListError>>> = Vec::new();
+
+    assert!(BatchRunner::new(0).run(ops).await.is_empty());
+    assert_eq!(BatchRunner::new(0).max_concurrency(), 1);
+}
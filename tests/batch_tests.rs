@@ -0,0 +1,288 @@
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use test_utils::MockApi;
+
+mod test_utils;
+
+/// Answers every request with `status` and `body`.
+fn api(status: u16, body: Value) -> Arc<MockApi> {
+    Arc::new(MockApi::respond(TransportResponse::new(
+        status,
+        body.to_string(),
+    )))
+}
+
+fn media(id: i32, romaji: &str) -> Value {
+    json!({ "id": id, "title": { "romaji": romaji } })
+}
+
+#[tokio::test]
+async fn test_lookups_are_merged_into_one_request() {
+    let transport = api(
+        200,
+        json!({ "data": {
+            "b0": media(1, "Cowboy Bebop"),
+            "b1": media(20, "Naruto"),
+            "b2": {
+                "id": 417,
+                "name": { "full": "Lelouch Lamperouge" },
+                "image": {},
+                "siteUrl": "https://anilist.co/character/417",
+                "favourites": 0
+            },
+        }}),
+    );
+    let client = transport.client();
+
+    let response = client
+        .batch()
+        .anime_by_id(1)
+        .anime_by_id(20)
+        .character_by_id(417)
+        .send()
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    let query = requests[0].query();
+    // The anime lookup also declares `$idMal`, which is left unset
+    assert!(
+        query.starts_with(
+            "query ($b0_id: Int, $b0_idMal: Int, $b1_id: Int, $b1_idMal: Int, $b2_id: Int) {"
+        ),
+        "{query}"
+    );
+    assert!(
+        query.contains("b0: Media(id: $b0_id, idMal: $b0_idMal, type: ANIME)"),
+        "{query}"
+    );
+    assert!(
+        query.contains("b1: Media(id: $b1_id, idMal: $b1_idMal, type: ANIME)"),
+        "{query}"
+    );
+    assert!(query.contains("b2: Character(id: $b2_id)"), "{query}");
+    assert_eq!(
+        requests[0].variables(),
+        &json!({ "b0_id": 1, "b1_id": 20, "b2_id": 417 })
+    );
+
+    assert_eq!(response.len(), 3);
+    assert_eq!(response.failed(), 0);
+    let bebop = response.anime(1).unwrap().unwrap();
+    assert_eq!(bebop.title.romaji.as_deref(), Some("Cowboy Bebop"));
+    assert_eq!(response.anime(20).unwrap().unwrap().id, 20);
+    let lelouch = response.character(417).unwrap().unwrap();
+    assert_eq!(lelouch.id, 417);
+    // Items that were not requested
+    assert!(response.anime(417).is_none());
+    assert!(response.manga(1).is_none());
+}
+
+#[tokio::test]
+async fn test_duplicate_lookups_share_one_alias() {
+    let transport = api(200, json!({ "data": { "b0": media(1, "Cowboy Bebop") } }));
+    let client = transport.client();
+
+    let batch = client.batch().anime_by_id(1).anime_by_id(1);
+    assert_eq!(batch.len(), 1);
+    let response = batch.send().await.unwrap();
+
+    let query = transport.requests()[0].query().to_string();
+    assert!(!query.contains("b1"), "{query}");
+    assert_eq!(response.anime(1).unwrap().unwrap().id, 1);
+}
+
+#[tokio::test]
+async fn test_colliding_variables_and_aliases_are_renamed() {
+    let transport = api(
+        200,
+        json!({ "data": { "b0": { "id": 1 }, "b1": { "id": 2 } } }),
+    );
+    let client = transport.client();
+
+    client
+        .batch()
+        // Both documents use `$id`, and both alias their root field `media`
+        .query(
+            "first",
+            "query ($id: Int, $asHtml: Boolean = false) { media: Media(id: $id) { id description(asHtml: $asHtml) } }",
+            json!({ "id": 1 }),
+        )
+        .query(
+            "second",
+            "query Named($id: Int) { media: Media(id: $id, search: \"$id {\") { id } }",
+            json!({ "id": 2 }),
+        )
+        .send()
+        .await
+        .unwrap();
+
+    let request = &transport.requests()[0];
+    let query = request.query();
+    assert!(
+        query.starts_with("query ($b0_id: Int, $b0_asHtml: Boolean = false, $b1_id: Int) {"),
+        "{query}"
+    );
+    assert!(
+        query.contains("b0: Media(id: $b0_id) { id description(asHtml: $b0_asHtml) }"),
+        "{query}"
+    );
+    // String literals are left alone
+    assert!(
+        query.contains("b1: Media(id: $b1_id, search: \"$id {\") { id }"),
+        "{query}"
+    );
+    assert!(!query.contains("media:"), "{query}");
+    assert_eq!(request.variables(), &json!({ "b0_id": 1, "b1_id": 2 }));
+}
+
+#[tokio::test]
+async fn test_failed_items_report_their_own_errors() {
+    // AniList answers with the status of the first error, here a 404
+    let transport = api(
+        404,
+        json!({
+            "data": { "b0": media(1, "Cowboy Bebop"), "b1": null, "b2": null, "b3": null },
+            "errors": [
+                { "message": "Not Found.", "status": 404, "path": ["b1"] },
+                { "message": "Private User", "status": 404, "path": ["b2"] },
+            ],
+        }),
+    );
+    let client = transport.client();
+
+    let response = client
+        .batch()
+        .anime_by_id(1)
+        .anime_by_id(999_999_999)
+        .user_by_id(2)
+        .staff_by_id(3)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.failed(), 3);
+    assert_eq!(response.anime(1).unwrap().unwrap().id, 1);
+    let error = response.anime(999_999_999).unwrap().unwrap_err();
+    assert!(
+        error.to_string().to_lowercase().contains("not found"),
+        "{error}"
+    );
+    assert!(matches!(
+        response.user(2).unwrap(),
+        Err(AniListError::Private { .. })
+    ));
+    // `null` without an error of its own
+    assert!(matches!(
+        response.staff(3).unwrap(),
+        Err(AniListError::NotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_mismatched_item_is_a_json_error_for_that_item_only() {
+    let transport = api(
+        200,
+        json!({ "data": { "b0": { "id": "one" }, "b1": media(20, "Naruto") } }),
+    );
+    let client = transport.client();
+
+    let response = client
+        .batch()
+        .anime_by_id(1)
+        .anime_by_id(20)
+        .send()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        response.anime(1).unwrap(),
+        Err(AniListError::Json(_))
+    ));
+    assert!(response.anime(20).unwrap().is_ok());
+}
+
+#[tokio::test]
+async fn test_request_level_errors_fail_the_batch() {
+    let transport = api(
+        400,
+        json!({
+            "data": null,
+            "errors": [{ "message": "Cannot query field \"nope\" on type \"Media\".", "status": 400 }],
+        }),
+    );
+    let client = transport.client();
+
+    let result = client.batch().anime_by_id(1).send().await;
+
+    assert!(matches!(result, Err(AniListError::BadRequest { .. })));
+}
+
+#[tokio::test]
+async fn test_invalid_batches_are_rejected_before_sending() {
+    let transport = api(200, json!({ "data": {} }));
+    let client = transport.client();
+
+    let cases = [
+        client
+            .batch()
+            .query("a", "mutation { ToggleFollow(userId: 1) { id } }", ()),
+        client
+            .batch()
+            .query("a", "query { Media(id: 1) { id } Staff(id: 1) { id } }", ()),
+        client
+            .batch()
+            .query("a", "query { Media(id: 1) { id } }", ())
+            .query("a", "query { Media(id: 2) { id } }", ()),
+        client.batch().query("a", "query { Media { id } }", [1, 2]),
+    ];
+    for batch in cases {
+        let result = batch.send().await;
+        assert!(
+            matches!(result, Err(AniListError::BadRequest { .. })),
+            "{result:?}"
+        );
+    }
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_empty_batch_sends_nothing() {
+    let transport = api(200, json!({ "data": {} }));
+    let client = transport.client();
+
+    let response = client.batch().send().await.unwrap();
+
+    assert!(response.is_empty());
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_raw_queries_keep_fragments() {
+    let transport = api(
+        200,
+        json!({ "data": { "b0": { "id": 1 }, "b1": { "id": 2 } } }),
+    );
+    let client = transport.client();
+    let document = "query ($id: Int) { Media(id: $id) { ...core } }\nfragment core on Media { id }";
+
+    let response = client
+        .batch()
+        .query("one", document, json!({ "id": 1 }))
+        .query("two", document, json!({ "id": 2 }))
+        .send()
+        .await
+        .unwrap();
+
+    let query = transport.requests()[0].query().to_string();
+    assert_eq!(
+        query.matches("fragment core on Media").count(),
+        1,
+        "{query}"
+    );
+    let two: Value = response.get("two").unwrap().unwrap();
+    assert_eq!(two, json!({ "id": 2 }));
+}
@@ -0,0 +1,64 @@
+use anilist_sdk::cache::TtlCache;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_cache_returns_fresh_entries() {
+    let cache = TtlCache::new(Duration::from_secs(60));
+    let start = Instant::now();
+
+    cache.insert_at("frieren", vec![154587], start);
+
+    assert_eq!(
+        cache.get_at(&"frieren", start + Duration::from_secs(59)),
+        Some(vec![154587])
+    );
+    assert_eq!(cache.get_at(&"naruto", start), None);
+}
+
+#[test]
+fn test_cache_expires_entries() {
+    let cache = TtlCache::new(Duration::from_secs(60));
+    let start = Instant::now();
+
+    cache.insert_at("frieren", 1, start);
+
+    assert_eq!(
+        cache.get_at(&"frieren", start + Duration::from_secs(60)),
+        None
+    );
+}
+
+#[test]
+fn test_cache_purges_expired_entries_on_insert() {
+    let cache = TtlCache::new(Duration::from_secs(10));
+    let start = Instant::now();
+
+    cache.insert_at("a", 1, start);
+    cache.insert_at("b", 2, start + Duration::from_secs(5));
+    assert_eq!(cache.len(), 2);
+
+    cache.insert_at("c", 3, start + Duration::from_secs(12));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get_at(&"a", start + Duration::from_secs(12)), None);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_cache_evicts_the_oldest_entry_when_full() {
+    let cache = TtlCache::with_max_entries(Duration::from_secs(60), 2);
+    let start = Instant::now();
+
+    cache.insert_at("a", 1, start);
+    cache.insert_at("b", 2, start + Duration::from_secs(1));
+    cache.insert_at("a", 10, start + Duration::from_secs(2));
+    assert_eq!(cache.len(), 2);
+
+    cache.insert_at("c", 3, start + Duration::from_secs(3));
+    let now = start + Duration::from_secs(3);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get_at(&"b", now), None);
+    assert_eq!(cache.get_at(&"a", now), Some(10));
+    assert_eq!(cache.get_at(&"c", now), Some(3));
+}
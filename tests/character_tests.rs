@@ -16,7 +16,7 @@ async fn test_get_popular_characters() {
     // Check that all characters have required fields
     for character in &characters {
         assert!(character.id > 0);
-        assert!(character.name.is_some());
+        assert!(character.name.full.is_some());
     }
 }
 
@@ -29,7 +29,7 @@ async fn test_get_character_by_id() {
         crate::character_api_call!(client, get_by_id, 417).expect("Failed to get character by ID");
 
     assert_eq!(character.id, 417);
-    assert!(character.name.is_some());
+    assert!(character.name.full.is_some());
 }
 
 #[tokio::test]
@@ -43,17 +43,14 @@ async fn test_search_characters() {
 
     // Check that results contain "Luffy" in some form
     let has_luffy = characters.iter().any(|character| {
-        if let Some(name) = &character.name {
-            name.full
+        let name = &character.name;
+        name.full
+            .as_ref()
+            .is_some_and(|n| n.to_lowercase().contains("luffy"))
+            || name
+                .first
                 .as_ref()
                 .is_some_and(|n| n.to_lowercase().contains("luffy"))
-                || name
-                    .first
-                    .as_ref()
-                    .is_some_and(|n| n.to_lowercase().contains("luffy"))
-        } else {
-            false
-        }
     });
     assert!(has_luffy);
 }
@@ -99,9 +96,108 @@ async fn test_get_most_favorited_characters() {
     let mut prev_favorites = i32::MAX;
     for character in &characters {
         assert!(character.id > 0);
-        if let Some(favourites) = character.favourites {
-            assert!(favourites <= prev_favorites);
-            prev_favorites = favourites;
+        assert!(character.favourites <= prev_favorites);
+        prev_favorites = character.favourites;
+    }
+}
+
+#[test]
+fn test_character_primary_media_title() {
+    use anilist_sdk::models::Character;
+
+    let search_result = serde_json::json!({
+        "id": 5,
+        "name": { "full": "Ichigo Kurosaki", "userPreferred": "Ichigo Kurosaki" },
+        "image": { "large": null, "medium": null },
+        "siteUrl": "https://anilist.co/character/5",
+        "favourites": 20000,
+        "media": {
+            "nodes": [{ "id": 269, "title": { "userPreferred": "BLEACH" } }]
         }
+    });
+    let character: Character = serde_json::from_value(search_result).unwrap();
+    assert_eq!(character.primary_media_title(), Some("BLEACH"));
+
+    // Queries that don't select media, or characters without any
+    let without_media = serde_json::json!({
+        "id": 6, "name": {}, "image": {}, "siteUrl": "https://anilist.co/character/6", "favourites": 0
+    });
+    let without_media: Character = serde_json::from_value(without_media).unwrap();
+    assert_eq!(without_media.primary_media_title(), None);
+
+    let no_nodes = serde_json::json!({
+        "id": 7, "name": {}, "image": {}, "siteUrl": "https://anilist.co/character/7", "favourites": 0,
+        "media": { "nodes": [] }
+    });
+    let no_nodes: Character = serde_json::from_value(no_nodes).unwrap();
+    assert_eq!(no_nodes.primary_media_title(), None);
+}
+
+#[test]
+fn test_character_search_selects_single_media_node() {
+    let query = anilist_sdk::queries::character::SEARCH;
+    assert!(query.contains("media(perPage: 1, sort: POPULARITY_DESC)"));
+}
+
+#[test]
+fn test_character_all_names() {
+    use anilist_sdk::models::Character;
+
+    let character: Character = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "name": {
+            "first": "Edward",
+            "last": "Elric",
+            "full": "Edward Elric",
+            "native": "エドワード・エルリック",
+            "alternative": ["Ed", "  ", "ＥＤ", "edward elric", "Fullmetal Alchemist"],
+            "alternativeSpoiler": ["The Alchemist Who Gave Up Alchemy", "ed"],
+            "userPreferred": "Edward Elric"
+        },
+        "image": {},
+        "siteUrl": "https://anilist.co/character/1",
+        "favourites": 0
+    }))
+    .unwrap();
+    let name = character.name;
+
+    // Blanks are dropped; case and full-width duplicates keep the first form
+    assert_eq!(
+        name.all_names(false),
+        vec![
+            "Edward Elric",
+            "エドワード・エルリック",
+            "Edward",
+            "Elric",
+            "Ed",
+            "Fullmetal Alchemist",
+        ]
+    );
+    assert_eq!(
+        name.all_names(true),
+        vec![
+            "Edward Elric",
+            "エドワード・エルリック",
+            "Edward",
+            "Elric",
+            "Ed",
+            "Fullmetal Alchemist",
+            "The Alchemist Who Gave Up Alchemy",
+        ]
+    );
+}
+
+#[test]
+fn test_character_queries_select_spoiler_names() {
+    use anilist_sdk::queries::character;
+
+    for query in [
+        character::GET_BY_ID,
+        character::SEARCH,
+        character::GET_POPULAR,
+        character::GET_MOST_FAVORITED,
+        character::GET_TODAY_BIRTHDAY,
+    ] {
+        assert!(query.contains("alternativeSpoiler"));
     }
 }
@@ -0,0 +1,167 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::{MockApi, not_found};
+
+mod test_utils;
+
+const PAGE: &str = r#"{"data":{"Page":{"media":[{"id":1,"title":{"romaji":"Cowboy Bebop"}}]}}}"#;
+
+/// Answers every request after 100ms with `status` and `body`.
+fn slow_api(status: u16, body: &str) -> Arc<MockApi> {
+    Arc::new(
+        MockApi::respond(TransportResponse::new(status, body)).delay(Duration::from_millis(100)),
+    )
+}
+
+fn client(api: &Arc<MockApi>, coalesce: bool) -> AniListClient {
+    api.attach(
+        AniListClient::builder()
+            .token("token")
+            .coalesce_requests(coalesce),
+    )
+}
+
+/// Runs `count` identical trending lookups at once, each on its own clone.
+async fn trending_at_once(
+    client: &AniListClient,
+    count: usize,
+) -> Vec<Result<Vec<i32>, AniListError>> {
+    let tasks: Vec<_> = (0..count)
+        .map(|_| {
+            let anime = client.clone().anime();
+            tokio::spawn(async move {
+                let page = anime.get_trending(1, 10).await?;
+                Ok(page.into_iter().map(|anime| anime.id).collect())
+            })
+        })
+        .collect();
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(task.await.unwrap());
+    }
+    results
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_identical_concurrent_queries_are_sent_once() {
+    let api = slow_api(200, PAGE);
+    let client = client(&api, true);
+
+    let results = trending_at_once(&client, 10).await;
+
+    assert_eq!(api.calls(), 1);
+    for result in results {
+        assert_eq!(result.unwrap(), [1]);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_queries_are_not_coalesced_by_default() {
+    let api = slow_api(200, PAGE);
+    let client = client(&api, false);
+
+    trending_at_once(&client, 10).await;
+
+    assert_eq!(api.calls(), 10);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_errors_are_shared_with_waiting_callers() {
+    let api = Arc::new(MockApi::respond(not_found()).delay(Duration::from_millis(100)));
+    let client = client(&api, true);
+
+    let results = trending_at_once(&client, 5).await;
+
+    assert_eq!(api.calls(), 1);
+    for result in results {
+        assert!(matches!(result, Err(AniListError::NotFound)), "{result:?}");
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_network_errors_are_shared_with_waiting_callers() {
+    // A request that cannot be built is a network error that is not retried
+    let api = Arc::new(
+        MockApi::try_from_fn(|_| {
+            let error = reqwest::Client::new().get("not a url").build().unwrap_err();
+            Err(AniListError::from(error))
+        })
+        .delay(Duration::from_millis(100)),
+    );
+    let client = client(&api, true);
+
+    let results = trending_at_once(&client, 10).await;
+
+    assert_eq!(api.calls(), 1);
+    for result in results {
+        assert!(
+            matches!(result, Err(AniListError::Network(_))),
+            "{result:?}"
+        );
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_different_variables_and_later_calls_are_sent_separately() {
+    let api = slow_api(200, PAGE);
+    let client = client(&api, true);
+    let anime = client.anime();
+
+    let (first, second) = tokio::join!(anime.get_trending(1, 10), anime.get_trending(2, 10));
+    first.unwrap();
+    second.unwrap();
+    assert_eq!(api.calls(), 2);
+
+    // Only requests still in flight are shared; this is not a cache
+    anime.get_trending(1, 10).await.unwrap();
+    assert_eq!(api.calls(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_mutations_are_never_coalesced() {
+    let api = slow_api(200, r#"{"data":{"ToggleFavourite":{}}}"#);
+    let client = client(&api, true);
+    let mutation =
+        "mutation ($id: Int) { ToggleFavourite(animeId: $id) { anime { nodes { id } } } }";
+
+    let (first, second) = tokio::join!(
+        client.execute(mutation, json!({ "id": 1 })),
+        client.execute(mutation, json!({ "id": 1 })),
+    );
+    first.unwrap();
+    second.unwrap();
+
+    assert_eq!(api.calls(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_cancelled_leader_releases_waiting_callers() {
+    let api = slow_api(200, PAGE);
+    let client = client(&api, true);
+
+    let leader = tokio::spawn({
+        let anime = client.anime();
+        async move { anime.get_trending(1, 10).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let follower = tokio::spawn({
+        let anime = client.anime();
+        async move { anime.get_trending(1, 10).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(api.calls(), 1);
+
+    leader.abort();
+    let page = follower.await.unwrap().unwrap();
+
+    assert_eq!(page[0].id, 1);
+    assert_eq!(api.calls(), 2);
+
+    // Nothing is left behind for later callers to wait on
+    client.anime().get_trending(1, 10).await.unwrap();
+    assert_eq!(api.calls(), 3);
+}
@@ -0,0 +1,97 @@
+use anilist_sdk::models::Color;
+use anilist_sdk::models::color::NAMED_COLORS;
+
+#[test]
+fn test_named_colors_match_site_values() {
+    let expected = [
+        ("blue", "#3db4f2"),
+        ("purple", "#c063ff"),
+        ("pink", "#fc9dd6"),
+        ("orange", "#ef881a"),
+        ("red", "#e13333"),
+        ("green", "#4cca51"),
+        ("gray", "#677b94"),
+    ];
+    assert_eq!(NAMED_COLORS.len(), expected.len());
+
+    for (name, hex) in expected {
+        let color = Color::parse(name).unwrap_or_else(|| panic!("{name} should parse"));
+        assert_eq!(color.to_hex(), hex, "{name}");
+        assert_eq!(color.name(), Some(name));
+        // Names are case-insensitive and trimmed
+        assert_eq!(
+            Color::parse(&format!(" {} ", name.to_uppercase())),
+            Some(color)
+        );
+    }
+}
+
+#[test]
+fn test_long_hex() {
+    let color = Color::parse("#e4a15d").unwrap();
+    assert_eq!(color.to_rgb(), (228, 161, 93));
+    assert_eq!(color.to_hex(), "#e4a15d");
+    assert_eq!(color.to_string(), "#e4a15d");
+    assert_eq!(color.name(), None);
+
+    assert_eq!(Color::parse("E4A15D"), Some(color));
+    assert_eq!(Color::parse("#000000"), Some(Color::rgb(0, 0, 0)));
+    assert_eq!(Color::parse("#FFFFFF"), Some(Color::rgb(255, 255, 255)));
+}
+
+#[test]
+fn test_short_hex() {
+    assert_eq!(Color::parse("#fa0"), Some(Color::rgb(255, 170, 0)));
+    assert_eq!(Color::parse("#FA0"), Color::parse("#ffaa00"));
+    assert_eq!(Color::parse("123").unwrap().to_hex(), "#112233");
+}
+
+#[test]
+fn test_garbage_is_rejected() {
+    let garbage = [
+        "",
+        "#",
+        "   ",
+        "#12",
+        "#1234",
+        "#12345",
+        "#1234567",
+        "#e4a15d80",
+        "#ggg",
+        "#zzzzzz",
+        "##e4a15d",
+        "blu",
+        "grey-ish",
+        "rgb(1, 2, 3)",
+        "#é4a15",
+    ];
+    for value in garbage {
+        assert_eq!(Color::parse(value), None, "{value:?}");
+    }
+}
+
+#[test]
+fn test_model_colors_keep_raw_strings() {
+    use anilist_sdk::models::MediaCoverImage;
+    use anilist_sdk::models::user::UserOptions;
+
+    let cover: MediaCoverImage = serde_json::from_value(serde_json::json!({
+        "large": "https://s4.anilist.co/file/cover.jpg",
+        "color": "#e4a15d"
+    }))
+    .unwrap();
+    assert_eq!(cover.color.as_deref(), Some("#e4a15d"));
+    assert_eq!(cover.parsed_color(), Some(Color::rgb(228, 161, 93)));
+
+    let options: UserOptions =
+        serde_json::from_value(serde_json::json!({ "profileColor": "purple" })).unwrap();
+    assert_eq!(options.profile_color.as_deref(), Some("purple"));
+    assert_eq!(
+        options.parsed_profile_color().map(|c| c.to_hex()),
+        Some("#c063ff".to_string())
+    );
+
+    let options: UserOptions =
+        serde_json::from_value(serde_json::json!({ "profileColor": null })).unwrap();
+    assert_eq!(options.parsed_profile_color(), None);
+}
@@ -0,0 +1,110 @@
+//! `ANILIST_TOKEN` is process-wide, so every check reading it lives in a
+//! single test of its own binary; the validation tests build their clients
+//! without reading the environment.
+
+use anilist_sdk::client::{AniListClient, TOKEN_ENV_VAR};
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use std::sync::Arc;
+use test_utils::MockApi;
+
+mod test_utils;
+
+fn set_env(value: Option<&str>) {
+    // SAFETY: only `test_token_from_environment` touches the environment,
+    // and no other test reads it
+    unsafe {
+        match value {
+            Some(value) => std::env::set_var(TOKEN_ENV_VAR, value),
+            None => std::env::remove_var(TOKEN_ENV_VAR),
+        }
+    }
+}
+
+#[test]
+fn test_token_from_environment() {
+    // Missing: anonymous, or an error when a token is required
+    set_env(None);
+    assert!(!AniListClient::from_env().unwrap().has_token());
+    let error = AniListClient::from_env_required().unwrap_err();
+    assert!(
+        matches!(&error, AniListError::Config { message } if message.contains(TOKEN_ENV_VAR)),
+        "{error:?}"
+    );
+
+    // Whitespace only counts as missing
+    set_env(Some("  \n"));
+    assert!(!AniListClient::from_env().unwrap().has_token());
+    assert!(matches!(
+        AniListClient::from_env_required(),
+        Err(AniListError::Config { .. })
+    ));
+
+    set_env(Some(" secret \n"));
+    assert!(AniListClient::from_env().unwrap().has_token());
+    assert!(AniListClient::from_env_required().unwrap().has_token());
+
+    set_env(None);
+}
+
+/// A client, with `token` if given, whose requests are answered with `status`
+/// and `body`.
+fn client(token: Option<&str>, status: u16, body: &str) -> (AniListClient, Arc<MockApi>) {
+    let api = Arc::new(MockApi::respond(TransportResponse::new(status, body)));
+    let mut builder = AniListClient::builder();
+    if let Some(token) = token {
+        builder = builder.token(token);
+    }
+    (api.attach(builder), api)
+}
+
+#[tokio::test]
+async fn test_validate_token_returns_the_viewer() {
+    let (client, transport) = client(
+        Some("secret"),
+        200,
+        r#"{"data":{"Viewer":{"id":5123456,"name":"Donator"}}}"#,
+    );
+
+    let viewer = client.validate_token().await.unwrap();
+
+    assert_eq!((viewer.id, viewer.name.as_str()), (5123456, "Donator"));
+    assert_eq!(transport.authorizations(), ["Bearer secret"]);
+    assert!(transport.last_request().query().contains("Viewer"));
+}
+
+#[tokio::test]
+async fn test_validate_token_maps_a_rejected_token() {
+    let (revoked, _) = client(
+        Some("revoked"),
+        401,
+        r#"{"errors":[{"message":"Invalid token","status":401}]}"#,
+    );
+
+    assert!(matches!(
+        revoked.validate_token().await,
+        Err(AniListError::AuthenticationRequired)
+    ));
+
+    let (garbled, _) = client(
+        Some("garbled"),
+        400,
+        r#"{"errors":[{"message":"Invalid token","status":400}]}"#,
+    );
+
+    assert!(matches!(
+        garbled.validate_token().await,
+        Err(AniListError::AuthenticationRequired)
+    ));
+}
+
+#[tokio::test]
+async fn test_validate_token_without_a_token_sends_nothing() {
+    let (client, transport) = client(None, 200, r#"{"data":{}}"#);
+
+    assert!(matches!(
+        client.validate_token().await,
+        Err(AniListError::AuthenticationRequired)
+    ));
+    assert_eq!(transport.calls(), 0);
+}
@@ -0,0 +1,101 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::envelope::{ResponseEnvelope, headers_of_interest};
+use anilist_sdk::health::RateLimitStatus;
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::collections::HashMap;
+use std::time::Duration;
+mod test_utils;
+
+#[tokio::test]
+async fn test_get_by_id_with_meta() {
+    let client = AniListClient::new();
+
+    test_utils::rate_limit().await;
+    let envelope = test_utils::with_retry(|| {
+        let client = client.clone();
+        Box::pin(async move { client.anime().get_by_id_with_meta(16498).await })
+    })
+    .await
+    .expect("Failed to get anime by ID");
+
+    assert_eq!(envelope.value.id, 16498);
+    assert_eq!(envelope.status, 200);
+    assert!(envelope.elapsed > Duration::ZERO);
+    assert!(envelope.rate_limit().is_some());
+    println!(
+        "✓ Request {:?} took {:?}",
+        envelope.request_id(),
+        envelope.elapsed
+    );
+}
+
+#[test]
+fn test_headers_of_interest_keeps_rate_limit_and_request_id() {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-RateLimit-Limit", HeaderValue::from_static("90"));
+    headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("42"));
+    headers.insert("Retry-After", HeaderValue::from_static("30"));
+    headers.insert("CF-Ray", HeaderValue::from_static("8a1b2c3d4e5f-AMS"));
+    headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+    headers.insert("Set-Cookie", HeaderValue::from_static("session=secret"));
+
+    let kept = headers_of_interest(&headers);
+
+    assert_eq!(kept.len(), 4);
+    assert_eq!(kept["x-ratelimit-limit"], "90");
+    assert_eq!(kept["x-ratelimit-remaining"], "42");
+    assert_eq!(kept["retry-after"], "30");
+    assert_eq!(kept["cf-ray"], "8a1b2c3d4e5f-AMS");
+    assert!(!kept.contains_key("set-cookie"));
+}
+
+fn envelope_with(headers: &[(&str, &str)]) -> ResponseEnvelope<i32> {
+    ResponseEnvelope {
+        value: 7,
+        status: 200,
+        headers_of_interest: headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>(),
+        elapsed: Duration::from_millis(120),
+        errors: Vec::new(),
+    }
+}
+
+#[test]
+fn test_envelope_accessors() {
+    let envelope = envelope_with(&[
+        ("x-ratelimit-limit", "90"),
+        ("x-ratelimit-remaining", "89"),
+        ("x-request-id", "abc123"),
+        ("cf-ray", "ray-id"),
+    ]);
+
+    assert_eq!(envelope.request_id(), Some("abc123"));
+    assert_eq!(
+        envelope.rate_limit(),
+        Some(RateLimitStatus {
+            limit: 90,
+            remaining: 89,
+            reset_at: None,
+        })
+    );
+
+    let empty = envelope_with(&[]);
+    assert_eq!(empty.request_id(), None);
+    assert_eq!(empty.rate_limit(), None);
+}
+
+#[test]
+fn test_envelope_map_keeps_metadata() {
+    let envelope = envelope_with(&[("cf-ray", "ray-id")]);
+
+    let mapped = envelope.clone().map(|value| value.to_string());
+    assert_eq!(mapped.value, "7");
+    assert_eq!(mapped.status, envelope.status);
+    assert_eq!(mapped.elapsed, envelope.elapsed);
+    assert_eq!(mapped.request_id(), Some("ray-id"));
+
+    let failed: Result<ResponseEnvelope<i32>, &str> = envelope.try_map(|_| Err("bad body"));
+    assert_eq!(failed.unwrap_err(), "bad body");
+}
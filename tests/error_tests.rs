@@ -0,0 +1,406 @@
+use anilist_sdk::error::{AniListError, GraphQLErrorDetail};
+use serde_json::json;
+use std::time::Duration;
+
+#[test]
+fn test_private_user_error_payload() {
+    // Response body AniList returns when fetching a private user's list
+    let body = json!({
+        "errors": [
+            {
+                "message": "Private User",
+                "status": 404,
+                "locations": [{ "line": 2, "column": 3 }]
+            }
+        ],
+        "data": { "MediaListCollection": null }
+    });
+
+    match AniListError::from_graphql_errors(&body["errors"]) {
+        AniListError::Private { resource } => assert_eq!(resource, "user"),
+        other => panic!("expected Private error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_only_the_private_user_error_is_private() {
+    // Other messages that merely mention privacy keep their own meaning
+    let forbidden = json!([{ "message": "Private messages are disabled", "status": 403 }]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&forbidden),
+        AniListError::AccessDenied
+    ));
+
+    let validation = json!([{ "message": "private must be a boolean", "status": 400 }]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&validation),
+        AniListError::GraphQL { .. }
+    ));
+
+    // The private-profile message with another status is not AniList's
+    let other_status = json!([{ "message": "Private User", "status": 500 }]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&other_status),
+        AniListError::GraphQL { .. }
+    ));
+}
+
+#[test]
+fn test_graphql_errors_are_joined() {
+    let errors = json!([
+        { "message": "Validation error", "status": 400 },
+        { "message": "Unknown argument", "status": 400 }
+    ]);
+
+    match AniListError::from_graphql_errors(&errors) {
+        AniListError::GraphQL { message, .. } => {
+            assert_eq!(message, "Validation error, Unknown argument")
+        }
+        other => panic!("expected GraphQL error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_rate_limit_graphql_error_maps_to_burst_limit() {
+    let errors = json!([{ "message": "Too Many Requests.", "status": 429 }]);
+
+    assert!(matches!(
+        AniListError::from_graphql_errors(&errors),
+        AniListError::BurstLimit
+    ));
+}
+
+#[test]
+fn test_forbidden_graphql_error_maps_to_access_denied() {
+    let errors = json!([{ "message": "Forbidden", "status": 403 }]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&errors),
+        AniListError::AccessDenied
+    ));
+
+    // Mixed statuses keep the full message
+    let errors = json!([
+        { "message": "Forbidden", "status": 403 },
+        { "message": "Validation error", "status": 400 }
+    ]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&errors),
+        AniListError::GraphQL { .. }
+    ));
+}
+
+#[test]
+fn test_adult_content_error_payload() {
+    // Response body for an isAdult title requested without a token: null data
+    // plus an error about the viewer's adult content setting
+    let body = json!({
+        "errors": [
+            {
+                "message": "Adult content is disabled for this user",
+                "status": 403,
+                "locations": [{ "line": 2, "column": 5 }]
+            }
+        ],
+        "data": { "Media": null }
+    });
+
+    // Takes precedence over the generic 403 -> AccessDenied mapping
+    match AniListError::from_graphql_errors(&body["errors"]) {
+        AniListError::AdultContentBlocked { message } => {
+            assert_eq!(message, "Adult content is disabled for this user")
+        }
+        other => panic!("expected AdultContentBlocked error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_only_the_adult_content_error_is_blocked() {
+    // Other errors that mention adult content are not the viewer's setting
+    for message in [
+        "Validation error: isAdult must be a boolean",
+        "Adult content is disabled for this user.",
+        "Unknown argument \"adult\" on field \"Media\"",
+    ] {
+        let errors = json!([{ "message": message, "status": 400 }]);
+        let error = AniListError::from_graphql_errors(&errors);
+        assert!(
+            !matches!(error, AniListError::AdultContentBlocked { .. }),
+            "{message}: {error:?}"
+        );
+    }
+
+    // The same message with another status is not it either
+    let errors = json!([{ "message": "Adult content is disabled for this user", "status": 500 }]);
+    assert!(!matches!(
+        AniListError::from_graphql_errors(&errors),
+        AniListError::AdultContentBlocked { .. }
+    ));
+}
+
+#[test]
+fn test_not_found_graphql_error_maps_to_not_found() {
+    // AniList's answer to `Media(id: 999999999)`, sent with a 200
+    let errors = json!([{ "message": "Not Found.", "status": 404 }]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&errors),
+        AniListError::NotFound
+    ));
+
+    // Mixed statuses keep the full message
+    let errors = json!([
+        { "message": "Not Found.", "status": 404 },
+        { "message": "Validation error", "status": 400 }
+    ]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&errors),
+        AniListError::GraphQL { .. }
+    ));
+}
+
+#[test]
+fn test_rate_limit_status_maps_to_burst_limit() {
+    let errors = json!([{ "message": "Slow down", "status": 429 }]);
+    assert!(matches!(
+        AniListError::from_graphql_errors(&errors),
+        AniListError::BurstLimit
+    ));
+}
+
+#[test]
+fn test_single_graphql_error_keeps_its_details() {
+    let body = json!({
+        "errors": [
+            {
+                "message": "Cannot query field \"nope\" on type \"Media\".",
+                "status": 400,
+                "locations": [{ "line": 3, "column": 9 }]
+            }
+        ],
+        "data": null
+    });
+
+    match AniListError::from_graphql_errors(&body["errors"]) {
+        AniListError::GraphQL { message, details } => {
+            assert_eq!(message, "Cannot query field \"nope\" on type \"Media\".");
+            assert_eq!(
+                details,
+                [GraphQLErrorDetail {
+                    message: message.clone(),
+                    status: Some(400),
+                    locations: vec![(3, 9)],
+                    validation: None,
+                }]
+            );
+        }
+        other => panic!("expected GraphQL error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_each_graphql_error_is_kept_in_order() {
+    let errors = json!([
+        { "message": "Validation error", "status": 400 },
+        { "message": "Unknown argument", "locations": [{ "line": 1, "column": 2 }, { "line": 4, "column": 1 }] },
+        { "message": "Odd entry", "status": "teapot" }
+    ]);
+
+    match AniListError::from_graphql_errors(&errors) {
+        AniListError::GraphQL { message, details } => {
+            // The display output is unchanged
+            assert_eq!(message, "Validation error, Unknown argument, Odd entry");
+            assert_eq!(details.len(), 3);
+            assert_eq!(details[0].status, Some(400));
+            assert!(details[0].locations.is_empty());
+            assert_eq!(details[1].status, None);
+            assert_eq!(details[1].locations, [(1, 2), (4, 1)]);
+            // Entries that do not parse keep their message
+            assert_eq!(details[2].message, "Odd entry");
+            assert_eq!(details[2].status, None);
+        }
+        other => panic!("expected GraphQL error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_validation_errors_are_deserialized() {
+    // Returned when SaveMediaListEntry is sent a score out of range
+    let body = json!({
+        "errors": [
+            {
+                "message": "validation",
+                "status": 400,
+                "locations": [{ "line": 2, "column": 5 }],
+                "validation": {
+                    "score": ["The score may not be greater than 100."],
+                    "progress": ["The progress must be at least 0.", "The progress must be an integer."]
+                }
+            }
+        ],
+        "data": { "SaveMediaListEntry": null }
+    });
+
+    let error = AniListError::from_graphql_errors(&body["errors"]);
+    assert_eq!(error.to_string(), "GraphQL error: validation");
+    let AniListError::GraphQL { details, .. } = error else {
+        panic!("expected GraphQL error, got {error:?}");
+    };
+    let validation = details[0].validation.as_ref().unwrap();
+    assert_eq!(validation["score"], ["The score may not be greater than 100."]);
+    assert_eq!(validation["progress"].len(), 2);
+
+    let detail: GraphQLErrorDetail = serde_json::from_value(body["errors"][0].clone()).unwrap();
+    assert_eq!(detail, details[0]);
+}
+
+#[test]
+fn test_null_locations_and_validation_are_empty() {
+    let detail: GraphQLErrorDetail = serde_json::from_value(json!({
+        "message": "Internal Server Error",
+        "status": 500,
+        "locations": null,
+        "validation": null
+    }))
+    .unwrap();
+
+    assert!(detail.locations.is_empty());
+    assert!(detail.validation.is_none());
+}
+
+fn rate_limit(retry_after: u32) -> AniListError {
+    AniListError::RateLimit {
+        limit: 90,
+        remaining: 0,
+        reset_at: 0,
+        retry_after,
+    }
+}
+
+fn server_error(status: u16) -> AniListError {
+    AniListError::ServerError {
+        status,
+        message: "Server error".to_string(),
+    }
+}
+
+/// Every variant with whether it is rate limited, whether it is retryable
+/// and the delay it suggests.
+fn classified() -> Vec<(AniListError, bool, bool, Option<Duration>)> {
+    let network = reqwest::Client::new().get("not a url").build().unwrap_err();
+    let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let message = || "message".to_string();
+    vec![
+        // Building the request failed; sending it again fails the same way
+        (AniListError::from(network), false, false, None),
+        (
+            AniListError::Timeout {
+                elapsed: Duration::from_secs(30),
+            },
+            false,
+            true,
+            None,
+        ),
+        (AniListError::from(json), false, false, None),
+        (
+            AniListError::GraphQL {
+                message: message(),
+                details: Vec::new(),
+            },
+            false,
+            false,
+            None,
+        ),
+        (rate_limit(12), true, true, Some(Duration::from_secs(12))),
+        (rate_limit(0), true, true, Some(Duration::from_secs(60))),
+        (
+            AniListError::RateLimitSimple,
+            true,
+            true,
+            Some(Duration::from_secs(60)),
+        ),
+        (
+            AniListError::BurstLimit,
+            true,
+            true,
+            Some(Duration::from_secs(5)),
+        ),
+        (AniListError::NotFound, false, false, None),
+        (AniListError::AuthenticationRequired, false, false, None),
+        (AniListError::AccessDenied, false, false, None),
+        (
+            AniListError::Config { message: message() },
+            false,
+            false,
+            None,
+        ),
+        (
+            AniListError::BudgetUnavailable {
+                requested: 2,
+                available: 1,
+            },
+            false,
+            false,
+            None,
+        ),
+        (
+            AniListError::Private {
+                resource: "user".to_string(),
+            },
+            false,
+            false,
+            None,
+        ),
+        (
+            AniListError::AdultContentBlocked { message: message() },
+            false,
+            false,
+            None,
+        ),
+        (
+            AniListError::BadRequest { message: message() },
+            false,
+            false,
+            None,
+        ),
+        (
+            AniListError::InvalidInput {
+                field: "per_page".to_string(),
+                message: message(),
+            },
+            false,
+            false,
+            None,
+        ),
+        (
+            AniListError::RequestAborted { message: message() },
+            false,
+            false,
+            None,
+        ),
+        (server_error(500), false, true, None),
+        (server_error(501), false, false, None),
+        (server_error(502), false, true, None),
+        (server_error(503), false, true, None),
+        (server_error(504), false, true, None),
+        (server_error(505), false, false, None),
+        (server_error(522), false, true, None),
+    ]
+}
+
+#[test]
+fn test_every_variant_is_classified() {
+    for (error, rate_limited, retryable, delay) in classified() {
+        assert_eq!(error.is_rate_limited(), rate_limited, "{error:?}");
+        assert_eq!(error.is_retryable(), retryable, "{error:?}");
+        assert_eq!(error.retry_delay(), delay, "{error:?}");
+    }
+}
+
+#[test]
+fn test_rate_limited_errors_are_retryable_with_a_delay() {
+    for (error, ..) in classified() {
+        if error.is_rate_limited() {
+            assert!(error.is_retryable(), "{error:?}");
+            assert!(error.retry_delay().is_some(), "{error:?}");
+        }
+    }
+}
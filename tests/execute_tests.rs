@@ -0,0 +1,242 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use test_utils::MockApi;
+
+mod test_utils;
+
+/// A client, with `token` if given, whose requests are answered with `status`
+/// and `body`.
+fn client_with(status: u16, body: &str, token: Option<&str>) -> (AniListClient, Arc<MockApi>) {
+    let api = Arc::new(MockApi::respond(TransportResponse::new(status, body)));
+    let mut builder = AniListClient::builder();
+    if let Some(token) = token {
+        builder = builder.token(token);
+    }
+    (api.attach(builder), api)
+}
+
+const RANKINGS_QUERY: &str = "query ($id: Int) { Media(id: $id) { rankings { rank context } } }";
+
+const RANKINGS: &str = r#"{"data":{"Media":{"rankings":[
+    {"rank":1,"context":"highest rated all time"},
+    {"rank":4,"context":"most popular all time"}
+]}}}"#;
+
+#[derive(Debug, Deserialize)]
+struct Data {
+    #[serde(rename = "Media")]
+    media: Rankings,
+}
+
+#[derive(Debug, Deserialize)]
+struct Rankings {
+    rankings: Vec<Ranking>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Ranking {
+    rank: i32,
+    context: String,
+}
+
+#[tokio::test]
+async fn test_execute_returns_the_data_object() {
+    let (client, transport) = client_with(200, RANKINGS, None);
+
+    let data = client
+        .execute(RANKINGS_QUERY, json!({ "id": 1 }))
+        .await
+        .unwrap();
+
+    assert_eq!(data["Media"]["rankings"][0]["rank"], 1);
+    let request = transport.last_request();
+    assert_eq!(request.query(), RANKINGS_QUERY);
+    assert_eq!(request.variables(), &json!({ "id": 1 }));
+    assert!(request.headers.get("authorization").is_none());
+}
+
+#[tokio::test]
+async fn test_execute_as_deserializes_data() {
+    #[derive(Serialize)]
+    struct Variables {
+        id: i32,
+    }
+
+    let (client, transport) = client_with(200, RANKINGS, Some("raw-token"));
+
+    let data: Data = client
+        .execute_as(RANKINGS_QUERY, Variables { id: 1 })
+        .await
+        .unwrap();
+
+    assert_eq!(data.media.rankings.len(), 2);
+    assert_eq!(data.media.rankings[1].rank, 4);
+    assert_eq!(data.media.rankings[1].context, "most popular all time");
+    // Same auth header as the endpoint methods
+    let request = transport.last_request();
+    assert_eq!(request.headers["authorization"], "Bearer raw-token");
+    assert_eq!(request.variables(), &json!({ "id": 1 }));
+}
+
+#[tokio::test]
+async fn test_execute_accepts_maps_and_no_variables() {
+    let (client, transport) = client_with(200, r#"{"data":{"SiteStatistics":{}}}"#, None);
+
+    let variables: HashMap<&str, i32> = HashMap::from([("page", 2)]);
+    client
+        .execute("query { Page { pageInfo { total } } }", variables)
+        .await
+        .unwrap();
+    assert_eq!(transport.last_request().variables(), &json!({ "page": 2 }));
+
+    client
+        .execute("query { SiteStatistics { users { nodes { count } } } }", ())
+        .await
+        .unwrap();
+    assert!(transport.last_request().variables().is_null());
+}
+
+#[tokio::test]
+async fn test_execute_rejects_non_object_variables() {
+    let (client, transport) = client_with(200, RANKINGS, None);
+
+    let result = client.execute(RANKINGS_QUERY, [1, 2, 3]).await;
+
+    assert!(matches!(result, Err(AniListError::BadRequest { .. })));
+    assert_eq!(transport.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_execute_maps_graphql_errors() {
+    let (client, _) = client_with(
+        200,
+        r#"{"data":null,"errors":[{"message":"Cannot query field \"nope\" on type \"Media\".","status":400}]}"#,
+        None,
+    );
+
+    let result = client.execute("query { Media { nope } }", ()).await;
+
+    match result {
+        Err(AniListError::GraphQL { message, .. }) => assert!(message.contains("nope"), "{message}"),
+        other => panic!("expected a GraphQL error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_rejected_mutation_arguments_keep_their_validation_messages() {
+    let (client, _) = client_with(
+        400,
+        r#"{"errors":[{"message":"validation","status":400,"locations":[{"line":2,"column":3}],"validation":{"score":["The score may not be greater than 100."]}}],"data":{"SaveMediaListEntry":null}}"#,
+        Some("token"),
+    );
+
+    let result = client
+        .execute(
+            "mutation ($score: Float) { SaveMediaListEntry(mediaId: 1, score: $score) { id } }",
+            json!({ "score": 1000 }),
+        )
+        .await;
+
+    match result {
+        Err(AniListError::GraphQL { details, .. }) => {
+            let validation = details[0].validation.as_ref().unwrap();
+            assert_eq!(validation["score"], ["The score may not be greater than 100."]);
+        }
+        other => panic!("expected a GraphQL error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_execute_maps_unauthorized() {
+    let (client, _) = client_with(
+        401,
+        r#"{"errors":[{"message":"Invalid token","status":401}]}"#,
+        Some("expired"),
+    );
+
+    let result = client.execute("query { Viewer { id } }", ()).await;
+
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+}
+
+#[tokio::test]
+async fn test_execute_checks_mutations_like_endpoints() {
+    let (client, transport) = client_with(200, r#"{"data":{}}"#, None);
+
+    let result = client
+        .execute("mutation { ToggleFollow(userId: 1) { id } }", ())
+        .await;
+
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+    assert_eq!(transport.calls(), 0);
+}
+
+const TWO_MEDIA: &str = "query { a: Media(id: 1) { id } b: Media(id: 0) { id } }";
+
+#[tokio::test]
+async fn test_partial_data_fails_by_default() {
+    let (client, _) = client_with(
+        200,
+        r#"{"data":{"a":{"id":1},"b":null},"errors":[{"message":"Not Found.","status":404,"path":["b"]}]}"#,
+        None,
+    );
+
+    assert!(client.fails_on_partial_errors());
+    assert!(matches!(
+        client.execute(TWO_MEDIA, ()).await,
+        Err(AniListError::NotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_partial_data_is_returned_with_its_errors() {
+    let (mut client, _) = client_with(
+        200,
+        r#"{"data":{"a":{"id":1},"b":null},"errors":[{"message":"Not Found.","status":404,"path":["b"]}]}"#,
+        None,
+    );
+    client.set_fail_on_partial_errors(false);
+
+    let response = client.execute_with_meta(TWO_MEDIA, ()).await.unwrap();
+    assert_eq!(response.value, json!({ "a": { "id": 1 }, "b": null }));
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(response.errors[0].message, "Not Found.");
+    assert_eq!(response.errors[0].status, Some(404));
+
+    // The plain variant returns the data alone
+    assert_eq!(client.execute(TWO_MEDIA, ()).await.unwrap()["a"]["id"], 1);
+}
+
+#[tokio::test]
+async fn test_errors_without_data_still_fail() {
+    for body in [
+        r#"{"data":null,"errors":[{"message":"Not Found.","status":404}]}"#,
+        r#"{"data":{"Media":null},"errors":[{"message":"Not Found.","status":404}]}"#,
+        r#"{"errors":[{"message":"Not Found.","status":404}]}"#,
+    ] {
+        let (mut client, _) = client_with(200, body, None);
+        client.set_fail_on_partial_errors(false);
+
+        let result = client.execute("query { Media(id: 0) { id } }", ()).await;
+        assert!(matches!(result, Err(AniListError::NotFound)), "{body}");
+    }
+}
+
+#[tokio::test]
+async fn test_data_without_errors_has_no_errors() {
+    let transport = Arc::new(MockApi::ok(RANKINGS));
+    let client = transport.attach(AniListClient::builder().fail_on_partial_errors(false));
+
+    let response = client
+        .execute_with_meta(RANKINGS_QUERY, json!({ "id": 1 }))
+        .await
+        .unwrap();
+    assert!(response.errors.is_empty());
+    assert_eq!(response.status, 200);
+    assert_eq!(response.value["Media"]["rankings"][0]["rank"], 1);
+}
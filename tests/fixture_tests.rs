@@ -0,0 +1,2899 @@
+//! Offline regression tests: captured responses from `tests/fixtures/` are
+//! replayed through a fake transport and must decode into the public models.
+
+use anilist_sdk::client::{AniListClient, ClientConfig};
+use anilist_sdk::error::AniListError;
+use anilist_sdk::models::social::{
+    ActivityFeedFilter, ActivityType, ActivityUnion, NotificationType, RecommendationRating,
+    RecommendationUpsert, ResolvedResource, ReviewRating, ThreadFilter,
+};
+use anilist_sdk::models::{
+    CharacterRole, Color, FavouriteItem, FavouriteKind, FavouriteSet, FavouriteSyncOptions,
+    FuzzyDate, MediaFormat, MediaListStatus, MediaRankType, MediaRelation, MediaSearchFilter,
+    MediaSeason, MediaSort, MediaStatus, MediaType, ProfileUpdateOptions, SaveListEntry,
+    StaffLanguage,
+};
+use anilist_sdk::queries;
+use anilist_sdk::transport::TransportResponse;
+use anilist_sdk::utils::seasons::{SeasonEntryKind, current_season, next_season};
+use futures_util::StreamExt;
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::MockApi;
+
+mod test_utils;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn load(name: &str) -> Value {
+    let path = fixtures_dir().join(format!("{name}.json"));
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("cannot read {}: {error}", path.display()));
+    serde_json::from_str(&text).unwrap_or_else(|error| panic!("{name}.json: {error}"))
+}
+
+/// Turns a fixture into the response the transport returns.
+fn response(fixture: &Value) -> TransportResponse {
+    let body = match &fixture["body"] {
+        Value::String(text) => text.clone().into_bytes(),
+        json => serde_json::to_vec(json).unwrap(),
+    };
+    let mut response = TransportResponse::new(fixture["status"].as_u64().unwrap() as u16, body);
+    for (name, value) in fixture["headers"].as_object().into_iter().flatten() {
+        response = response.with_header(name, value.as_str().unwrap());
+    }
+    response
+}
+
+/// A client with a token whose requests are answered by the named fixture.
+fn replay(name: &str) -> (AniListClient, Arc<MockApi>) {
+    let transport = Arc::new(MockApi::respond(response(&load(name))));
+    let mut client =
+        AniListClient::from_config(&ClientConfig::default().token("fixture-token")).unwrap();
+    client.set_transport(transport.clone());
+    (client, transport)
+}
+
+/// A client with a token whose requests are answered by the named fixtures in
+/// order, for methods that send several requests.
+fn replay_in_order(names: &[&str]) -> (AniListClient, Arc<MockApi>) {
+    let responses: Vec<TransportResponse> =
+        names.iter().map(|name| response(&load(name))).collect();
+    let transport = Arc::new(MockApi::scripted(responses));
+    let mut client =
+        AniListClient::from_config(&ClientConfig::default().token("fixture-token")).unwrap();
+    client.set_transport(transport.clone());
+    (client, transport)
+}
+
+#[test]
+fn test_every_fixture_is_well_formed() {
+    let mut count = 0;
+    for entry in std::fs::read_dir(fixtures_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+        let fixture = load(&name);
+
+        assert!(fixture["method"].is_string(), "{name}: missing method");
+        assert!(fixture["status"].is_u64(), "{name}: missing status");
+        assert!(fixture["headers"].is_object(), "{name}: missing headers");
+        for (header, _) in fixture["headers"].as_object().unwrap() {
+            assert_ne!(
+                header.to_lowercase(),
+                "authorization",
+                "{name}: leaked token"
+            );
+            assert_ne!(header.to_lowercase(), "set-cookie", "{name}: leaked cookie");
+        }
+        if !name.starts_with("error_") {
+            assert_eq!(fixture["status"], 200, "{name}");
+            assert!(fixture["body"]["data"].is_object(), "{name}: no data");
+            assert!(
+                fixture["body"].get("errors").is_none(),
+                "{name}: has errors"
+            );
+        }
+        count += 1;
+    }
+    assert!(count >= 140, "only {count} fixtures found");
+}
+
+#[tokio::test]
+async fn test_anime_get_by_id() {
+    let (client, transport) = replay("anime_get_by_id");
+    let envelope = client.anime().get_by_id_with_meta(1).await.unwrap();
+    let anime = &envelope.value;
+
+    assert_eq!(anime.id, 1);
+    assert_eq!(anime.id_mal, Some(1));
+    assert_eq!(anime.title.english.as_deref(), Some("Cowboy Bebop"));
+    assert_eq!(anime.episodes, Some(26));
+    assert_eq!(anime.start_date.as_ref().unwrap().year, Some(1998));
+    assert_eq!(anime.genres.as_ref().unwrap().len(), 4);
+    assert!(anime.trailer.is_some());
+    assert_eq!(anime.external_links.as_ref().unwrap().len(), 2);
+    let episodes = anime.streaming_episodes.as_ref().unwrap();
+    assert_eq!(episodes.len(), 2);
+    assert_eq!(
+        episodes[0].title.as_deref(),
+        Some("Episode 1 - Asteroid Blues")
+    );
+    let studios = anime.studios.as_ref().unwrap().nodes.as_ref().unwrap();
+    assert_eq!(studios[0].name, "Sunrise");
+    assert!(studios[0].is_animation_studio);
+
+    // Spoiler tags are kept on the model but hidden by visible_tags
+    let tags = anime.tags.as_ref().unwrap();
+    assert_eq!(tags.len(), 5);
+    assert_eq!(tags[0].category.as_deref(), Some("Setting/Universe"));
+    assert!(tags[2].is_spoiler() && tags[3].is_spoiler());
+    let visible: Vec<&str> = anime
+        .visible_tags()
+        .iter()
+        .map(|tag| tag.name.as_str())
+        .collect();
+    assert_eq!(visible, ["Space", "Heterosexual", "Episodic"]);
+
+    // Seasonal and all-time ranks side by side
+    let rankings = anime.rankings.as_ref().unwrap();
+    assert_eq!(rankings.len(), 5);
+    assert_eq!(rankings[0].rank_type, MediaRankType::Rated);
+    assert_eq!(rankings[0].season, Some(MediaSeason::Spring));
+    assert_eq!(rankings[0].year, Some(1998));
+    assert_eq!(rankings[3].year, None);
+    assert_eq!(rankings[3].season, None);
+    let best = anime.best_ranking().unwrap();
+    assert_eq!(best.rank, 35);
+    assert_eq!(best.context, "highest rated all time");
+
+    // Headers reach the envelope as if the response came over the network
+    assert_eq!(envelope.status, 200);
+    assert_eq!(envelope.request_id(), Some("8f2b1c3d4e5f6a7b-FRA"));
+    assert_eq!(envelope.rate_limit().unwrap().remaining, 89);
+
+    let request = transport.last_request();
+    assert_eq!(request.url, "https://graphql.anilist.co");
+    assert_eq!(request.variables()["id"], 1);
+    assert_eq!(
+        request.headers["authorization"].to_str().unwrap(),
+        "Bearer fixture-token"
+    );
+}
+
+#[tokio::test]
+async fn test_anime_get_by_mal_id() {
+    let (client, transport) = replay("anime_get_by_mal_id");
+    let anime = client.anime().get_by_mal_id(16498).await.unwrap();
+
+    assert_eq!(anime.id, 16498);
+    assert_eq!(anime.id_mal, Some(16498));
+    assert_eq!(anime.title.english.as_deref(), Some("Attack on Titan"));
+    assert_eq!(anime.external_links.as_ref().unwrap().len(), 1);
+    let visible: Vec<&str> = anime
+        .visible_tags()
+        .iter()
+        .map(|tag| tag.name.as_str())
+        .collect();
+    assert_eq!(visible, ["Military"]);
+    // Ranked all time by popularity only
+    assert_eq!(anime.rankings.as_ref().unwrap().len(), 3);
+    assert!(anime.best_ranking().is_none());
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables, json!({"idMal": 16498}));
+}
+
+#[tokio::test]
+async fn test_anime_get_relations() {
+    let (client, transport) = replay("anime_get_relations");
+    let relations = client.anime().get_relations(16498).await.unwrap();
+
+    // The edge to a hidden entry is dropped
+    let kinds: Vec<_> = relations
+        .iter()
+        .map(|edge| {
+            let media = &edge.node.as_ref().unwrap().media;
+            (
+                edge.relation_type.unwrap(),
+                media.media_type.unwrap(),
+                media.id,
+            )
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (MediaRelation::Source, MediaType::Manga, 53390),
+            (MediaRelation::Sequel, MediaType::Anime, 20958),
+            (MediaRelation::SideStory, MediaType::Anime, 18397),
+            (MediaRelation::SpinOff, MediaType::Manga, 77115),
+        ]
+    );
+    let sequel = relations[1].node.as_ref().unwrap();
+    assert_eq!(
+        sequel.media.title.as_ref().unwrap().english.as_deref(),
+        Some("Attack on Titan Season 2")
+    );
+    assert_eq!(sequel.media.format, Some(MediaFormat::Tv));
+    assert_eq!(sequel.media.status, Some(MediaStatus::Finished));
+    assert!(sequel.cover_image.as_ref().unwrap().large.is_some());
+    let source = relations[0].node.as_ref().unwrap();
+    assert_eq!(source.media.format, Some(MediaFormat::Manga));
+
+    assert_eq!(transport.last_request().variables()["id"], 16498);
+}
+
+#[tokio::test]
+async fn test_anime_get_characters() {
+    let (client, transport) = replay("anime_get_characters");
+    let edges = client.anime().get_characters(16498, 1, 3).await.unwrap();
+
+    let roles: Vec<_> = edges.iter().map(|edge| edge.role.unwrap()).collect();
+    assert_eq!(
+        roles,
+        [
+            CharacterRole::Main,
+            CharacterRole::Main,
+            CharacterRole::Supporting
+        ]
+    );
+    let eren = &edges[0];
+    let name = &eren.node.as_ref().unwrap().name;
+    assert_eq!(name.full.as_deref(), Some("Eren Yeager"));
+    // Spoiler names are selected, but only listed on request
+    assert!(!name.all_names(false).contains(&"Attack Titan".to_string()));
+    assert!(name.all_names(true).contains(&"Attack Titan".to_string()));
+    let voice_actors = eren.voice_actors.as_ref().unwrap();
+    let ids: Vec<i32> = voice_actors.iter().map(|staff| staff.id).collect();
+    assert_eq!(ids, [95869, 95672]);
+    assert_eq!(voice_actors[0].language_v2.as_deref(), Some("Japanese"));
+    assert!(edges[2].voice_actors.as_ref().unwrap().is_empty());
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["id"], 16498);
+    assert_eq!(variables["language"], "JAPANESE");
+    assert_eq!(variables["page"], 1);
+    assert_eq!(variables["perPage"], 3);
+
+    // The page info is the characters connection's own
+    let page = client
+        .anime()
+        .get_characters_page(16498, StaffLanguage::English, 1, 3)
+        .await
+        .unwrap();
+    assert_eq!(page.page_info.total, Some(74));
+    assert!(page.has_next_page());
+    assert_eq!(transport.last_request().variables()["language"], "ENGLISH");
+    assert!(matches!(
+        client.anime().get_characters(16498, 1, 51).await,
+        Err(AniListError::InvalidInput { field, .. }) if field == "per_page"
+    ));
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_anime_get_staff() {
+    let (client, transport) = replay("anime_get_staff");
+    let page = client.anime().get_staff_page(16498, 1, 4).await.unwrap();
+
+    let credits: Vec<(&str, i32)> = page
+        .items
+        .iter()
+        .map(|edge| {
+            (
+                edge.role.as_deref().unwrap(),
+                edge.node.as_ref().unwrap().id,
+            )
+        })
+        .collect();
+    assert_eq!(
+        credits,
+        [
+            ("Original Creator", 106400),
+            ("Director", 100298),
+            ("Music", 95990),
+            ("Storyboard (eps 1, 25)", 100298),
+        ]
+    );
+    let director = page.items[1].node.as_ref().unwrap();
+    assert_eq!(director.name.full.as_deref(), Some("Tetsurou Araki"));
+    assert_eq!(page.page_info.current_page, Some(1));
+    assert_eq!(page.page_info.last_page, Some(30));
+    assert!(page.has_next_page());
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables, json!({"id": 16498, "page": 1, "perPage": 4}));
+
+    client.anime().get_staff(16498, 2, 4).await.unwrap();
+    assert_eq!(transport.last_request().variables()["page"], 2);
+}
+
+#[tokio::test]
+async fn test_anime_get_streaming_episodes() {
+    let (client, transport) = replay("anime_get_streaming_episodes");
+    let episodes = client.anime().get_streaming_episodes(21).await.unwrap();
+
+    assert_eq!(episodes.len(), 120);
+    assert_eq!(episodes[0].site.as_deref(), Some("Crunchyroll"));
+    assert!(episodes[0].url.as_deref().unwrap().starts_with("https://"));
+    let sites: Vec<_> = episodes[118..]
+        .iter()
+        .map(|episode| episode.site.as_deref().unwrap())
+        .collect();
+    assert_eq!(sites, ["Hidive", "Youtube"]);
+    assert!(episodes[119].thumbnail.is_none());
+
+    assert_eq!(transport.last_request().variables()["id"], 21);
+}
+
+#[tokio::test]
+async fn test_anime_get_streaming_links() {
+    let (client, transport) = replay("anime_get_streaming_links");
+    let links = client.anime().get_streaming_links(140960).await.unwrap();
+
+    // The official site, Twitter, the disabled Hulu link and the link of an
+    // unknown type are left out
+    let sites: Vec<&str> = links.iter().map(|link| link.site.as_str()).collect();
+    assert_eq!(sites, ["Crunchyroll", "Netflix"]);
+    let netflix = &links[1];
+    assert_eq!(netflix.site_id, Some(10));
+    assert_eq!(netflix.language.as_deref(), Some("Japanese"));
+    assert_eq!(netflix.parsed_color(), Some(Color::rgb(0xE5, 0x09, 0x14)));
+    assert!(netflix.icon.as_deref().unwrap().ends_with(".png"));
+
+    assert_eq!(transport.last_request().variables()["id"], 140960);
+}
+
+#[tokio::test]
+async fn test_anime_get_stats() {
+    let (client, transport) = replay("anime_get_stats");
+    let stats = client.anime().get_stats(1).await.unwrap();
+
+    let scores: Vec<i32> = stats.score_distribution.iter().map(|b| b.score).collect();
+    assert_eq!(scores, (1..=10).map(|step| step * 10).collect::<Vec<_>>());
+    let statuses: Vec<MediaListStatus> = stats
+        .status_distribution
+        .iter()
+        .map(|bucket| bucket.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            MediaListStatus::Current,
+            MediaListStatus::Planning,
+            MediaListStatus::Completed,
+            MediaListStatus::Dropped,
+            MediaListStatus::Paused,
+            MediaListStatus::Repeating,
+        ]
+    );
+
+    // Only users with the entry on their list can score it
+    assert_eq!(stats.total_scored(), 197_586);
+    assert_eq!(stats.total_listed(), 381_886);
+    assert!(stats.total_scored() <= stats.total_listed());
+
+    assert_eq!(transport.last_request().variables()["id"], 1);
+}
+
+#[tokio::test]
+async fn test_anime_get_popular() {
+    let (client, transport) = replay("anime_get_popular");
+    let anime = client.anime().get_popular(1, 2).await.unwrap();
+
+    assert_eq!(
+        anime.iter().map(|a| a.id).collect::<Vec<_>>(),
+        vec![16498, 21]
+    );
+    assert_eq!(anime[1].episodes, None);
+    assert_eq!(anime[1].next_airing_episode.as_ref().unwrap().episode, 1123);
+    assert_eq!(transport.last_request().variables()["perPage"], 2);
+}
+
+#[tokio::test]
+async fn test_anime_search_advanced() {
+    let (client, transport) = replay("anime_search_advanced");
+    let filter = MediaSearchFilter::new()
+        .genres(["Action"])
+        .year_range(2015, 2020)
+        .formats([MediaFormat::Tv])
+        .sort(MediaSort::ScoreDesc);
+    let anime = client.anime().search_advanced(&filter, 1, 2).await.unwrap();
+
+    assert_eq!(
+        anime.iter().map(|a| a.id).collect::<Vec<_>>(),
+        vec![21507, 21087]
+    );
+    for anime in &anime {
+        assert!(anime.genres.as_ref().unwrap().iter().any(|g| g == "Action"));
+        let year = anime.start_date.as_ref().unwrap().year.unwrap();
+        assert!((2015..=2020).contains(&year), "{year}");
+        assert_eq!(anime.format, Some(MediaFormat::Tv));
+    }
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["genres"], json!(["Action"]));
+    assert_eq!(variables["startDateGreater"], 20150000);
+    assert_eq!(variables["startDateLesser"], 20210000);
+    assert_eq!(variables["formats"], json!(["TV"]));
+    assert_eq!(variables["sort"], json!(["SCORE_DESC"]));
+}
+
+#[tokio::test]
+async fn test_anime_get_by_genre() {
+    let (client, transport) = replay("anime_get_by_genre");
+    let anime = client
+        .anime()
+        .get_by_genre_excluding(&["Action"], &["Romance"], MediaSort::ScoreDesc, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(anime.len(), 2);
+    for anime in &anime {
+        assert!(anime.genres.as_ref().unwrap().iter().any(|g| g == "Action"));
+    }
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["genres"], json!(["Action"]));
+    assert_eq!(variables["excludedGenres"], json!(["Romance"]));
+    assert_eq!(variables["sort"], json!(["SCORE_DESC"]));
+
+    // Without exclusions the argument is left out, and no genre at all is refused
+    client
+        .anime()
+        .get_by_genre(&["Action", "Drama"], MediaSort::PopularityDesc, 1, 2)
+        .await
+        .unwrap();
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["genres"], json!(["Action", "Drama"]));
+    assert!(variables.get("excludedGenres").is_none());
+    assert!(matches!(
+        client
+            .anime()
+            .get_by_genre(&[], MediaSort::ScoreDesc, 1, 2)
+            .await,
+        Err(AniListError::InvalidInput { field, .. }) if field == "genres"
+    ));
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_anime_get_by_tags() {
+    let (client, transport) = replay("anime_get_by_tags");
+    let anime = client
+        .anime()
+        .get_by_tags(&["Time Travel"], Some(80), 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        anime.iter().map(|a| a.id).collect::<Vec<_>>(),
+        vec![9253, 21355]
+    );
+    for anime in &anime {
+        let tag = anime
+            .tags
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|tag| tag.name == "Time Travel")
+            .unwrap();
+        assert!(tag.rank.unwrap() >= 80);
+        assert_eq!(tag.category.as_deref(), Some("Theme-Fantasy"));
+    }
+    let tragedy = &anime[0].tags.as_ref().unwrap()[3];
+    assert_eq!(tragedy.name, "Tragedy");
+    assert_eq!(tragedy.is_media_spoiler, Some(true));
+    assert_eq!(tragedy.is_general_spoiler, Some(false));
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["tags"], json!(["Time Travel"]));
+    assert_eq!(variables["minimumTagRank"], 80);
+    assert_eq!(variables["withTags"], true);
+    assert_eq!(variables["sort"], json!(["POPULARITY_DESC"]));
+
+    // Without a minimum rank the argument is left out, and no tag at all is refused
+    client
+        .anime()
+        .get_by_tags_sorted(&["Time Travel", "Isekai"], None, MediaSort::ScoreDesc, 1, 2)
+        .await
+        .unwrap();
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["tags"], json!(["Time Travel", "Isekai"]));
+    assert!(variables.get("minimumTagRank").is_none());
+    assert_eq!(variables["sort"], json!(["SCORE_DESC"]));
+    assert!(matches!(
+        client.anime().get_by_tags(&[], None, 1, 2).await,
+        Err(AniListError::InvalidInput { field, .. }) if field == "tags"
+    ));
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_anime_get_trending() {
+    let (client, transport) = replay("anime_get_trending");
+    let anime = client.anime().get_trending(1, 2).await.unwrap();
+
+    assert_eq!(anime.iter().map(|a| a.id).collect::<Vec<_>>(), [171018, 21]);
+    let next = anime[0].next_airing_episode.as_ref().unwrap();
+    assert_eq!((next.episode, next.media_id), (4, 171018));
+    assert_eq!(anime[0].status, Some(MediaStatus::Releasing));
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["sort"], json!(["TRENDING_DESC"]));
+    assert_eq!(variables["perPage"], 2);
+}
+
+#[tokio::test]
+async fn test_anime_get_by_ids() {
+    let (client, transport) = replay("anime_get_by_ids");
+    let anime = client.anime().get_by_ids(&[1, 5, 999999999]).await.unwrap();
+
+    let found: Vec<Option<i32>> = anime.iter().map(|a| a.as_ref().map(|a| a.id)).collect();
+    assert_eq!(found, [Some(1), Some(5), None]);
+    assert_eq!(anime[1].as_ref().unwrap().format, Some(MediaFormat::Movie));
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["ids"], json!([1, 5, 999999999]));
+    assert_eq!(variables["perPage"], 3);
+}
+
+#[tokio::test]
+async fn test_anime_search() {
+    let (client, transport) = replay("anime_search");
+    let anime = client.anime().search("cowboy bebop", 1, 2).await.unwrap();
+
+    assert_eq!(anime.iter().map(|a| a.id).collect::<Vec<_>>(), [1, 5]);
+    assert_eq!(anime[1].duration, Some(115));
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["search"], "cowboy bebop");
+    assert_eq!(variables["sort"], json!(["SEARCH_MATCH"]));
+
+    let page = client
+        .anime()
+        .search_page("cowboy  bebop", 1, 2)
+        .await
+        .unwrap();
+    assert_eq!(page.page_info.total, Some(2));
+    assert!(!page.has_next_page());
+    assert_eq!(
+        transport.last_request().variables()["search"],
+        "cowboy bebop"
+    );
+
+    // The only page ends the stream
+    let streamed: Vec<i32> = client
+        .anime()
+        .search_stream("cowboy bebop", 2)
+        .map(|anime| anime.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(streamed, [1, 5]);
+    assert_eq!(transport.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_anime_autocomplete() {
+    let (client, transport) = replay("anime_autocomplete");
+    let suggestions = client.anime().autocomplete("bebop", 2).await.unwrap();
+
+    let titles: Vec<_> = suggestions
+        .iter()
+        .map(|s| s.display_title().unwrap())
+        .collect();
+    assert_eq!(titles, ["Cowboy Bebop", "Cowboy Bebop: Tengoku no Tobira"]);
+    assert_eq!(suggestions[0].media_type, Some(MediaType::Anime));
+    assert_eq!(suggestions[1].season_year, Some(2001));
+    assert!(
+        suggestions[1]
+            .cover_image
+            .as_ref()
+            .unwrap()
+            .medium
+            .is_some()
+    );
+
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(
+        variables,
+        json!({"search": "bebop", "type": "ANIME", "perPage": 2})
+    );
+}
+
+#[tokio::test]
+async fn test_anime_get_by_season() {
+    let (mut client, transport) = replay("anime_get_by_season");
+    let anime = client
+        .anime()
+        .get_by_season("fall", 2023, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        anime.iter().map(|a| a.id).collect::<Vec<_>>(),
+        [154587, 161645]
+    );
+    for anime in &anime {
+        assert_eq!(anime.season, Some(MediaSeason::Fall));
+        assert_eq!(anime.season_year, Some(2023));
+    }
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["season"], "FALL");
+    assert_eq!(variables["year"], 2023);
+    assert_eq!(variables["sort"], json!(["POPULARITY_DESC"]));
+
+    // The current and next season go through the same document
+    client.anime().get_current_season(1, 2).await.unwrap();
+    let (season, year) = current_season();
+    assert_eq!(
+        transport.last_request().variables()["season"],
+        season.as_str()
+    );
+    assert_eq!(transport.last_request().variables()["year"], year);
+    client.anime().get_next_season(1, 2).await.unwrap();
+    let (season, year) = next_season((season, year));
+    assert_eq!(
+        transport.last_request().variables()["season"],
+        season.as_str()
+    );
+    assert_eq!(transport.last_request().variables()["year"], year);
+
+    // 132 entries would take three pages of 50; the limit stops at two
+    client.set_max_pages(2);
+    client.set_page_delay(Duration::ZERO);
+    let lineup = client.anime().get_by_season_all("FALL", 2023).await;
+    assert!(lineup.truncated);
+    assert_eq!(lineup.pages_fetched, 2);
+    assert_eq!(transport.last_request().variables()["perPage"], 50);
+}
+
+#[tokio::test]
+async fn test_anime_get_season_chart() {
+    let (client, transport) = replay_in_order(&["anime_get_by_season", "anime_get_season_chart"]);
+    let chart = client
+        .anime()
+        .get_season_chart(MediaSeason::Fall, 2023, 1, 2)
+        .await
+        .unwrap();
+
+    let kinds: Vec<(i32, SeasonEntryKind)> = chart
+        .iter()
+        .map(|(anime, kind)| (anime.id, *kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (154587, SeasonEntryKind::New),
+            (161645, SeasonEntryKind::New),
+            (145064, SeasonEntryKind::Leftover),
+        ]
+    );
+
+    // A past season: the leftovers are the shows that ended during or after it
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["startDateLesser"], 20231001);
+    assert_eq!(variables["endDateGreater"], 20230930);
+    assert!(variables.get("status").is_none());
+
+    // Classifying and finding neighbours send nothing
+    let (anime, _): (Vec<_>, Vec<_>) = chart.into_iter().unzip();
+    let reclassified = client
+        .anime()
+        .classify_season_entries(anime, MediaSeason::Summer, 2023);
+    assert_eq!(reclassified[2].1, SeasonEntryKind::New);
+    assert_eq!(
+        client.anime().get_season_neighbors(MediaSeason::Fall, 2023),
+        (
+            Some((MediaSeason::Summer, 2023)),
+            Some((MediaSeason::Winter, 2024))
+        )
+    );
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_anime_get_top_rated() {
+    let (client, transport) = replay("anime_get_top_rated");
+    let anime = client.anime().get_top_rated(1, 2).await.unwrap();
+
+    assert_eq!(
+        anime.iter().map(|a| a.id).collect::<Vec<_>>(),
+        [154587, 5114]
+    );
+    assert!(anime[0].average_score >= anime[1].average_score);
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["sort"], json!(["SCORE_DESC"]));
+    assert!(variables["popularityGreater"].is_null());
+
+    client
+        .anime()
+        .get_top_rated_with_min_popularity(1, 2, Some(10_000))
+        .await
+        .unwrap();
+    assert_eq!(
+        transport.last_request().variables()["popularityGreater"],
+        9_999
+    );
+}
+
+#[tokio::test]
+async fn test_anime_get_airing() {
+    let (client, transport) = replay("anime_get_airing");
+    let anime = client.anime().get_airing(1, 2).await.unwrap();
+
+    assert_eq!(anime.iter().map(|a| a.id).collect::<Vec<_>>(), [21, 171018]);
+    for anime in &anime {
+        assert_eq!(anime.status, Some(MediaStatus::Releasing));
+        assert!(anime.next_airing_episode.is_some());
+    }
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["status"], "RELEASING");
+    assert_eq!(variables["sort"], json!(["POPULARITY_DESC"]));
+}
+
+#[tokio::test]
+async fn test_anime_get_premiering_within() {
+    let (client, transport) = replay("anime_get_premiering_within");
+    let anime = client
+        .anime()
+        .get_premiering_within(90, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(anime.iter().map(|a| a.id).collect::<Vec<_>>(), [176496]);
+    assert_eq!(anime[0].status, Some(MediaStatus::NotYetReleased));
+    assert_eq!(anime[0].average_score, None);
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["status"], "NOT_YET_RELEASED");
+    assert_eq!(variables["sort"], json!(["START_DATE"]));
+    assert!(variables["startDateGreater"].as_i64() < variables["startDateLesser"].as_i64());
+}
+
+#[tokio::test]
+async fn test_manga_get_by_id() {
+    let (client, _) = replay("manga_get_by_id");
+    let manga = client.manga().get_by_id(30013).await.unwrap();
+
+    assert_eq!(manga.id, 30013);
+    assert_eq!(manga.chapters, None);
+    assert_eq!(manga.average_score, Some(92));
+    assert_eq!(manga.external_links.as_ref().unwrap()[0].site, "VIZ");
+    let tags = manga.tags.as_ref().unwrap();
+    assert_eq!(tags[0].category.as_deref(), Some("Theme/Fantasy"));
+    assert_eq!(manga.visible_tags().len(), 2);
+    let best = manga.best_ranking().unwrap();
+    assert_eq!((best.rank, best.format), (10, Some(MediaFormat::Manga)));
+    let envelope = client.manga().get_by_id_with_meta(30013).await.unwrap();
+    assert_eq!(envelope.value.id, 30013);
+    assert_eq!(envelope.rate_limit().unwrap().remaining, 87);
+}
+
+#[tokio::test]
+async fn test_manga_get_stats() {
+    let (client, transport) = replay("manga_get_stats");
+    let stats = client.manga().get_stats(30013).await.unwrap();
+
+    assert_eq!(stats.score_distribution.len(), 10);
+    let reading = stats
+        .status_distribution
+        .iter()
+        .find(|bucket| bucket.status == MediaListStatus::Current)
+        .unwrap();
+    assert_eq!(reading.amount, 139_560);
+    assert!(stats.total_scored() <= stats.total_listed());
+
+    // The same document serves both media types
+    let request = transport.last_request();
+    assert_eq!(request.query(), queries::anime::GET_STATS);
+    assert_eq!(request.variables()["id"], 30013);
+}
+
+#[tokio::test]
+async fn test_manga_get_popular() {
+    let (client, transport) = replay("manga_get_popular");
+    let manga = client.manga().get_popular(1, 2).await.unwrap();
+
+    assert_eq!(
+        manga.iter().map(|m| m.id).collect::<Vec<_>>(),
+        [30013, 30002]
+    );
+    assert_eq!(manga[0].status, Some(MediaStatus::Releasing));
+    assert_eq!(manga[0].chapters, None);
+    assert!(manga[1].updated_at.is_some());
+    assert_eq!(transport.last_request().variables()["perPage"], 2);
+}
+
+#[tokio::test]
+async fn test_manga_get_trending() {
+    let (client, _) = replay("manga_get_trending");
+    let manga = client.manga().get_trending(1, 2).await.unwrap();
+
+    assert_eq!(
+        manga.iter().map(|m| m.id).collect::<Vec<_>>(),
+        [132029, 30013]
+    );
+    assert_eq!(manga[0].title.english.as_deref(), Some("Dandadan"));
+}
+
+#[tokio::test]
+async fn test_manga_search() {
+    let (client, transport) = replay("manga_search");
+    let manga = client
+        .manga()
+        .search("  fullmetal   alchemist ", 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(manga.len(), 1);
+    assert_eq!(manga[0].format, Some(MediaFormat::Manga));
+    assert_eq!((manga[0].chapters, manga[0].volumes), (Some(116), Some(27)));
+    assert_eq!(
+        transport.last_request().variables()["search"],
+        "fullmetal alchemist"
+    );
+}
+
+#[tokio::test]
+async fn test_manga_autocomplete() {
+    let (client, transport) = replay("manga_autocomplete");
+    let suggestions = client.manga().autocomplete("berserk", 2).await.unwrap();
+
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].display_title(), Some("Berserk"));
+    assert_eq!(suggestions[0].media_type, Some(MediaType::Manga));
+    assert_eq!(suggestions[0].season_year, None);
+    assert_eq!(transport.last_request().variables()["type"], "MANGA");
+}
+
+#[tokio::test]
+async fn test_manga_get_top_rated() {
+    let (client, transport) = replay("manga_get_top_rated");
+    let manga = client.manga().get_top_rated(1, 2).await.unwrap();
+
+    assert_eq!(
+        manga.iter().map(|m| m.id).collect::<Vec<_>>(),
+        [30002, 30013]
+    );
+    assert!(transport.last_request().variables()["popularityGreater"].is_null());
+
+    client
+        .manga()
+        .get_top_rated_with_min_popularity(1, 2, Some(1))
+        .await
+        .unwrap();
+    assert_eq!(transport.last_request().variables()["popularityGreater"], 0);
+}
+
+#[tokio::test]
+async fn test_manga_get_releasing() {
+    let (client, transport) = replay("manga_get_releasing");
+    let manga = client.manga().get_releasing(1, 2).await.unwrap();
+
+    assert_eq!(
+        manga.iter().map(|m| m.id).collect::<Vec<_>>(),
+        [30013, 105778]
+    );
+    for manga in &manga {
+        assert_eq!(manga.status, Some(MediaStatus::Releasing));
+    }
+
+    let page = client.manga().get_releasing_page(1, 2).await.unwrap();
+    assert_eq!(page.page_info.total, Some(2));
+    assert!(!page.has_next_page());
+
+    let streamed: Vec<i32> = client
+        .manga()
+        .get_releasing_stream(2)
+        .map(|manga| manga.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(streamed, [30013, 105778]);
+    assert_eq!(transport.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_manga_get_completed() {
+    let (client, _) = replay("manga_get_completed");
+    let manga = client.manga().get_completed(1, 2).await.unwrap();
+
+    assert_eq!(
+        manga.iter().map(|m| m.id).collect::<Vec<_>>(),
+        [30025, 30001]
+    );
+    for manga in &manga {
+        assert_eq!(manga.status, Some(MediaStatus::Finished));
+        assert!(manga.chapters.is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_manga_get_adaptation_airing() {
+    let (client, transport) = replay("manga_get_adaptation_airing");
+    let (adaptation, airing) = client
+        .manga()
+        .get_adaptation_airing(132029)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(adaptation.id, 171018);
+    assert_eq!(adaptation.media_type, Some(MediaType::Anime));
+    assert_eq!(adaptation.status, Some(MediaStatus::Releasing));
+    assert_eq!((airing.media_id, airing.episode), (171018, 4));
+    assert_eq!(transport.last_request().variables()["id"], 132029);
+}
+
+#[tokio::test]
+async fn test_character_get_by_id() {
+    let (client, _) = replay("character_get_by_id");
+    let character = client.character().get_by_id(11).await.unwrap();
+
+    assert_eq!(character.id, 11);
+    let name = character.name;
+    assert_eq!(name.full.as_deref(), Some("Edward Elric"));
+    assert_eq!(name.alternative.as_ref().unwrap().len(), 3);
+    assert_eq!(name.alternative_spoiler.as_deref(), Some(&[][..]));
+    assert_eq!(character.date_of_birth.as_ref().unwrap().day, Some(3));
+    assert_eq!(character.age.as_deref(), Some("15-16"));
+}
+
+#[tokio::test]
+async fn test_character_get_popular() {
+    let (client, transport) = replay("character_get_popular");
+    let characters = client.character().get_popular(1, 2).await.unwrap();
+
+    assert_eq!(
+        characters.iter().map(|c| c.id).collect::<Vec<_>>(),
+        [417, 45627]
+    );
+    // A single name still decodes, with the rest left empty
+    let levi = &characters[1].name;
+    assert_eq!(levi.full.as_deref(), Some("Levi"));
+    assert_eq!(levi.last, None);
+    assert_eq!(characters[0].blood_type.as_deref(), Some("A"));
+    assert_eq!(transport.last_request().variables()["perPage"], 2);
+}
+
+#[tokio::test]
+async fn test_character_search() {
+    let (client, transport) = replay("character_search");
+    let characters = client.character().search("edward", 1, 2).await.unwrap();
+
+    // Same-named characters are told apart by their best-known media
+    let labels: Vec<_> = characters
+        .iter()
+        .map(|c| c.primary_media_title().unwrap())
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "Hagane no Renkinjutsushi: FULLMETAL ALCHEMIST",
+            "Cowboy Bebop"
+        ]
+    );
+    let ed = &characters[1].name;
+    assert_eq!(
+        ed.alternative_spoiler.as_deref(),
+        Some(&["Françoise Appledelhi".to_string()][..])
+    );
+    assert_eq!(transport.last_request().variables()["search"], "edward");
+}
+
+#[tokio::test]
+async fn test_character_get_today_birthday() {
+    let (client, _) = replay("character_get_today_birthday");
+    let characters = client.character().get_today_birthday(1, 1).await.unwrap();
+
+    assert_eq!(characters.len(), 1);
+    let birthday = characters[0].date_of_birth.as_ref().unwrap();
+    assert_eq!(
+        (birthday.year, birthday.month, birthday.day),
+        (None, Some(10), Some(10))
+    );
+}
+
+#[tokio::test]
+async fn test_character_get_most_favorited() {
+    let (client, _) = replay("character_get_most_favorited");
+    let characters = client.character().get_most_favorited(1, 2).await.unwrap();
+
+    assert_eq!(
+        characters.iter().map(|c| c.id).collect::<Vec<_>>(),
+        [417, 45627]
+    );
+    assert!(characters[0].favourites >= characters[1].favourites);
+    // Not selected by this document
+    assert_eq!(characters[0].is_favourite, None);
+}
+
+#[tokio::test]
+async fn test_staff_get_by_id() {
+    let (client, _) = replay("staff_get_by_id");
+    let staff = client.staff().get_by_id(95185).await.unwrap();
+
+    assert_eq!(staff.id, 95185);
+    assert_eq!(staff.age, Some(59));
+    assert_eq!(staff.years_active.as_deref(), Some(&[1988][..]));
+    assert_eq!(staff.primary_occupations.as_ref().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_staff_get_popular() {
+    let (client, transport) = replay("staff_get_popular");
+    let staff = client.staff().get_popular(1, 2).await.unwrap();
+
+    assert_eq!(
+        staff.iter().map(|s| s.id).collect::<Vec<_>>(),
+        [95011, 95672]
+    );
+    assert_eq!(staff[0].language_v2.as_deref(), Some("Japanese"));
+    assert_eq!(staff[1].primary_occupations.as_ref().unwrap().len(), 2);
+    let death = staff[0].date_of_death.as_ref().unwrap();
+    assert_eq!((death.year, death.month, death.day), (None, None, None));
+    assert_eq!(transport.last_request().variables()["perPage"], 2);
+}
+
+#[tokio::test]
+async fn test_staff_search() {
+    let (client, transport) = replay("staff_search");
+    let staff = client.staff().search("watanabe", 1, 2).await.unwrap();
+
+    assert_eq!(
+        staff.iter().map(|s| s.id).collect::<Vec<_>>(),
+        [95185, 100130]
+    );
+    assert_eq!(staff[0].home_town.as_deref(), Some("Kyoto, Japan"));
+    assert_eq!(staff[1].age, Some(58));
+    assert_eq!(transport.last_request().variables()["search"], "watanabe");
+}
+
+#[tokio::test]
+async fn test_staff_get_today_birthday() {
+    let (client, _) = replay("staff_get_today_birthday");
+    let staff = client.staff().get_today_birthday(1, 1).await.unwrap();
+
+    assert_eq!(staff.len(), 1);
+    let birthday = staff[0].date_of_birth.as_ref().unwrap();
+    assert_eq!((birthday.month, birthday.day), (Some(1), Some(1)));
+    assert_eq!(
+        staff[0].primary_occupations.as_deref(),
+        Some(&["Mangaka".to_string()][..])
+    );
+}
+
+#[tokio::test]
+async fn test_staff_get_most_favorited() {
+    let (client, _) = replay("staff_get_most_favorited");
+    let staff = client.staff().get_most_favorited(1, 2).await.unwrap();
+
+    assert_eq!(
+        staff.iter().map(|s| s.id).collect::<Vec<_>>(),
+        [95011, 95672]
+    );
+    assert!(staff[0].favourites >= staff[1].favourites);
+    // Not selected by this document
+    assert_eq!(staff[0].language_v2, None);
+}
+
+#[tokio::test]
+async fn test_studio_get_by_id() {
+    let (client, _) = replay("studio_get_by_id");
+    let studio = client.studio().get_by_id(14).await.unwrap();
+
+    assert_eq!(studio.id, 14);
+    assert_eq!(studio.name, "Sunrise");
+    assert_eq!(studio.favourites, Some(6500));
+}
+
+#[tokio::test]
+async fn test_studio_get_popular() {
+    let (client, transport) = replay("studio_get_popular");
+    let studios = client.studio().get_popular(1, 2).await.unwrap();
+
+    let names: Vec<_> = studios.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["Kyoto Animation", "ufotable"]);
+    assert!(studios.iter().all(|s| s.is_animation_studio));
+    assert_eq!(transport.last_request().variables()["perPage"], 2);
+}
+
+#[tokio::test]
+async fn test_studio_search() {
+    let (client, transport) = replay("studio_search");
+    let studios = client.studio().search("bones", 1, 2).await.unwrap();
+
+    assert_eq!(studios.iter().map(|s| s.id).collect::<Vec<_>>(), [4, 7141]);
+    assert_eq!(studios[1].favourites, Some(85));
+    assert_eq!(transport.last_request().variables()["search"], "bones");
+}
+
+#[tokio::test]
+async fn test_studio_get_most_favorited() {
+    let (client, _) = replay("studio_get_most_favorited");
+    let studios = client.studio().get_most_favorited(1, 2).await.unwrap();
+
+    assert_eq!(studios.iter().map(|s| s.id).collect::<Vec<_>>(), [2, 43]);
+    assert_eq!(
+        studios[0].site_url.as_deref(),
+        Some("https://anilist.co/studio/2")
+    );
+}
+
+#[tokio::test]
+async fn test_studio_toggle_favorite() {
+    let (client, transport) = replay("studio_toggle_favorite");
+    let studio = client.studio().toggle_favorite(14).await.unwrap();
+
+    assert_eq!(studio.id, 14);
+    assert_eq!(studio.is_favourite, Some(true));
+    assert_eq!(studio.favourites, Some(6501));
+    let request = transport.last_request();
+    assert!(request.query().starts_with("mutation"));
+    assert_eq!(request.variables()["studioId"], 14);
+}
+
+#[tokio::test]
+async fn test_user_get_by_id() {
+    let (client, _) = replay("user_get_by_id");
+    let user = client.user().get_by_id(5123456).await.unwrap();
+
+    assert_eq!(user.id, 5123456);
+    assert_eq!(user.donator_tier, Some(3));
+    let statistics = user.statistics.as_ref().unwrap();
+    assert_eq!(statistics.anime.as_ref().unwrap().count, Some(412));
+    assert_eq!(statistics.manga.as_ref().unwrap().volumes_read, Some(310));
+
+    let favourites = user.favourites.as_ref().unwrap();
+    assert!(favourites.is_truncated());
+    assert_eq!(favourites.anime.as_ref().unwrap().total(), Some(26));
+}
+
+#[tokio::test]
+async fn test_user_get_by_id_as_html() {
+    let (client, transport) = replay("user_get_by_id_as_html");
+    let user = client.user().get_by_id_as_html(5123456).await.unwrap();
+
+    assert_eq!(
+        user.about.as_deref(),
+        Some("<p>Hello <strong>world</strong></p>")
+    );
+    assert_eq!(transport.last_request().variables()["asHtml"], true);
+}
+
+#[tokio::test]
+async fn test_user_get_by_name() {
+    let (client, transport) = replay("user_get_by_name");
+    let user = client.user().get_by_name("Donator").await.unwrap();
+
+    assert_eq!(user.id, 5123456);
+    assert_eq!(user.about.as_deref(), Some("Hello __world__"));
+    assert_eq!(transport.last_request().variables()["name"], "Donator");
+
+    let (client, transport) = replay("user_get_by_name_as_html");
+    let user = client.user().get_by_name_as_html("Donator").await.unwrap();
+    assert_eq!(
+        user.about.as_deref(),
+        Some("<p>Hello <strong>world</strong></p>")
+    );
+    assert_eq!(transport.last_request().variables()["asHtml"], true);
+}
+
+#[tokio::test]
+async fn test_user_get_current_user() {
+    let (client, transport) = replay("user_get_current_user");
+    let viewer = client.user().get_current_user().await.unwrap();
+
+    assert_eq!(viewer.id, 5123456);
+    assert_eq!(viewer.unread_notification_count, Some(7));
+    assert_eq!(transport.last_request().variables()["asHtml"], false);
+
+    let response = client.user().get_current_user_with_meta().await.unwrap();
+    assert_eq!(response.value.name, "Donator");
+
+    let (client, transport) = replay("user_get_current_user_as_html");
+    let viewer = client.user().get_current_user_as_html().await.unwrap();
+    assert_eq!(
+        viewer.about.as_deref(),
+        Some("<p>Hello <strong>world</strong></p>")
+    );
+    assert_eq!(transport.last_request().variables()["asHtml"], true);
+}
+
+#[tokio::test]
+async fn test_user_get_current_user_anime_list() {
+    let (client, transport) =
+        replay_in_order(&["user_get_current_user", "user_get_current_user_anime_list"]);
+    let entries = client
+        .user()
+        .get_current_user_anime_list(None)
+        .await
+        .unwrap();
+
+    // Entries from every status list, in AniList's order
+    assert_eq!(
+        entries.iter().map(|e| e.media_id).collect::<Vec<_>>(),
+        [21, 171018, 154587, 1, 176496]
+    );
+    assert!(entries.iter().all(|e| e.user_id == 5123456));
+    assert_eq!(entries[2].status, MediaListStatus::Completed);
+    assert_eq!(entries[2].score, 95.0);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let variables = requests[1].variables();
+    assert_eq!(variables["userId"], 5123456);
+    assert_eq!(variables["type"], "ANIME");
+    assert_eq!(variables["status"], Value::Null);
+    assert_eq!(variables["chunk"], 1);
+}
+
+#[tokio::test]
+async fn test_user_get_current_user_anime_list_all() {
+    let (client, transport) = replay_in_order(&[
+        "user_get_current_user",
+        "user_get_current_user_anime_list_all",
+    ]);
+    let all = client.user().get_current_user_anime_list_all(None).await;
+
+    assert!(all.error.is_none());
+    assert!(!all.truncated);
+    assert_eq!(all.pages_fetched, 1);
+    assert_eq!(
+        all.items.iter().map(|e| e.media_id).collect::<Vec<_>>(),
+        [1, 21, 154587, 171018, 176496]
+    );
+    assert_eq!(transport.requests()[1].variables()["perPage"], 50);
+}
+
+#[tokio::test]
+async fn test_user_get_list_collection() {
+    let (client, transport) = replay("user_get_list_collection");
+    let collection = client
+        .user()
+        .get_list_collection(5123456, MediaType::Manga, Some(MediaListStatus::Current), 2)
+        .await
+        .unwrap();
+
+    assert_eq!(collection.entries.len(), 2);
+    assert_eq!(collection.chunks_fetched, 1);
+    assert!(!collection.truncated);
+    assert_eq!(collection.entries[0].progress_volumes, Some(109));
+
+    let request = transport.last_request();
+    assert_eq!(request.variables()["type"], "MANGA");
+    assert_eq!(request.variables()["status"], "CURRENT");
+}
+
+#[tokio::test]
+async fn test_user_get_next_up() {
+    let (client, _) = replay_in_order(&["user_get_current_user", "user_get_next_up"]);
+    let next_up = client.user().get_next_up().await.unwrap();
+
+    // Dandadan is caught up with the aired episodes, so only One Piece is left
+    assert_eq!(next_up.len(), 1);
+    assert_eq!(next_up[0].entry.media_id, 21);
+    assert_eq!(next_up[0].available_episodes, 12);
+}
+
+#[tokio::test]
+async fn test_user_get_list_entries_updated_since() {
+    let (client, transport) = replay("user_get_list_entries_updated_since");
+    let updated = client
+        .user()
+        .get_list_entries_updated_since(5123456, MediaType::Anime, 1728777600)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        updated
+            .entries
+            .iter()
+            .map(|e| e.media_id)
+            .collect::<Vec<_>>(),
+        [171018, 21]
+    );
+    assert_eq!(updated.next_checkpoint(), 1729378800);
+    assert_eq!(updated.pages_fetched, 1);
+    // The page reports more, but older entries were reached
+    assert_eq!(transport.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_user_search() {
+    let (client, transport) = replay("user_search");
+    let users = client.user().search("donator", 1, 2).await.unwrap();
+
+    assert_eq!(
+        users.iter().map(|u| u.name.as_str()).collect::<Vec<_>>(),
+        ["Donator", "DonatorFan"]
+    );
+    assert_eq!(users[1].donator_badge, None);
+    assert_eq!(transport.last_request().variables()["search"], "donator");
+}
+
+#[tokio::test]
+async fn test_user_get_most_anime_watched() {
+    let (client, _) = replay("user_get_most_anime_watched");
+    let users = client.user().get_most_anime_watched(1, 2).await.unwrap();
+
+    let minutes: Vec<_> = users
+        .iter()
+        .map(|u| {
+            u.statistics
+                .as_ref()
+                .unwrap()
+                .anime
+                .as_ref()
+                .unwrap()
+                .minutes_watched
+        })
+        .collect();
+    assert_eq!(minutes, [Some(4511820), Some(210345)]);
+}
+
+#[tokio::test]
+async fn test_user_get_most_manga_read() {
+    let (client, _) = replay("user_get_most_manga_read");
+    let users = client.user().get_most_manga_read(1, 2).await.unwrap();
+
+    let chapters: Vec<_> = users
+        .iter()
+        .map(|u| {
+            u.statistics
+                .as_ref()
+                .unwrap()
+                .manga
+                .as_ref()
+                .unwrap()
+                .chapters_read
+        })
+        .collect();
+    assert_eq!(chapters, [Some(412300), Some(4120)]);
+}
+
+#[tokio::test]
+async fn test_user_toggle_follow() {
+    let (client, transport) = replay("user_toggle_follow");
+    let user = client.user().toggle_follow(312).await.unwrap();
+
+    assert_eq!(user.name, "Replier");
+    assert_eq!(user.is_following, Some(true));
+    assert_eq!(transport.last_request().variables()["userId"], 312);
+}
+
+#[tokio::test]
+async fn test_user_update_about() {
+    let (client, transport) = replay("user_update_about");
+    let about = client.user().update_about("Hello __world__").await.unwrap();
+
+    assert_eq!(about, "Hello __world__");
+    assert_eq!(
+        transport.last_request().variables()["about"],
+        "Hello __world__"
+    );
+
+    let options = ProfileUpdateOptions { dry_run: true };
+    let about = client
+        .user()
+        .update_about_with_options("Dry run", options)
+        .await
+        .unwrap();
+    assert_eq!(about, "Dry run");
+    assert_eq!(transport.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_user_toggle_favorite() {
+    let (client, transport) = replay("user_toggle_favorite");
+    let toggled = client
+        .user()
+        .toggle_favorite(Some(171018), None)
+        .await
+        .unwrap();
+
+    assert!(toggled);
+    let request = transport.last_request();
+    assert!(
+        request
+            .query()
+            .contains("ToggleFavourite(animeId: $animeId")
+    );
+    assert_eq!(request.variables()["animeId"], 171018);
+    assert!(request.variables().get("mangaId").is_none());
+}
+
+#[tokio::test]
+async fn test_user_save_list_entry() {
+    let (client, transport) = replay("user_save_list_entry");
+    let entry = SaveListEntry::for_media(21).progress(1120).private(true);
+    let saved = client.user().save_list_entry(&entry).await.unwrap();
+
+    assert_eq!(saved.id, 98765401);
+    assert_eq!(saved.progress, 1120);
+    assert!(saved.private);
+    assert!(saved.media.is_none());
+
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["mediaId"], 21);
+    assert_eq!(variables["progress"], 1120);
+    assert_eq!(variables["private"], true);
+}
+
+#[tokio::test]
+async fn test_user_update_media_list_progress() {
+    let (client, transport) = replay("user_update_media_list_progress");
+    client
+        .user()
+        .update_media_list_progress(98765401, 1121)
+        .await
+        .unwrap();
+
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["saveMediaListEntryId"], 98765401);
+    assert_eq!(variables["progress"], 1121);
+}
+
+#[tokio::test]
+async fn test_user_update_media_list_status() {
+    let (client, transport) = replay("user_update_media_list_status");
+    let completed_at = FuzzyDate {
+        year: Some(2024),
+        month: Some(12),
+        day: Some(20),
+    };
+    client
+        .user()
+        .update_media_list_status(98765402, MediaListStatus::Completed, Some(completed_at))
+        .await
+        .unwrap();
+
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["status"], "COMPLETED");
+    assert_eq!(
+        variables["completedAt"],
+        json!({"year": 2024, "month": 12, "day": 20})
+    );
+}
+
+#[tokio::test]
+async fn test_user_get_favourite_set() {
+    let (client, transport) = replay("user_get_favourite_set");
+    let set = client.user().get_favourite_set().await.unwrap();
+
+    assert_eq!(set.anime.iter().copied().collect::<Vec<_>>(), [1, 205]);
+    assert_eq!(set.manga.iter().copied().collect::<Vec<_>>(), [30013]);
+    assert!(set.staff.is_empty());
+    // No connection reports a next page
+    assert_eq!(transport.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_user_toggle_favourite_item() {
+    let (client, transport) = replay("user_toggle_favourite_item");
+    let item = FavouriteItem {
+        kind: FavouriteKind::Anime,
+        id: 16498,
+    };
+    client.user().toggle_favourite_item(item).await.unwrap();
+
+    assert_eq!(transport.last_request().variables()["animeId"], 16498);
+}
+
+#[tokio::test]
+async fn test_user_sync_favourites() {
+    let (client, transport) = replay_in_order(&[
+        "user_get_favourite_set",
+        "user_toggle_favourite_item",
+        "user_toggle_favourite_item",
+    ]);
+    let mut desired = FavouriteSet::default();
+    desired.anime.extend([1, 16498]);
+    desired.manga.insert(30013);
+    desired.characters.insert(1);
+    desired.studios.insert(14);
+    let options = FavouriteSyncOptions {
+        dry_run: false,
+        delay_ms: 0,
+    };
+    let report = client
+        .user()
+        .sync_favourites(&desired, options)
+        .await
+        .unwrap();
+
+    let anime = |id| FavouriteItem {
+        kind: FavouriteKind::Anime,
+        id,
+    };
+    assert_eq!(report.added, [anime(16498)]);
+    assert_eq!(report.removed, [anime(205)]);
+    assert_eq!(report.skipped.len(), 4);
+    assert!(report.failed.is_empty());
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[1].variables()["animeId"], 16498);
+    assert_eq!(requests[2].variables()["animeId"], 205);
+}
+
+#[tokio::test]
+async fn test_activity_get_activity_by_id() {
+    let (client, _) = replay("activity_get_activity_by_id");
+    let activity = client
+        .activity()
+        .get_activity_by_id(701234567)
+        .await
+        .unwrap();
+
+    assert_eq!(activity.id, 701234567);
+    assert_eq!(activity.activity_type, Some(ActivityType::Text));
+    assert_eq!(activity.like_count, 14);
+    assert_eq!(activity.user.as_ref().unwrap().name, "Donator");
+}
+
+#[tokio::test]
+async fn test_activity_get_recent_activities() {
+    let (client, transport) = replay("activity_get_recent_activities");
+    let activities = client.activity().get_recent_activities(1, 3).await.unwrap();
+
+    let types: Vec<_> = activities.iter().map(|a| a.activity_type).collect();
+    assert_eq!(
+        types,
+        [
+            Some(ActivityType::Message),
+            Some(ActivityType::AnimeList),
+            Some(ActivityType::MangaList)
+        ]
+    );
+    // Messages have a messenger and recipient but no author
+    assert!(activities[0].user.is_none());
+    assert_eq!(activities[1].media.as_ref().unwrap().id, 171018);
+    assert!(transport.last_request().variables().get("typeIn").is_none());
+}
+
+#[tokio::test]
+async fn test_activity_get_recent_activities_filtered() {
+    let (client, transport) = replay("activity_get_recent_activities_filtered");
+    let filter = ActivityFeedFilter::new().types(&[ActivityType::AnimeList]);
+    let activities = client
+        .activity()
+        .get_recent_activities_filtered(1, 2, &filter)
+        .await
+        .unwrap();
+
+    assert_eq!(activities.len(), 2);
+    assert!(
+        activities
+            .iter()
+            .all(|a| a.activity_type == Some(ActivityType::AnimeList))
+    );
+    assert_eq!(
+        transport.last_request().variables()["typeIn"],
+        json!(["ANIME_LIST"])
+    );
+}
+
+#[tokio::test]
+async fn test_activity_get_following_activities() {
+    let (client, _) = replay("activity_get_following_activities");
+    let activities = client
+        .activity()
+        .get_following_activities(1, 2)
+        .await
+        .unwrap();
+
+    assert!(activities.iter().all(|a| a.user_id == Some(312)));
+    assert_eq!(activities[0].activity_type, Some(ActivityType::Text));
+}
+
+#[tokio::test]
+async fn test_activity_get_following_activities_filtered() {
+    let (client, transport) = replay("activity_get_following_activities_filtered");
+    let filter = ActivityFeedFilter::new().types(&[ActivityType::AnimeList]);
+    let activities = client
+        .activity()
+        .get_following_activities_filtered(1, 2, &filter)
+        .await
+        .unwrap();
+
+    assert_eq!(activities.len(), 1);
+    assert_eq!(activities[0].media.as_ref().unwrap().id, 21);
+    assert_eq!(
+        transport.last_request().variables()["typeIn"],
+        json!(["ANIME_LIST"])
+    );
+}
+
+#[tokio::test]
+async fn test_activity_get_user_activities() {
+    let (mut client, transport) = replay("activity_get_user_activities");
+    let activities = client
+        .activity()
+        .get_user_activities(5123456, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        activities.iter().map(|a| a.id).collect::<Vec<_>>(),
+        [812000003, 701234567]
+    );
+    assert_eq!(transport.last_request().variables()["userId"], 5123456);
+
+    let page = client
+        .activity()
+        .get_user_activities_page(5123456, 1, 2)
+        .await
+        .unwrap();
+    assert_eq!(page.page_info.total, Some(340));
+    assert!(page.has_next_page());
+
+    client.set_page_delay(Duration::ZERO);
+    let streamed: Vec<_> = client
+        .activity()
+        .get_user_activities_stream(5123456, 2)
+        .take(3)
+        .collect()
+        .await;
+    assert!(streamed.iter().all(Result::is_ok));
+    assert_eq!(transport.requests()[3].variables()["page"], 2);
+}
+
+#[tokio::test]
+async fn test_activity_get_user_activities_filtered() {
+    let (client, transport) = replay("activity_get_user_activities_filtered");
+    let filter = ActivityFeedFilter::new().types(&[ActivityType::Text]);
+    let activities = client
+        .activity()
+        .get_user_activities_filtered(5123456, 1, 2, &filter)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        activities.iter().map(|a| a.id).collect::<Vec<_>>(),
+        [701234567, 690112233]
+    );
+    assert_eq!(
+        transport.last_request().variables()["typeIn"],
+        json!(["TEXT"])
+    );
+}
+
+#[tokio::test]
+async fn test_activity_get_text_activities() {
+    let (client, _) = replay("activity_get_text_activities");
+    let activities = client.activity().get_text_activities(1, 2).await.unwrap();
+
+    assert_eq!(activities.len(), 2);
+    assert_eq!(
+        activities[0].text,
+        "Dandadan episode 3 had no right to be that good."
+    );
+    assert_eq!(activities[1].user.as_ref().unwrap().name, "Donator");
+}
+
+#[tokio::test]
+async fn test_activity_get_activity_detail() {
+    let (client, _) = replay("activity_get_activity_detail");
+    let activity = client
+        .activity()
+        .get_activity_detail(812000010)
+        .await
+        .unwrap();
+
+    let ActivityUnion::Message(message) = activity else {
+        panic!("expected a message activity");
+    };
+    assert_eq!(
+        message.message.as_deref(),
+        Some("Thanks for the Frieren review!")
+    );
+    assert_eq!(message.messenger.as_ref().unwrap().id, 312);
+    assert_eq!(message.is_private, Some(false));
+}
+
+#[tokio::test]
+async fn test_activity_get_activity_replies() {
+    let (client, transport) = replay("activity_get_activity_replies");
+    let replies = client
+        .activity()
+        .get_activity_replies(701234567, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(replies.len(), 2);
+    assert!(replies.iter().all(|r| r.activity_id == Some(701234567)));
+    assert_eq!(replies[0].user.as_ref().unwrap().name, "Replier");
+    assert_eq!(
+        transport.last_request().variables()["activityId"],
+        701234567
+    );
+}
+
+#[tokio::test]
+async fn test_activity_create_text_activity() {
+    let (client, transport) = replay("activity_create_text_activity");
+    let activity = client
+        .activity()
+        .create_text_activity("Dandadan episode 3 lived up to the hype.")
+        .await
+        .unwrap();
+
+    assert_eq!(activity.id, 812000020);
+    assert_eq!(activity.reply_count, 0);
+    assert_eq!(
+        transport.last_request().variables()["text"],
+        "Dandadan episode 3 lived up to the hype."
+    );
+}
+
+#[tokio::test]
+async fn test_activity_post_activity_reply() {
+    let (client, transport) = replay("activity_post_activity_reply");
+    let reply = client
+        .activity()
+        .post_activity_reply(812000007, "The opening alone sold me on it.")
+        .await
+        .unwrap();
+
+    assert_eq!(reply.activity_id, Some(812000007));
+    // The mutation only selects the medium avatar
+    let avatar = reply.user.as_ref().unwrap().avatar.as_ref().unwrap();
+    assert!(avatar.large.is_none());
+    assert_eq!(
+        transport.last_request().variables()["activityId"],
+        812000007
+    );
+}
+
+#[tokio::test]
+async fn test_activity_toggle_activity_like() {
+    let (client, transport) = replay("activity_toggle_activity_like");
+    let activity = client
+        .activity()
+        .toggle_activity_like(812000001)
+        .await
+        .unwrap();
+
+    assert_eq!(activity.is_liked, Some(true));
+    assert_eq!(activity.like_count, 9);
+    let request = transport.last_request();
+    assert!(
+        request
+            .query()
+            .contains("ToggleLikeV2(id: $id, type: $type)")
+    );
+    assert_eq!(request.variables()["id"], 812000001);
+    assert_eq!(request.variables()["type"], "ACTIVITY");
+}
+
+#[tokio::test]
+async fn test_activity_toggle_activity_reply_like() {
+    let (client, transport) = replay("activity_toggle_activity_reply_like");
+    let reply = client
+        .activity()
+        .toggle_activity_reply_like(30001)
+        .await
+        .unwrap();
+
+    assert_eq!(reply.like_count, 4);
+    assert_eq!(
+        transport.last_request().variables()["type"],
+        "ACTIVITY_REPLY"
+    );
+}
+
+#[tokio::test]
+async fn test_activity_get_subscribed_activities() {
+    let (client, _) = replay("activity_get_subscribed_activities");
+    let page = client
+        .activity()
+        .get_subscribed_activities(1, 2)
+        .await
+        .unwrap();
+
+    assert!(!page.has_next_page());
+    assert!(page.items.iter().all(|a| a.is_subscribed == Some(true)));
+}
+
+#[tokio::test]
+async fn test_activity_toggle_activity_subscription() {
+    let (client, transport) = replay("activity_toggle_activity_subscription");
+    let activity = client
+        .activity()
+        .toggle_activity_subscription(701234567, true)
+        .await
+        .unwrap();
+
+    assert_eq!(activity.is_subscribed, Some(true));
+    assert_eq!(transport.last_request().variables()["subscribe"], true);
+}
+
+#[tokio::test]
+async fn test_activity_get_my_latest_list_activity() {
+    let (client, transport) = replay_in_order(&[
+        "client_health_check",
+        "activity_get_my_latest_list_activity",
+    ]);
+    let activity = client
+        .activity()
+        .get_my_latest_list_activity(171018)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(activity.progress.as_deref(), Some("3"));
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["userId"], 5123456);
+    assert_eq!(variables["mediaId"], 171018);
+}
+
+#[tokio::test]
+async fn test_activity_toggle_activity_pin() {
+    let (client, transport) = replay("activity_toggle_activity_pin");
+    let activity = client
+        .activity()
+        .toggle_activity_pin(701234567, true)
+        .await
+        .unwrap();
+
+    let ActivityUnion::Text(text) = activity else {
+        panic!("expected a text activity");
+    };
+    assert_eq!(text.is_pinned, Some(true));
+    assert_eq!(transport.last_request().variables()["pinned"], true);
+}
+
+#[tokio::test]
+async fn test_activity_delete_activity() {
+    let (client, transport) = replay("activity_delete_activity");
+    let deleted = client.activity().delete_activity(812000020).await.unwrap();
+
+    assert!(deleted);
+    assert_eq!(transport.last_request().variables()["id"], 812000020);
+}
+
+#[tokio::test]
+async fn test_activity_get_likers() {
+    let (client, transport) = replay("activity_get_likers");
+    let likers = client.activity().get_likers(701234567, 1, 2).await.unwrap();
+
+    assert_eq!(likers.page_info.total, Some(14));
+    assert_eq!(likers.items[0].name, "Replier");
+    assert_eq!(transport.last_request().variables()["type"], "ACTIVITY");
+}
+
+#[tokio::test]
+async fn test_activity_get_reply_likers() {
+    let (client, transport) = replay("activity_get_reply_likers");
+    let likers = client
+        .activity()
+        .get_reply_likers(30001, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(likers.items.len(), 2);
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["likeableId"], 30001);
+    assert_eq!(variables["type"], "ACTIVITY_REPLY");
+}
+
+#[tokio::test]
+async fn test_client_health_check() {
+    let (client, _) = replay("client_health_check");
+    let report = client.health_check().await.unwrap();
+
+    assert!(report.authenticated);
+    assert_eq!(report.viewer_id, Some(5123456));
+    assert_eq!(report.rate_limit.unwrap().remaining, 12);
+}
+
+#[tokio::test]
+async fn test_forum_get_thread_by_id() {
+    let (client, _) = replay("forum_get_thread_by_id");
+    let thread = client.forum().get_thread_by_id(4).await.unwrap();
+
+    assert_eq!(thread.id, 4);
+    assert_eq!(thread.is_sticky, Some(true));
+    assert_eq!(thread.categories.as_ref().unwrap()[0].name, "Site Feedback");
+    let author = thread.user.as_ref().unwrap();
+    assert_eq!(author.moderator_roles.as_ref().unwrap().len(), 1);
+    assert_eq!(thread.reply_user.as_ref().unwrap().id, 312);
+}
+
+#[tokio::test]
+async fn test_forum_get_thread_by_id_as_html() {
+    let (client, transport) = replay("forum_get_thread_by_id_as_html");
+    let thread = client.forum().get_thread_by_id_as_html(4).await.unwrap();
+
+    assert!(thread.body.unwrap().contains("<strong>feedback</strong>"));
+    assert_eq!(transport.last_request().variables()["asHtml"], true);
+}
+
+#[tokio::test]
+async fn test_forum_get_recent_threads() {
+    let (client, transport) = replay("forum_get_recent_threads");
+    let threads = client.forum().get_recent_threads(1, 3).await.unwrap();
+
+    assert_eq!(
+        threads.iter().map(|t| t.id).collect::<Vec<_>>(),
+        [75310, 74100, 74000]
+    );
+    assert_eq!(threads[0].reply_user.as_ref().unwrap().name, "Donator");
+    assert_eq!(threads[2].is_locked, Some(true));
+    assert_eq!(transport.last_request().variables()["asHtml"], false);
+}
+
+#[tokio::test]
+async fn test_forum_get_recent_threads_as_html() {
+    let (client, transport) = replay("forum_get_recent_threads_as_html");
+    let threads = client
+        .forum()
+        .get_recent_threads_as_html(1, 3)
+        .await
+        .unwrap();
+
+    assert!(
+        threads[0]
+            .body
+            .as_deref()
+            .unwrap()
+            .contains("<strong>turbo granny</strong>")
+    );
+    assert_eq!(transport.last_request().variables()["asHtml"], true);
+}
+
+#[tokio::test]
+async fn test_forum_get_recent_threads_filtered() {
+    let (client, _) = replay("forum_get_recent_threads");
+    let threads = client
+        .forum()
+        .get_recent_threads_filtered(1, 3, &ThreadFilter::new().exclude_locked(true))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        threads.iter().map(|t| t.id).collect::<Vec<_>>(),
+        [75310, 74100]
+    );
+}
+
+#[tokio::test]
+async fn test_forum_lock_thread() {
+    let (mut client, transport) = replay("forum_lock_thread");
+    client.set_moderator_mode(true);
+    let moderation = client.forum().lock_thread(74000, true).await.unwrap();
+
+    assert_eq!(moderation.id, 74000);
+    assert_eq!(moderation.is_locked, Some(true));
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["id"], 74000);
+    assert_eq!(variables["locked"], true);
+}
+
+#[tokio::test]
+async fn test_forum_sticky_thread() {
+    let (mut client, transport) = replay("forum_sticky_thread");
+    client.set_moderator_mode(true);
+    let moderation = client.forum().sticky_thread(75310, true).await.unwrap();
+
+    assert_eq!(moderation.is_sticky, Some(true));
+    assert_eq!(transport.last_request().variables()["sticky"], true);
+}
+
+#[tokio::test]
+async fn test_forum_get_my_threads() {
+    let (client, transport) = replay_in_order(&["client_health_check", "forum_get_my_threads"]);
+    let threads = client.forum().get_my_threads(1, 2).await.unwrap();
+
+    assert_eq!(threads.items[0].id, 69800);
+    assert_eq!(threads.page_info.total, Some(1));
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["userId"], 5123456);
+    assert_eq!(variables["replyUserId"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_forum_get_my_commented_threads() {
+    let (client, transport) =
+        replay_in_order(&["client_health_check", "forum_get_my_commented_threads"]);
+    let threads = client.forum().get_my_commented_threads(1, 2).await.unwrap();
+
+    assert!(
+        threads
+            .items
+            .iter()
+            .all(|t| t.reply_user_id == Some(5123456))
+    );
+    assert_eq!(threads.page_info.has_next_page, Some(true));
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["replyUserId"], 5123456);
+    assert_eq!(variables["userId"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_forum_get_subscribed_threads() {
+    let (client, transport) = replay("forum_get_subscribed_threads");
+    let threads = client.forum().get_subscribed_threads(1, 2).await.unwrap();
+
+    assert_eq!(threads.items[0].is_subscribed, Some(true));
+    assert_eq!(transport.last_request().variables()["subscribed"], true);
+}
+
+#[tokio::test]
+async fn test_forum_toggle_thread_subscription() {
+    let (client, transport) = replay("forum_toggle_thread_subscription");
+    let thread = client
+        .forum()
+        .toggle_thread_subscription(69800, true)
+        .await
+        .unwrap();
+
+    assert_eq!(thread.is_subscribed, Some(true));
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["threadId"], 69800);
+    assert_eq!(variables["subscribe"], true);
+}
+
+#[tokio::test]
+async fn test_forum_search_threads() {
+    let (client, transport) = replay("forum_search_threads");
+    let threads = client
+        .forum()
+        .search_threads("frieren", 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        threads.iter().map(|t| t.id).collect::<Vec<_>>(),
+        [69800, 74100]
+    );
+    assert_eq!(transport.last_request().variables()["search"], "frieren");
+}
+
+#[tokio::test]
+async fn test_forum_search_threads_filtered() {
+    let (client, _) = replay("forum_search_threads");
+    let threads = client
+        .forum()
+        .search_threads_filtered("frieren", 1, 2, &ThreadFilter::new().is_sticky(false))
+        .await
+        .unwrap();
+
+    assert_eq!(threads.iter().map(|t| t.id).collect::<Vec<_>>(), [69800]);
+}
+
+#[tokio::test]
+async fn test_forum_get_thread_comments() {
+    let (client, transport) = replay("forum_get_thread_comments");
+    let comments = client
+        .forum()
+        .get_thread_comments(69800, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].like_count, 45);
+    assert_eq!(comments[1].user.as_ref().unwrap().name, "Donator");
+    assert_eq!(transport.last_request().variables()["threadId"], 69800);
+}
+
+#[tokio::test]
+async fn test_forum_create_thread() {
+    let (client, transport) = replay("forum_create_thread");
+    let thread = client
+        .forum()
+        .create_thread(
+            "Cowboy Bebop rewatch club",
+            "One session a week, starting with Asteroid Blues.",
+            Some(vec![2]),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(thread.id, 75400);
+    assert_eq!(thread.user_id, 5123456);
+    assert_eq!(
+        transport.last_request().variables()["categories"],
+        json!([2])
+    );
+}
+
+#[tokio::test]
+async fn test_forum_post_comment() {
+    let (client, transport) = replay("forum_post_comment");
+    let comment = client
+        .forum()
+        .post_comment(75310, "The animation in the chase was unreal.")
+        .await
+        .unwrap();
+
+    assert_eq!(comment.thread_id, 75310);
+    assert_eq!(comment.comment, "The animation in the chase was unreal.");
+    assert_eq!(transport.last_request().variables()["threadId"], 75310);
+}
+
+#[tokio::test]
+async fn test_forum_toggle_thread_like() {
+    let (client, transport) = replay("forum_toggle_thread_like");
+    let thread = client.forum().toggle_thread_like(75310).await.unwrap();
+
+    assert_eq!(thread.is_liked, Some(true));
+    assert_eq!(thread.like_count, 97);
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["id"], 75310);
+    assert_eq!(variables["type"], "THREAD");
+}
+
+#[tokio::test]
+async fn test_forum_toggle_comment_like() {
+    let (client, transport) = replay("forum_toggle_comment_like");
+    let comment = client.forum().toggle_comment_like(2101234).await.unwrap();
+
+    assert_eq!(comment.is_liked, Some(true));
+    assert_eq!(comment.like_count, 46);
+    let request = transport.last_request();
+    assert!(
+        request
+            .query()
+            .contains("ToggleLikeV2(id: $id, type: $type)")
+    );
+    assert_eq!(request.variables()["id"], 2101234);
+    assert_eq!(request.variables()["type"], "THREAD_COMMENT");
+}
+
+#[tokio::test]
+async fn test_forum_get_thread_likers() {
+    let (client, transport) = replay("forum_get_thread_likers");
+    let likers = client.forum().get_thread_likers(75310, 1, 2).await.unwrap();
+
+    assert_eq!(likers.page_info.total, Some(97));
+    assert_eq!(likers.items[1].name, "DonatorFan");
+    assert_eq!(transport.last_request().variables()["type"], "THREAD");
+}
+
+#[tokio::test]
+async fn test_forum_get_comment_likers() {
+    let (client, transport) = replay("forum_get_comment_likers");
+    let likers = client
+        .forum()
+        .get_comment_likers(2101234, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(likers.page_info.total, Some(46));
+    assert_eq!(
+        transport.last_request().variables()["type"],
+        "THREAD_COMMENT"
+    );
+}
+
+#[tokio::test]
+async fn test_review_get_review_by_id() {
+    let (client, _) = replay("review_get_review_by_id");
+    let review = client.review().get_review_by_id(2000).await.unwrap();
+
+    assert_eq!(review.id, 2000);
+    assert_eq!(review.score, Some(95));
+    assert_eq!(review.is_private, Some(false));
+    assert_eq!(review.media.as_ref().unwrap().id, 1);
+}
+
+#[tokio::test]
+async fn test_review_get_recent_reviews() {
+    let (client, _) = replay("review_get_recent_reviews");
+    let reviews = client.review().get_recent_reviews(1, 2).await.unwrap();
+
+    assert_eq!(
+        reviews.iter().map(|r| r.id).collect::<Vec<_>>(),
+        [6200, 6199]
+    );
+    assert!(reviews[0].created_at > reviews[1].created_at);
+    assert_eq!(reviews[0].user.as_ref().unwrap().name, "Replier");
+    // Not requested without the viewer-status flag
+    assert!(reviews[0].media.as_ref().unwrap().viewer_entry.is_none());
+}
+
+#[tokio::test]
+async fn test_review_get_reviews_for_media() {
+    let (client, transport) = replay("review_get_reviews_for_media");
+    let reviews = client
+        .review()
+        .get_reviews_for_media(154587, 1, 2)
+        .await
+        .unwrap();
+
+    assert!(reviews.iter().all(|r| r.media_id == 154587));
+    assert!(reviews[0].rating >= reviews[1].rating);
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["mediaId"], 154587);
+    assert!(variables.get("withViewerStatus").is_none());
+}
+
+#[tokio::test]
+async fn test_review_get_reviews_for_media_with_viewer_status() {
+    let (client, transport) = replay("review_get_reviews_for_media_with_viewer_status");
+    let reviews = client
+        .review()
+        .get_reviews_for_media_with_viewer_status(154587, 1, 2)
+        .await
+        .unwrap();
+
+    let entry = reviews[0]
+        .media
+        .as_ref()
+        .unwrap()
+        .viewer_entry
+        .as_ref()
+        .unwrap();
+    assert_eq!(entry.status, Some(MediaListStatus::Completed));
+    assert_eq!((entry.progress, entry.score), (Some(28), Some(9.5)));
+    assert_eq!(
+        transport.last_request().variables()["withViewerStatus"],
+        true
+    );
+}
+
+#[tokio::test]
+async fn test_review_get_reviews_by_user() {
+    let (client, transport) = replay("review_get_reviews_by_user");
+    let reviews = client
+        .review()
+        .get_reviews_by_user(5123456, 1, 2)
+        .await
+        .unwrap();
+
+    assert!(reviews.iter().all(|r| r.user_id == 5123456));
+    assert_eq!(
+        reviews.iter().map(|r| r.media_id).collect::<Vec<_>>(),
+        [161645, 154587]
+    );
+    assert_eq!(transport.last_request().variables()["userId"], 5123456);
+}
+
+#[tokio::test]
+async fn test_review_get_top_rated_reviews() {
+    let (client, _) = replay("review_get_top_rated_reviews");
+    let reviews = client.review().get_top_rated_reviews(1, 2).await.unwrap();
+
+    assert_eq!(
+        reviews.iter().map(|r| r.id).collect::<Vec<_>>(),
+        [6100, 2000]
+    );
+    assert!(reviews[0].rating >= reviews[1].rating);
+}
+
+#[tokio::test]
+async fn test_review_save_review() {
+    let (client, transport) = replay("review_save_review");
+    let body =
+        "Dandadan opens with a bet about ghosts and aliens and never stops escalating. ".repeat(30);
+    let review = client
+        .review()
+        .save_review(
+            171018,
+            &body,
+            Some("Three episodes of pure momentum"),
+            Some(90),
+            Some(false),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!((review.id, review.media_id), (6201, 171018));
+    assert_eq!(review.body, body);
+    assert_eq!(review.rating_amount, 0);
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["summary"], "Three episodes of pure momentum");
+    assert_eq!(variables["score"], 90);
+    assert_eq!(variables["private"], false);
+}
+
+#[tokio::test]
+async fn test_review_rate_review() {
+    let (client, transport) = replay("review_rate_review");
+    let review = client.review().rate_review(2000, "UP_VOTE").await.unwrap();
+
+    assert!(matches!(review.user_rating, Some(ReviewRating::UpVote)));
+    assert_eq!(review.rating, 411);
+    // The mutation selects no summary or media
+    assert!(review.summary.is_none() && review.media.is_none());
+    assert_eq!(transport.last_request().variables()["rating"], "UP_VOTE");
+}
+
+#[tokio::test]
+async fn test_review_delete_review() {
+    let (client, transport) = replay("review_delete_review");
+    assert!(client.review().delete_review(6201).await.unwrap());
+    assert_eq!(transport.last_request().variables()["id"], 6201);
+}
+
+#[tokio::test]
+async fn test_recommendation_get_recommendation_by_id() {
+    let (client, _) = replay("recommendation_get_recommendation_by_id");
+    let recommendation = client
+        .recommendation()
+        .get_recommendation_by_id(1500)
+        .await
+        .unwrap();
+
+    assert_eq!(recommendation.id, 1500);
+    assert_eq!(
+        recommendation.user_rating,
+        Some(RecommendationRating::NoRating)
+    );
+    assert_eq!(recommendation.media.as_ref().unwrap().id, 1);
+    assert_eq!(
+        recommendation.media_recommendation.as_ref().unwrap().id,
+        205
+    );
+}
+
+#[tokio::test]
+async fn test_recommendation_get_recent_recommendations() {
+    let (client, _) = replay("recommendation_get_recent_recommendations");
+    let recommendations = client
+        .recommendation()
+        .get_recent_recommendations(1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recommendations.iter().map(|r| r.id).collect::<Vec<_>>(),
+        [9101, 9100]
+    );
+    assert_eq!(recommendations[0].user.as_ref().unwrap().name, "Replier");
+}
+
+#[tokio::test]
+async fn test_recommendation_get_recommendations_for_media() {
+    let (client, transport) = replay("recommendation_get_recommendations_for_media");
+    let recommendations = client
+        .recommendation()
+        .get_recommendations_for_media(154587, 1, 2)
+        .await
+        .unwrap();
+
+    assert!(
+        recommendations
+            .iter()
+            .all(|r| r.media.as_ref().unwrap().id == 154587)
+    );
+    assert_eq!(recommendations[0].rating, 420);
+    assert_eq!(transport.last_request().variables()["mediaId"], 154587);
+}
+
+#[tokio::test]
+async fn test_recommendation_get_top_rated_recommendations() {
+    let (client, _) = replay("recommendation_get_top_rated_recommendations");
+    let recommendations = client
+        .recommendation()
+        .get_top_rated_recommendations(1, 2)
+        .await
+        .unwrap();
+
+    let ratings: Vec<_> = recommendations.iter().map(|r| r.rating).collect();
+    assert_eq!(ratings, [1040, 980]);
+}
+
+#[tokio::test]
+async fn test_recommendation_save_recommendation() {
+    let (client, transport) = replay("recommendation_save_recommendation");
+    let recommendation = client
+        .recommendation()
+        .save_recommendation(171018, 145064, Some(1))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recommendation.user_rating,
+        Some(RecommendationRating::RateUp)
+    );
+    let request = transport.last_request();
+    let variables = request.variables();
+    assert_eq!(variables["mediaRecommendationId"], 145064);
+    assert_eq!(variables["rating"], "RATE_UP");
+}
+
+#[tokio::test]
+async fn test_recommendation_rate_recommendation() {
+    let (client, transport) = replay("recommendation_rate_recommendation");
+    let recommendation = client
+        .recommendation()
+        .rate_recommendation(1500, 1)
+        .await
+        .unwrap();
+
+    assert_eq!(recommendation.rating, 88);
+    assert!(recommendation.user.is_none());
+    assert_eq!(
+        transport.last_request().variables()["recommendationId"],
+        1500
+    );
+}
+
+#[tokio::test]
+async fn test_recommendation_get_my_recommendation() {
+    let (client, transport) = replay("recommendation_get_my_recommendation");
+    let recommendation = client
+        .recommendation()
+        .get_my_recommendation(1, 205)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(recommendation.id, 1500);
+    assert_eq!(
+        transport.last_request().variables()["mediaRecommendationId"],
+        205
+    );
+}
+
+#[tokio::test]
+async fn test_recommendation_recommend_or_update() {
+    let (client, transport) = replay_in_order(&[
+        "recommendation_get_my_recommendation",
+        "recommendation_recommend_or_update",
+    ]);
+    let (outcome, recommendation) = client
+        .recommendation()
+        .recommend_or_update((1, 205), RecommendationRating::RateUp)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, RecommendationUpsert::Updated);
+    assert_eq!(
+        recommendation.user_rating,
+        Some(RecommendationRating::RateUp)
+    );
+    assert_eq!(transport.last_request().variables()["rating"], "RATE_UP");
+
+    // The viewer has not rated the pair, so asking for no rating changes nothing
+    let (client, transport) = replay("recommendation_get_my_recommendation");
+    let (outcome, _) = client
+        .recommendation()
+        .recommend_or_update((1, 205), RecommendationRating::NoRating)
+        .await
+        .unwrap();
+    assert_eq!(outcome, RecommendationUpsert::Unchanged);
+    assert_eq!(transport.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_recommendation_get_personalized() {
+    let (client, transport) = replay_in_order(&[
+        "user_get_current_user",
+        "user_get_current_user_anime_list",
+        "recommendation_get_personalized",
+    ]);
+    let personalized = client.recommendation().get_personalized(3).await.unwrap();
+
+    // Dandadan is already on the list; FMAB is suggested from both sources
+    let suggestions: Vec<_> = personalized
+        .iter()
+        .map(|p| (p.suggestion.id, p.source.id))
+        .collect();
+    assert_eq!(suggestions, [(161645, 154587), (5114, 154587), (205, 1)]);
+    assert!(personalized[1].score > 90.0);
+
+    // Sources are the completed shows, best score first
+    assert_eq!(
+        transport.last_request().variables()["ids"],
+        json!([154587, 1])
+    );
+}
+
+#[tokio::test]
+async fn test_airing_get_upcoming_episodes() {
+    let (mut client, transport) = replay("airing_get_upcoming_episodes");
+    let schedules = client.airing().get_upcoming_episodes(1, 1).await.unwrap();
+
+    assert_eq!(schedules.len(), 1);
+    assert_eq!(schedules[0].episode, 1123);
+    assert_eq!(schedules[0].media.as_ref().unwrap().id, 21);
+    assert_eq!(
+        transport.last_request().variables()["sort"],
+        json!(["TIME"])
+    );
+
+    let page = client
+        .airing()
+        .get_upcoming_episodes_page(1, 1)
+        .await
+        .unwrap();
+    assert_eq!(page.page_info.total, Some(1800));
+    assert!(page.has_next_page());
+
+    // Every page after the first keeps the same cut-off
+    client.set_page_delay(Duration::ZERO);
+    let streamed: Vec<_> = client
+        .airing()
+        .get_upcoming_episodes_stream(1)
+        .take(2)
+        .collect()
+        .await;
+    assert_eq!(streamed.len(), 2);
+    let requests = transport.requests();
+    let (first, second) = (&requests[2], &requests[3]);
+    assert_eq!(
+        (
+            first.variables()["page"].clone(),
+            second.variables()["page"].clone()
+        ),
+        (json!(1), json!(2))
+    );
+    assert_eq!(
+        first.variables()["airingAtGreater"],
+        second.variables()["airingAtGreater"]
+    );
+}
+
+#[tokio::test]
+async fn test_airing_get_today_episodes() {
+    let (client, transport) = replay("airing_get_today_episodes");
+    let schedules = client.airing().get_today_episodes(1, 2).await.unwrap();
+
+    let media: Vec<i32> = schedules.iter().map(|s| s.media_id).collect();
+    assert_eq!(media, [21, 163146]);
+    assert!(schedules[0].airing_at <= schedules[1].airing_at);
+    assert_eq!(
+        schedules[1].media.as_ref().unwrap().format,
+        Some(MediaFormat::Tv)
+    );
+
+    // The window is the current UTC day
+    let variables = transport.last_request().variables().clone();
+    let start = variables["airingAtGreater"].as_i64().unwrap();
+    assert_eq!(start % 86400, 0);
+    assert_eq!(variables["airingAtLesser"], start + 86400);
+    assert_eq!(variables["sort"], json!(["TIME"]));
+}
+
+#[tokio::test]
+async fn test_airing_get_recently_aired() {
+    let (client, transport) = replay("airing_get_recently_aired");
+    let schedules = client.airing().get_recently_aired(1, 2).await.unwrap();
+
+    let episodes: Vec<i32> = schedules.iter().map(|s| s.episode).collect();
+    assert_eq!(episodes, [3, 2]);
+    // Aired episodes count down into the past
+    assert!(schedules.iter().all(|s| s.time_until_airing < 0));
+    let variables = transport.last_request().variables().clone();
+    assert!(variables.get("airingAtGreater").is_none());
+    assert_eq!(variables["sort"], json!(["TIME_DESC"]));
+}
+
+#[tokio::test]
+async fn test_airing_get_schedule_for_media() {
+    let (client, transport) = replay("airing_get_schedule_for_media");
+    let schedules = client
+        .airing()
+        .get_schedule_for_media(171018, 1, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        schedules.iter().map(|s| s.episode).collect::<Vec<_>>(),
+        [1, 2]
+    );
+    assert!(schedules.iter().all(|s| s.media_id == 171018));
+    assert_eq!(schedules[1].airing_at - schedules[0].airing_at, 7 * 86400);
+    assert_eq!(transport.last_request().variables()["mediaId"], 171018);
+}
+
+#[tokio::test]
+async fn test_airing_get_schedule_by_id() {
+    let (client, transport) = replay("airing_get_schedule_by_id");
+    let schedule = client.airing().get_schedule_by_id(372188).await.unwrap();
+
+    assert_eq!((schedule.id, schedule.episode), (372188, 4));
+    let media = schedule.media.as_ref().unwrap();
+    assert_eq!(media.episodes, Some(12));
+    assert_eq!(
+        media.site_url.as_deref(),
+        Some("https://anilist.co/anime/171018")
+    );
+    assert_eq!(transport.last_request().variables()["id"], 372188);
+}
+
+#[tokio::test]
+async fn test_airing_get_episodes_in_range() {
+    let (client, transport) = replay("airing_get_episodes_in_range");
+    let schedules = client
+        .airing()
+        .get_episodes_in_range(1729382400, 1729987200, 1, 2)
+        .await
+        .unwrap();
+
+    for schedule in &schedules {
+        assert!((1729382400..1729987200).contains(&schedule.airing_at));
+    }
+    let variables = transport.last_request().variables().clone();
+    assert_eq!(variables["airingAtGreater"], 1729382400);
+    assert_eq!(variables["airingAtLesser"], 1729987200);
+}
+
+#[tokio::test]
+async fn test_airing_get_next_episode() {
+    let (client, transport) = replay("airing_get_next_episode");
+    let schedule = client
+        .airing()
+        .get_next_episode(171018)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(schedule.episode, 4);
+    let media = schedule.media.as_ref().unwrap();
+    assert_eq!(
+        media.title.as_ref().unwrap().user_preferred.as_deref(),
+        Some("Dandadan")
+    );
+    // Only the title is selected
+    assert!(media.cover_image.is_none());
+    assert_eq!(transport.last_request().variables()["mediaId"], 171018);
+}
+
+#[tokio::test]
+async fn test_notification_get_notifications() {
+    let (client, _) = replay("notification_get_notifications");
+    let notifications = client.notification().get_notifications(1, 3).await.unwrap();
+
+    let types: Vec<_> = notifications.iter().map(|n| n.notification_type).collect();
+    assert_eq!(
+        types,
+        vec![
+            NotificationType::Airing,
+            NotificationType::Following,
+            NotificationType::ThreadCommentReply,
+        ]
+    );
+    assert_eq!(notifications[0].episode, Some(1122));
+    assert_eq!(notifications[2].thread.as_ref().unwrap().id, 4);
+}
+
+#[tokio::test]
+async fn test_notification_load_context() {
+    let (client, transport) =
+        replay_in_order(&["notification_get_notifications", "forum_get_thread_by_id"]);
+    let notifications = client.notification().get_notifications(1, 3).await.unwrap();
+
+    // A comment reply resolves to the thread it was posted in
+    let context = notifications[2].load_context(&client).await.unwrap();
+    let ResolvedResource::ThreadComment { thread, comment_id } = context else {
+        panic!("expected a thread comment, got {context:?}");
+    };
+    assert_eq!(thread.id, 4);
+    assert_eq!(Some(comment_id), notifications[2].comment_id);
+    assert_eq!(transport.last_request().variables()["id"], 4);
+}
+
+#[tokio::test]
+async fn test_notification_get_unread_count() {
+    let (client, transport) = replay("notification_get_unread_count");
+    assert_eq!(client.notification().get_unread_count().await.unwrap(), 7);
+    assert!(transport.last_request().body["variables"].is_null());
+}
+
+#[tokio::test]
+async fn test_notification_get_notifications_by_type() {
+    let (client, transport) = replay("notification_get_notifications_by_type");
+    let notifications = client
+        .notification()
+        .get_notifications_by_type("AIRING", 1, 2)
+        .await
+        .unwrap();
+
+    assert!(
+        notifications
+            .iter()
+            .all(|n| n.notification_type == NotificationType::Airing)
+    );
+    let episodes: Vec<_> = notifications
+        .iter()
+        .map(|n| (n.anime_id.unwrap(), n.episode.unwrap()))
+        .collect();
+    assert_eq!(episodes, [(171018, 3), (21, 1122)]);
+    // Only the large cover is selected here
+    let cover = notifications[0]
+        .media
+        .as_ref()
+        .unwrap()
+        .cover_image
+        .as_ref()
+        .unwrap();
+    assert!(cover.large.is_some() && cover.medium.is_none());
+    assert_eq!(
+        transport.last_request().variables()["type"],
+        json!(["AIRING"])
+    );
+}
+
+#[tokio::test]
+async fn test_notification_mark_notifications_as_read() {
+    let (client, transport) = replay("notification_mark_notifications_as_read");
+    let complete = client
+        .notification()
+        .mark_notifications_as_read(vec![900005, 900003])
+        .await
+        .unwrap();
+
+    assert!(complete);
+    let request = transport.last_request();
+    assert!(request.query().starts_with("mutation"));
+    assert_eq!(
+        request.variables()["notificationIds"],
+        json!([900005, 900003])
+    );
+
+    let report = client
+        .notification()
+        .mark_notifications_as_read_chunked(&[900005])
+        .await;
+    assert_eq!(report.marked, [900005]);
+    assert_eq!(report.mutations, 1);
+    assert!(report.is_complete());
+}
+
+#[tokio::test]
+async fn test_error_not_found() {
+    let (client, _) = replay("error_not_found");
+    let result = client.anime().get_by_id(999999999).await;
+    assert!(matches!(result, Err(AniListError::NotFound)), "{result:?}");
+}
+
+#[tokio::test]
+async fn test_error_private_user() {
+    let (client, _) = replay("error_private_user");
+    let result = client
+        .user()
+        .get_list_collection(5123457, MediaType::Anime, None, 1)
+        .await;
+    assert!(
+        matches!(&result, Err(AniListError::Private { resource }) if resource == "user"),
+        "{result:?}"
+    );
+
+    let result = client
+        .user()
+        .get_list_entries_updated_since(5123457, MediaType::Anime, 0)
+        .await;
+    assert!(
+        matches!(result, Err(AniListError::Private { .. })),
+        "{result:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_error_adult_content() {
+    // Sent with a 403, which would otherwise be AccessDenied
+    let (client, _) = replay("error_adult_content");
+    let results = [
+        client.anime().get_by_id(101517).await.map(drop),
+        client.manga().get_by_id(101517).await.map(drop),
+    ];
+    for result in results {
+        assert!(
+            matches!(&result, Err(AniListError::AdultContentBlocked { message })
+                if message == "Adult content is disabled for this user"),
+            "{result:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_error_not_found_with_ok_status() {
+    // The same error sent with a 200, as AniList usually does
+    let (client, _) = replay("error_not_found_ok_status");
+    let id = 999999999;
+    let results = [
+        client.anime().get_by_id(id).await.map(drop),
+        client.anime().get_by_mal_id(id).await.map(drop),
+        client.manga().get_by_id(id).await.map(drop),
+        client.character().get_by_id(id).await.map(drop),
+        client.staff().get_by_id(id).await.map(drop),
+        client.studio().get_by_id(id).await.map(drop),
+        client.user().get_by_id(id).await.map(drop),
+        client.forum().get_thread_by_id(id).await.map(drop),
+    ];
+    for result in results {
+        assert!(matches!(result, Err(AniListError::NotFound)), "{result:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_error_rate_limited() {
+    let (client, _) = replay("error_rate_limited");
+    match client.anime().get_by_id(1).await {
+        Err(AniListError::RateLimit {
+            limit,
+            remaining,
+            retry_after,
+            ..
+        }) => {
+            assert_eq!(limit, 90);
+            assert_eq!(remaining, 0);
+            assert_eq!(retry_after, 42);
+        }
+        other => panic!("expected a rate limit error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_error_maintenance_page() {
+    let (client, _) = replay("error_maintenance");
+    match client.anime().get_by_id(1).await {
+        Err(AniListError::ServerError { status, message }) => {
+            assert_eq!(status, 503);
+            assert!(message.contains("maintenance"));
+        }
+        other => panic!("expected a server error, got {other:?}"),
+    }
+    assert_eq!(client.metrics().http_errors, 1);
+}
+
+#[tokio::test]
+async fn test_error_maintenance_page_with_ok_status() {
+    let (client, _) = replay("error_maintenance_ok_status");
+    let result = client.anime().get_by_id(1).await;
+
+    // Not JSON: a decoding failure, not a network one
+    assert!(matches!(result, Err(AniListError::Json(_))), "{result:?}");
+    assert_eq!(client.metrics().network_errors, 0);
+}
+
+#[tokio::test]
+async fn test_error_partial_data() {
+    let (client, _) = replay("error_partial_data");
+    let result = client.user().get_by_id(5123456).await;
+
+    // Errors next to data still fail the call
+    match result {
+        Err(AniListError::GraphQL { message, .. }) => assert_eq!(message, "Internal Server Error"),
+        other => panic!("expected a GraphQL error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_error_partial_data_can_be_kept() {
+    let (mut client, _) = replay("error_partial_data");
+    client.set_fail_on_partial_errors(false);
+
+    let response = client.user().get_by_id_with_meta(5123456).await.unwrap();
+
+    assert_eq!(response.value.name, "Donator");
+    assert!(response.value.statistics.is_none());
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(response.errors[0].status, Some(500));
+    assert_eq!(response.errors[0].locations, [(40, 9)]);
+}
+
+#[tokio::test]
+async fn test_transport_errors_are_returned() {
+    let offline = Arc::new(MockApi::try_from_fn(|_| {
+        Err(AniListError::Config {
+            message: "offline".to_string(),
+        })
+    }));
+    let client = offline.client();
+    let result = client.anime().get_by_id(1).await;
+    assert!(
+        matches!(result, Err(AniListError::Config { .. })),
+        "{result:?}"
+    );
+    assert_eq!(client.metrics().requests, 1);
+}
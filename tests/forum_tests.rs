@@ -1,4 +1,7 @@
 use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::models::social::Thread;
+use serde_json::json;
 mod test_utils;
 
 #[tokio::test]
@@ -67,3 +70,289 @@ async fn test_get_thread_comments() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_get_thread_likers() {
+    let client = AniListClient::new();
+    let result = crate::forum_api_call!(client, get_thread_likers, 1, 1, 5);
+
+    if let Ok(likers) = result {
+        assert!(likers.items.len() <= 5);
+        for user in &likers.items {
+            assert!(user.id > 0);
+            assert!(!user.name.is_empty());
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_recent_threads_as_html() {
+    let client = AniListClient::new();
+    let result = crate::forum_api_call!(client, get_recent_threads_as_html, 1, 3);
+
+    if let Ok(threads) = result {
+        assert!(threads.len() <= 3);
+        for thread in &threads {
+            assert!(thread.excerpt(80).chars().count() <= 80);
+        }
+    }
+}
+
+fn thread_with_body(body: &str) -> Thread {
+    serde_json::from_value(json!({
+        "id": 1,
+        "title": "Test thread",
+        "body": body,
+        "userId": 1,
+        "likeCount": 0,
+        "createdAt": 0,
+        "updatedAt": 0
+    }))
+    .expect("thread fixture should deserialize")
+}
+
+#[test]
+fn test_thread_excerpt_strips_markup() {
+    let thread = thread_with_body(
+        "# Episode 12 discussion\n\n__Spoilers__ ahead: ~!the hero dies!~\n\
+         img220(https://example.com/a.png) Watch on [Crunchyroll](https://crunchyroll.com) &amp; enjoy <br>",
+    );
+
+    assert_eq!(
+        thread.excerpt(200),
+        "Episode 12 discussion Spoilers ahead: Watch on Crunchyroll & enjoy"
+    );
+}
+
+#[test]
+fn test_thread_excerpt_truncates_on_word_boundary() {
+    let thread = thread_with_body("The quick brown fox jumps over the lazy dog");
+
+    assert_eq!(thread.excerpt(16), "The quick brown…");
+    assert_eq!(thread.excerpt(18), "The quick brown…");
+    assert_eq!(
+        thread.excerpt(100),
+        "The quick brown fox jumps over the lazy dog"
+    );
+    assert_eq!(thread.excerpt(0), "");
+}
+
+#[test]
+fn test_thread_excerpt_is_unicode_safe() {
+    let thread = thread_with_body("進撃の巨人 最終話の感想 ネタバレあり");
+
+    let excerpt = thread.excerpt(10);
+    assert!(excerpt.chars().count() <= 10);
+    assert_eq!(excerpt, "進撃の巨人…");
+
+    // A single long word is cut mid-word rather than producing nothing
+    let thread = thread_with_body("ありがとうございましたありがとうございました");
+    assert_eq!(thread.excerpt(5), "ありがと…");
+}
+
+#[tokio::test]
+async fn test_moderation_requires_moderator_mode() {
+    let client = AniListClient::with_token("token".to_string());
+
+    let result = client.forum().lock_thread(1, true).await;
+    assert!(matches!(result, Err(AniListError::AccessDenied)));
+
+    let result = client.forum().sticky_thread(1, true).await;
+    assert!(matches!(result, Err(AniListError::AccessDenied)));
+}
+
+#[tokio::test]
+async fn test_moderation_requires_token() {
+    let mut client = AniListClient::new();
+    client.set_moderator_mode(true);
+
+    let result = client.forum().lock_thread(1, true).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+}
+
+#[test]
+fn test_moderation_response_fixtures() {
+    use anilist_sdk::models::ThreadModeration;
+
+    // Moderator token: the mutation returns the updated flags
+    let granted = json!({
+        "data": { "SaveThread": { "id": 4242, "isLocked": true, "isSticky": false } }
+    });
+    let thread: ThreadModeration =
+        serde_json::from_value(granted["data"]["SaveThread"].clone()).unwrap();
+    assert_eq!(thread.id, 4242);
+    assert_eq!(thread.is_locked, Some(true));
+    assert_eq!(thread.is_sticky, Some(false));
+
+    // Regular token: the mutation is rejected with a 403 status
+    let denied = json!({
+        "errors": [{ "message": "Forbidden", "status": 403 }],
+        "data": { "SaveThread": null }
+    });
+    assert!(matches!(
+        AniListError::from_graphql_errors(&denied["errors"]),
+        AniListError::AccessDenied
+    ));
+}
+
+#[tokio::test]
+async fn test_my_threads_require_token() {
+    let client = AniListClient::new();
+
+    let result = client.forum().get_my_threads(1, 10).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+
+    let result = client.forum().get_my_commented_threads(1, 10).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+
+    let result = client.forum().get_subscribed_threads(1, 10).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+}
+
+#[test]
+fn test_my_commented_threads_fixture() {
+    use anilist_sdk::models::PageInfo;
+
+    let response = json!({
+        "data": {
+            "Page": {
+                "pageInfo": { "total": 1, "perPage": 10, "currentPage": 1, "lastPage": 1, "hasNextPage": false },
+                "threads": [{
+                    "id": 77,
+                    "title": "Episode 12 discussion",
+                    "userId": 5,
+                    "replyUserId": 42,
+                    "replyCommentId": 9001,
+                    "likeCount": 3,
+                    "createdAt": 1700000000,
+                    "updatedAt": 1700003600
+                }]
+            }
+        }
+    });
+
+    let page_info: PageInfo =
+        serde_json::from_value(response["data"]["Page"]["pageInfo"].clone()).unwrap();
+    assert_eq!(page_info.has_next_page, Some(false));
+
+    let threads: Vec<Thread> =
+        serde_json::from_value(response["data"]["Page"]["threads"].clone()).unwrap();
+    assert_eq!(threads.len(), 1);
+    // The viewer (42) posted the latest reply but did not start the thread
+    assert_eq!(threads[0].reply_user_id, Some(42));
+    assert_ne!(threads[0].user_id, 42);
+}
+
+fn thread_with_flags(id: i32, is_sticky: Option<bool>, is_locked: Option<bool>) -> Thread {
+    serde_json::from_value(json!({
+        "id": id,
+        "title": "Thread",
+        "userId": 1,
+        "isSticky": is_sticky,
+        "isLocked": is_locked,
+        "likeCount": 0,
+        "replyCount": id * 10,
+        "viewCount": id * 100,
+        "createdAt": 0,
+        "updatedAt": 0
+    }))
+    .expect("thread fixture should deserialize")
+}
+
+#[test]
+fn test_thread_filter_sticky_and_locked() {
+    use anilist_sdk::models::ThreadFilter;
+
+    let threads = vec![
+        thread_with_flags(1, Some(true), Some(false)),
+        thread_with_flags(2, Some(true), Some(true)),
+        thread_with_flags(3, Some(false), Some(true)),
+        thread_with_flags(4, None, None),
+    ];
+    let ids = |filter: &ThreadFilter| -> Vec<i32> {
+        filter
+            .apply(threads.clone())
+            .iter()
+            .map(|thread| thread.id)
+            .collect()
+    };
+
+    assert_eq!(ids(&ThreadFilter::new()), vec![1, 2, 3, 4]);
+    assert_eq!(ids(&ThreadFilter::new().is_sticky(true)), vec![1, 2]);
+    // Missing flags count as not sticky and not locked
+    assert_eq!(ids(&ThreadFilter::new().is_sticky(false)), vec![3, 4]);
+    assert_eq!(ids(&ThreadFilter::new().exclude_locked(true)), vec![1, 4]);
+    assert_eq!(
+        ids(&ThreadFilter::new().is_sticky(true).exclude_locked(true)),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_thread_selections_include_engagement_counts() {
+    use anilist_sdk::queries::forum;
+
+    for query in [
+        forum::GET_RECENT_THREADS,
+        forum::GET_THREAD_BY_ID,
+        forum::SEARCH_THREADS,
+        forum::GET_THREADS_BY_USER,
+        forum::CREATE_THREAD,
+    ] {
+        for field in ["viewCount", "replyCount", "isSticky", "isLocked"] {
+            assert!(query.contains(field), "thread selection is missing {field}");
+        }
+    }
+
+    let mut threads = [
+        thread_with_flags(1, None, None),
+        thread_with_flags(3, None, None),
+        thread_with_flags(2, None, None),
+    ];
+    threads.sort_by_key(|thread| std::cmp::Reverse(thread.view_count));
+    let ids: Vec<i32> = threads.iter().map(|thread| thread.id).collect();
+    assert_eq!(ids, vec![3, 2, 1]);
+}
+
+#[test]
+fn test_subscribed_threads_fixture() {
+    use anilist_sdk::models::PageInfo;
+
+    let response = json!({
+        "data": {
+            "Page": {
+                "pageInfo": { "total": 2, "perPage": 10, "currentPage": 1, "lastPage": 1, "hasNextPage": false },
+                "threads": [
+                    { "id": 81, "title": "Weekly recommendations", "userId": 3, "isSubscribed": true, "likeCount": 12, "createdAt": 1700000000, "updatedAt": 1700090000 },
+                    { "id": 82, "title": "Site feedback", "userId": 4, "isSubscribed": true, "likeCount": 0, "createdAt": 1700000500, "updatedAt": 1700080000 }
+                ]
+            }
+        }
+    });
+
+    let page_info: PageInfo =
+        serde_json::from_value(response["data"]["Page"]["pageInfo"].clone()).unwrap();
+    assert_eq!(page_info.total, Some(2));
+
+    let threads: Vec<Thread> =
+        serde_json::from_value(response["data"]["Page"]["threads"].clone()).unwrap();
+    assert_eq!(threads.len(), 2);
+    assert!(
+        threads
+            .iter()
+            .all(|thread| thread.is_subscribed == Some(true))
+    );
+
+    // Unsubscribing returns the thread with the flag cleared
+    let toggled = json!({
+        "data": {
+            "ToggleThreadSubscription": {
+                "id": 81, "title": "Weekly recommendations", "userId": 3, "isSubscribed": false,
+                "likeCount": 12, "createdAt": 1700000000, "updatedAt": 1700090000
+            }
+        }
+    });
+    let thread: Thread =
+        serde_json::from_value(toggled["data"]["ToggleThreadSubscription"].clone()).unwrap();
+    assert_eq!(thread.is_subscribed, Some(false));
+}
@@ -0,0 +1,166 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use anilist_sdk::utils::RetryConfig;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_utils::{MockApi, media_item, page_of, too_many_requests};
+
+mod test_utils;
+
+/// Serves 120 items at the requested page size, and viewer 42. Pages in
+/// `rate_limited` are answered with a 429 the first time they are asked for.
+fn three_pages(rate_limited: &[i64]) -> Arc<MockApi> {
+    let rate_limited = Mutex::new(rate_limited.to_vec());
+    Arc::new(MockApi::from_fn(move |request| {
+        if request.query().contains("Viewer") {
+            return TransportResponse::new(200, r#"{"data":{"Viewer":{"id":42,"name":"viewer"}}}"#);
+        }
+        let page = request.variables()["page"].as_i64().unwrap();
+        let mut rate_limited = rate_limited.lock().unwrap();
+        if let Some(index) = rate_limited.iter().position(|p| *p == page) {
+            rate_limited.remove(index);
+            return too_many_requests();
+        }
+        if request.query().contains("mediaList") {
+            let entry = |id| {
+                json!({
+                    "id": id, "mediaId": id, "userId": 42, "status": "CURRENT", "score": 0,
+                    "progress": 0, "repeat": 0, "priority": 0, "private": false,
+                    "hiddenFromStatusLists": false, "startedAt": {}, "completedAt": {}
+                })
+            };
+            page_of(request, "mediaList", 120, entry)
+        } else {
+            page_of(request, "media", 120, media_item)
+        }
+    }))
+}
+
+fn client(api: &Arc<MockApi>) -> AniListClient {
+    api.attach(AniListClient::builder().token("token"))
+}
+
+fn ids<T>(items: &[T], id: impl Fn(&T) -> i32) -> Vec<i32> {
+    items.iter().map(id).collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_season_lineup_is_fetched_across_three_pages() {
+    let api = three_pages(&[]);
+    let client = client(&api);
+
+    let lineup = client.anime().get_by_season_all("winter", 2024).await;
+
+    assert!(lineup.is_complete());
+    assert_eq!(lineup.pages_fetched, 3);
+    assert_eq!(
+        ids(&lineup.items, |anime| anime.id),
+        (1..=120).collect::<Vec<_>>()
+    );
+    assert_eq!(api.pages_requested(), [1, 2, 3]);
+    for variables in api.variables() {
+        assert_eq!(variables["season"], "WINTER");
+        assert_eq!(variables["year"], 2024);
+        assert_eq!(variables["perPage"], 50);
+    }
+    assert_eq!(api.gaps(), [Duration::from_millis(700); 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_max_pages_truncates_the_result() {
+    let api = three_pages(&[]);
+    let mut client = client(&api);
+    client.set_max_pages(2);
+
+    let lineup = client.anime().get_by_season_all("WINTER", 2024).await;
+
+    assert!(lineup.truncated);
+    assert!(!lineup.is_complete());
+    assert_eq!(lineup.items.len(), 100);
+    assert_eq!(api.pages_requested(), [1, 2]);
+    assert_eq!(lineup.into_result().unwrap().len(), 100);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limit_midway_keeps_the_pages_fetched() {
+    let api = three_pages(&[2]);
+    let client = client(&api);
+
+    let lineup = client.anime().get_by_season_all("WINTER", 2024).await;
+
+    assert_eq!(lineup.pages_fetched, 1);
+    assert_eq!(lineup.items.len(), 50);
+    assert!(!lineup.truncated);
+    assert!(matches!(lineup.error, Some(AniListError::RateLimitSimple)));
+    assert!(matches!(
+        lineup.into_result(),
+        Err(AniListError::RateLimitSimple)
+    ));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limit_midway_is_retried_when_configured() {
+    let api = three_pages(&[2]);
+    let mut client = client(&api);
+    client.set_retry(Some(RetryConfig::default()));
+
+    let lineup = client.anime().get_by_season_all("WINTER", 2024).await;
+
+    assert!(lineup.is_complete());
+    assert_eq!(lineup.items.len(), 120);
+    assert_eq!(api.pages_requested(), [1, 2, 2, 3]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_current_user_anime_list_is_fetched_across_three_pages() {
+    let api = three_pages(&[]);
+    let mut client = client(&api);
+    client.set_page_delay(Duration::ZERO);
+
+    let list = client
+        .user()
+        .get_current_user_anime_list_all(Some("current"))
+        .await;
+
+    assert!(list.is_complete());
+    assert_eq!(
+        ids(&list.items, |entry| entry.id),
+        (1..=120).collect::<Vec<_>>()
+    );
+    // One viewer lookup, then the pages
+    assert_eq!(api.variables().len(), 4);
+    assert_eq!(api.pages_requested(), [1, 2, 3]);
+    for variables in &api.variables()[1..] {
+        assert_eq!(variables["userId"], 42);
+        assert_eq!(variables["type"], "ANIME");
+        assert_eq!(variables["status"], "CURRENT");
+    }
+    assert_eq!(api.gaps(), [Duration::ZERO; 3]);
+}
+
+#[tokio::test]
+async fn test_current_user_anime_list_needs_a_token() {
+    let api = three_pages(&[]);
+    let mut client = client(&api);
+    client.clear_token();
+
+    let list = client.user().get_current_user_anime_list_all(None).await;
+
+    assert!(matches!(
+        list.error,
+        Some(AniListError::AuthenticationRequired)
+    ));
+    assert!(list.items.is_empty());
+    assert!(api.variables().is_empty());
+}
+
+#[test]
+fn test_max_pages_must_be_positive() {
+    assert_eq!(AniListClient::new().max_pages(), 40);
+    assert!(matches!(
+        AniListClient::builder().max_pages(0).build(),
+        Err(AniListError::Config { .. })
+    ));
+}
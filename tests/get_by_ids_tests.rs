@@ -0,0 +1,109 @@
+use anilist_sdk::transport::TransportResponse;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::{MockApi, media_item};
+
+mod test_utils;
+
+/// Answers `id_in` lookups for the IDs up to `last_id`, in AniList's own
+/// (descending) order rather than the order asked for.
+fn id_api(last_id: usize) -> Arc<MockApi> {
+    Arc::new(MockApi::from_fn(move |request| {
+        let mut ids: Vec<usize> = request.variables()["ids"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_u64().unwrap() as usize)
+            .filter(|id| *id <= last_id)
+            .collect();
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        let media: Vec<Value> = ids.iter().copied().map(media_item).collect();
+        let body = json!({"data": {"Page": {
+            "media": media,
+            "pageInfo": {"total": ids.len(), "currentPage": 1, "hasNextPage": false},
+        }}});
+        TransportResponse::new(200, body.to_string())
+    }))
+}
+
+fn ids_of(anime: &[Option<anilist_sdk::models::Anime>]) -> Vec<Option<i32>> {
+    anime
+        .iter()
+        .map(|anime| anime.as_ref().map(|anime| anime.id))
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_results_follow_the_input_order_with_gaps_for_missing_ids() {
+    let api = id_api(100);
+    let client = api.client();
+
+    let anime = client
+        .anime()
+        .get_by_ids(&[20, 999_999, 1, 20, 5000, 7])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        ids_of(&anime),
+        [Some(20), None, Some(1), Some(20), None, Some(7)]
+    );
+    assert_eq!(
+        anime[2].as_ref().unwrap().title.romaji.as_deref(),
+        Some("Title 1")
+    );
+
+    // One request, each ID sent once
+    let variables = api.variables();
+    assert_eq!(variables.len(), 1);
+    assert_eq!(variables[0]["ids"], json!([20, 999_999, 1, 5000, 7]));
+    assert_eq!(variables[0]["page"], 1);
+    assert_eq!(variables[0]["perPage"], 5);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_more_than_fifty_ids_are_split_into_paced_requests() {
+    let api = id_api(1000);
+    let client = api.client();
+    let ids: Vec<i32> = (1..=120).rev().collect();
+
+    let anime = client.anime().get_by_ids(&ids).await.unwrap();
+
+    assert_eq!(
+        ids_of(&anime),
+        ids.iter().map(|id| Some(*id)).collect::<Vec<_>>()
+    );
+    let sizes: Vec<usize> = api
+        .variables()
+        .iter()
+        .map(|variables| variables["ids"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(sizes, [50, 50, 20]);
+    assert_eq!(api.variables()[2]["perPage"], 20);
+    assert_eq!(api.gaps(), [Duration::from_millis(700); 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_duplicates_do_not_count_towards_a_chunk() {
+    let api = id_api(1000);
+    let client = api.client();
+    let ids: Vec<i32> = (1..=50).chain(1..=50).collect();
+
+    let anime = client.anime().get_by_ids(&ids).await.unwrap();
+
+    assert_eq!(anime.len(), 100);
+    assert!(anime.iter().all(Option::is_some));
+    assert_eq!(api.variables().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_no_ids_sends_no_request() {
+    let api = id_api(100);
+    let client = api.client();
+
+    let anime = client.anime().get_by_ids(&[]).await.unwrap();
+
+    assert!(anime.is_empty());
+    assert!(api.variables().is_empty());
+}
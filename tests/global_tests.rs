@@ -0,0 +1,57 @@
+use anilist_sdk::client::{AniListClient, ClientConfig, TOKEN_ENV_VAR};
+use anilist_sdk::error::AniListError;
+
+// The global client can only be initialized once per process, so its
+// semantics are exercised in a single test to keep the order deterministic.
+#[test]
+fn test_global_client_init_semantics() {
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::set_var(TOKEN_ENV_VAR, "env-token") };
+
+    let config = ClientConfig::default().moderator_mode(true);
+    let client = anilist_sdk::init(config.clone()).expect("first init succeeds");
+    assert!(client.has_token(), "token is picked up from ANILIST_TOKEN");
+    assert!(client.is_moderator_mode());
+
+    // Re-initializing with the same configuration returns the same client
+    let again = anilist_sdk::init(config).expect("same config is accepted");
+    assert!(std::ptr::eq(client, again));
+    assert!(std::ptr::eq(client, anilist_sdk::global().unwrap()));
+
+    // A different configuration is rejected
+    let conflicting = anilist_sdk::init(ClientConfig::default().token("other-token"));
+    match conflicting {
+        Err(AniListError::Config { message }) => {
+            assert!(!message.contains("env-token"), "tokens are not leaked");
+        }
+        other => panic!("expected Config error, got {:?}", other.map(|_| ())),
+    }
+
+    // Environment handling in the configuration itself
+    assert_eq!(
+        ClientConfig::default().resolved_token().as_deref(),
+        Some("env-token")
+    );
+    assert_eq!(
+        ClientConfig::default()
+            .token("explicit")
+            .resolved_token()
+            .as_deref(),
+        Some("explicit")
+    );
+    assert_eq!(
+        ClientConfig::default()
+            .use_env_token(false)
+            .resolved_token(),
+        None
+    );
+    assert!(
+        !AniListClient::from_config(&ClientConfig::default().use_env_token(false))
+            .unwrap()
+            .has_token()
+    );
+
+    unsafe { std::env::set_var(TOKEN_ENV_VAR, "  ") };
+    assert_eq!(ClientConfig::default().resolved_token(), None);
+}
@@ -0,0 +1,118 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::health::RateLimitStatus;
+use anilist_sdk::transport::TransportResponse;
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::sync::Arc;
+use test_utils::{MockApi, authorization};
+mod test_utils;
+
+const VIEWER: &str = r#"{"data":{"Viewer":{"id":5123456}}}"#;
+const MEDIA_PROBE: &str = r#"{"data":{"Media":{"id":1}}}"#;
+const INVALID_TOKEN: &str = r#"{"errors":[{"message":"Invalid token","status":400}]}"#;
+
+/// A refused connection, as the transport reports it.
+async fn connection_refused() -> AniListError {
+    // Nothing listens on a port just released by a listener
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let error = reqwest::get(url).await.expect_err("nothing should answer");
+    AniListError::from(error)
+}
+
+#[tokio::test]
+async fn test_health_check_authenticated() {
+    let transport = Arc::new(MockApi::respond(
+        TransportResponse::new(200, VIEWER)
+            .with_header("X-RateLimit-Limit", "90")
+            .with_header("X-RateLimit-Remaining", "57"),
+    ));
+    let client = transport.attach(AniListClient::builder().token("token"));
+
+    let report = client.health_check().await.unwrap();
+
+    assert!(report.authenticated);
+    assert!(!report.token_rejected);
+    assert_eq!(report.viewer_id, Some(5123456));
+    assert_eq!(report.rate_limit.unwrap().remaining, 57);
+    assert!(report.is_healthy());
+    assert_eq!(transport.calls(), 1);
+    assert_eq!(authorization(&transport.last_request()), "Bearer token");
+}
+
+#[tokio::test]
+async fn test_health_check_unauthenticated() {
+    let transport = Arc::new(MockApi::ok(MEDIA_PROBE));
+    let client = transport.client();
+
+    let report = client.health_check().await.unwrap();
+
+    assert!(!report.authenticated);
+    assert!(!report.token_rejected);
+    assert!(report.viewer_id.is_none());
+    assert!(report.is_healthy());
+    assert_eq!(transport.authorizations(), [""]);
+    assert!(transport.last_request().query().contains("Media"));
+}
+
+#[tokio::test]
+async fn test_health_check_rejected_token() {
+    let transport = Arc::new(MockApi::scripted([
+        TransportResponse::new(400, INVALID_TOKEN),
+        TransportResponse::new(200, MEDIA_PROBE),
+    ]));
+    let client = transport.attach(AniListClient::builder().token("not-a-valid-token"));
+
+    let report = client
+        .health_check()
+        .await
+        .expect("An invalid token should be reported, not returned as an error");
+
+    assert!(!report.authenticated);
+    assert!(report.token_rejected);
+    assert!(report.viewer_id.is_none());
+    assert!(!report.is_healthy());
+    // The API itself was confirmed reachable without the token
+    assert_eq!(transport.authorizations(), ["Bearer not-a-valid-token", ""]);
+}
+
+#[tokio::test]
+async fn test_health_check_network_failure() {
+    let error = connection_refused().await;
+    let transport = Arc::new(MockApi::try_from_fn(move |_| Err(error.clone())));
+
+    for client in [
+        transport.client(),
+        transport.attach(AniListClient::builder().token("token")),
+    ] {
+        let result = client.health_check().await;
+        assert!(
+            matches!(result, Err(AniListError::Network(_))),
+            "{result:?}"
+        );
+    }
+    // A failure that is not about the token is not probed again without it
+    assert_eq!(transport.calls(), 2);
+}
+
+#[test]
+fn test_rate_limit_status_from_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-RateLimit-Limit", HeaderValue::from_static("90"));
+    headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("57"));
+
+    let status = RateLimitStatus::from_headers(&headers).expect("headers are present");
+    assert_eq!(status.limit, 90);
+    assert_eq!(status.remaining, 57);
+    assert_eq!(status.reset_at, None);
+
+    headers.insert("X-RateLimit-Reset", HeaderValue::from_static("1700000000"));
+    let status = RateLimitStatus::from_headers(&headers).unwrap();
+    assert_eq!(status.reset_at, Some(1_700_000_000));
+}
+
+#[test]
+fn test_rate_limit_status_missing_headers() {
+    assert!(RateLimitStatus::from_headers(&HeaderMap::new()).is_none());
+}
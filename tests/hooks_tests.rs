@@ -0,0 +1,136 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use reqwest::header::HeaderValue;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_utils::{MEDIA, MockApi};
+
+mod test_utils;
+
+/// Answers every request with `MEDIA` and a request ID after 40ms.
+fn client() -> (AniListClient, Arc<MockApi>) {
+    let response = TransportResponse::new(200, MEDIA).with_header("x-request-id", "abc");
+    let api = Arc::new(MockApi::respond(response).delay(Duration::from_millis(40)));
+    (api.client(), api)
+}
+
+#[tokio::test]
+async fn test_request_hook_header_reaches_the_server() {
+    let (mut client, api) = client();
+    client.add_request_hook(|request| {
+        request
+            .headers_mut()
+            .insert("x-correlation-id", HeaderValue::from_static("checkout-42"));
+        Ok::<_, String>(())
+    });
+
+    client.anime().get_by_id(1).await.unwrap();
+
+    let requests = api.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].headers["x-correlation-id"], "checkout-42");
+}
+
+#[tokio::test]
+async fn test_failing_request_hook_aborts_without_sending() {
+    let (mut client, api) = client();
+    let later_hook_ran = Arc::new(Mutex::new(false));
+    client.add_request_hook(|request| {
+        if request.headers().contains_key("x-correlation-id") {
+            Ok(())
+        } else {
+            Err("missing correlation ID")
+        }
+    });
+    let ran = later_hook_ran.clone();
+    client.add_request_hook(move |_| {
+        *ran.lock().unwrap() = true;
+        Ok::<_, String>(())
+    });
+
+    let result = client.anime().get_by_id(1).await;
+
+    match result {
+        Err(AniListError::RequestAborted { message }) => {
+            assert_eq!(message, "missing correlation ID");
+        }
+        other => panic!("expected RequestAborted, got {other:?}"),
+    }
+    assert!(api.requests().is_empty());
+    assert!(!*later_hook_ran.lock().unwrap());
+    assert_eq!(client.metrics().requests, 0);
+}
+
+#[tokio::test]
+async fn test_hooks_run_in_registration_order() {
+    let (mut client, _api) = client();
+    let order = Arc::new(Mutex::new(Vec::new()));
+    for name in ["request 1", "request 2"] {
+        let order = order.clone();
+        client.add_request_hook(move |_| {
+            order.lock().unwrap().push(name);
+            Ok::<_, String>(())
+        });
+    }
+    for name in ["response 1", "response 2"] {
+        let order = order.clone();
+        client.add_response_hook(move |_| order.lock().unwrap().push(name));
+    }
+
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(
+        *order.lock().unwrap(),
+        ["request 1", "request 2", "response 1", "response 2"]
+    );
+}
+
+#[tokio::test]
+async fn test_hooks_see_the_operation_and_response() {
+    let (mut client, _api) = client();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let requests = seen.clone();
+    client.add_request_hook(move |request| {
+        requests.lock().unwrap().push(format!(
+            "{} id={}",
+            request.query().contains("Media("),
+            request.variables()["id"]
+        ));
+        Ok::<_, String>(())
+    });
+    let responses = seen.clone();
+    client.add_response_hook(move |response| {
+        responses.lock().unwrap().push(format!(
+            "{} {} {} {}",
+            response.query().contains("Media("),
+            response.status(),
+            response.headers()["x-request-id"].to_str().unwrap(),
+            response.elapsed() >= Duration::from_millis(40),
+        ));
+    });
+
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), ["true id=1", "true 200 abc true"]);
+}
+
+#[tokio::test]
+async fn test_clones_made_afterwards_share_hooks() {
+    let (mut client, api) = client();
+    let before = client.clone();
+    client.add_request_hook(|request| {
+        request
+            .headers_mut()
+            .insert("x-tenant", HeaderValue::from_static("shop"));
+        Ok::<_, String>(())
+    });
+    let after = client.clone();
+
+    after.anime().get_by_id(1).await.unwrap();
+    before.anime().get_by_id(1).await.unwrap();
+
+    let requests = api.requests();
+    assert_eq!(requests[0].headers["x-tenant"], "shop");
+    assert!(!requests[1].headers.contains_key("x-tenant"));
+}
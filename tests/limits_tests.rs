@@ -0,0 +1,131 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::limits;
+
+fn assert_violates<T: std::fmt::Debug>(result: Result<T, AniListError>, limit_name: &str) {
+    match result {
+        Err(AniListError::BadRequest { message }) => assert!(
+            message.contains(limit_name),
+            "expected {limit_name} in {message:?}"
+        ),
+        other => panic!("expected BadRequest naming {limit_name}, got {other:?}"),
+    }
+}
+
+fn valid_review_body() -> String {
+    "A thoughtful paragraph. ".repeat(limits::REVIEW_BODY_MIN_CHARS / 20)
+}
+
+#[tokio::test]
+async fn test_review_body_min_chars() {
+    let client = AniListClient::with_token("token".to_string());
+    let short_body = "x".repeat(limits::REVIEW_BODY_MIN_CHARS - 1);
+
+    let result = client
+        .review()
+        .save_review(1, &short_body, None, None, None)
+        .await;
+    assert_violates(result, "REVIEW_BODY_MIN_CHARS");
+
+    // Whitespace padding does not count towards the minimum
+    let padded = format!(
+        "{}{}",
+        "x".repeat(10),
+        " ".repeat(limits::REVIEW_BODY_MIN_CHARS)
+    );
+    let result = client
+        .review()
+        .save_review(1, &padded, None, None, None)
+        .await;
+    assert_violates(result, "REVIEW_BODY_MIN_CHARS");
+}
+
+#[tokio::test]
+async fn test_review_summary_limits() {
+    let client = AniListClient::with_token("token".to_string());
+    let body = valid_review_body();
+
+    let short = "x".repeat(limits::SUMMARY_MIN_CHARS - 1);
+    let result = client
+        .review()
+        .save_review(1, &body, Some(&short), None, None)
+        .await;
+    assert_violates(result, "SUMMARY_MIN_CHARS");
+
+    let long = "x".repeat(limits::SUMMARY_MAX_CHARS + 1);
+    let result = client
+        .review()
+        .save_review(1, &body, Some(&long), None, None)
+        .await;
+    assert_violates(result, "SUMMARY_MAX_CHARS");
+
+    // Limits count characters, not bytes
+    let multibyte = "進".repeat(limits::SUMMARY_MAX_CHARS + 1);
+    let result = client
+        .review()
+        .save_review(1, &body, Some(&multibyte), None, None)
+        .await;
+    assert_violates(result, "SUMMARY_MAX_CHARS");
+}
+
+#[tokio::test]
+async fn test_review_score_range() {
+    let client = AniListClient::with_token("token".to_string());
+    let body = valid_review_body();
+    let summary = "A fair and balanced summary";
+
+    let result = client
+        .review()
+        .save_review(
+            1,
+            &body,
+            Some(summary),
+            Some(limits::REVIEW_SCORE_MAX + 1),
+            None,
+        )
+        .await;
+    assert_violates(result, "REVIEW_SCORE_MAX");
+
+    let result = client
+        .review()
+        .save_review(
+            1,
+            &body,
+            Some(summary),
+            Some(limits::REVIEW_SCORE_MIN - 1),
+            None,
+        )
+        .await;
+    assert_violates(result, "REVIEW_SCORE_MIN");
+}
+
+#[tokio::test]
+async fn test_text_min_chars() {
+    let client = AniListClient::with_token("token".to_string());
+
+    assert_violates(
+        client.activity().create_text_activity("   ").await,
+        "TEXT_MIN_CHARS",
+    );
+    assert_violates(
+        client.activity().post_activity_reply(1, "").await,
+        "TEXT_MIN_CHARS",
+    );
+    assert_violates(
+        client.forum().create_thread("", "body", None).await,
+        "TEXT_MIN_CHARS",
+    );
+    assert_violates(
+        client.forum().create_thread("title", "\n", None).await,
+        "TEXT_MIN_CHARS",
+    );
+    assert_violates(client.forum().post_comment(1, "").await, "TEXT_MIN_CHARS");
+}
+
+#[tokio::test]
+async fn test_about_max_chars() {
+    let client = AniListClient::with_token("token".to_string());
+
+    let about = "a".repeat(limits::ABOUT_MAX_CHARS + 1);
+    assert_violates(client.user().update_about(&about).await, "ABOUT_MAX_CHARS");
+}
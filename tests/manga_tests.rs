@@ -13,7 +13,7 @@ async fn test_get_popular_manga() {
     // Check that all manga have required fields
     for manga in &manga_list {
         assert!(manga.id > 0);
-        assert!(manga.title.is_some());
+        assert!(manga.title.romaji.is_some());
     }
 }
 
@@ -35,7 +35,7 @@ async fn test_get_manga_by_id() {
 
     let manga = result.expect("Failed to get manga by ID");
     assert_eq!(manga.id, 30013);
-    assert!(manga.title.is_some());
+    assert!(manga.title.romaji.is_some());
 }
 
 #[tokio::test]
@@ -48,18 +48,15 @@ async fn test_search_manga() {
 
     // Check that results contain "One Piece" in some form
     let has_one_piece = manga_list.iter().any(|manga| {
-        if let Some(title) = &manga.title {
-            title
-                .romaji
+        let title = &manga.title;
+        title
+            .romaji
+            .as_ref()
+            .is_some_and(|t| t.to_lowercase().contains("one piece"))
+            || title
+                .english
                 .as_ref()
                 .is_some_and(|t| t.to_lowercase().contains("one piece"))
-                || title
-                    .english
-                    .as_ref()
-                    .is_some_and(|t| t.to_lowercase().contains("one piece"))
-        } else {
-            false
-        }
     });
     assert!(has_one_piece);
 }
@@ -79,6 +76,28 @@ async fn test_get_top_rated_manga() {
     }
 }
 
+#[tokio::test]
+async fn test_get_top_rated_manga_with_min_popularity() {
+    let client = AniListClient::new();
+    let min_popularity = 50_000;
+
+    let manga_list = crate::manga_api_call!(
+        client,
+        get_top_rated_with_min_popularity,
+        1,
+        25,
+        Some(min_popularity)
+    )
+    .expect("Failed to get top rated manga with a popularity threshold");
+
+    assert!(!manga_list.is_empty());
+    // Tiny-sample outliers fall below the threshold and must be filtered out
+    for manga in &manga_list {
+        let popularity = manga.popularity.expect("popularity should be selected");
+        assert!(popularity >= min_popularity);
+    }
+}
+
 #[tokio::test]
 async fn test_get_releasing_manga() {
     let client = AniListClient::new();
@@ -102,6 +121,139 @@ async fn test_get_completed_manga() {
 
     for manga in &manga_list {
         assert!(manga.id > 0);
-        assert!(manga.title.is_some());
+        assert!(manga.title.romaji.is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_get_adaptation_airing() {
+    let client = AniListClient::new();
+    // One Piece (manga) has a long-running anime adaptation
+    let result = crate::manga_api_call!(client, get_adaptation_airing, 30013)
+        .expect("Failed to get adaptation airing");
+
+    if let Some((anime, airing)) = result {
+        assert!(anime.id > 0);
+        assert_eq!(airing.media_id, anime.id);
+        assert!(airing.episode > 0);
+    }
+}
+
+fn adaptation_edge(
+    id: i32,
+    relation: &str,
+    media_type: &str,
+    status: &str,
+    episode: Option<i32>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "relationType": relation,
+        "node": {
+            "id": id,
+            "type": media_type,
+            "title": { "userPreferred": format!("Media {id}") },
+            "format": if media_type == "ANIME" { "TV" } else { "MANGA" },
+            "status": status,
+            "nextAiringEpisode": episode.map(|episode| serde_json::json!({
+                "id": id * 100 + episode,
+                "airingAt": 1_700_000_000,
+                "timeUntilAiring": 3600,
+                "episode": episode,
+                "mediaId": id
+            }))
+        }
+    })
+}
+
+fn select(edges: serde_json::Value) -> Option<(i32, i32)> {
+    use anilist_sdk::models::MediaRelationEdge;
+    use anilist_sdk::models::relation::select_airing_adaptation;
+
+    let edges: Vec<MediaRelationEdge> = serde_json::from_value(edges).unwrap();
+    select_airing_adaptation(&edges).map(|(media, airing)| (media.id, airing.episode))
+}
+
+#[test]
+fn test_adaptation_selection_prefers_releasing() {
+    let edges = serde_json::json!([
+        adaptation_edge(1, "ADAPTATION", "ANIME", "NOT_YET_RELEASED", Some(1)),
+        adaptation_edge(2, "ADAPTATION", "ANIME", "RELEASING", Some(9)),
+        adaptation_edge(3, "ADAPTATION", "ANIME", "RELEASING", Some(4)),
+    ]);
+
+    assert_eq!(select(edges), Some((2, 9)));
+}
+
+#[test]
+fn test_adaptation_selection_ignores_other_relations() {
+    let edges = serde_json::json!([
+        adaptation_edge(1, "SEQUEL", "ANIME", "RELEASING", Some(3)),
+        adaptation_edge(2, "ADAPTATION", "MANGA", "RELEASING", None),
+        adaptation_edge(3, "ADAPTATION", "ANIME", "FINISHED", None),
+        adaptation_edge(4, "ADAPTATION", "ANIME", "NOT_YET_RELEASED", Some(1)),
+    ]);
+
+    assert_eq!(select(edges), Some((4, 1)));
+}
+
+#[test]
+fn test_adaptation_selection_without_scheduled_episode() {
+    let edges = serde_json::json!([
+        adaptation_edge(1, "ADAPTATION", "ANIME", "FINISHED", None),
+        { "relationType": "ADAPTATION", "node": null },
+    ]);
+
+    assert_eq!(select(edges), None);
+    assert_eq!(select(serde_json::json!([])), None);
+}
+
+#[test]
+fn test_manga_query_selects_trailer() {
+    assert!(anilist_sdk::queries::manga::GET_BY_ID.contains("trailer {"));
+}
+
+#[test]
+fn test_reading_sites_normalize_and_prefer_official() {
+    use anilist_sdk::models::Manga;
+
+    let manga: Manga = serde_json::from_value(serde_json::json!({
+        "id": 30013,
+        "title": { "romaji": "ONE PIECE" },
+        "externalLinks": [
+            { "id": 1, "url": "https://www.viz.com/one-piece", "site": "VIZ Media", "type": "STREAMING", "language": "English" },
+            { "id": 2, "url": "https://mangaplus.shueisha.co.jp/titles/100020", "site": "MANGA Plus by SHUEISHA", "type": "STREAMING", "language": "English" },
+            { "id": 3, "url": "https://one-piece.com", "site": "Official Site", "type": "INFO", "language": "Japanese" },
+            { "id": 4, "url": "https://twitter.com/Eiichiro_Staff", "site": "Twitter", "type": "SOCIAL" },
+            { "id": 5, "url": "https://www.crunchyroll.com/one-piece", "site": "Crunchyroll", "type": "STREAMING" }
+        ]
+    }))
+    .unwrap();
+
+    let names: Vec<_> = manga.reading_sites().iter().map(|site| site.site).collect();
+    assert_eq!(names, ["Official Site", "MANGA Plus", "VIZ"]);
+}
+
+#[test]
+fn test_normalize_read_site() {
+    use anilist_sdk::models::external_link::normalize_read_site;
+
+    assert_eq!(
+        normalize_read_site("MANGA Plus by SHUEISHA"),
+        Some("MANGA Plus")
+    );
+    assert_eq!(normalize_read_site("LINE Webtoon"), Some("WEBTOON"));
+    assert_eq!(normalize_read_site("BOOK☆WALKER"), Some("BookWalker"));
+    assert_eq!(normalize_read_site("Crunchyroll"), None);
+}
+
+#[test]
+fn test_get_by_id_selects_external_link_language_and_type() {
+    for query in [
+        anilist_sdk::queries::manga::GET_BY_ID,
+        anilist_sdk::queries::anime::GET_BY_ID,
+    ] {
+        let links = &query[query.find("externalLinks").expect("externalLinks selected")..];
+        assert!(links.contains("language"));
+        assert!(links.contains("type"));
     }
 }
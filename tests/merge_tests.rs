@@ -0,0 +1,230 @@
+use anilist_sdk::utils::merge::{HasId, dedup_by_id, interleave, stable_rank_merge};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    id: i32,
+    /// Distinguishes occurrences of the same ID
+    tag: u32,
+    score: f64,
+}
+
+impl HasId for Item {
+    fn id(&self) -> i32 {
+        self.id
+    }
+}
+
+fn item(id: i32, tag: u32) -> Item {
+    Item {
+        id,
+        tag,
+        score: 0.0,
+    }
+}
+
+fn ids<T: HasId>(items: &[T]) -> Vec<i32> {
+    items.iter().map(HasId::id).collect()
+}
+
+/// Small deterministic xorshift generator, so failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    /// Items with IDs drawn from a small range, so duplicates are common.
+    fn items(&mut self, max_len: u64) -> Vec<Item> {
+        (0..self.below(max_len + 1))
+            .map(|tag| Item {
+                id: self.below(20) as i32,
+                tag: tag as u32,
+                score: self.below(10) as f64,
+            })
+            .collect()
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+fn assert_unique<T: HasId>(items: &[T]) {
+    let mut seen = HashSet::new();
+    for id in ids(items) {
+        assert!(seen.insert(id), "duplicate id {id} in {:?}", ids(items));
+    }
+}
+
+#[test]
+fn test_dedup_keeps_first_occurrence() {
+    let merged = dedup_by_id(vec![
+        item(3, 0),
+        item(1, 1),
+        item(3, 2),
+        item(2, 3),
+        item(1, 4),
+    ]);
+    assert_eq!(merged, vec![item(3, 0), item(1, 1), item(2, 3)]);
+    assert!(dedup_by_id(Vec::<Item>::new()).is_empty());
+}
+
+#[test]
+fn test_dedup_properties() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..500 {
+        let input = rng.items(40);
+        let merged = dedup_by_id(input.clone());
+
+        assert_unique(&merged);
+        // Same inputs, same output
+        assert_eq!(merged, dedup_by_id(input.clone()));
+        // Idempotent
+        assert_eq!(merged, dedup_by_id(merged.clone()));
+        // Every ID survives, as its first occurrence, in input order
+        let expected: Vec<Item> = input
+            .iter()
+            .enumerate()
+            .filter(|(i, item)| !input[..*i].iter().any(|earlier| earlier.id == item.id))
+            .map(|(_, item)| item.clone())
+            .collect();
+        assert_eq!(merged, expected);
+    }
+}
+
+#[test]
+fn test_interleave_round_robin() {
+    let merged = interleave(vec![
+        vec![item(1, 0), item(2, 0), item(3, 0)],
+        vec![item(10, 1)],
+        vec![item(20, 2), item(21, 2)],
+    ]);
+    assert_eq!(ids(&merged), vec![1, 10, 20, 2, 21, 3]);
+
+    // A title in several sources appears once, where it is first reached
+    let merged = interleave(vec![
+        vec![item(1, 0), item(5, 0)],
+        vec![item(5, 1), item(1, 1), item(7, 1)],
+    ]);
+    assert_eq!(merged, vec![item(1, 0), item(5, 1), item(7, 1)]);
+
+    assert!(interleave(Vec::<Vec<Item>>::new()).is_empty());
+    assert!(interleave(vec![Vec::<Item>::new(), Vec::new()]).is_empty());
+}
+
+#[test]
+fn test_interleave_properties() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..500 {
+        let sources: Vec<Vec<Item>> = (0..rng.below(5)).map(|_| rng.items(15)).collect();
+        let merged = interleave(sources.clone());
+
+        assert_unique(&merged);
+        assert_eq!(merged, interleave(sources.clone()));
+
+        let all: HashSet<i32> = sources.iter().flat_map(|s| ids(s)).collect();
+        let kept: HashSet<i32> = ids(&merged).into_iter().collect();
+        assert_eq!(kept, all);
+    }
+}
+
+#[test]
+fn test_stable_rank_merge_combines_and_sorts() {
+    let input = vec![
+        Item {
+            id: 4,
+            tag: 0,
+            score: 2.0,
+        },
+        Item {
+            id: 9,
+            tag: 1,
+            score: 5.0,
+        },
+        Item {
+            id: 4,
+            tag: 2,
+            score: 4.0,
+        },
+        Item {
+            id: 1,
+            tag: 3,
+            score: 6.0,
+        },
+        Item {
+            id: 2,
+            tag: 4,
+            score: f64::NAN,
+        },
+    ];
+    let merged = stable_rank_merge(
+        input,
+        |merged, other| merged.score += other.score,
+        |item| item.score,
+    );
+
+    // 4 and 1 tie at 6.0 and are ordered by ID; NaN sorts last
+    assert_eq!(ids(&merged), vec![1, 4, 9, 2]);
+    // Duplicates are folded into the first occurrence
+    assert_eq!(merged[1].tag, 0);
+    assert_eq!(merged[1].score, 6.0);
+}
+
+#[test]
+fn test_stable_rank_merge_properties() {
+    let mut rng = Rng(0xdead_beef_cafe_f00d);
+    let sum = |merged: &mut Item, other: Item| merged.score += other.score;
+    let score = |item: &Item| item.score;
+
+    for _ in 0..500 {
+        let input = rng.items(40);
+        let merged = stable_rank_merge(input.clone(), sum, score);
+
+        assert_unique(&merged);
+        assert_eq!(merged, stable_rank_merge(input.clone(), sum, score));
+
+        // Sorted by score, then ID
+        for pair in merged.windows(2) {
+            assert!(
+                pair[0].score > pair[1].score
+                    || (pair[0].score == pair[1].score && pair[0].id < pair[1].id),
+                "{pair:?}"
+            );
+        }
+
+        // With an order-independent combine the ranking does not depend on
+        // the input order either
+        let mut shuffled = input.clone();
+        rng.shuffle(&mut shuffled);
+        let reordered = stable_rank_merge(shuffled, sum, score);
+        assert_eq!(
+            ids(&merged),
+            ids(&reordered),
+            "input order changed the ranking"
+        );
+        let scores = |items: &[Item]| items.iter().map(|i| i.score).collect::<Vec<_>>();
+        assert_eq!(scores(&merged), scores(&reordered));
+    }
+}
+
+#[test]
+fn test_models_use_their_anilist_id() {
+    use anilist_sdk::models::MediaRef;
+
+    let media: MediaRef = serde_json::from_value(serde_json::json!({ "id": 21 })).unwrap();
+    assert_eq!(HasId::id(&media), 21);
+    let merged = dedup_by_id(vec![media.clone(), media]);
+    assert_eq!(merged.len(), 1);
+}
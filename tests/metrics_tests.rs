@@ -0,0 +1,214 @@
+use anilist_sdk::client::{AniListClient, ClientConfig};
+use anilist_sdk::error::AniListError;
+use anilist_sdk::metrics::{MetricsSnapshot, OperationMetrics};
+use anilist_sdk::transport::TransportResponse;
+use anilist_sdk::utils::RetryConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::{EMPTY_PAGE, MEDIA, MockApi, not_found, too_many_requests};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+
+mod test_utils;
+
+/// Sends a request to a local address and wraps the failure like the client does.
+async fn local_error(client: &reqwest::Client, port: u16) -> AniListError {
+    let error = client
+        .post(format!("http://127.0.0.1:{port}"))
+        .body("{}")
+        .send()
+        .await
+        .expect_err("local server should fail the request");
+    AniListError::from(error)
+}
+
+#[tokio::test]
+async fn test_refused_connection_is_a_connection_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let error = local_error(&reqwest::Client::new(), port).await;
+    assert!(error.is_connection_error(), "{error:?}");
+}
+
+#[tokio::test]
+async fn test_dropped_connection_is_a_connection_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    // Read the request, then hang up without answering, like a CDN closing a
+    // pooled connection
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = [0; 1024];
+        let _ = socket.read(&mut buffer).await;
+    });
+
+    let error = local_error(&reqwest::Client::new(), port).await;
+    assert!(error.is_connection_error(), "{error:?}");
+}
+
+#[tokio::test]
+async fn test_timeout_is_not_a_connection_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    // Accept and stay silent
+    tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        drop(socket);
+    });
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let error = local_error(&client, port).await;
+    assert!(!error.is_connection_error(), "{error:?}");
+}
+
+#[test]
+fn test_other_errors_are_not_connection_errors() {
+    assert!(!AniListError::NotFound.is_connection_error());
+    assert!(
+        !AniListError::ServerError {
+            status: 502,
+            message: "Bad Gateway".to_string()
+        }
+        .is_connection_error()
+    );
+}
+
+#[test]
+fn test_new_client_metrics_are_zero() {
+    let client = AniListClient::new();
+    assert_eq!(client.metrics(), MetricsSnapshot::default());
+    assert_eq!(client.clone().metrics(), MetricsSnapshot::default());
+}
+
+#[test]
+fn test_connection_error_rate() {
+    assert_eq!(MetricsSnapshot::default().connection_error_rate(), 0.0);
+
+    let snapshot = MetricsSnapshot {
+        requests: 200,
+        http_errors: 3,
+        network_errors: 6,
+        connection_errors: 5,
+        ..MetricsSnapshot::default()
+    };
+    assert_eq!(snapshot.connection_error_rate(), 0.025);
+}
+
+#[test]
+fn test_pool_settings_on_config() {
+    let config = ClientConfig::default();
+    assert_eq!(config.pool_idle_timeout, None);
+    assert_eq!(config.pool_max_idle_per_host, None);
+    assert_eq!(config.tcp_keepalive, None);
+
+    let config = ClientConfig::default()
+        .use_env_token(false)
+        .pool_idle_timeout(Duration::from_secs(30))
+        .pool_max_idle_per_host(4)
+        .tcp_keepalive(Duration::from_secs(15));
+    assert_eq!(config.pool_idle_timeout, Some(Duration::from_secs(30)));
+    assert_eq!(config.pool_max_idle_per_host, Some(4));
+    assert_eq!(config.tcp_keepalive, Some(Duration::from_secs(15)));
+
+    let debug = format!("{config:?}");
+    assert!(debug.contains("pool_idle_timeout: Some(30s)"));
+
+    // The settings are passed through when building the client
+    let client = AniListClient::from_config(&config).unwrap();
+    assert!(!client.has_token());
+    assert_eq!(client.metrics(), MetricsSnapshot::default());
+}
+
+fn scripted_client(
+    retry: Option<RetryConfig>,
+    responses: impl IntoIterator<Item = TransportResponse>,
+) -> AniListClient {
+    let mut client = Arc::new(MockApi::scripted(responses)).client();
+    client.set_retry(retry);
+    client
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_counters_track_successes_rate_limits_and_retries() {
+    let retry = RetryConfig::builder()
+        .max_retries(3)
+        .base_delay(Duration::from_millis(100))
+        .build();
+    let client = scripted_client(
+        Some(retry),
+        [
+            too_many_requests(),
+            too_many_requests(),
+            TransportResponse::new(200, MEDIA),
+            TransportResponse::new(200, EMPTY_PAGE),
+            not_found(),
+        ],
+    );
+
+    client.anime().get_by_id(1).await.unwrap();
+    client.anime().get_popular(1, 5).await.unwrap();
+    assert!(client.anime().get_by_id(2).await.is_err());
+
+    // The counters are shared with the clone handed out by each accessor
+    let metrics = client.clone().metrics();
+    assert_eq!(metrics.requests, 5);
+    assert_eq!(metrics.responses, 5);
+    assert_eq!(metrics.rate_limited, 2);
+    assert_eq!(metrics.retries, 2);
+    assert_eq!(metrics.http_errors, 3);
+    assert_eq!(metrics.errors_total(), 3);
+    assert_eq!(metrics.errors.get("RateLimitSimple"), Some(&2));
+    assert_eq!(metrics.errors.get("NotFound"), Some(&1));
+    assert_eq!(metrics.avg_latency(), metrics.total_latency / 5);
+
+    assert_eq!(
+        metrics.operations.get("Media"),
+        Some(&OperationMetrics {
+            requests: 4,
+            errors: 3,
+            rate_limited: 2,
+            retries: 2,
+        })
+    );
+    assert_eq!(
+        metrics.operations.get("Page.media"),
+        Some(&OperationMetrics {
+            requests: 1,
+            ..OperationMetrics::default()
+        })
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_errors_are_counted_without_retries() {
+    let client = scripted_client(None, [too_many_requests()]);
+
+    assert!(client.anime().get_by_id(1).await.is_err());
+
+    let metrics = client.metrics();
+    assert_eq!(metrics.requests, 1);
+    assert_eq!(metrics.rate_limited, 1);
+    assert_eq!(metrics.retries, 0);
+    assert_eq!(metrics.errors.get("RateLimitSimple"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_named_operations_are_counted_by_name() {
+    let client = scripted_client(None, [TransportResponse::new(200, MEDIA)]);
+
+    client
+        .execute("# lookup\nquery CowboyBebop { Media(id: 1) { id } }", ())
+        .await
+        .unwrap();
+
+    let metrics = client.metrics();
+    assert_eq!(metrics.operations.len(), 1);
+    assert_eq!(metrics.operations["CowboyBebop"].requests, 1);
+    assert_eq!(metrics.avg_latency(), metrics.total_latency);
+}
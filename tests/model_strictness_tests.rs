@@ -0,0 +1,1070 @@
+//! Checks that the models' required fields hold for every query: each query
+//! must select them, and each captured fixture answering it must fill them.
+//! The replay tests in `fixture_tests.rs` decode the same fixtures end to end.
+
+use anilist_sdk::models::{Activity, ActivityReply, Anime, Manga, Review, Thread, ThreadComment};
+use anilist_sdk::queries;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Fields selected at one level of a GraphQL document, keyed by response name.
+#[derive(Debug, Default)]
+struct Selection {
+    fields: BTreeMap<String, Selection>,
+}
+
+impl Selection {
+    fn merge(&mut self, other: Selection) {
+        for (name, child) in other.fields {
+            self.fields.entry(name).or_default().merge(child);
+        }
+    }
+
+    fn at(&self, path: &[&str]) -> &Selection {
+        path.iter().fold(self, |node, field| {
+            node.fields
+                .get(*field)
+                .unwrap_or_else(|| panic!("`{field}` is not selected"))
+        })
+    }
+}
+
+fn tokenize(document: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = document.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                }
+                tokens.push("\"\"".to_string());
+            }
+            '.' => {
+                chars.next();
+                chars.next();
+                tokens.push("...".to_string());
+            }
+            c if c.is_whitespace() || c == ',' => {}
+            c if c.is_alphanumeric() || c == '_' || c == '$' || c == '-' => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' {
+                        word.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(word);
+            }
+            c => tokens.push(c.to_string()),
+        }
+    }
+    tokens
+}
+
+fn skip_parens(tokens: &[String], pos: &mut usize) {
+    let mut depth = 0;
+    loop {
+        match tokens[*pos].as_str() {
+            "(" => depth += 1,
+            ")" => {
+                depth -= 1;
+                if depth == 0 {
+                    *pos += 1;
+                    return;
+                }
+            }
+            _ => {}
+        }
+        *pos += 1;
+    }
+}
+
+fn parse_selection(tokens: &[String], pos: &mut usize) -> Selection {
+    assert_eq!(tokens[*pos], "{");
+    *pos += 1;
+    let mut selection = Selection::default();
+    while tokens[*pos] != "}" {
+        if tokens[*pos] == "..." {
+            // Inline fragments contribute their fields to the enclosing level.
+            *pos += 1;
+            if tokens[*pos] == "on" {
+                *pos += 2;
+            }
+            let fragment = parse_selection(tokens, pos);
+            selection.merge(fragment);
+            continue;
+        }
+        let name = tokens[*pos].clone();
+        *pos += 1;
+        if tokens[*pos] == ":" {
+            *pos += 2;
+        }
+        if tokens[*pos] == "(" {
+            skip_parens(tokens, pos);
+        }
+        let child = if tokens[*pos] == "{" {
+            parse_selection(tokens, pos)
+        } else {
+            Selection::default()
+        };
+        selection.fields.entry(name).or_default().merge(child);
+    }
+    *pos += 1;
+    selection
+}
+
+fn parse_document(document: &str) -> Selection {
+    let tokens = tokenize(document);
+    let mut pos = tokens
+        .iter()
+        .position(|t| t == "(" || t == "{")
+        .expect("document has no selection set");
+    if tokens[pos] == "(" {
+        skip_parens(&tokens, &mut pos);
+    }
+    parse_selection(&tokens, &mut pos)
+}
+
+const ANIME: &[&str] = &["id", "title"];
+const MANGA: &[&str] = &["id", "title"];
+const ACTIVITY: &[&str] = &["id", "replyCount", "likeCount", "createdAt"];
+const TEXT_ACTIVITY: &[&str] = &[
+    "id",
+    "userId",
+    "text",
+    "replyCount",
+    "likeCount",
+    "siteUrl",
+    "createdAt",
+];
+const LIST_ACTIVITY: &[&str] = &[
+    "id",
+    "userId",
+    "type",
+    "status",
+    "replyCount",
+    "likeCount",
+    "siteUrl",
+    "createdAt",
+];
+const ACTIVITY_REPLY: &[&str] = &["id", "text", "likeCount", "createdAt"];
+const AIRING_SCHEDULE: &[&str] = &["id", "airingAt", "timeUntilAiring", "episode", "mediaId"];
+const THREAD: &[&str] = &[
+    "id",
+    "title",
+    "userId",
+    "likeCount",
+    "createdAt",
+    "updatedAt",
+];
+const THREAD_COMMENT: &[&str] = &[
+    "id",
+    "userId",
+    "threadId",
+    "comment",
+    "likeCount",
+    "createdAt",
+    "updatedAt",
+];
+const CHARACTER: &[&str] = &["id", "name", "image", "siteUrl", "favourites"];
+const STAFF: &[&str] = &["id", "name", "image", "siteUrl"];
+const REVIEW: &[&str] = &[
+    "id",
+    "userId",
+    "mediaId",
+    "body",
+    "rating",
+    "ratingAmount",
+    "siteUrl",
+    "createdAt",
+    "updatedAt",
+];
+const RECOMMENDATION: &[&str] = &["id", "rating"];
+const MEDIA_LIST: &[&str] = &[
+    "id",
+    "userId",
+    "mediaId",
+    "status",
+    "score",
+    "progress",
+    "repeat",
+    "priority",
+    "private",
+    "hiddenFromStatusLists",
+    "startedAt",
+    "completedAt",
+];
+const NOTIFICATION: &[&str] = &["id", "userId", "type", "createdAt"];
+const STUDIO: &[&str] = &["id", "name", "isAnimationStudio"];
+const USER: &[&str] = &["id", "name"];
+const SCORE_DISTRIBUTION: &[&str] = &["score", "amount"];
+const STATUS_DISTRIBUTION: &[&str] = &["status", "amount"];
+const ID_ONLY: &[&str] = &["id"];
+
+type StrictnessCase = (
+    &'static str,
+    &'static str,
+    &'static [&'static str],
+    &'static [&'static str],
+    &'static [&'static str],
+);
+
+/// Every query whose response is deserialized into a model, the path to the
+/// model inside `data`, the fields that model refuses to go without, and the
+/// captured fixtures answering that query.
+const CASES: &[StrictnessCase] = &[
+    (
+        "activity::GET_RECENT_ACTIVITIES",
+        queries::activity::GET_RECENT_ACTIVITIES,
+        &["Page", "activities"],
+        ACTIVITY,
+        &[
+            "activity_get_recent_activities",
+            "activity_get_recent_activities_filtered",
+        ],
+    ),
+    (
+        "activity::GET_FOLLOWING_ACTIVITIES",
+        queries::activity::GET_FOLLOWING_ACTIVITIES,
+        &["Page", "activities"],
+        ACTIVITY,
+        &[
+            "activity_get_following_activities",
+            "activity_get_following_activities_filtered",
+        ],
+    ),
+    (
+        "activity::GET_USER_ACTIVITIES",
+        queries::activity::GET_USER_ACTIVITIES,
+        &["Page", "activities"],
+        ACTIVITY,
+        &[
+            "activity_get_user_activities",
+            "activity_get_user_activities_filtered",
+        ],
+    ),
+    (
+        "activity::GET_TEXT_ACTIVITIES",
+        queries::activity::GET_TEXT_ACTIVITIES,
+        &["Page", "activities"],
+        TEXT_ACTIVITY,
+        &["activity_get_text_activities"],
+    ),
+    (
+        "activity::GET_SUBSCRIBED_ACTIVITIES",
+        queries::activity::GET_SUBSCRIBED_ACTIVITIES,
+        &["Page", "activities"],
+        ACTIVITY,
+        &["activity_get_subscribed_activities"],
+    ),
+    (
+        "activity::TOGGLE_ACTIVITY_SUBSCRIPTION",
+        queries::activity::TOGGLE_ACTIVITY_SUBSCRIPTION,
+        &["ToggleActivitySubscription"],
+        ACTIVITY,
+        &["activity_toggle_activity_subscription"],
+    ),
+    (
+        "activity::GET_LATEST_LIST_ACTIVITY",
+        queries::activity::GET_LATEST_LIST_ACTIVITY,
+        &["Page", "activities"],
+        LIST_ACTIVITY,
+        &["activity_get_my_latest_list_activity"],
+    ),
+    (
+        "activity::TOGGLE_ACTIVITY_PIN",
+        queries::activity::TOGGLE_ACTIVITY_PIN,
+        &["ToggleActivityPin"],
+        ACTIVITY,
+        &["activity_toggle_activity_pin"],
+    ),
+    (
+        "activity::GET_ACTIVITY_BY_ID",
+        queries::activity::GET_ACTIVITY_BY_ID,
+        &["Activity"],
+        ACTIVITY,
+        &["activity_get_activity_by_id"],
+    ),
+    (
+        "activity::GET_ACTIVITY_DETAIL",
+        queries::activity::GET_ACTIVITY_DETAIL,
+        &["Activity"],
+        ACTIVITY,
+        &["activity_get_activity_detail"],
+    ),
+    (
+        "activity::GET_ACTIVITY_REPLIES",
+        queries::activity::GET_ACTIVITY_REPLIES,
+        &["Page", "activityReplies"],
+        ACTIVITY_REPLY,
+        &["activity_get_activity_replies"],
+    ),
+    (
+        "activity::CREATE_TEXT_ACTIVITY",
+        queries::activity::CREATE_TEXT_ACTIVITY,
+        &["SaveTextActivity"],
+        TEXT_ACTIVITY,
+        &["activity_create_text_activity"],
+    ),
+    (
+        "activity::REPLY_TO_ACTIVITY",
+        queries::activity::REPLY_TO_ACTIVITY,
+        &["SaveActivityReply"],
+        ACTIVITY_REPLY,
+        &["activity_post_activity_reply"],
+    ),
+    (
+        "activity::TOGGLE_LIKE",
+        queries::activity::TOGGLE_LIKE,
+        &["ToggleLikeV2"],
+        ACTIVITY,
+        &["activity_toggle_activity_like"],
+    ),
+    (
+        "activity::TOGGLE_ACTIVITY_REPLY_LIKE",
+        queries::activity::TOGGLE_ACTIVITY_REPLY_LIKE,
+        &["ToggleLikeV2"],
+        ACTIVITY_REPLY,
+        &["activity_toggle_activity_reply_like"],
+    ),
+    (
+        "airing::GET_UPCOMING_EPISODES",
+        queries::airing::GET_UPCOMING_EPISODES,
+        &["Page", "airingSchedules"],
+        AIRING_SCHEDULE,
+        &["airing_get_upcoming_episodes"],
+    ),
+    (
+        "airing::GET_TODAY_EPISODES",
+        queries::airing::GET_TODAY_EPISODES,
+        &["Page", "airingSchedules"],
+        AIRING_SCHEDULE,
+        &["airing_get_today_episodes"],
+    ),
+    (
+        "airing::GET_RECENTLY_AIRED",
+        queries::airing::GET_RECENTLY_AIRED,
+        &["Page", "airingSchedules"],
+        AIRING_SCHEDULE,
+        &["airing_get_recently_aired"],
+    ),
+    (
+        "airing::GET_SCHEDULE_FOR_MEDIA",
+        queries::airing::GET_SCHEDULE_FOR_MEDIA,
+        &["Page", "airingSchedules"],
+        AIRING_SCHEDULE,
+        &["airing_get_schedule_for_media"],
+    ),
+    (
+        "airing::GET_SCHEDULE_BY_ID",
+        queries::airing::GET_SCHEDULE_BY_ID,
+        &["AiringSchedule"],
+        AIRING_SCHEDULE,
+        &["airing_get_schedule_by_id"],
+    ),
+    (
+        "airing::GET_EPISODES_IN_RANGE",
+        queries::airing::GET_EPISODES_IN_RANGE,
+        &["Page", "airingSchedules"],
+        AIRING_SCHEDULE,
+        &["airing_get_episodes_in_range"],
+    ),
+    (
+        "airing::GET_NEXT_EPISODE",
+        queries::airing::GET_NEXT_EPISODE,
+        &["Page", "airingSchedules"],
+        AIRING_SCHEDULE,
+        &["airing_get_next_episode"],
+    ),
+    (
+        "anime::GET_MEDIA_PAGE",
+        queries::anime::GET_MEDIA_PAGE,
+        &["Page", "media"],
+        ANIME,
+        &[
+            "anime_get_popular",
+            "anime_get_trending",
+            "anime_search",
+            "anime_search_advanced",
+            "anime_get_by_season",
+            "anime_get_season_chart",
+            "anime_get_top_rated",
+            "anime_get_by_genre",
+            "anime_get_by_tags",
+            "anime_get_airing",
+            "anime_get_premiering_within",
+            "anime_get_by_ids",
+        ],
+    ),
+    (
+        "anime::GET_BY_ID",
+        queries::anime::GET_BY_ID,
+        &["Media"],
+        ANIME,
+        &["anime_get_by_id", "anime_get_by_mal_id"],
+    ),
+    (
+        "anime::GET_CHARACTERS",
+        queries::anime::GET_CHARACTERS,
+        &["Media", "characters", "edges", "node"],
+        CHARACTER,
+        &["anime_get_characters"],
+    ),
+    (
+        "anime::GET_CHARACTERS",
+        queries::anime::GET_CHARACTERS,
+        &["Media", "characters", "edges", "voiceActors"],
+        STAFF,
+        &["anime_get_characters"],
+    ),
+    (
+        "anime::GET_STAFF",
+        queries::anime::GET_STAFF,
+        &["Media", "staff", "edges", "node"],
+        STAFF,
+        &["anime_get_staff"],
+    ),
+    (
+        "anime::GET_STREAMING_EPISODES",
+        queries::anime::GET_STREAMING_EPISODES,
+        &["Media"],
+        ID_ONLY,
+        &["anime_get_streaming_episodes"],
+    ),
+    (
+        "anime::GET_EXTERNAL_LINKS",
+        queries::anime::GET_EXTERNAL_LINKS,
+        &["Media", "externalLinks"],
+        ID_ONLY,
+        &["anime_get_streaming_links"],
+    ),
+    (
+        "anime::GET_STATS",
+        queries::anime::GET_STATS,
+        &["Media", "stats", "scoreDistribution"],
+        SCORE_DISTRIBUTION,
+        &["anime_get_stats", "manga_get_stats"],
+    ),
+    (
+        "anime::GET_STATS",
+        queries::anime::GET_STATS,
+        &["Media", "stats", "statusDistribution"],
+        STATUS_DISTRIBUTION,
+        &["anime_get_stats", "manga_get_stats"],
+    ),
+    (
+        "anime::GET_RELATIONS",
+        queries::anime::GET_RELATIONS,
+        &["Media", "relations", "edges", "node"],
+        ID_ONLY,
+        &["anime_get_relations"],
+    ),
+    (
+        "character::GET_POPULAR",
+        queries::character::GET_POPULAR,
+        &["Page", "characters"],
+        CHARACTER,
+        &["character_get_popular"],
+    ),
+    (
+        "character::GET_BY_ID",
+        queries::character::GET_BY_ID,
+        &["Character"],
+        CHARACTER,
+        &["character_get_by_id"],
+    ),
+    (
+        "character::SEARCH",
+        queries::character::SEARCH,
+        &["Page", "characters"],
+        CHARACTER,
+        &["character_search"],
+    ),
+    (
+        "character::GET_TODAY_BIRTHDAY",
+        queries::character::GET_TODAY_BIRTHDAY,
+        &["Page", "characters"],
+        CHARACTER,
+        &["character_get_today_birthday"],
+    ),
+    (
+        "character::GET_MOST_FAVORITED",
+        queries::character::GET_MOST_FAVORITED,
+        &["Page", "characters"],
+        CHARACTER,
+        &["character_get_most_favorited"],
+    ),
+    (
+        "forum::MODERATE_THREAD",
+        queries::forum::MODERATE_THREAD,
+        &["SaveThread"],
+        ID_ONLY,
+        &["forum_lock_thread", "forum_sticky_thread"],
+    ),
+    (
+        "forum::TOGGLE_THREAD_SUBSCRIPTION",
+        queries::forum::TOGGLE_THREAD_SUBSCRIPTION,
+        &["ToggleThreadSubscription"],
+        THREAD,
+        &["forum_toggle_thread_subscription"],
+    ),
+    (
+        "forum::GET_RECENT_THREADS",
+        queries::forum::GET_RECENT_THREADS,
+        &["Page", "threads"],
+        THREAD,
+        &[
+            "forum_get_recent_threads",
+            "forum_get_recent_threads_as_html",
+        ],
+    ),
+    (
+        "forum::GET_THREAD_BY_ID",
+        queries::forum::GET_THREAD_BY_ID,
+        &["Thread"],
+        THREAD,
+        &["forum_get_thread_by_id", "forum_get_thread_by_id_as_html"],
+    ),
+    (
+        "forum::GET_THREADS_BY_USER",
+        queries::forum::GET_THREADS_BY_USER,
+        &["Page", "threads"],
+        THREAD,
+        &[
+            "forum_get_my_threads",
+            "forum_get_my_commented_threads",
+            "forum_get_subscribed_threads",
+        ],
+    ),
+    (
+        "forum::SEARCH_THREADS",
+        queries::forum::SEARCH_THREADS,
+        &["Page", "threads"],
+        THREAD,
+        &["forum_search_threads"],
+    ),
+    (
+        "forum::GET_THREAD_COMMENTS",
+        queries::forum::GET_THREAD_COMMENTS,
+        &["Page", "threadComments"],
+        THREAD_COMMENT,
+        &["forum_get_thread_comments"],
+    ),
+    (
+        "forum::CREATE_THREAD",
+        queries::forum::CREATE_THREAD,
+        &["SaveThread"],
+        THREAD,
+        &["forum_create_thread"],
+    ),
+    (
+        "forum::COMMENT_ON_THREAD",
+        queries::forum::COMMENT_ON_THREAD,
+        &["SaveThreadComment"],
+        THREAD_COMMENT,
+        &["forum_post_comment"],
+    ),
+    (
+        "forum::TOGGLE_THREAD_LIKE",
+        queries::forum::TOGGLE_THREAD_LIKE,
+        &["ToggleLikeV2"],
+        THREAD,
+        &["forum_toggle_thread_like"],
+    ),
+    (
+        "forum::LIKE_THREAD_COMMENT",
+        queries::forum::LIKE_THREAD_COMMENT,
+        &["ToggleLikeV2"],
+        THREAD_COMMENT,
+        &["forum_toggle_comment_like"],
+    ),
+    (
+        "manga::GET_POPULAR",
+        queries::manga::GET_POPULAR,
+        &["Page", "media"],
+        MANGA,
+        &["manga_get_popular"],
+    ),
+    (
+        "manga::GET_TRENDING",
+        queries::manga::GET_TRENDING,
+        &["Page", "media"],
+        MANGA,
+        &["manga_get_trending"],
+    ),
+    (
+        "manga::GET_BY_ID",
+        queries::manga::GET_BY_ID,
+        &["Media"],
+        MANGA,
+        &["manga_get_by_id"],
+    ),
+    (
+        "manga::SEARCH",
+        queries::manga::SEARCH,
+        &["Page", "media"],
+        MANGA,
+        &["manga_search"],
+    ),
+    (
+        "manga::GET_TOP_RATED",
+        queries::manga::GET_TOP_RATED,
+        &["Page", "media"],
+        MANGA,
+        &["manga_get_top_rated"],
+    ),
+    (
+        "manga::GET_RELEASING",
+        queries::manga::GET_RELEASING,
+        &["Page", "media"],
+        MANGA,
+        &["manga_get_releasing"],
+    ),
+    (
+        "manga::GET_COMPLETED",
+        queries::manga::GET_COMPLETED,
+        &["Page", "media"],
+        MANGA,
+        &["manga_get_completed"],
+    ),
+    (
+        "manga::GET_ADAPTATION_AIRING",
+        queries::manga::GET_ADAPTATION_AIRING,
+        &["Media", "relations", "edges", "node"],
+        ID_ONLY,
+        &["manga_get_adaptation_airing"],
+    ),
+    (
+        "notification::GET_NOTIFICATIONS",
+        queries::notification::GET_NOTIFICATIONS,
+        &["Page", "notifications"],
+        NOTIFICATION,
+        &["notification_get_notifications"],
+    ),
+    (
+        "notification::GET_NOTIFICATIONS_BY_TYPE",
+        queries::notification::GET_NOTIFICATIONS_BY_TYPE,
+        &["Page", "notifications"],
+        NOTIFICATION,
+        &["notification_get_notifications_by_type"],
+    ),
+    (
+        "recommendation::GET_RECENT_RECOMMENDATIONS",
+        queries::recommendation::GET_RECENT_RECOMMENDATIONS,
+        &["Page", "recommendations"],
+        RECOMMENDATION,
+        &["recommendation_get_recent_recommendations"],
+    ),
+    (
+        "recommendation::GET_RECOMMENDATIONS_FOR_MEDIA",
+        queries::recommendation::GET_RECOMMENDATIONS_FOR_MEDIA,
+        &["Page", "recommendations"],
+        RECOMMENDATION,
+        &["recommendation_get_recommendations_for_media"],
+    ),
+    (
+        "recommendation::GET_TOP_RATED_RECOMMENDATIONS",
+        queries::recommendation::GET_TOP_RATED_RECOMMENDATIONS,
+        &["Page", "recommendations"],
+        RECOMMENDATION,
+        &["recommendation_get_top_rated_recommendations"],
+    ),
+    (
+        "recommendation::GET_RECOMMENDATION_BY_ID",
+        queries::recommendation::GET_RECOMMENDATION_BY_ID,
+        &["Recommendation"],
+        RECOMMENDATION,
+        &["recommendation_get_recommendation_by_id"],
+    ),
+    (
+        "recommendation::SAVE_RECOMMENDATION",
+        queries::recommendation::SAVE_RECOMMENDATION,
+        &["SaveRecommendation"],
+        RECOMMENDATION,
+        &[
+            "recommendation_save_recommendation",
+            "recommendation_recommend_or_update",
+        ],
+    ),
+    (
+        "recommendation::RATE_RECOMMENDATION",
+        queries::recommendation::RATE_RECOMMENDATION,
+        &["SaveRecommendation"],
+        RECOMMENDATION,
+        &["recommendation_rate_recommendation"],
+    ),
+    (
+        "recommendation::GET_RECOMMENDATION_FOR_PAIR",
+        queries::recommendation::GET_RECOMMENDATION_FOR_PAIR,
+        &["Page", "recommendations"],
+        RECOMMENDATION,
+        &["recommendation_get_my_recommendation"],
+    ),
+    (
+        "review::GET_RECENT_REVIEWS",
+        queries::review::GET_RECENT_REVIEWS,
+        &["Page", "reviews"],
+        REVIEW,
+        &["review_get_recent_reviews"],
+    ),
+    (
+        "review::GET_REVIEWS_FOR_MEDIA",
+        queries::review::GET_REVIEWS_FOR_MEDIA,
+        &["Page", "reviews"],
+        REVIEW,
+        &[
+            "review_get_reviews_for_media",
+            "review_get_reviews_for_media_with_viewer_status",
+        ],
+    ),
+    (
+        "review::GET_REVIEWS_BY_USER",
+        queries::review::GET_REVIEWS_BY_USER,
+        &["Page", "reviews"],
+        REVIEW,
+        &["review_get_reviews_by_user"],
+    ),
+    (
+        "review::GET_REVIEW_BY_ID",
+        queries::review::GET_REVIEW_BY_ID,
+        &["Review"],
+        REVIEW,
+        &["review_get_review_by_id"],
+    ),
+    (
+        "review::SAVE_REVIEW",
+        queries::review::SAVE_REVIEW,
+        &["SaveReview"],
+        REVIEW,
+        &["review_save_review"],
+    ),
+    (
+        "review::RATE_REVIEW",
+        queries::review::RATE_REVIEW,
+        &["RateReview"],
+        REVIEW,
+        &["review_rate_review"],
+    ),
+    (
+        "review::GET_TOP_RATED_REVIEWS",
+        queries::review::GET_TOP_RATED_REVIEWS,
+        &["Page", "reviews"],
+        REVIEW,
+        &["review_get_top_rated_reviews"],
+    ),
+    (
+        "staff::GET_POPULAR",
+        queries::staff::GET_POPULAR,
+        &["Page", "staff"],
+        STAFF,
+        &["staff_get_popular"],
+    ),
+    (
+        "staff::GET_BY_ID",
+        queries::staff::GET_BY_ID,
+        &["Staff"],
+        STAFF,
+        &["staff_get_by_id"],
+    ),
+    (
+        "staff::SEARCH",
+        queries::staff::SEARCH,
+        &["Page", "staff"],
+        STAFF,
+        &["staff_search"],
+    ),
+    (
+        "staff::GET_TODAY_BIRTHDAY",
+        queries::staff::GET_TODAY_BIRTHDAY,
+        &["Page", "staff"],
+        STAFF,
+        &["staff_get_today_birthday"],
+    ),
+    (
+        "staff::GET_MOST_FAVORITED",
+        queries::staff::GET_MOST_FAVORITED,
+        &["Page", "staff"],
+        STAFF,
+        &["staff_get_most_favorited"],
+    ),
+    (
+        "studio::GET_POPULAR",
+        queries::studio::GET_POPULAR,
+        &["Page", "studios"],
+        STUDIO,
+        &["studio_get_popular"],
+    ),
+    (
+        "studio::GET_BY_ID",
+        queries::studio::GET_BY_ID,
+        &["Studio"],
+        STUDIO,
+        &["studio_get_by_id"],
+    ),
+    (
+        "studio::SEARCH",
+        queries::studio::SEARCH,
+        &["Page", "studios"],
+        STUDIO,
+        &["studio_search"],
+    ),
+    (
+        "studio::GET_MOST_FAVORITED",
+        queries::studio::GET_MOST_FAVORITED,
+        &["Page", "studios"],
+        STUDIO,
+        &["studio_get_most_favorited"],
+    ),
+    (
+        "studio::TOGGLE_FAVORITE",
+        queries::studio::TOGGLE_FAVORITE,
+        &["ToggleFavourite", "studios", "nodes"],
+        STUDIO,
+        &["studio_toggle_favorite"],
+    ),
+    (
+        "user::GET_CURRENT_USER",
+        queries::user::GET_CURRENT_USER,
+        &["Viewer"],
+        USER,
+        &["user_get_current_user", "user_get_current_user_as_html"],
+    ),
+    (
+        "user::GET_CURRENT_USER_ANIME_LIST",
+        queries::user::GET_CURRENT_USER_ANIME_LIST,
+        &["MediaListCollection", "lists", "entries"],
+        MEDIA_LIST,
+        &[
+            "user_get_current_user_anime_list",
+            "user_get_list_collection",
+            "user_get_next_up",
+        ],
+    ),
+    (
+        "user::SAVE_LIST_ENTRY",
+        queries::user::SAVE_LIST_ENTRY,
+        &["SaveMediaListEntry"],
+        MEDIA_LIST,
+        &["user_save_list_entry"],
+    ),
+    (
+        "user::GET_LIST_ENTRIES_UPDATED",
+        queries::user::GET_LIST_ENTRIES_UPDATED,
+        &["Page", "mediaList"],
+        MEDIA_LIST,
+        &["user_get_list_entries_updated_since"],
+    ),
+    (
+        "user::GET_MEDIA_LIST_PAGE",
+        queries::user::GET_MEDIA_LIST_PAGE,
+        &["Page", "mediaList"],
+        MEDIA_LIST,
+        &["user_get_current_user_anime_list_all"],
+    ),
+    (
+        "user::GET_BY_ID",
+        queries::user::GET_BY_ID,
+        &["User"],
+        USER,
+        &["user_get_by_id", "user_get_by_id_as_html"],
+    ),
+    (
+        "user::GET_BY_NAME",
+        queries::user::GET_BY_NAME,
+        &["User"],
+        USER,
+        &["user_get_by_name", "user_get_by_name_as_html"],
+    ),
+    (
+        "user::SEARCH",
+        queries::user::SEARCH,
+        &["Page", "users"],
+        USER,
+        &["user_search"],
+    ),
+    (
+        "user::GET_MOST_ANIME_WATCHED",
+        queries::user::GET_MOST_ANIME_WATCHED,
+        &["Page", "users"],
+        USER,
+        &["user_get_most_anime_watched"],
+    ),
+    (
+        "user::GET_MOST_MANGA_READ",
+        queries::user::GET_MOST_MANGA_READ,
+        &["Page", "users"],
+        USER,
+        &["user_get_most_manga_read"],
+    ),
+    (
+        "user::TOGGLE_FOLLOW",
+        queries::user::TOGGLE_FOLLOW,
+        &["ToggleFollow"],
+        USER,
+        &["user_toggle_follow"],
+    ),
+];
+
+#[test]
+fn test_every_query_selects_the_fields_its_model_requires() {
+    let mut missing = Vec::new();
+    for (name, query, path, required, _) in CASES {
+        let document = parse_document(query);
+        let node = document.at(path);
+        for field in *required {
+            if !node.fields.contains_key(*field) {
+                missing.push(format!("{name} does not select `{field}`"));
+            }
+        }
+    }
+    assert!(missing.is_empty(), "{}", missing.join("\n"));
+}
+
+fn load_fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{name}.json"));
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("cannot read {}: {error}", path.display()));
+    serde_json::from_str(&text).unwrap_or_else(|error| panic!("{name}.json: {error}"))
+}
+
+/// Every object found by following `path` from `node`, stepping into lists.
+fn objects_at<'a>(node: &'a Value, path: &[&str]) -> Vec<&'a Value> {
+    path.iter().fold(vec![node], |nodes, field| {
+        nodes
+            .into_iter()
+            .filter_map(|node| node.get(*field))
+            .flat_map(|value| match value {
+                Value::Array(items) => items.iter().collect(),
+                Value::Null => Vec::new(),
+                other => vec![other],
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn test_every_fixture_fills_the_fields_its_model_requires() {
+    let mut missing = Vec::new();
+    for (name, _, path, required, fixtures) in CASES {
+        assert!(!fixtures.is_empty(), "{name} has no fixture");
+        let mut found = 0;
+        for fixture in *fixtures {
+            let body = load_fixture(fixture)["body"].take();
+            for object in objects_at(&body["data"], path) {
+                found += 1;
+                for field in *required {
+                    if object.get(*field).is_none_or(Value::is_null) {
+                        missing.push(format!("{fixture}.json: {name} has no `{field}`"));
+                    }
+                }
+            }
+        }
+        assert!(found > 0, "no fixture of {name} reaches {}", path.join("."));
+    }
+    assert!(missing.is_empty(), "{}", missing.join("\n"));
+}
+
+#[test]
+fn test_selection_parser_merges_inline_fragments() {
+    let document = parse_document(queries::activity::TOGGLE_LIKE);
+    let node = document.at(&["ToggleLikeV2"]);
+    assert!(node.fields.contains_key("user"));
+    assert!(node.fields.contains_key("messenger"));
+    assert!(node.at(&["user"]).fields.contains_key("name"));
+}
+
+#[test]
+fn test_anime_without_title_is_rejected() {
+    let fixture = json!({ "id": 1 });
+    assert!(serde_json::from_value::<Anime>(fixture).is_err());
+
+    let fixture = json!({ "id": 1, "title": { "romaji": "Shingeki no Kyojin" } });
+    let anime: Anime = serde_json::from_value(fixture).unwrap();
+    assert_eq!(anime.title.romaji.as_deref(), Some("Shingeki no Kyojin"));
+    assert!(anime.title.english.is_none());
+}
+
+#[test]
+fn test_manga_title_languages_stay_optional() {
+    let fixture = json!({ "id": 30013, "title": { "romaji": null, "english": null, "native": "ワンピース" } });
+    let manga: Manga = serde_json::from_value(fixture).unwrap();
+    assert!(manga.title.romaji.is_none());
+    assert_eq!(manga.title.native.as_deref(), Some("ワンピース"));
+}
+
+#[test]
+fn test_thread_with_deleted_author_deserializes() {
+    let fixture = json!({
+        "id": 1, "title": "Orphaned", "userId": 42, "likeCount": 0,
+        "createdAt": 1_700_000_000, "updatedAt": 1_700_000_000,
+        "user": null
+    });
+    let thread: Thread = serde_json::from_value(fixture).unwrap();
+    assert!(thread.user.is_none());
+}
+
+#[test]
+fn test_toggle_like_responses_deserialize() {
+    let thread = json!({
+        "id": 7, "title": "Weekly thread", "userId": 3, "likeCount": 12, "isLiked": true,
+        "siteUrl": "https://anilist.co/forum/thread/7",
+        "createdAt": 1_700_000_000, "updatedAt": 1_700_000_100
+    });
+    let thread: Thread = serde_json::from_value(thread).unwrap();
+    assert_eq!(thread.is_liked, Some(true));
+
+    let comment = json!({
+        "id": 9, "userId": 3, "threadId": 7, "comment": "Agreed", "isLiked": true,
+        "likeCount": 2, "createdAt": 1_700_000_000, "updatedAt": 1_700_000_000,
+        "user": { "id": 3, "name": "someone" }
+    });
+    let comment: ThreadComment = serde_json::from_value(comment).unwrap();
+    assert_eq!(comment.like_count, 2);
+
+    let activity = json!({
+        "id": 11, "isLiked": false, "replyCount": 0, "likeCount": 4,
+        "createdAt": 1_700_000_000, "user": { "id": 3, "name": "someone" }
+    });
+    let activity: Activity = serde_json::from_value(activity).unwrap();
+    assert_eq!(activity.like_count, 4);
+
+    let message = json!({
+        "id": 12, "isLiked": true, "replyCount": 1, "likeCount": 1,
+        "createdAt": 1_700_000_000, "messenger": { "id": 4, "name": "other" }
+    });
+    let message: Activity = serde_json::from_value(message).unwrap();
+    assert!(message.user.is_none());
+
+    let reply = json!({
+        "id": 13, "text": "nice", "likeCount": 1, "isLiked": true, "createdAt": 1_700_000_000
+    });
+    let reply: ActivityReply = serde_json::from_value(reply).unwrap();
+    assert_eq!(reply.text, "nice");
+}
+
+#[test]
+fn test_rate_review_response_deserializes() {
+    let fixture = json!({
+        "id": 5, "userId": 3, "mediaId": 16498, "body": "Long review body",
+        "rating": 10, "ratingAmount": 12, "userRating": "UP_VOTE",
+        "siteUrl": "https://anilist.co/review/5",
+        "createdAt": 1_700_000_000, "updatedAt": 1_700_000_000
+    });
+    let review: Review = serde_json::from_value(fixture).unwrap();
+    assert_eq!(review.rating_amount, 12);
+}
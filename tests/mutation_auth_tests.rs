@@ -0,0 +1,184 @@
+use anilist_sdk::client::{AniListClient, ClientConfig};
+use anilist_sdk::error::AniListError;
+use anilist_sdk::limits;
+use anilist_sdk::models::media_list::MediaListStatus;
+use anilist_sdk::models::user::{FavouriteItem, FavouriteKind, FavouriteSet};
+use std::sync::Arc;
+use test_utils::MockApi;
+
+mod test_utils;
+
+/// Asserts that a call on an anonymous client failed without sending anything.
+macro_rules! assert_rejected_locally {
+    ($client:expr, $call:expr) => {{
+        let result = $call.await;
+        assert!(
+            matches!(result, Err(AniListError::AuthenticationRequired)),
+            "{}: {:?}",
+            stringify!($call),
+            result
+        );
+        assert_eq!(
+            $client.metrics().requests,
+            0,
+            "{} made a request",
+            stringify!($call)
+        );
+    }};
+}
+
+fn anonymous() -> AniListClient {
+    AniListClient::from_config(&ClientConfig::default().use_env_token(false)).unwrap()
+}
+
+#[tokio::test]
+async fn test_activity_mutations_need_token() {
+    let client = anonymous();
+    let activity = client.activity();
+
+    assert_rejected_locally!(client, activity.create_text_activity("Hello!"));
+    assert_rejected_locally!(client, activity.post_activity_reply(1, "Nice"));
+    assert_rejected_locally!(client, activity.toggle_activity_like(1));
+    assert_rejected_locally!(client, activity.toggle_activity_reply_like(1));
+    assert_rejected_locally!(client, activity.toggle_activity_subscription(1, true));
+    assert_rejected_locally!(client, activity.delete_activity(1));
+}
+
+#[tokio::test]
+async fn test_forum_mutations_need_token() {
+    let client = anonymous();
+    let forum = client.forum();
+
+    assert_rejected_locally!(client, forum.create_thread("Title", "Body", None));
+    assert_rejected_locally!(client, forum.post_comment(1, "Agreed"));
+    assert_rejected_locally!(client, forum.toggle_thread_like(1));
+    assert_rejected_locally!(client, forum.toggle_comment_like(1));
+    assert_rejected_locally!(client, forum.toggle_thread_subscription(1, true));
+}
+
+#[tokio::test]
+async fn test_review_mutations_need_token() {
+    let client = anonymous();
+    let review = client.review();
+    let body = "A thoughtful paragraph. ".repeat(limits::REVIEW_BODY_MIN_CHARS / 20);
+
+    assert_rejected_locally!(
+        client,
+        review.save_review(21, &body, Some("A long, long journey"), Some(90), None)
+    );
+    assert_rejected_locally!(client, review.rate_review(1, "UP_VOTE"));
+    assert_rejected_locally!(client, review.delete_review(1));
+}
+
+#[tokio::test]
+async fn test_recommendation_mutations_need_token() {
+    let client = anonymous();
+    let recommendation = client.recommendation();
+
+    assert_rejected_locally!(client, recommendation.save_recommendation(21, 30, Some(1)));
+    assert_rejected_locally!(client, recommendation.rate_recommendation(1, 1));
+}
+
+#[tokio::test]
+async fn test_user_and_list_mutations_need_token() {
+    let client = anonymous();
+    let user = client.user();
+
+    assert_rejected_locally!(client, user.toggle_follow(1));
+    assert_rejected_locally!(client, user.toggle_favorite(Some(21), None));
+    assert_rejected_locally!(
+        client,
+        user.toggle_favourite_item(FavouriteItem {
+            kind: FavouriteKind::Character,
+            id: 40
+        })
+    );
+    assert_rejected_locally!(client, user.update_media_list_progress(1, 12));
+    assert_rejected_locally!(
+        client,
+        user.update_media_list_status(1, MediaListStatus::Completed, None)
+    );
+    assert_rejected_locally!(client, client.studio().toggle_favorite(1));
+}
+
+#[tokio::test]
+async fn test_notification_mutations_need_token() {
+    let client = anonymous();
+
+    assert_rejected_locally!(
+        client,
+        client.notification().mark_notifications_as_read(vec![1, 2])
+    );
+}
+
+#[tokio::test]
+async fn test_bypass_sends_anonymous_mutations() {
+    let client = AniListClient::from_config(
+        &ClientConfig::default()
+            .use_env_token(false)
+            .allow_unauthenticated_mutations(true),
+    )
+    .unwrap();
+    assert!(client.allows_unauthenticated_mutations());
+
+    // The request goes out; AniList (or the missing network) decides the outcome
+    let result = client.activity().toggle_activity_like(1).await;
+    assert!(result.is_err());
+    assert_eq!(client.metrics().requests, 1);
+}
+
+#[tokio::test]
+async fn test_queries_are_not_affected() {
+    let mut client = anonymous();
+    client.set_allow_unauthenticated_mutations(false);
+
+    // Reads go out as before, whatever their outcome
+    let _ = client.anime().get_by_id(1).await;
+    assert_eq!(client.metrics().requests, 1);
+}
+
+#[tokio::test]
+async fn test_viewer_queries_need_token() {
+    let transport = Arc::new(MockApi::ok(r#"{"data":{}}"#));
+    let mut client = anonymous();
+    client.set_transport(transport.clone());
+    let user = client.user();
+    let notification = client.notification();
+
+    assert_rejected_locally!(client, notification.get_notifications(1, 10));
+    assert_rejected_locally!(client, notification.get_unread_count());
+    assert_rejected_locally!(
+        client,
+        notification.get_notifications_by_type("AIRING", 1, 10)
+    );
+    assert_rejected_locally!(client, user.get_current_user());
+    assert_rejected_locally!(client, user.get_current_user_with_meta());
+    assert_rejected_locally!(client, user.get_current_user_as_html());
+    assert_rejected_locally!(client, user.get_current_user_anime_list(None));
+    assert_rejected_locally!(client, user.get_next_up());
+    assert_rejected_locally!(client, user.get_favourite_set());
+    assert_rejected_locally!(
+        client,
+        user.sync_favourites(&FavouriteSet::default(), Default::default())
+    );
+    assert_rejected_locally!(client, client.activity().get_following_activities(1, 10));
+    assert_rejected_locally!(client, client.activity().get_subscribed_activities(1, 10));
+    assert_rejected_locally!(client, client.activity().get_my_latest_list_activity(1));
+    assert_rejected_locally!(client, client.forum().get_my_threads(1, 10));
+    assert_rejected_locally!(client, client.forum().get_subscribed_threads(1, 10));
+    assert_rejected_locally!(client, client.recommendation().get_personalized(5));
+    assert_eq!(transport.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_viewer_dependent_fields_are_not_gated() {
+    let transport = Arc::new(MockApi::ok(r#"{"data":{}}"#));
+    let mut client = anonymous();
+    client.set_transport(transport.clone());
+
+    // `isFavourite` and friends are simply null without a token
+    let _ = client.anime().get_by_id(1).await;
+    let _ = client.user().get_by_id(1).await;
+    let _ = client.activity().get_user_activities(1, 1, 10).await;
+    assert_eq!(transport.calls(), 3);
+}
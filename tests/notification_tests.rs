@@ -1,5 +1,12 @@
 use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
 use std::env;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use test_utils::{MockApi, too_many_requests};
+use tokio::time::Instant;
 mod test_utils;
 
 #[tokio::test]
@@ -17,10 +24,7 @@ async fn test_get_notifications() {
 
     for notification in &notifications {
         assert!(notification.id > 0);
-        if let Some(created_at) = notification.created_at {
-            assert!(created_at > 0);
-        }
-        assert!(notification.notification_type.is_some());
+        assert!(notification.created_at > 0);
     }
 }
 
@@ -40,10 +44,7 @@ async fn test_get_notifications_by_type() {
 
     for notification in &notifications {
         assert!(notification.id > 0);
-        if let Some(_notification_type) = &notification.notification_type {
-            // We can't easily compare enum variants in string format, so just check it exists
-            assert!(notification.notification_type.is_some());
-        }
+        assert!(notification.created_at > 0);
     }
 }
 
@@ -92,3 +93,269 @@ async fn test_get_unread_count() {
     let count = result.expect("Failed to get unread count");
     assert!(count >= 0);
 }
+
+const MARKED: &str = r#"{"data":{"SaveNotificationSettings":{"id":1}}}"#;
+
+/// The notification IDs of each mark-as-read mutation sent to `transport`.
+fn chunks_sent(transport: &MockApi) -> Vec<Vec<i32>> {
+    transport
+        .variables()
+        .iter()
+        .map(|variables| serde_json::from_value(variables["notificationIds"].clone()).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_mark_read_chunks_large_input() {
+    let transport = Arc::new(MockApi::ok(MARKED));
+    let client = transport.attach(AniListClient::builder().token("token"));
+    let ids: Vec<i32> = (1..=120).collect();
+
+    let report = client
+        .notification()
+        .mark_notifications_as_read_chunked(&ids)
+        .await;
+
+    let sizes: Vec<usize> = chunks_sent(&transport).iter().map(Vec::len).collect();
+    assert_eq!(sizes, [50, 50, 20]);
+    assert_eq!(report.mutations, 3);
+    assert_eq!(report.marked, ids);
+    assert!(report.is_complete());
+}
+
+#[tokio::test]
+async fn test_mark_read_empty_input_sends_nothing() {
+    let transport = Arc::new(MockApi::ok(MARKED));
+    let client = transport.client();
+
+    // No token is needed, since nothing is sent
+    let report = client
+        .notification()
+        .mark_notifications_as_read_chunked(&[])
+        .await;
+    assert_eq!(report.mutations, 0);
+    assert_eq!(
+        client
+            .notification()
+            .mark_notifications_as_read(Vec::new())
+            .await
+            .ok(),
+        Some(true)
+    );
+    assert_eq!(transport.calls(), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_mark_read_retries_retryable_failures_per_chunk() {
+    // The first chunk succeeds, the second hits a rate limit and a timeout
+    // before succeeding, the third is rejected outright and is not retried
+    let transport = Arc::new(MockApi::try_from_fn({
+        let calls = AtomicUsize::new(0);
+        move |request| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            match (
+                request.body["variables"]["notificationIds"][0].as_i64(),
+                call,
+            ) {
+                (Some(51), 1) => Ok(too_many_requests()),
+                (Some(51), 2) => Err(AniListError::Timeout {
+                    elapsed: Duration::from_secs(30),
+                }),
+                (Some(101), _) => Ok(TransportResponse::new(
+                    400,
+                    r#"{"errors":[{"message":"invalid notification","status":400}]}"#,
+                )),
+                _ => Ok(TransportResponse::new(200, MARKED)),
+            }
+        }
+    }));
+    let client = transport.attach(AniListClient::builder().token("token"));
+    let ids: Vec<i32> = (1..=120).collect();
+    let started = Instant::now();
+
+    let report = client
+        .notification()
+        .mark_notifications_as_read_chunked(&ids)
+        .await;
+
+    assert_eq!(transport.calls(), 5);
+    assert_eq!(report.mutations, 5);
+    let firsts: Vec<i32> = chunks_sent(&transport)
+        .iter()
+        .map(|chunk| chunk[0])
+        .collect();
+    assert_eq!(firsts, [1, 51, 51, 51, 101]);
+    assert_eq!(report.marked, (1..=100).collect::<Vec<_>>());
+    assert_eq!(report.failed_ids(), (101..=120).collect::<Vec<_>>());
+    assert!(matches!(
+        report.failed[0].1,
+        AniListError::BadRequest { .. }
+    ));
+    // The rate limit waits its full window, the timeout backs off
+    assert!(started.elapsed() >= Duration::from_secs(60));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_mark_read_gives_up_after_max_retries() {
+    let transport = Arc::new(MockApi::respond(TransportResponse::new(503, "unavailable")));
+    let client = transport.attach(AniListClient::builder().token("token"));
+
+    let report = client
+        .notification()
+        .mark_notifications_as_read_chunked(&[1, 2, 3])
+        .await;
+
+    assert_eq!(transport.calls(), 3);
+    assert_eq!(report.mutations, 3);
+    assert!(report.marked.is_empty());
+    assert_eq!(report.failed_ids(), vec![1, 2, 3]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_mark_read_does_not_retry_client_errors() {
+    let transport = Arc::new(MockApi::respond(TransportResponse::new(418, "teapot")));
+    let client = transport.attach(AniListClient::builder().token("token"));
+
+    let report = client
+        .notification()
+        .mark_notifications_as_read_chunked(&[1, 2, 3])
+        .await;
+
+    assert_eq!(transport.calls(), 1);
+    assert!(matches!(
+        report.failed[0].1,
+        AniListError::ServerError { status: 418, .. }
+    ));
+}
+
+#[test]
+fn test_notification_target_for_each_type() {
+    use anilist_sdk::models::{MediaType, Notification, NotificationTarget};
+    use serde_json::json;
+
+    let user = json!({ "id": 7, "name": "Josh", "avatar": null });
+    let thread = json!({ "id": 40 });
+    let media = json!({ "id": 101, "type": "MANGA", "title": { "userPreferred": "Berserk" } });
+    let fixtures = [
+        (
+            json!({ "type": "AIRING", "animeId": 16498, "episode": 3 }),
+            NotificationTarget::Media {
+                id: 16498,
+                media_type: Some(MediaType::Anime),
+            },
+        ),
+        (
+            json!({ "type": "FOLLOWING", "user": user }),
+            NotificationTarget::User(7),
+        ),
+        (
+            json!({ "type": "ACTIVITY_MESSAGE", "activityId": 11, "user": user }),
+            NotificationTarget::Activity(11),
+        ),
+        (
+            json!({ "type": "ACTIVITY_MENTION", "activityId": 12, "user": user }),
+            NotificationTarget::Activity(12),
+        ),
+        (
+            json!({ "type": "ACTIVITY_REPLY", "activityId": 13, "user": user }),
+            NotificationTarget::Activity(13),
+        ),
+        (
+            json!({ "type": "ACTIVITY_REPLY_SUBSCRIBED", "activityId": 14, "user": user }),
+            NotificationTarget::Activity(14),
+        ),
+        (
+            json!({ "type": "ACTIVITY_LIKE", "activityId": 15, "user": user }),
+            NotificationTarget::Activity(15),
+        ),
+        (
+            json!({ "type": "ACTIVITY_REPLY_LIKE", "activityId": 16, "user": user }),
+            NotificationTarget::Activity(16),
+        ),
+        (
+            json!({ "type": "THREAD_LIKE", "threadId": 40, "user": user }),
+            NotificationTarget::Thread(40),
+        ),
+        (
+            json!({ "type": "THREAD_COMMENT_MENTION", "commentId": 50, "thread": thread }),
+            NotificationTarget::ThreadComment {
+                thread_id: 40,
+                comment_id: 50,
+            },
+        ),
+        (
+            json!({ "type": "THREAD_COMMENT_REPLY", "commentId": 51, "thread": thread }),
+            NotificationTarget::ThreadComment {
+                thread_id: 40,
+                comment_id: 51,
+            },
+        ),
+        (
+            json!({ "type": "THREAD_SUBSCRIBED", "commentId": 52, "thread": thread }),
+            NotificationTarget::ThreadComment {
+                thread_id: 40,
+                comment_id: 52,
+            },
+        ),
+        (
+            json!({ "type": "THREAD_COMMENT_LIKE", "commentId": 53, "thread": thread }),
+            NotificationTarget::ThreadComment {
+                thread_id: 40,
+                comment_id: 53,
+            },
+        ),
+        (
+            json!({ "type": "RELATED_MEDIA_ADDITION", "mediaId": 101, "media": media }),
+            NotificationTarget::Media {
+                id: 101,
+                media_type: Some(MediaType::Manga),
+            },
+        ),
+        (
+            json!({ "type": "MEDIA_DATA_CHANGE", "mediaId": 101, "media": media }),
+            NotificationTarget::Media {
+                id: 101,
+                media_type: Some(MediaType::Manga),
+            },
+        ),
+        (
+            json!({ "type": "MEDIA_MERGE", "mediaId": 102 }),
+            NotificationTarget::Media {
+                id: 102,
+                media_type: None,
+            },
+        ),
+        (
+            json!({ "type": "MEDIA_DELETION", "contexts": ["was deleted"] }),
+            NotificationTarget::None,
+        ),
+    ];
+
+    for (mut fixture, expected) in fixtures {
+        fixture["id"] = json!(1);
+        fixture["userId"] = json!(2);
+        fixture["createdAt"] = json!(1_700_000_000);
+        let notification: Notification = serde_json::from_value(fixture.clone()).unwrap();
+        assert_eq!(notification.target(), expected, "fixture: {fixture}");
+    }
+}
+
+#[test]
+fn test_notification_target_without_linkage_is_none() {
+    use anilist_sdk::models::{Notification, NotificationTarget};
+    use serde_json::json;
+
+    let fixtures = [
+        json!({ "type": "ACTIVITY_REPLY" }),
+        json!({ "type": "THREAD_COMMENT_REPLY", "commentId": 50 }),
+        json!({ "type": "FOLLOWING" }),
+    ];
+
+    for mut fixture in fixtures {
+        fixture["id"] = json!(1);
+        fixture["userId"] = json!(2);
+        fixture["createdAt"] = json!(1_700_000_000);
+        let notification: Notification = serde_json::from_value(fixture).unwrap();
+        assert_eq!(notification.target(), NotificationTarget::None);
+    }
+}
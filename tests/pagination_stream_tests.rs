@@ -0,0 +1,174 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use futures_util::{Stream, StreamExt};
+use serde_json::{Value, json};
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::{MockApi, media_item, page_of};
+
+mod test_utils;
+
+/// An item every list field below can deserialize, numbered `id`.
+fn item(field: &str, id: usize) -> Value {
+    match field {
+        "activities" => json!({"id": id, "replyCount": 0, "likeCount": 0, "createdAt": 0}),
+        "airingSchedules" => json!({
+            "id": id, "airingAt": 0, "timeUntilAiring": 0, "episode": id, "mediaId": 1
+        }),
+        _ => media_item(id),
+    }
+}
+
+/// Serves `total` items at the requested page size, answering after 100ms,
+/// and answers `failing_page` with a 500.
+fn paged_api(total: usize, failing_page: Option<i64>) -> Arc<MockApi> {
+    let api = MockApi::from_fn(move |request| {
+        if request.variables()["page"].as_i64() == failing_page {
+            return TransportResponse::new(500, "{}");
+        }
+        let field = ["activities", "airingSchedules", "media"]
+            .into_iter()
+            .find(|field| request.query().contains(field))
+            .unwrap();
+        page_of(request, field, total, |id| item(field, id))
+    });
+    Arc::new(api.delay(Duration::from_millis(100)))
+}
+
+async fn collect_ids<T>(
+    stream: impl Stream<Item = Result<T, AniListError>>,
+    id: impl Fn(&T) -> i32,
+) -> Vec<i32> {
+    stream.map(|item| id(&item.unwrap())).collect().await
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_search_stream_walks_every_page_then_ends() {
+    let api = paged_api(5, None);
+    let client = api.client();
+
+    let ids = collect_ids(client.anime().search_stream("one piece", 2), |anime| {
+        anime.id
+    })
+    .await;
+
+    assert_eq!(ids, [1, 2, 3, 4, 5]);
+    assert_eq!(api.pages_requested(), [1, 2, 3]);
+    for variables in api.variables() {
+        assert_eq!(variables["search"], "one piece");
+        assert_eq!(variables["perPage"], 2);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_pages_are_fetched_as_the_stream_is_polled() {
+    let api = paged_api(6, None);
+    let client = api.client();
+    let mut stream = pin!(client.anime().search_stream("bebop", 3));
+
+    assert!(api.pages_requested().is_empty());
+    for id in 1..=3 {
+        assert_eq!(stream.next().await.unwrap().unwrap().id, id);
+    }
+    assert_eq!(api.pages_requested(), [1]);
+    assert_eq!(stream.next().await.unwrap().unwrap().id, 4);
+    assert_eq!(api.pages_requested(), [1, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_page_delay_is_waited_between_pages() {
+    let api = paged_api(6, None);
+    let mut client = api.client();
+    client.set_page_delay(Duration::from_secs(2));
+
+    let ids = collect_ids(client.manga().get_releasing_stream(2), |manga| manga.id).await;
+
+    assert_eq!(ids.len(), 6);
+    // Each gap is the delay plus the 100ms the previous response took
+    assert_eq!(api.gaps(), [Duration::from_millis(2100); 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_default_page_delay() {
+    let client = AniListClient::builder().build().unwrap();
+    assert_eq!(
+        client.page_delay(),
+        anilist_sdk::utils::pagination::DEFAULT_PAGE_DELAY
+    );
+
+    let client = AniListClient::builder()
+        .page_delay(Duration::ZERO)
+        .build()
+        .unwrap();
+    assert_eq!(client.page_delay(), Duration::ZERO);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_an_error_ends_the_stream() {
+    let api = paged_api(6, Some(2));
+    let client = api.client();
+    let mut stream = pin!(client.anime().search_stream("bebop", 2));
+
+    assert_eq!(stream.next().await.unwrap().unwrap().id, 1);
+    assert_eq!(stream.next().await.unwrap().unwrap().id, 2);
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(AniListError::ServerError { status: 500, .. }))
+    ));
+    assert!(stream.next().await.is_none());
+    assert_eq!(api.pages_requested(), [1, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_invalid_page_size_is_reported_once() {
+    let api = paged_api(6, None);
+    let client = api.client();
+    let mut stream = pin!(client.anime().search_stream("bebop", 0));
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(AniListError::InvalidInput { .. }))
+    ));
+    assert!(stream.next().await.is_none());
+    assert!(api.pages_requested().is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_dropping_mid_page_leaves_the_client_usable() {
+    let api = paged_api(4, None);
+    let client = api.client();
+
+    // The stream can move to another task; cancel it while page 2 is in flight
+    let stream = client.activity().get_user_activities_stream(7, 2);
+    let task = tokio::spawn(async move {
+        let mut stream = pin!(stream);
+        while stream.next().await.is_some() {}
+    });
+    tokio::time::sleep(Duration::from_millis(850)).await;
+    task.abort();
+    assert!(task.await.unwrap_err().is_cancelled());
+    assert_eq!(api.pages_requested(), [1, 2]);
+
+    let ids = collect_ids(client.activity().get_user_activities_stream(7, 2), |a| a.id).await;
+    assert_eq!(ids, [1, 2, 3, 4]);
+    assert!(api.variables().iter().all(|v| v["userId"] == 7));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_upcoming_episodes_stream_keeps_its_start_time() {
+    let api = paged_api(5, None);
+    let client = api.client();
+
+    let ids = collect_ids(client.airing().get_upcoming_episodes_stream(2), |s| s.id).await;
+
+    assert_eq!(ids, [1, 2, 3, 4, 5]);
+    let variables = api.variables();
+    assert_eq!(variables.len(), 3);
+    assert!(
+        variables
+            .iter()
+            .all(|v| v["airingAtGreater"] == variables[0]["airingAtGreater"])
+    );
+}
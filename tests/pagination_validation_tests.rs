@@ -0,0 +1,94 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::limits::MAX_PER_PAGE;
+use std::sync::Arc;
+use test_utils::{EMPTY_PAGE, MockApi};
+
+mod test_utils;
+
+/// A strict client, so oversized pages are rejected rather than split, whose
+/// queries are all answered with an empty page.
+fn client() -> (AniListClient, Arc<MockApi>) {
+    let api = Arc::new(MockApi::ok(
+        r#"{"data":{"Page":{"media":[],"characters":[],"staff":[],"studios":[]}}}"#,
+    ));
+    let client = api.attach(AniListClient::builder().strict_page_size(true));
+    (client, api)
+}
+
+fn assert_invalid(result: Result<impl Sized, AniListError>, expected_field: &str) {
+    match result {
+        Err(AniListError::InvalidInput { field, message }) => {
+            assert_eq!(field, expected_field, "{message}");
+        }
+        Err(error) => panic!("expected InvalidInput for {expected_field}, got {error:?}"),
+        Ok(_) => panic!("expected InvalidInput for {expected_field}, got a page"),
+    }
+}
+
+#[tokio::test]
+async fn test_per_page_boundaries() {
+    let (client, api) = client();
+    let anime = client.anime();
+
+    assert_invalid(anime.get_popular(1, 0).await, "per_page");
+    assert!(anime.get_popular(1, 1).await.is_ok());
+    assert!(anime.get_popular(1, MAX_PER_PAGE).await.is_ok());
+    assert_invalid(anime.get_popular(1, MAX_PER_PAGE + 1).await, "per_page");
+    assert_invalid(anime.get_popular(1, -5).await, "per_page");
+
+    // Only the two valid calls reached the API
+    assert_eq!(api.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_page_boundaries() {
+    let (client, api) = client();
+    let manga = client.manga();
+
+    assert_invalid(manga.get_popular(0, 10).await, "page");
+    assert_invalid(manga.get_popular(-1, 10).await, "page");
+    assert!(manga.get_popular(1, 10).await.is_ok());
+    assert!(manga.get_popular(50, 10).await.is_ok());
+    assert!(manga.get_popular(51, 10).await.is_ok());
+
+    assert_eq!(api.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_every_paginated_endpoint_checks_before_sending() {
+    let (client, api) = client();
+
+    assert_invalid(client.anime().search("bebop", 1, 51).await, "per_page");
+    assert_invalid(client.manga().search("berserk", 0, 10).await, "page");
+    assert_invalid(client.character().get_popular(1, 0).await, "per_page");
+    assert_invalid(client.staff().get_popular(0, 10).await, "page");
+    assert_invalid(client.studio().get_popular(1, 51).await, "per_page");
+    assert_invalid(client.airing().get_upcoming_episodes(0, 10).await, "page");
+    assert_invalid(client.forum().get_recent_threads(1, 100).await, "per_page");
+
+    assert_eq!(api.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_viewer_lookups_are_not_spent_on_invalid_pages() {
+    let api = Arc::new(MockApi::ok(EMPTY_PAGE));
+    let client = api.attach(AniListClient::builder().token("token"));
+
+    assert_invalid(client.forum().get_my_threads(1, 0).await, "per_page");
+
+    assert_eq!(api.calls(), 0);
+}
+
+#[test]
+fn test_invalid_input_message_names_the_argument() {
+    let error = AniListError::InvalidInput {
+        field: "per_page".to_string(),
+        message: "must be between 1 and 50 (MAX_PER_PAGE), got 51".to_string(),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Invalid per_page: must be between 1 and 50 (MAX_PER_PAGE), got 51"
+    );
+}
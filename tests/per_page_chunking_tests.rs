@@ -0,0 +1,122 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::{MockApi, media_item, page_of};
+
+mod test_utils;
+
+/// Serves `total` media at whatever page size is asked for.
+fn media_api(total: usize) -> Arc<MockApi> {
+    Arc::new(MockApi::from_fn(move |request| {
+        page_of(request, "media", total, media_item)
+    }))
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_fifty_items_take_one_request() {
+    let api = media_api(300);
+    let client = api.client();
+
+    let anime = client.anime().get_popular(1, 50).await.unwrap();
+
+    assert_eq!(anime.len(), 50);
+    assert_eq!(api.pages_requested(), [1]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_fifty_one_items_take_two_requests() {
+    let api = media_api(300);
+    let client = api.client();
+
+    let anime = client.anime().get_popular(1, 51).await.unwrap();
+
+    let ids: Vec<i32> = anime.iter().map(|anime| anime.id).collect();
+    assert_eq!(ids, (1..=51).collect::<Vec<_>>());
+    assert_eq!(api.pages_requested(), [1, 2]);
+    for variables in api.variables() {
+        assert_eq!(variables["perPage"], 50);
+        assert_eq!(variables["sort"], json!(["POPULARITY_DESC"]));
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_hundred_twenty_five_items_take_three_paced_requests() {
+    let api = media_api(300);
+    let client = api.client();
+
+    let page = client.anime().search_page("bebop", 1, 125).await.unwrap();
+
+    assert_eq!(page.items.len(), 125);
+    assert_eq!(page.items.last().unwrap().id, 125);
+    assert_eq!(page.page_info.per_page, Some(125));
+    assert_eq!(page.page_info.current_page, Some(1));
+    assert_eq!(page.page_info.last_page, Some(3));
+    assert!(page.has_next_page());
+    assert_eq!(api.pages_requested(), [1, 2, 3]);
+    assert_eq!(api.gaps(), [Duration::from_millis(700); 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_later_pages_start_part_way_into_an_api_page() {
+    let api = media_api(300);
+    let mut client = api.client();
+    client.set_page_delay(Duration::ZERO);
+
+    let page = client.anime().search_page("bebop", 2, 125).await.unwrap();
+
+    let ids: Vec<i32> = page.items.iter().map(|anime| anime.id).collect();
+    assert_eq!(ids, (126..=250).collect::<Vec<_>>());
+    assert_eq!(page.page_info.current_page, Some(2));
+    assert!(page.has_next_page());
+    // Items 126 to 250 lie on API pages 3 to 5
+    assert_eq!(api.pages_requested(), [3, 4, 5]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stops_once_the_results_run_out() {
+    let api = media_api(60);
+    let client = api.client();
+
+    let page = client.anime().search_page("bebop", 1, 125).await.unwrap();
+
+    assert_eq!(page.items.len(), 60);
+    assert!(!page.has_next_page());
+    assert_eq!(page.page_info.last_page, Some(1));
+    assert_eq!(api.pages_requested(), [1, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_strict_mode_rejects_oversized_pages() {
+    let api = media_api(300);
+    let client = api.attach(AniListClient::builder().strict_page_size(true));
+    assert!(client.is_strict_page_size());
+
+    match client.anime().get_popular(1, 51).await {
+        Err(AniListError::InvalidInput { field, message }) => {
+            assert_eq!(field, "per_page");
+            assert_eq!(message, "must be between 1 and 50 (MAX_PER_PAGE), got 51");
+        }
+        other => panic!("expected InvalidInput, got {other:?}"),
+    }
+    assert!(client.anime().get_popular(1, 50).await.is_ok());
+    assert_eq!(api.pages_requested(), [1]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_raw_queries_are_sent_as_written() {
+    let api = media_api(300);
+    let client = api.client();
+
+    client
+        .execute(
+            "query ($page: Int, $perPage: Int) { Page(page: $page, perPage: $perPage) { media { id } } }",
+            json!({"page": 1, "perPage": 100}),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(api.variables().len(), 1);
+    assert_eq!(api.variables()[0]["perPage"], 100);
+}
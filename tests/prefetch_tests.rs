@@ -0,0 +1,215 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use anilist_sdk::utils::prefetch::Prefetcher;
+use serde_json::json;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::{MockApi, media_item, not_found};
+
+mod test_utils;
+
+type Fetched = Pin<Box<dyn Future<Output = Result<i32, AniListError>> + Send>>;
+type Fetch = fn(AniListClient, i32) -> Fetched;
+
+/// Looks up an anime and keeps its ID.
+fn fetch(client: AniListClient, id: i32) -> Fetched {
+    Box::pin(async move { Ok(client.anime().get_by_id(id).await?.id) })
+}
+
+/// Answers `Media(id:)` after a delay that varies by ID, so later requests
+/// often finish first. ID 0 does not exist.
+fn api() -> Arc<MockApi> {
+    Arc::new(
+        MockApi::from_fn(|request| {
+            let id = request.variables()["id"].as_u64().unwrap() as usize;
+            if id == 0 {
+                return not_found();
+            }
+            let body = json!({"data": {"Media": media_item(id)}});
+            TransportResponse::new(200, body.to_string())
+        })
+        .delay_with(|request| {
+            let id = request.variables()["id"].as_u64().unwrap() as usize;
+            Duration::from_millis([30, 5, 20, 1, 12][id % 5])
+        }),
+    )
+}
+
+/// The IDs requested from `api`, in the order they were sent.
+fn started(api: &MockApi) -> Vec<i32> {
+    api.variables()
+        .iter()
+        .map(|variables| variables["id"].as_i64().unwrap() as i32)
+        .collect()
+}
+
+fn prefetcher(api: &Arc<MockApi>, ids: Vec<i32>, lookahead: usize) -> Prefetcher<i32, Fetch> {
+    Prefetcher::new(api.client(), ids, fetch as Fetch, lookahead)
+}
+
+async fn collect(prefetcher: &mut Prefetcher<i32, Fetch>) -> Vec<i32> {
+    let mut items = Vec::new();
+    while let Some(item) = prefetcher.next().await {
+        items.push(item.unwrap());
+    }
+    items
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_items_arrive_in_order() {
+    let api = api();
+    let ids: Vec<i32> = (1..=12).collect();
+    let mut prefetcher = prefetcher(&api, ids.clone(), 3);
+
+    assert_eq!(prefetcher.remaining(), 12);
+    assert_eq!(collect(&mut prefetcher).await, ids);
+    assert_eq!(started(&api), ids);
+    assert_eq!(prefetcher.remaining(), 0);
+    assert!(prefetcher.next().await.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_at_most_lookahead_requests_in_flight() {
+    for lookahead in [1, 2, 4] {
+        let api = api();
+        let mut prefetcher = prefetcher(&api, (1..=20).collect(), lookahead);
+
+        while let Some(item) = prefetcher.next().await {
+            item.unwrap();
+            assert!(prefetcher.in_flight() <= lookahead);
+            // The next items are being warmed while the caller is busy
+            tokio::time::sleep(Duration::from_millis(3)).await;
+        }
+        assert_eq!(api.max_in_flight(), lookahead, "lookahead {lookahead}");
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_zero_lookahead_still_fetches() {
+    let api = api();
+    let mut prefetcher = prefetcher(&api, vec![1, 2, 3], 0);
+
+    assert_eq!(collect(&mut prefetcher).await, vec![1, 2, 3]);
+    assert_eq!(api.max_in_flight(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_nothing_is_requested_before_next() {
+    let api = api();
+    let mut prefetcher = prefetcher(&api, vec![1, 2, 3], 3);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(api.calls(), 0);
+
+    prefetcher.next().await.unwrap().unwrap();
+    // The next items were requested as soon as the first was needed
+    assert_eq!(started(&api), vec![1, 2, 3]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_errors_are_returned_in_place() {
+    let api = api();
+    let mut prefetcher = prefetcher(&api, vec![1, 0, 2], 2);
+
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 1);
+    assert!(matches!(
+        prefetcher.next().await,
+        Some(Err(AniListError::NotFound))
+    ));
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_dropped_next_keeps_the_request() {
+    let api = api();
+    // ID 5 takes 30ms
+    let mut prefetcher = prefetcher(&api, vec![5, 6], 2);
+
+    let timed_out = tokio::time::timeout(Duration::from_millis(10), prefetcher.next()).await;
+    assert!(timed_out.is_err());
+    assert_eq!(prefetcher.in_flight(), 2);
+
+    // The same request completes; nothing was fetched twice or skipped
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 5);
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 6);
+    assert_eq!(started(&api), vec![5, 6]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reset_aborts_old_requests() {
+    let api = api();
+    // The first item is quick, the ones warmed behind it are slow
+    let mut prefetcher = prefetcher(&api, vec![1, 5, 10, 15], 3);
+
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 1);
+    let warming = prefetcher.in_flight();
+    assert!(warming > 0);
+
+    // The user jumps to another list
+    prefetcher.reset(vec![7, 8]);
+    assert_eq!(prefetcher.in_flight(), 0);
+    assert_eq!(collect(&mut prefetcher).await, vec![7, 8]);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    // Only the first item and the new list ran to completion
+    assert_eq!(api.answered(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_drop_aborts_in_flight_requests() {
+    let api = api();
+    let mut prefetcher = prefetcher(&api, vec![1, 5, 10, 15], 3);
+    prefetcher.next().await.unwrap().unwrap();
+    drop(prefetcher);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(api.answered(), 1);
+    assert_eq!(api.in_flight(), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_warming_uses_the_spare_budget() {
+    let api = api();
+    let client = api.client();
+    // Someone else holds all but four requests of the window's budget
+    let guard = client
+        .reserve_budget(client.available_budget() - 4)
+        .await
+        .unwrap();
+
+    let ids = vec![1, 2, 3, 4, 5];
+    let mut prefetcher = Prefetcher::new(client.clone(), ids.clone(), fetch as Fetch, 3);
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 1);
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    // The requests in flight were already counted against the budget, so
+    // the last spare one warms the fourth item
+    assert_eq!(started(&api), vec![1, 2, 3, 4]);
+    assert_eq!(client.available_budget(), 0);
+
+    // With no budget left the last item is only fetched when needed
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 2);
+    assert_eq!(prefetcher.next().await.unwrap().unwrap(), 3);
+    assert_eq!(started(&api), vec![1, 2, 3, 4]);
+    assert_eq!(collect(&mut prefetcher).await, vec![4, 5]);
+    assert_eq!(started(&api), ids);
+    drop(guard);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_no_warming_without_spare_budget() {
+    let api = api();
+    let client = api.client();
+    // Someone else holds all but one request of the window's budget
+    let guard = client
+        .reserve_budget(client.available_budget() - 1)
+        .await
+        .unwrap();
+
+    let mut prefetcher = Prefetcher::new(client.clone(), vec![1, 2, 3], fetch as Fetch, 3);
+    assert_eq!(collect(&mut prefetcher).await, vec![1, 2, 3]);
+    // Each item was still fetched when needed, but never ahead of time
+    assert_eq!(api.max_in_flight(), 1);
+    drop(guard);
+}
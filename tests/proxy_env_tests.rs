@@ -0,0 +1,66 @@
+//! Proxy environment variables are process-wide, so these checks live in their
+//! own test binary and run as a single test.
+
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use std::time::Duration;
+use test_utils::{MEDIA, serve_once};
+
+mod test_utils;
+
+fn set_env(name: &str, value: &str) {
+    // SAFETY: this binary runs a single test, so nothing reads the
+    // environment concurrently
+    unsafe { std::env::set_var(name, value) };
+}
+
+#[tokio::test]
+async fn test_proxy_environment_variables() {
+    for name in [
+        "HTTPS_PROXY",
+        "https_proxy",
+        "http_proxy",
+        "NO_PROXY",
+        "no_proxy",
+    ] {
+        // SAFETY: as in `set_env`
+        unsafe { std::env::remove_var(name) };
+    }
+
+    // A malformed variable fails the build instead of the first request
+    set_env("HTTP_PROXY", "::not a url::");
+    let error = match AniListClient::builder().proxy_from_env().build() {
+        Err(error) => error,
+        Ok(_) => panic!("expected a config error, got a client"),
+    };
+    match error {
+        AniListError::Config { message } => assert!(message.contains("HTTP_PROXY"), "{message}"),
+        other => panic!("expected a config error, got {other:?}"),
+    }
+
+    // A valid one routes requests through the proxy
+    let (proxy, server) = serve_once(Duration::ZERO, MEDIA).await;
+    set_env("HTTP_PROXY", &proxy);
+    let client = AniListClient::builder()
+        .base_url("http://anilist.invalid/graphql")
+        .proxy_from_env()
+        .build()
+        .unwrap();
+    client.anime().get_by_id(1).await.unwrap();
+    let request = server.await.unwrap();
+    assert!(
+        request.starts_with("POST http://anilist.invalid/graphql HTTP/1.1"),
+        "{request}"
+    );
+
+    // `no_proxy` ignores the variables and connects directly
+    let (direct, server) = serve_once(Duration::ZERO, MEDIA).await;
+    set_env("HTTP_PROXY", "http://127.0.0.1:9");
+    let client = AniListClient::builder()
+        .base_url(format!("{direct}/graphql"))
+        .no_proxy()
+        .build()
+        .unwrap();
+    client.anime().get_by_id(1).await.unwrap();
+    assert!(server.await.unwrap().starts_with("POST /graphql HTTP/1.1"));
+}
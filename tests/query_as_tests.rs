@@ -0,0 +1,84 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use std::sync::Arc;
+use test_utils::MockApi;
+
+mod test_utils;
+
+fn client(body: &str) -> AniListClient {
+    Arc::new(MockApi::ok(body)).client()
+}
+
+fn json_error<T: std::fmt::Debug>(result: Result<T, AniListError>) -> String {
+    match result {
+        Err(AniListError::Json(error)) => error.to_string(),
+        other => panic!("expected a JSON error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_missing_node_names_the_path() {
+    let message = json_error(client(r#"{"data":{}}"#).anime().get_by_id(1).await);
+    assert!(message.contains("missing `data.Media`"), "{message}");
+
+    let message = json_error(
+        client(r#"{"data":{"Page":{}}}"#)
+            .anime()
+            .get_popular(1, 5)
+            .await,
+    );
+    assert!(message.contains("missing `data.Page.media`"), "{message}");
+}
+
+#[tokio::test]
+async fn test_null_node_names_the_path() {
+    let message = json_error(
+        client(r#"{"data":{"Page":{"media":null}}}"#)
+            .anime()
+            .get_popular(1, 5)
+            .await,
+    );
+    assert!(message.contains("`data.Page.media` is null"), "{message}");
+
+    // Nulls above the node are reported the same way
+    let message = json_error(
+        client(r#"{"data":{"Page":null}}"#)
+            .manga()
+            .get_popular(1, 5)
+            .await,
+    );
+    assert!(message.contains("`data.Page.media` is null"), "{message}");
+}
+
+#[tokio::test]
+async fn test_type_mismatch_names_the_path() {
+    let message = json_error(
+        client(r#"{"data":{"Media":{"id":"one","title":{}}}}"#)
+            .anime()
+            .get_by_id(1)
+            .await,
+    );
+    assert!(message.contains("`data.Media`"), "{message}");
+    assert!(message.contains("invalid type"), "{message}");
+
+    let message = json_error(
+        client(r#"{"data":{"Page":[]}}"#)
+            .staff()
+            .get_popular(1, 5)
+            .await,
+    );
+    assert!(
+        message.contains("expected an object at `data.Page`"),
+        "{message}"
+    );
+}
+
+#[tokio::test]
+async fn test_null_node_is_none_for_optional_results() {
+    let adaptation = client(r#"{"data":{"Media":null}}"#)
+        .manga()
+        .get_adaptation_airing(1)
+        .await
+        .unwrap();
+    assert!(adaptation.is_none());
+}
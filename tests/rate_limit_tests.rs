@@ -0,0 +1,319 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::rate_limit::{DEFAULT_BURST, DEFAULT_REQUESTS_PER_MINUTE};
+use anilist_sdk::transport::TransportResponse;
+use anilist_sdk::utils::calculate_delay;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use test_utils::{MEDIA, MockApi};
+use tokio::time::{Duration, Instant};
+
+mod test_utils;
+
+/// A client built by `builder` whose requests are answered with [`MEDIA`].
+fn timed_client(builder: anilist_sdk::AniListClientBuilder) -> (AniListClient, Arc<MockApi>) {
+    let transport = Arc::new(MockApi::new());
+    (transport.attach(builder), transport)
+}
+
+/// Offsets of each request `transport` received from `started`, in order.
+fn offsets(transport: &MockApi, started: Instant) -> Vec<Duration> {
+    let mut sent: Vec<Duration> = transport
+        .times()
+        .iter()
+        .map(|at| at.duration_since(started))
+        .collect();
+    sent.sort();
+    sent
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reserve_budget_reduces_available_budget() {
+    let client = AniListClient::new();
+    assert_eq!(client.available_budget(), DEFAULT_REQUESTS_PER_MINUTE);
+
+    let guard = client.reserve_budget(25).await.unwrap();
+    assert_eq!(guard.remaining(), 25);
+    assert_eq!(client.available_budget(), DEFAULT_REQUESTS_PER_MINUTE - 25);
+
+    // Clones share the same budget
+    assert_eq!(
+        client.clone().available_budget(),
+        DEFAULT_REQUESTS_PER_MINUTE - 25
+    );
+
+    drop(guard);
+    assert_eq!(client.available_budget(), DEFAULT_REQUESTS_PER_MINUTE);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reserve_budget_times_out() {
+    let client = AniListClient::new();
+    let _held = client.reserve_budget(80).await.unwrap();
+
+    let started = Instant::now();
+    let result = client
+        .reserve_budget_with_timeout(20, Duration::from_secs(5))
+        .await;
+
+    match result {
+        Err(AniListError::BudgetUnavailable {
+            requested,
+            available,
+        }) => {
+            assert_eq!(requested, 20);
+            assert_eq!(available, 10);
+        }
+        other => panic!("expected BudgetUnavailable, got {other:?}"),
+    }
+    assert_eq!(started.elapsed(), Duration::from_secs(5));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reserve_budget_waits_for_release() {
+    let client = AniListClient::new();
+    let held = client.reserve_budget(80).await.unwrap();
+
+    let waiter = {
+        let client = client.clone();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let guard = client
+                .reserve_budget_with_timeout(20, Duration::from_secs(30))
+                .await
+                .unwrap();
+            (started.elapsed(), guard.remaining())
+        })
+    };
+
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    drop(held);
+
+    let (waited, remaining) = waiter.await.unwrap();
+    assert_eq!(waited, Duration::from_secs(3));
+    assert_eq!(remaining, 20);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reserve_more_than_window_fails_immediately() {
+    let client = AniListClient::new();
+    let started = Instant::now();
+
+    let result = client.reserve_budget(DEFAULT_REQUESTS_PER_MINUTE + 1).await;
+
+    assert!(matches!(
+        result,
+        Err(AniListError::BudgetUnavailable { .. })
+    ));
+    assert_eq!(started.elapsed(), Duration::ZERO);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_paced_client_spreads_requests_over_the_minute() {
+    let (client, transport) = timed_client(AniListClient::builder().rate_limited(true));
+    assert_eq!(
+        client.requests_per_minute(),
+        Some(DEFAULT_REQUESTS_PER_MINUTE)
+    );
+    let started = Instant::now();
+
+    for _ in 0..100 {
+        client.anime().get_by_id(1).await.unwrap();
+    }
+
+    let sent = offsets(&transport, started);
+    assert_eq!(sent.len(), 100);
+    // Within the first window the first few go out at once and the rest one
+    // interval (2/3 s) apart
+    let burst = DEFAULT_BURST as usize;
+    let window = DEFAULT_REQUESTS_PER_MINUTE as usize;
+    assert!(sent[..burst].iter().all(|at| *at == Duration::ZERO));
+    for pair in sent[burst..window].windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!(
+            gap >= Duration::from_millis(660) && gap <= Duration::from_millis(670),
+            "{gap:?}"
+        );
+    }
+    assert!(started.elapsed() > Duration::from_secs(60));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_unpaced_client_sends_the_window_at_once() {
+    let (client, transport) = timed_client(AniListClient::builder());
+    assert_eq!(client.requests_per_minute(), None);
+    let started = Instant::now();
+
+    for _ in 0..DEFAULT_REQUESTS_PER_MINUTE {
+        client.anime().get_by_id(1).await.unwrap();
+    }
+
+    let sent = offsets(&transport, started);
+    assert!(sent.iter().all(|at| *at == Duration::ZERO));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_pace_is_shared_by_concurrent_clones() {
+    let (client, transport) = timed_client(AniListClient::builder().requests_per_minute(60));
+    let started = Instant::now();
+
+    let tasks: Vec<_> = (0..20)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.anime().get_by_id(1).await.map(|_| ()) })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    // Simultaneous tasks are queued one second apart after the burst rather
+    // than all waking together
+    let sent = offsets(&transport, started);
+    let burst = DEFAULT_BURST as usize;
+    assert!(sent[..burst].iter().all(|at| *at == Duration::ZERO));
+    for (index, at) in sent.iter().enumerate().skip(burst) {
+        let expected = Duration::from_secs((index + 1 - burst) as u64);
+        assert!(
+            *at >= expected && *at <= expected + Duration::from_millis(10),
+            "request {index} sent at {at:?}"
+        );
+    }
+}
+
+#[test]
+fn test_zero_requests_per_minute_is_rejected() {
+    let result = AniListClient::builder().requests_per_minute(0).build();
+    assert!(matches!(result, Err(AniListError::Config { .. })));
+}
+
+/// Reports `limit` in the rate limit headers, counting `remaining` down from
+/// it with every request. Once nothing remains, the response also carries
+/// `X-RateLimit-Reset`, `reset_in` seconds from now.
+fn reporting_api(limit: &Arc<AtomicU32>, reset_in: u64) -> Arc<MockApi> {
+    let limit = limit.clone();
+    let sent = AtomicU32::new(0);
+    Arc::new(MockApi::from_fn(move |_| {
+        let sent = sent.fetch_add(1, Ordering::SeqCst) + 1;
+        let limit = limit.load(Ordering::SeqCst);
+        let remaining = limit.saturating_sub(sent);
+        let mut response = TransportResponse::new(200, MEDIA)
+            .with_header("X-RateLimit-Limit", &limit.to_string())
+            .with_header("X-RateLimit-Remaining", &remaining.to_string());
+        if remaining == 0 {
+            let reset_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + reset_in;
+            response = response.with_header("X-RateLimit-Reset", &reset_at.to_string());
+        }
+        response
+    }))
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_pace_adapts_to_a_reported_limit_of_30() {
+    let limit = Arc::new(AtomicU32::new(1_000));
+    let transport = reporting_api(&limit, 60);
+    let client = transport.attach(AniListClient::builder().rate_limited(true));
+    limit.store(30, Ordering::SeqCst);
+    let started = Instant::now();
+
+    for _ in 0..15 {
+        client.anime().get_by_id(1).await.unwrap();
+    }
+
+    assert_eq!(client.requests_per_minute(), Some(30));
+    let state = client.rate_limit_state();
+    assert!(state.reported);
+    assert_eq!(state.limit, 30);
+    assert_eq!(state.remaining, 15);
+
+    // After the burst, requests go out two seconds apart rather than 2/3 s
+    let sent = offsets(&transport, started);
+    for pair in sent[DEFAULT_BURST as usize..].windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!(
+            gap >= Duration::from_millis(1990) && gap <= Duration::from_millis(2010),
+            "{gap:?}"
+        );
+    }
+
+    // The configured pace returns once the server reports it again
+    limit.store(1_000, Ordering::SeqCst);
+    client.anime().get_by_id(1).await.unwrap();
+    assert_eq!(
+        client.requests_per_minute(),
+        Some(DEFAULT_REQUESTS_PER_MINUTE)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_window_budget_follows_a_reported_limit_of_30() {
+    let limit = Arc::new(AtomicU32::new(30));
+    let transport = reporting_api(&limit, 60);
+    let client = transport.attach(AniListClient::builder());
+    let started = Instant::now();
+
+    for _ in 0..29 {
+        client.anime().get_by_id(1).await.unwrap();
+    }
+    assert_eq!(client.available_budget(), 1);
+    assert!(
+        offsets(&transport, started)
+            .iter()
+            .all(|at| *at == Duration::ZERO)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_exhausted_budget_waits_for_the_reported_reset() {
+    let limit = Arc::new(AtomicU32::new(1));
+    let transport = reporting_api(&limit, 20);
+    let client = transport.attach(AniListClient::builder());
+    let started = Instant::now();
+
+    client.anime().get_by_id(1).await.unwrap();
+    let state = client.rate_limit_state();
+    assert_eq!(state.remaining, 0);
+    let resets_in = state.resets_in.unwrap();
+    assert!(resets_in > Duration::from_secs(18) && resets_in <= Duration::from_secs(20));
+    assert_eq!(state.suggested_delay(), resets_in);
+
+    // The next request waits for the reset instead of the end of a full
+    // minute, and is not sent early to draw a 429
+    limit.store(30, Ordering::SeqCst);
+    client.anime().get_by_id(1).await.unwrap();
+    let sent = offsets(&transport, started);
+    assert_eq!(sent.len(), 2);
+    assert!(sent[1] > Duration::from_secs(18) && sent[1] <= Duration::from_secs(20));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reported_count_lowers_the_local_count() {
+    // Ten requests were counted locally, but the server reports having
+    // charged only one of them
+    let transport = Arc::new(MockApi::respond(
+        TransportResponse::new(200, MEDIA)
+            .with_header("X-RateLimit-Limit", "90")
+            .with_header("X-RateLimit-Remaining", "89"),
+    ));
+    let client = transport.attach(AniListClient::builder());
+
+    for _ in 0..10 {
+        client.anime().get_by_id(1).await.unwrap();
+    }
+
+    let state = client.rate_limit_state();
+    assert_eq!(state.remaining, 89);
+    assert_eq!(client.available_budget(), 89);
+}
+
+#[test]
+fn test_calculate_delay_spreads_the_remaining_budget() {
+    assert_eq!(calculate_delay(30, 60), Duration::from_secs(2));
+    assert_eq!(calculate_delay(120, 60), Duration::from_millis(500));
+    assert_eq!(calculate_delay(0, 45), Duration::from_secs(45));
+}
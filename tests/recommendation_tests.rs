@@ -1,4 +1,7 @@
 use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::models::social::{Recommendation, RecommendationRating, RecommendationUpsert};
+use serde_json::json;
 mod test_utils;
 
 #[tokio::test]
@@ -46,10 +49,8 @@ async fn test_get_top_rated_recommendations() {
     let mut prev_rating = i32::MAX;
     for recommendation in &recommendations {
         assert!(recommendation.id > 0);
-        if let Some(rating) = recommendation.rating {
-            assert!(rating <= prev_rating);
-            prev_rating = rating;
-        }
+        assert!(recommendation.rating <= prev_rating);
+        prev_rating = recommendation.rating;
     }
 }
 
@@ -71,3 +72,230 @@ async fn test_get_recommendation_by_id() {
         }
     }
 }
+
+/// Parse a scripted `Page.recommendations` response the way `get_my_recommendation` does.
+fn scripted_lookup(response: serde_json::Value) -> Option<Recommendation> {
+    let data = response["data"]["Page"]["recommendations"].clone();
+    let recommendations: Vec<Recommendation> = serde_json::from_value(data).unwrap();
+    recommendations.into_iter().next()
+}
+
+fn pair_response(user_rating: Option<&str>) -> serde_json::Value {
+    json!({
+        "data": {
+            "Page": {
+                "recommendations": [{
+                    "id": 42,
+                    "rating": 7,
+                    "userRating": user_rating,
+                    "media": { "id": 16498 },
+                    "mediaRecommendation": { "id": 20958 },
+                    "user": null
+                }]
+            }
+        }
+    })
+}
+
+#[test]
+fn test_recommend_or_update_creates_when_pair_missing() {
+    let empty = json!({ "data": { "Page": { "recommendations": [] } } });
+    let existing = scripted_lookup(empty);
+    assert!(existing.is_none());
+
+    let outcome = RecommendationUpsert::plan(existing.as_ref(), RecommendationRating::RateUp);
+    assert_eq!(outcome, RecommendationUpsert::Created);
+    assert!(outcome.needs_mutation());
+}
+
+#[test]
+fn test_recommend_or_update_updates_when_rating_differs() {
+    let existing = scripted_lookup(pair_response(Some("RATE_DOWN")));
+    assert_eq!(existing.as_ref().map(|r| r.id), Some(42));
+
+    let outcome = RecommendationUpsert::plan(existing.as_ref(), RecommendationRating::RateUp);
+    assert_eq!(outcome, RecommendationUpsert::Updated);
+    assert!(outcome.needs_mutation());
+}
+
+#[test]
+fn test_recommend_or_update_unchanged_when_rating_matches() {
+    let existing = scripted_lookup(pair_response(Some("RATE_UP")));
+
+    let outcome = RecommendationUpsert::plan(existing.as_ref(), RecommendationRating::RateUp);
+    assert_eq!(outcome, RecommendationUpsert::Unchanged);
+    assert!(!outcome.needs_mutation());
+}
+
+#[test]
+fn test_recommend_or_update_missing_user_rating_counts_as_no_rating() {
+    let existing = scripted_lookup(pair_response(None));
+
+    assert_eq!(
+        RecommendationUpsert::plan(existing.as_ref(), RecommendationRating::NoRating),
+        RecommendationUpsert::Unchanged
+    );
+    assert_eq!(
+        RecommendationUpsert::plan(existing.as_ref(), RecommendationRating::RateDown),
+        RecommendationUpsert::Updated
+    );
+}
+
+#[tokio::test]
+async fn test_get_my_recommendation_requires_auth() {
+    let client = AniListClient::new();
+    let result = client
+        .recommendation()
+        .get_my_recommendation(16498, 20958)
+        .await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+
+    let result = client
+        .recommendation()
+        .recommend_or_update((16498, 20958), RecommendationRating::RateUp)
+        .await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+}
+
+fn media_ref(id: i32) -> anilist_sdk::models::MediaRef {
+    serde_json::from_value(json!({
+        "id": id,
+        "type": "ANIME",
+        "title": { "userPreferred": format!("Title {id}") }
+    }))
+    .unwrap()
+}
+
+fn list_entry(media_id: i32, status: &str, score: f64) -> anilist_sdk::models::MediaList {
+    serde_json::from_value(json!({
+        "id": media_id * 10,
+        "userId": 1,
+        "mediaId": media_id,
+        "progress": 0,
+        "repeat": 0,
+        "priority": 0,
+        "private": false,
+        "hiddenFromStatusLists": false,
+        "startedAt": {},
+        "completedAt": {},
+        "status": status,
+        "score": score
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_pick_sources_prefers_high_scored_completed() {
+    use anilist_sdk::models::personalized::pick_sources;
+
+    let entries = vec![
+        list_entry(1, "COMPLETED", 7.0),
+        list_entry(2, "CURRENT", 10.0),
+        list_entry(3, "COMPLETED", 10.0),
+        list_entry(4, "COMPLETED", 5.0),
+        list_entry(5, "DROPPED", 9.0),
+    ];
+
+    let sources = pick_sources(&entries, 2);
+    assert_eq!(sources, vec![(3, 1.0), (1, 0.7)]);
+
+    // Unscored lists weigh every source equally
+    let unscored = vec![
+        list_entry(1, "COMPLETED", 0.0),
+        list_entry(2, "COMPLETED", 0.0),
+    ];
+    assert_eq!(pick_sources(&unscored, 5), vec![(1, 1.0), (2, 1.0)]);
+}
+
+#[test]
+fn test_rank_personalized_dedups_and_ranks() {
+    use anilist_sdk::models::RecommendationCandidate;
+    use anilist_sdk::models::personalized::rank_personalized;
+    use std::collections::HashSet;
+
+    let candidate =
+        |source: i32, weight: f64, suggestion: i32, rating: i32| RecommendationCandidate {
+            source: media_ref(source),
+            source_weight: weight,
+            suggestion: media_ref(suggestion),
+            rating,
+        };
+
+    let candidates = vec![
+        // 100 is suggested by both sources: 50 * 1.0 + 80 * 0.5 = 90
+        candidate(1, 1.0, 100, 50),
+        candidate(2, 0.5, 100, 80),
+        // 200 only by the top source: 60
+        candidate(1, 1.0, 200, 60),
+        // 300 is already on the viewer's list
+        candidate(1, 1.0, 300, 500),
+        // 400 has a negative community rating
+        candidate(2, 0.5, 400, -3),
+        // 500 ties with 600 on score; lower id first
+        candidate(2, 0.5, 600, 20),
+        candidate(2, 0.5, 500, 20),
+    ];
+    let on_list: HashSet<i32> = [1, 2, 300].into_iter().collect();
+
+    let ranked = rank_personalized(candidates, &on_list, 10);
+    let summary: Vec<(i32, i32, f64)> = ranked
+        .iter()
+        .map(|r| (r.suggestion.id, r.source.id, r.score))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (100, 1, 90.0),
+            (200, 1, 60.0),
+            (500, 2, 10.0),
+            (600, 2, 10.0)
+        ]
+    );
+
+    // Source of 100 is the one contributing most (50 vs 40)
+    assert_eq!(ranked[0].source.id, 1);
+
+    // Limit truncates after ranking
+    let top = rank_personalized(
+        vec![candidate(1, 1.0, 7, 1), candidate(1, 1.0, 8, 2)],
+        &on_list,
+        1,
+    );
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].suggestion.id, 8);
+}
+
+#[test]
+fn test_personalized_batched_response_fixture() {
+    use anilist_sdk::models::MediaRef;
+
+    // Shape of the single batched response for all sources
+    let response = json!({
+        "data": { "Page": { "media": [{
+            "id": 16498,
+            "type": "ANIME",
+            "title": { "userPreferred": "Shingeki no Kyojin" },
+            "format": "TV",
+            "status": "FINISHED",
+            "recommendations": { "nodes": [
+                { "rating": 120, "mediaRecommendation": { "id": 20958, "type": "ANIME", "title": { "userPreferred": "Shingeki no Kyojin 2" }, "format": "TV", "status": "FINISHED" } },
+                { "rating": 3, "mediaRecommendation": null }
+            ] }
+        }] } }
+    });
+
+    let media = &response["data"]["Page"]["media"][0];
+    let source: MediaRef = serde_json::from_value(media.clone()).unwrap();
+    assert_eq!(source.id, 16498);
+    let suggestion: MediaRef =
+        serde_json::from_value(media["recommendations"]["nodes"][0]["mediaRecommendation"].clone())
+            .unwrap();
+    assert_eq!(suggestion.id, 20958);
+}
+
+#[tokio::test]
+async fn test_get_personalized_requires_auth() {
+    let client = AniListClient::new();
+    let result = client.recommendation().get_personalized(10).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+}
@@ -0,0 +1,68 @@
+use anilist_sdk::auth::SecretToken;
+use anilist_sdk::client::{AniListClient, ClientConfig};
+use reqwest::header::AUTHORIZATION;
+use std::sync::Arc;
+use test_utils::MockApi;
+
+mod test_utils;
+
+const TOKEN: &str = "eyJ0eXAiOiJKV1QiLCJhbGciOiJSUzI1NiJ9.secret-bytes";
+
+#[test]
+fn test_client_debug_redacts_the_token() {
+    let client = AniListClient::with_token(TOKEN.to_string());
+    let debug = format!("{client:?}");
+
+    assert!(!debug.contains("secret-bytes"), "{debug}");
+    assert!(debug.contains(r#"token: Some("***redacted***")"#), "{debug}");
+    assert!(!format!("{client:#?}").contains("secret-bytes"));
+}
+
+#[test]
+fn test_per_user_handles_are_redacted() {
+    let client = AniListClient::from_config(&ClientConfig::default().use_env_token(false)).unwrap();
+    let debug = format!("{:?}", client.as_user(TOKEN));
+
+    assert!(!debug.contains("secret-bytes"), "{debug}");
+}
+
+#[test]
+fn test_clear_token_wipes_it() {
+    let mut client = AniListClient::with_token(TOKEN.to_string());
+    client.clear_token();
+
+    assert!(!client.has_token());
+    assert!(format!("{client:?}").contains("token: None"));
+
+    client.set_token(TOKEN.to_string());
+    assert!(client.has_token());
+    assert!(!format!("{client:?}").contains("secret-bytes"));
+}
+
+#[test]
+fn test_secret_token_is_redacted() {
+    let token = SecretToken::new(TOKEN);
+
+    assert_eq!(format!("{token:?}"), r#""***redacted***""#);
+    assert_eq!(token.to_string(), "***redacted***");
+    assert_eq!(token.expose(), TOKEN);
+}
+
+#[test]
+fn test_config_debug_redacts_the_token() {
+    let config = ClientConfig::default().token(TOKEN);
+    assert!(!format!("{config:?}").contains("secret-bytes"));
+}
+
+#[tokio::test]
+async fn test_authorization_header_is_sensitive() {
+    let transport = Arc::new(MockApi::ok(r#"{"data":{"Media":null}}"#));
+    let mut client = transport.client();
+    client.set_token(TOKEN.to_string());
+
+    let _ = client.anime().get_by_id(1).await;
+
+    let request = transport.last_request();
+    assert!(request.headers[AUTHORIZATION].is_sensitive());
+    assert!(!format!("{request:?}").contains("secret-bytes"));
+}
@@ -0,0 +1,165 @@
+use anilist_sdk::cache::CacheConfig;
+use anilist_sdk::client::{AniListClient, AniListClientBuilder, ClientConfig};
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use test_utils::MockApi;
+
+mod test_utils;
+
+fn cached(ttl: Duration) -> AniListClientBuilder {
+    AniListClient::builder().cache(CacheConfig {
+        ttl,
+        max_entries: 100,
+    })
+}
+
+fn client(builder: AniListClientBuilder, transport: &Arc<MockApi>) -> AniListClient {
+    transport.attach(builder)
+}
+
+#[tokio::test]
+async fn test_second_identical_query_is_served_from_cache() {
+    let transport = Arc::new(MockApi::new());
+    let client = client(cached(Duration::from_secs(300)), &transport);
+
+    let first = client.anime().get_by_id(1).await.unwrap();
+    let second = client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(first.id, second.id);
+    assert_eq!(transport.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_queries_are_not_cached_by_default() {
+    let transport = Arc::new(MockApi::new());
+    let client = client(AniListClient::builder(), &transport);
+
+    client.anime().get_by_id(1).await.unwrap();
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_different_variables_or_tokens_are_cached_separately() {
+    let transport = Arc::new(MockApi::new());
+    let mut client = client(cached(Duration::from_secs(300)), &transport);
+
+    client.anime().get_by_id(1).await.unwrap();
+    client.anime().get_by_id(2).await.unwrap();
+    assert_eq!(transport.calls(), 2);
+
+    client.set_token("token".to_string());
+    client.anime().get_by_id(1).await.unwrap();
+    assert_eq!(transport.calls(), 3);
+
+    client.anime().get_by_id(1).await.unwrap();
+    assert_eq!(transport.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_clones_share_the_cache() {
+    let transport = Arc::new(MockApi::new());
+    let client = client(cached(Duration::from_secs(300)), &transport);
+
+    client.anime().get_by_id(1).await.unwrap();
+    client.clone().anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(transport.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_errors_are_not_cached() {
+    let transport = Arc::new(MockApi::new().script([TransportResponse::new(
+        500,
+        r#"{"errors":[{"message":"Internal Server Error"}]}"#,
+    )]));
+    let client = client(cached(Duration::from_secs(300)), &transport);
+
+    let error = client.anime().get_by_id(1).await.unwrap_err();
+    assert!(matches!(error, AniListError::ServerError { .. }));
+
+    let anime = client.anime().get_by_id(1).await.unwrap();
+    assert_eq!(anime.id, 1);
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_mutations_are_never_cached() {
+    let saved = TransportResponse::new(200, r#"{"data":{"ToggleFollow":{"id":1}}}"#);
+    let transport = Arc::new(MockApi::new().script([saved.clone(), saved]));
+    let client = client(cached(Duration::from_secs(300)).token("token"), &transport);
+    let mutation = "mutation ($id: Int) { ToggleFollow(userId: $id) { id } }";
+
+    client.execute(mutation, json!({ "id": 1 })).await.unwrap();
+    client.execute(mutation, json!({ "id": 1 })).await.unwrap();
+
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_entries_expire_after_the_ttl() {
+    let transport = Arc::new(MockApi::new());
+    let client = client(cached(Duration::from_millis(50)), &transport);
+
+    client.anime().get_by_id(1).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_invalidate_cache_forgets_every_response() {
+    let transport = Arc::new(MockApi::new());
+    let client = client(cached(Duration::from_secs(300)), &transport);
+
+    client.anime().get_by_id(1).await.unwrap();
+    client.clone().invalidate_cache();
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_without_cache_refreshes_the_cached_response() {
+    let stale = TransportResponse::new(
+        200,
+        r#"{"data":{"Media":{"id":1,"title":{"romaji":"Stale"}}}}"#,
+    );
+    let transport = Arc::new(MockApi::new().script([stale]));
+    let client = client(cached(Duration::from_secs(300)), &transport);
+
+    let first = client.anime().get_by_id(1).await.unwrap();
+    let fresh = client.without_cache().anime().get_by_id(1).await.unwrap();
+    let cached = client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(first.title.romaji.as_deref(), Some("Stale"));
+    assert_eq!(fresh.title.romaji.as_deref(), Some("Cowboy Bebop"));
+    assert_eq!(cached.title.romaji.as_deref(), Some("Cowboy Bebop"));
+    assert_eq!(transport.calls(), 2);
+}
+
+#[test]
+fn test_empty_cache_settings_are_rejected() {
+    let zero_ttl = ClientConfig::default().cache(CacheConfig {
+        ttl: Duration::ZERO,
+        max_entries: 10,
+    });
+    let zero_entries = ClientConfig::default().cache(CacheConfig {
+        ttl: Duration::from_secs(1),
+        max_entries: 0,
+    });
+
+    assert!(matches!(
+        zero_ttl.validate(),
+        Err(AniListError::Config { .. })
+    ));
+    assert!(matches!(
+        zero_entries.validate(),
+        Err(AniListError::Config { .. })
+    ));
+}
@@ -0,0 +1,475 @@
+use anilist_sdk::client::{AniListClient, ClientConfig};
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use anilist_sdk::utils::{RetryConfig, RetryConfigBuilder, RetryOn, retry_with_backoff};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use test_utils::{MockApi, not_found, too_many_requests};
+use tokio::time::{Duration, Instant};
+
+mod test_utils;
+
+fn quick_retry(max_retries: u32) -> RetryConfigBuilder {
+    RetryConfig::builder()
+        .max_retries(max_retries)
+        .base_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_secs(1))
+}
+
+fn retrying_client(retry: RetryConfig, transport: &Arc<MockApi>) -> AniListClient {
+    transport.attach(AniListClient::builder().retry(retry))
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limited_calls_are_retried_transparently() {
+    let transport = Arc::new(MockApi::new().script([too_many_requests(), too_many_requests()]));
+    let client = retrying_client(quick_retry(3).build(), &transport);
+    let started = Instant::now();
+
+    let anime = client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(anime.id, 1);
+    assert_eq!(transport.calls(), 3);
+    // Backs off 100ms, then 200ms
+    assert_eq!(started.elapsed(), Duration::from_millis(300));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_after_header_sets_the_delay() {
+    let limited = too_many_requests()
+        .with_header("x-ratelimit-limit", "90")
+        .with_header("x-ratelimit-remaining", "0")
+        .with_header("x-ratelimit-reset", "1700000000")
+        .with_header("retry-after", "7");
+    let transport = Arc::new(MockApi::new().script([limited]));
+    let client = retrying_client(quick_retry(3).build(), &transport);
+    let started = Instant::now();
+
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(transport.calls(), 2);
+    assert!(started.elapsed() >= Duration::from_secs(7));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_exhausted_retries_return_the_original_error() {
+    let limited = too_many_requests()
+        .with_header("x-ratelimit-limit", "30")
+        .with_header("x-ratelimit-remaining", "0")
+        .with_header("x-ratelimit-reset", "1700000000")
+        .with_header("retry-after", "2");
+    let transport = Arc::new(MockApi::new().script(vec![limited; 5]));
+    let client = retrying_client(quick_retry(2).build(), &transport);
+
+    let result = client.anime().get_by_id(1).await;
+
+    assert_eq!(transport.calls(), 3);
+    match result {
+        Err(AniListError::RateLimit {
+            limit,
+            reset_at,
+            retry_after,
+            ..
+        }) => {
+            assert_eq!(limit, 30);
+            assert_eq!(reset_at, 1700000000);
+            assert_eq!(retry_after, 2);
+        }
+        other => panic!("expected RateLimit, got {other:?}"),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_transient_server_errors_are_retried() {
+    let transport = Arc::new(MockApi::new().script([
+        TransportResponse::new(503, "<html>maintenance</html>"),
+        TransportResponse::new(502, "bad gateway"),
+    ]));
+    let client = retrying_client(quick_retry(3).build(), &transport);
+
+    client.anime().get_by_id(1).await.unwrap();
+    assert_eq!(transport.calls(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_permanent_errors_are_not_retried() {
+    let transport = Arc::new(
+        MockApi::new().script([not_found(), TransportResponse::new(501, "not implemented")]),
+    );
+    let client = retrying_client(quick_retry(3).build(), &transport);
+
+    assert!(matches!(
+        client.anime().get_by_id(1).await,
+        Err(AniListError::NotFound)
+    ));
+    assert!(matches!(
+        client.anime().get_by_id(1).await,
+        Err(AniListError::ServerError { status: 501, .. })
+    ));
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_without_retry_fails_fast() {
+    let transport = Arc::new(MockApi::new().script([too_many_requests()]));
+    let client = retrying_client(quick_retry(3).build(), &transport);
+
+    let result = client.without_retry().anime().get_by_id(1).await;
+
+    assert!(matches!(result, Err(AniListError::RateLimitSimple)));
+    assert_eq!(transport.calls(), 1);
+    assert!(client.retry_config().is_some());
+    assert!(client.without_retry().retry_config().is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_mutations_are_not_retried_after_server_errors() {
+    let transport = Arc::new(MockApi::new().script([
+        TransportResponse::new(500, "internal error"),
+        too_many_requests(),
+    ]));
+    let mut client = AniListClient::builder()
+        .token("token")
+        .retry(quick_retry(3).build())
+        .build()
+        .unwrap();
+    client.set_transport(transport.clone());
+
+    // A 5xx may arrive after the mutation was applied, so it is not repeated
+    let result = client.user().toggle_follow(1).await;
+    assert!(matches!(
+        result,
+        Err(AniListError::ServerError { status: 500, .. })
+    ));
+    assert_eq!(transport.calls(), 1);
+
+    // A 429 means it was never processed
+    let _ = client.user().toggle_follow(1).await;
+    assert_eq!(transport.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_clients_do_not_retry_by_default() {
+    let transport = Arc::new(MockApi::new().script([too_many_requests()]));
+    let mut client = AniListClient::new();
+    client.set_transport(transport.clone());
+
+    assert!(client.retry_config().is_none());
+    assert!(ClientConfig::default().retry.is_none());
+    assert!(matches!(
+        client.anime().get_by_id(1).await,
+        Err(AniListError::RateLimitSimple)
+    ));
+    assert_eq!(transport.calls(), 1);
+}
+
+#[test]
+fn test_retry_setting_carries_over_from_config() {
+    let config = ClientConfig::default()
+        .use_env_token(false)
+        .retry(quick_retry(4).build());
+    let client = AniListClient::from_config(&config).unwrap();
+    assert_eq!(client.retry_config(), Some(&quick_retry(4).build()));
+
+    let mut client = client;
+    client.set_retry(None);
+    assert!(client.retry_config().is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_jittered_delays_stay_within_the_backoff_window() {
+    let retry = quick_retry(3).jitter(true).build();
+    for _ in 0..20 {
+        let transport = Arc::new(MockApi::new().script([too_many_requests(), too_many_requests()]));
+        let client = retrying_client(retry.clone(), &transport);
+        let started = Instant::now();
+
+        client.anime().get_by_id(1).await.unwrap();
+
+        assert_eq!(transport.calls(), 3);
+        // Each wait is drawn from zero up to 100ms, then 200ms
+        assert!(started.elapsed() <= Duration::from_millis(300));
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_total_delay_budget_stops_retrying_early() {
+    let retry = quick_retry(5)
+        .max_total_delay(Duration::from_millis(250))
+        .build();
+    let transport = Arc::new(MockApi::new().script(vec![too_many_requests(); 5]));
+    let client = retrying_client(retry, &transport);
+    let started = Instant::now();
+
+    let result = client.anime().get_by_id(1).await;
+
+    // 100ms fits the budget, a further 200ms would not
+    assert!(matches!(result, Err(AniListError::RateLimitSimple)));
+    assert_eq!(transport.calls(), 2);
+    assert_eq!(started.elapsed(), Duration::from_millis(100));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_after_is_capped_by_the_remaining_budget() {
+    let limited = too_many_requests()
+        .with_header("x-ratelimit-limit", "90")
+        .with_header("x-ratelimit-remaining", "10")
+        .with_header("x-ratelimit-reset", "1700000000")
+        .with_header("retry-after", "60");
+    let retry = quick_retry(3)
+        .max_total_delay(Duration::from_secs(5))
+        .build();
+    let transport = Arc::new(MockApi::new().script([limited]));
+    let client = retrying_client(retry, &transport);
+    let started = Instant::now();
+
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(transport.calls(), 2);
+    assert_eq!(started.elapsed(), Duration::from_secs(5));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_with_backoff_honors_the_total_delay_budget() {
+    let attempts = Mutex::new(0);
+    let retry = quick_retry(10)
+        .jitter(true)
+        .max_total_delay(Duration::from_millis(500))
+        .build();
+    let started = Instant::now();
+
+    let result: Result<(), _> = retry_with_backoff(
+        || {
+            *attempts.lock().unwrap() += 1;
+            async { Err(AniListError::RateLimitSimple) }
+        },
+        retry,
+    )
+    .await;
+
+    assert!(matches!(result, Err(AniListError::RateLimitSimple)));
+    assert!(*attempts.lock().unwrap() < 11);
+    assert!(started.elapsed() <= Duration::from_millis(500));
+}
+
+fn server_error(status: u16) -> AniListError {
+    AniListError::ServerError {
+        status,
+        message: "Service Unavailable".to_string(),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_with_backoff_retries_server_errors_with_growing_delays() {
+    let attempts = Mutex::new(Vec::new());
+    let retry = RetryConfig::builder()
+        .base_delay(Duration::from_secs(1))
+        .build();
+
+    let result = retry_with_backoff(
+        || {
+            let mut attempts = attempts.lock().unwrap();
+            attempts.push(Instant::now());
+            let failed = attempts.len() <= 2;
+            async move {
+                if failed {
+                    Err(server_error(503))
+                } else {
+                    Ok("done")
+                }
+            }
+        },
+        retry,
+    )
+    .await;
+
+    assert_eq!(result.unwrap(), "done");
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 3);
+    assert_eq!(attempts[1] - attempts[0], Duration::from_secs(1));
+    assert_eq!(attempts[2] - attempts[1], Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_retry_with_backoff_retries_connection_failures() {
+    // Nothing listens on a port just released by a listener
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let attempts = Mutex::new(0);
+
+    let result = retry_with_backoff(
+        || {
+            *attempts.lock().unwrap() += 1;
+            let url = url.clone();
+            async move { reqwest::get(url).await.map_err(AniListError::from) }
+        },
+        quick_retry(2).base_delay(Duration::from_millis(1)).build(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AniListError::Network(_))));
+    assert_eq!(*attempts.lock().unwrap(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_with_backoff_fails_fast_on_permanent_errors() {
+    let permanent = [
+        || AniListError::BadRequest {
+            message: "Validation error".to_string(),
+        },
+        || AniListError::AuthenticationRequired,
+        || AniListError::NotFound,
+        || AniListError::from(serde_json::from_str::<()>("{").unwrap_err()),
+        || server_error(501),
+    ];
+
+    for error in permanent {
+        let attempts = Mutex::new(0);
+        let result: Result<(), _> = retry_with_backoff(
+            || {
+                *attempts.lock().unwrap() += 1;
+                async move { Err(error()) }
+            },
+            quick_retry(3).build(),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(*attempts.lock().unwrap(), 1, "{:?}", result.unwrap_err());
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_on_opts_out_of_server_errors() {
+    let retry = quick_retry(3)
+        .retry_on(RetryOn::RATE_LIMIT | RetryOn::NETWORK)
+        .build();
+    let attempts = Mutex::new(0);
+
+    let result: Result<(), _> = retry_with_backoff(
+        || {
+            *attempts.lock().unwrap() += 1;
+            async { Err(server_error(503)) }
+        },
+        retry.clone(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AniListError::ServerError { status: 503, .. })));
+    assert_eq!(*attempts.lock().unwrap(), 1);
+
+    // The client follows the same setting
+    let transport = Arc::new(MockApi::new().script([TransportResponse::new(503, "unavailable")]));
+    let client = retrying_client(retry, &transport);
+    assert!(client.anime().get_by_id(1).await.is_err());
+    assert_eq!(transport.calls(), 1);
+}
+
+#[test]
+fn test_retry_on_sets_combine() {
+    assert_eq!(RetryConfig::default().retry_on, RetryOn::ALL);
+    assert!(RetryOn::ALL.contains(RetryOn::RATE_LIMIT | RetryOn::SERVER_ERROR));
+    assert!(!RetryOn::RATE_LIMIT.contains(RetryOn::NETWORK));
+    assert!(RetryOn::NONE.contains(RetryOn::NONE));
+
+    let mut set = RetryOn::NONE;
+    set |= RetryOn::NETWORK;
+    assert!(set.contains(RetryOn::NETWORK));
+    assert!(!set.contains(RetryOn::ALL));
+}
+
+#[test]
+fn test_builder_starts_from_the_defaults() {
+    assert_eq!(RetryConfig::builder().build(), RetryConfig::default());
+
+    let retry = RetryConfig::builder()
+        .max_retries(4)
+        .base_delay(Duration::from_millis(250))
+        .exponential(false)
+        .max_delay(Duration::from_secs(5))
+        .max_rate_limit_retries(6)
+        .max_server_error_retries(1)
+        .build();
+    assert_eq!(retry.base_delay_ms, 250);
+    assert!(!retry.exponential_backoff);
+    assert_eq!(retry.max_delay_ms, 5000);
+    assert_eq!(retry.max_retries_for(RetryOn::RATE_LIMIT), 6);
+    assert_eq!(retry.max_retries_for(RetryOn::SERVER_ERROR), 1);
+    assert_eq!(retry.max_retries_for(RetryOn::NETWORK), 4);
+}
+
+fn rate_limits(count: usize) -> Vec<AniListError> {
+    (0..count).map(|_| AniListError::RateLimitSimple).collect()
+}
+
+/// Runs `retry_with_backoff` over `errors` in order, then succeeds; returns
+/// the number of attempts and the result.
+async fn run_script(
+    errors: Vec<AniListError>,
+    retry: RetryConfig,
+) -> (u32, Result<(), AniListError>) {
+    let script = Mutex::new(VecDeque::from(errors));
+    let attempts = Mutex::new(0);
+    let result = retry_with_backoff(
+        || {
+            *attempts.lock().unwrap() += 1;
+            let next = script.lock().unwrap().pop_front();
+            async move { next.map_or(Ok(()), Err) }
+        },
+        retry,
+    )
+    .await;
+    (attempts.into_inner().unwrap(), result)
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_per_kind_limits_are_enforced_independently() {
+    let retry = quick_retry(3)
+        .max_rate_limit_retries(5)
+        .max_server_error_retries(1)
+        .build();
+
+    // Rate limits get five retries, past `max_retries`
+    let (attempts, result) = run_script(rate_limits(5), retry.clone()).await;
+    assert!(result.is_ok());
+    assert_eq!(attempts, 6);
+    let (attempts, result) = run_script(rate_limits(6), retry.clone()).await;
+    assert!(matches!(result, Err(AniListError::RateLimitSimple)));
+    assert_eq!(attempts, 6);
+
+    // Server errors only one, whatever the rate limits in between used
+    let (attempts, result) = run_script(
+        vec![
+            server_error(503),
+            AniListError::RateLimitSimple,
+            AniListError::BurstLimit,
+            server_error(502),
+        ],
+        retry,
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(AniListError::ServerError { status: 502, .. })
+    ));
+    assert_eq!(attempts, 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_client_enforces_per_kind_limits() {
+    let retry = quick_retry(3).max_server_error_retries(1).build();
+    let transport = Arc::new(MockApi::new().script([
+        TransportResponse::new(503, "unavailable"),
+        too_many_requests(),
+        too_many_requests(),
+        TransportResponse::new(503, "unavailable"),
+    ]));
+    let client = retrying_client(retry, &transport);
+
+    assert!(matches!(
+        client.anime().get_by_id(1).await,
+        Err(AniListError::ServerError { status: 503, .. })
+    ));
+    assert_eq!(transport.calls(), 4);
+}
@@ -84,9 +84,73 @@ async fn test_get_top_rated_reviews() {
     let mut prev_rating = i32::MAX;
     for review in &reviews {
         assert!(review.id > 0);
-        if let Some(rating) = review.rating {
-            assert!(rating <= prev_rating);
-            prev_rating = rating;
+        assert!(review.rating <= prev_rating);
+        prev_rating = review.rating;
+    }
+}
+
+#[tokio::test]
+async fn test_get_reviews_for_media_with_viewer_status_anonymous() {
+    let client = AniListClient::new();
+    let result = crate::review_api_call!(client, get_reviews_for_media_with_viewer_status, 1, 1, 3);
+
+    if let Ok(reviews) = result {
+        for review in &reviews {
+            let media = review.media.as_ref();
+            assert!(media.and_then(|m| m.viewer_entry.as_ref()).is_none());
         }
     }
 }
+
+fn review_fixture(media_list_entry: Option<serde_json::Value>) -> serde_json::Value {
+    let mut media = serde_json::json!({
+        "id": 1,
+        "title": { "userPreferred": "Cowboy Bebop" },
+        "coverImage": null,
+        "bannerImage": null
+    });
+    if let Some(entry) = media_list_entry {
+        media["mediaListEntry"] = entry;
+    }
+    serde_json::json!({
+        "id": 10,
+        "userId": 2,
+        "mediaId": 1,
+        "mediaType": "ANIME",
+        "summary": "A timeless classic of the genre",
+        "body": "Review body",
+        "rating": 40,
+        "ratingAmount": 42,
+        "score": 95,
+        "siteUrl": "https://anilist.co/review/10",
+        "createdAt": 1_600_000_000,
+        "updatedAt": 1_600_000_000,
+        "media": media
+    })
+}
+
+#[test]
+fn test_review_media_viewer_entry_shapes() {
+    use anilist_sdk::models::{MediaListStatus, Review};
+
+    // Authenticated, media on the viewer's list
+    let authed: Review = serde_json::from_value(review_fixture(Some(serde_json::json!({
+        "status": "COMPLETED",
+        "progress": 26,
+        "score": 9.5
+    }))))
+    .unwrap();
+    let entry = authed.media.unwrap().viewer_entry.expect("viewer entry");
+    assert!(matches!(entry.status, Some(MediaListStatus::Completed)));
+    assert_eq!(entry.progress, Some(26));
+    assert_eq!(entry.score, Some(9.5));
+
+    // Authenticated, media not on the viewer's list
+    let not_listed: Review =
+        serde_json::from_value(review_fixture(Some(serde_json::Value::Null))).unwrap();
+    assert!(not_listed.media.unwrap().viewer_entry.is_none());
+
+    // Anonymous: the field is not selected at all
+    let anon: Review = serde_json::from_value(review_fixture(None)).unwrap();
+    assert!(anon.media.unwrap().viewer_entry.is_none());
+}
@@ -0,0 +1,131 @@
+use anilist_sdk::models::{
+    MediaList, MediaListOptions, ScoreDisplay, ScoreFormat, Smiley, format_score,
+};
+
+fn numeric(score: &str) -> ScoreDisplay {
+    ScoreDisplay::Numeric(score.to_string())
+}
+
+#[test]
+fn test_point_100() {
+    assert_eq!(format_score(85.0, ScoreFormat::Point100), numeric("85"));
+    assert_eq!(format_score(84.5, ScoreFormat::Point100), numeric("85"));
+    assert_eq!(format_score(84.4, ScoreFormat::Point100), numeric("84"));
+}
+
+#[test]
+fn test_point_10_decimal() {
+    assert_eq!(
+        format_score(8.5, ScoreFormat::Point10Decimal),
+        numeric("8.5")
+    );
+    assert_eq!(
+        format_score(8.25, ScoreFormat::Point10Decimal),
+        numeric("8.3")
+    );
+    assert_eq!(
+        format_score(10.0, ScoreFormat::Point10Decimal),
+        numeric("10.0")
+    );
+}
+
+#[test]
+fn test_point_10() {
+    assert_eq!(format_score(7.0, ScoreFormat::Point10), numeric("7"));
+    assert_eq!(format_score(7.5, ScoreFormat::Point10), numeric("8"));
+}
+
+#[test]
+fn test_point_5_stars() {
+    assert_eq!(
+        format_score(3.0, ScoreFormat::Point5),
+        ScoreDisplay::Stars(3)
+    );
+    assert_eq!(
+        format_score(2.5, ScoreFormat::Point5),
+        ScoreDisplay::Stars(3)
+    );
+    assert_eq!(
+        format_score(0.4, ScoreFormat::Point5),
+        ScoreDisplay::Stars(1)
+    );
+    assert_eq!(
+        format_score(7.0, ScoreFormat::Point5),
+        ScoreDisplay::Stars(5)
+    );
+    assert_eq!(format_score(4.0, ScoreFormat::Point5).to_string(), "★★★★☆");
+}
+
+#[test]
+fn test_point_3_smileys() {
+    let cases = [
+        (1.0, Smiley::Sad, "🙁"),
+        (2.0, Smiley::Neutral, "😐"),
+        (3.0, Smiley::Happy, "🙂"),
+        (1.5, Smiley::Neutral, "😐"),
+        (5.0, Smiley::Happy, "🙂"),
+    ];
+    for (score, smiley, emoji) in cases {
+        let display = format_score(score, ScoreFormat::Point3);
+        assert_eq!(display, ScoreDisplay::Smiley(smiley));
+        assert_eq!(display.to_string(), emoji);
+    }
+}
+
+#[test]
+fn test_score_format_names() {
+    let formats: Vec<ScoreFormat> = serde_json::from_value(serde_json::json!([
+        "POINT_100",
+        "POINT_10_DECIMAL",
+        "POINT_10",
+        "POINT_5",
+        "POINT_3"
+    ]))
+    .unwrap();
+    assert_eq!(
+        formats,
+        vec![
+            ScoreFormat::Point100,
+            ScoreFormat::Point10Decimal,
+            ScoreFormat::Point10,
+            ScoreFormat::Point5,
+            ScoreFormat::Point3,
+        ]
+    );
+
+    let options: MediaListOptions =
+        serde_json::from_value(serde_json::json!({ "scoreFormat": "POINT_3" })).unwrap();
+    assert_eq!(options.parsed_score_format(), Some(ScoreFormat::Point3));
+}
+
+#[test]
+fn test_media_list_score_display() {
+    let entry = |score: f64| -> MediaList {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "userId": 1,
+            "mediaId": 1,
+            "status": "CURRENT",
+            "progress": 0,
+            "repeat": 0,
+            "priority": 0,
+            "private": false,
+            "hiddenFromStatusLists": false,
+            "startedAt": {},
+            "completedAt": {},
+            "score": score
+        }))
+        .unwrap()
+    };
+
+    assert_eq!(
+        entry(3.0).score_display(ScoreFormat::Point3),
+        Some(ScoreDisplay::Smiley(Smiley::Happy))
+    );
+    assert_eq!(
+        entry(4.0).score_display(ScoreFormat::Point5),
+        Some(ScoreDisplay::Stars(4))
+    );
+    // Unscored entries have a score of 0
+    assert_eq!(entry(0.0).score_display(ScoreFormat::Point100), None);
+}
@@ -0,0 +1,258 @@
+use anilist_sdk::client::{AniListClient, ClientConfig};
+use anilist_sdk::error::AniListError;
+use anilist_sdk::models::{MediaSearchFilter, MediaSeason, MediaSort, MediaStatus};
+use anilist_sdk::utils::normalize_search;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use test_utils::{EMPTY_PAGE, MockApi};
+
+mod test_utils;
+
+#[test]
+fn test_normalize_search_collapses_whitespace() {
+    assert_eq!(normalize_search("naruto").unwrap(), "naruto");
+    assert_eq!(normalize_search("  naruto  ").unwrap(), "naruto");
+    assert_eq!(
+        normalize_search("attack\t on \n\n titan").unwrap(),
+        "attack on titan"
+    );
+    assert_eq!(
+        normalize_search("\u{3000}進撃の巨人 ").unwrap(),
+        "進撃の巨人"
+    );
+}
+
+#[test]
+fn test_normalize_search_rejects_blank_terms() {
+    for blank in ["", " ", "   ", "\t\n", "\u{3000}"] {
+        assert!(
+            matches!(
+                normalize_search(blank),
+                Err(AniListError::BadRequest { .. })
+            ),
+            "{blank:?} should be rejected"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_blank_search_is_rejected_without_request() {
+    let client = AniListClient::new();
+
+    assert!(matches!(
+        client.anime().search("   ", 1, 10).await,
+        Err(AniListError::BadRequest { .. })
+    ));
+    assert!(matches!(
+        client.manga().search("", 1, 10).await,
+        Err(AniListError::BadRequest { .. })
+    ));
+    assert!(matches!(
+        client.character().search("\t", 1, 10).await,
+        Err(AniListError::BadRequest { .. })
+    ));
+    assert!(matches!(
+        client.staff().search(" ", 1, 10).await,
+        Err(AniListError::BadRequest { .. })
+    ));
+    assert!(matches!(
+        client.studio().search(" ", 1, 10).await,
+        Err(AniListError::BadRequest { .. })
+    ));
+    assert!(matches!(
+        client.user().search(" ", 1, 10).await,
+        Err(AniListError::BadRequest { .. })
+    ));
+    assert!(matches!(
+        client.forum().search_threads(" ", 1, 10).await,
+        Err(AniListError::BadRequest { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_lenient_search_returns_empty_results() {
+    let mut client = AniListClient::new();
+    client.set_lenient_search(true);
+    assert!(client.is_lenient_search());
+
+    assert!(
+        client
+            .anime()
+            .search("   ", 1, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(client.manga().search("", 1, 10).await.unwrap().is_empty());
+    assert!(
+        client
+            .forum()
+            .search_threads("\n", 1, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let configured = AniListClient::from_config(
+        &ClientConfig::default()
+            .use_env_token(false)
+            .lenient_search(true),
+    )
+    .unwrap();
+    assert!(configured.is_lenient_search());
+}
+
+#[tokio::test]
+async fn test_blank_autocomplete_returns_no_suggestions() {
+    let client = AniListClient::new();
+    assert!(
+        client
+            .anime()
+            .autocomplete("  ", 5)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(client.manga().autocomplete("", 5).await.unwrap().is_empty());
+}
+
+async fn advanced_search_variables(client: &mut AniListClient, filter: MediaSearchFilter) -> Value {
+    let transport = Arc::new(MockApi::ok(EMPTY_PAGE));
+    client.set_transport(transport.clone());
+    client
+        .anime()
+        .search_advanced(&filter, 1, 10)
+        .await
+        .unwrap();
+    transport.last_request().variables().clone()
+}
+
+#[tokio::test]
+async fn test_advanced_search_sends_only_the_fields_set() {
+    let mut client = AniListClient::new();
+
+    let variables = advanced_search_variables(
+        &mut client,
+        MediaSearchFilter::new().genres(["Action"]).year(2016),
+    )
+    .await;
+    let mut names: Vec<&str> = variables
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    names.sort();
+    assert_eq!(names, ["genres", "page", "perPage", "year"]);
+
+    let variables = advanced_search_variables(&mut client, MediaSearchFilter::new()).await;
+    assert_eq!(variables, json!({"page": 1, "perPage": 10}));
+}
+
+#[tokio::test]
+async fn test_advanced_search_maps_every_field() {
+    let mut client = AniListClient::with_token("token".to_string());
+    let filter = MediaSearchFilter::new()
+        .search("  fate  stay ")
+        .tags(["Magic"])
+        .minimum_tag_rank(60)
+        .tag_categories(["Theme-Fantasy", "Setting-Universe"])
+        .season(MediaSeason::Fall)
+        .statuses([MediaStatus::Finished, MediaStatus::Releasing])
+        .average_score_greater(70)
+        .episodes_range(12, 26)
+        .on_list(false)
+        .is_adult(false)
+        .sort(MediaSort::PopularityDesc);
+
+    let variables = advanced_search_variables(&mut client, filter).await;
+
+    assert_eq!(
+        variables,
+        json!({
+            "page": 1,
+            "perPage": 10,
+            "search": "fate stay",
+            "tags": ["Magic"],
+            "minimumTagRank": 60,
+            "tagCategories": ["Theme-Fantasy", "Setting-Universe"],
+            "withTags": true,
+            "season": "FALL",
+            "statuses": ["FINISHED", "RELEASING"],
+            "averageScoreGreater": 70,
+            "episodesGreater": 11,
+            "episodesLesser": 27,
+            "onList": false,
+            "isAdult": false,
+            "sort": ["POPULARITY_DESC"],
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_advanced_search_on_list_needs_a_token() {
+    let transport = Arc::new(MockApi::ok(EMPTY_PAGE));
+    let client = transport.client();
+
+    let result = client
+        .anime()
+        .search_advanced(&MediaSearchFilter::new().on_list(true), 1, 10)
+        .await;
+
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+    assert_eq!(transport.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_advanced_search_rejects_blank_terms_like_plain_search() {
+    let transport = Arc::new(MockApi::ok(EMPTY_PAGE));
+    let mut client = transport.client();
+    let filter = MediaSearchFilter::new().search("  \t ").genres(["Action"]);
+
+    let result = client.anime().search_advanced(&filter, 1, 10).await;
+    assert!(
+        matches!(result, Err(AniListError::BadRequest { .. })),
+        "{result:?}"
+    );
+
+    client.set_lenient_search(true);
+    let anime = client
+        .anime()
+        .search_advanced(&filter, 1, 10)
+        .await
+        .unwrap();
+    assert!(anime.is_empty());
+    assert_eq!(transport.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_advanced_search_rejects_bounds_out_of_range() {
+    let transport = Arc::new(MockApi::ok(EMPTY_PAGE));
+    let client = transport.client();
+
+    for (filter, field) in [
+        (
+            MediaSearchFilter::new().year_range(2000, i32::MAX),
+            "year_range",
+        ),
+        (
+            MediaSearchFilter::new().year_range(-300_000, 2000),
+            "year_range",
+        ),
+        (
+            MediaSearchFilter::new().episodes_range(i32::MIN, 12),
+            "min_episodes",
+        ),
+        (
+            MediaSearchFilter::new().episodes_range(1, i32::MAX),
+            "max_episodes",
+        ),
+    ] {
+        let result = client.anime().search_advanced(&filter, 1, 10).await;
+        assert!(
+            matches!(&result, Err(AniListError::InvalidInput { field: f, .. }) if f == field),
+            "{filter:?}: {result:?}"
+        );
+    }
+    assert_eq!(transport.calls(), 0);
+}
@@ -0,0 +1,385 @@
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::models::MediaSeason;
+use anilist_sdk::utils::seasons::{
+    ARCHIVE_START_YEAR, SeasonLabel, archive_range, archive_range_until, current_season,
+    neighbors_within, next_season, previous_season, season_for_month,
+};
+use serde_json::json;
+use std::sync::Arc;
+use test_utils::{EMPTY_PAGE, MockApi};
+
+mod test_utils;
+
+#[test]
+fn test_season_labels() {
+    assert_eq!((MediaSeason::Fall, 2023).label(), "Fall 2023");
+    assert_eq!((MediaSeason::Winter, 1940).label(), "Winter 1940");
+    assert_eq!(MediaSeason::Summer.as_str(), "SUMMER");
+}
+
+#[test]
+fn test_season_for_month_follows_quarters() {
+    let seasons: Vec<MediaSeason> = (1..=12).map(season_for_month).collect();
+    use MediaSeason::*;
+    assert_eq!(
+        seasons,
+        vec![
+            Winter, Winter, Winter, Spring, Spring, Spring, Summer, Summer, Summer, Fall, Fall,
+            Fall
+        ]
+    );
+}
+
+#[test]
+fn test_previous_and_next_wrap_year_boundaries() {
+    assert_eq!(
+        next_season((MediaSeason::Fall, 2023)),
+        (MediaSeason::Winter, 2024)
+    );
+    assert_eq!(
+        previous_season((MediaSeason::Winter, 2024)),
+        (MediaSeason::Fall, 2023)
+    );
+    assert_eq!(
+        next_season((MediaSeason::Spring, 2024)),
+        (MediaSeason::Summer, 2024)
+    );
+    assert_eq!(
+        previous_season((MediaSeason::Summer, 2024)),
+        (MediaSeason::Spring, 2024)
+    );
+}
+
+#[test]
+fn test_archive_range_bounds() {
+    let range = archive_range_until(2025);
+    assert_eq!(range.len(), (2025 - ARCHIVE_START_YEAR + 1) as usize * 4);
+    assert_eq!(range.first(), Some(&(MediaSeason::Winter, 1940)));
+    assert_eq!(range.last(), Some(&(MediaSeason::Fall, 2025)));
+
+    // Consecutive entries are exactly one season apart
+    for pair in range.windows(2) {
+        assert_eq!(next_season(pair[0]), pair[1]);
+    }
+
+    // The live archive always includes next year so upcoming seasons are reachable
+    let (_, this_year) = current_season();
+    assert_eq!(
+        archive_range().last(),
+        Some(&(MediaSeason::Fall, this_year + 1))
+    );
+}
+
+#[test]
+fn test_neighbors_at_archive_edges() {
+    assert_eq!(
+        neighbors_within((MediaSeason::Winter, 1940), 2025),
+        (None, Some((MediaSeason::Spring, 1940)))
+    );
+    assert_eq!(
+        neighbors_within((MediaSeason::Fall, 2025), 2025),
+        (Some((MediaSeason::Summer, 2025)), None)
+    );
+    assert_eq!(
+        neighbors_within((MediaSeason::Winter, 2000), 2025),
+        (
+            Some((MediaSeason::Fall, 1999)),
+            Some((MediaSeason::Spring, 2000))
+        )
+    );
+}
+
+#[test]
+fn test_current_season_has_both_neighbors() {
+    let client = AniListClient::new();
+    let (season, year) = current_season();
+
+    let (previous, next) = client.anime().get_season_neighbors(season, year);
+    assert_eq!(previous, Some(previous_season((season, year))));
+    assert_eq!(next, Some(next_season((season, year))));
+
+    // Fall of next year is the end of the archive
+    let (_, after_end) = client
+        .anime()
+        .get_season_neighbors(MediaSeason::Fall, year + 1);
+    assert_eq!(after_end, None);
+}
+
+#[test]
+fn test_season_for_date_at_boundaries() {
+    use anilist_sdk::utils::seasons::season_for_date;
+    use chrono::NaiveDate;
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    let cases = [
+        (date(2024, 12, 31), (MediaSeason::Fall, 2024)),
+        (date(2025, 1, 1), (MediaSeason::Winter, 2025)),
+        (date(2024, 3, 1), (MediaSeason::Winter, 2024)),
+        (date(2024, 3, 31), (MediaSeason::Winter, 2024)),
+        (date(2024, 4, 1), (MediaSeason::Spring, 2024)),
+    ];
+    for (day, expected) in cases {
+        assert_eq!(season_for_date(day), expected, "{day}");
+    }
+
+    // The season after New Year's Eve is in the new year
+    assert_eq!(
+        next_season(season_for_date(date(2024, 12, 31))),
+        (MediaSeason::Winter, 2025)
+    );
+    assert_eq!(
+        next_season(season_for_date(date(2024, 3, 1))),
+        (MediaSeason::Spring, 2024)
+    );
+}
+
+#[tokio::test]
+async fn test_current_and_next_season_lineups() {
+    let transport = Arc::new(MockApi::ok(EMPTY_PAGE));
+    let client = transport.client();
+
+    client.anime().get_current_season(1, 10).await.unwrap();
+    client.anime().get_next_season(2, 10).await.unwrap();
+
+    let current = current_season();
+    let next = next_season(current);
+    let variables = transport.variables();
+    assert_eq!(variables[0]["season"], current.0.as_str());
+    assert_eq!(variables[0]["year"], current.1);
+    assert_eq!(variables[1]["season"], next.0.as_str());
+    assert_eq!(variables[1]["year"], next.1);
+    assert_eq!(variables[1]["page"], 2);
+    assert_eq!(variables[1]["sort"], json!(["POPULARITY_DESC"]));
+}
+
+#[test]
+fn test_fuzzy_date_int() {
+    use anilist_sdk::utils::seasons::fuzzy_date_int;
+    use chrono::NaiveDate;
+
+    let date = NaiveDate::from_ymd_opt(2023, 10, 7).unwrap();
+    assert_eq!(fuzzy_date_int(date), Some(20231007));
+    // Years after 214748 do not fit the YYYYMMDD form
+    let last = NaiveDate::from_ymd_opt(214_748, 12, 31).unwrap();
+    assert_eq!(fuzzy_date_int(last), Some(2_147_481_231));
+    let far = NaiveDate::from_ymd_opt(214_749, 1, 1).unwrap();
+    assert_eq!(fuzzy_date_int(far), None);
+    assert_eq!(fuzzy_date_int(NaiveDate::MAX), None);
+}
+
+#[test]
+fn test_premiere_window_bounds_are_exclusive() {
+    use anilist_sdk::utils::seasons::premiere_window;
+    use chrono::NaiveDate;
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let window = |today, days| premiere_window(today, days).unwrap();
+
+    // Crosses a year boundary
+    assert_eq!(window(date(2023, 12, 20), 30), (20231219, 20240120));
+    // Crosses a month boundary backwards on the first of the month
+    assert_eq!(window(date(2024, 3, 1), 7), (20240229, 20240309));
+    // Leap day inside the window
+    assert_eq!(window(date(2024, 2, 27), 3), (20240226, 20240302));
+    // Zero days still covers today
+    assert_eq!(window(date(2024, 6, 15), 0), (20240614, 20240616));
+}
+
+#[test]
+fn test_premiere_window_month_only_dates() {
+    use anilist_sdk::utils::seasons::premiere_window;
+    use chrono::NaiveDate;
+
+    // A title starting "November 2024" is stored as 20241100
+    let month_only = 20241100;
+    let (after, before) =
+        premiere_window(NaiveDate::from_ymd_opt(2024, 10, 20).unwrap(), 30).unwrap();
+    assert!(after < month_only && month_only < before);
+
+    let (after, before) =
+        premiere_window(NaiveDate::from_ymd_opt(2024, 10, 1).unwrap(), 10).unwrap();
+    assert!(!(after < month_only && month_only < before));
+}
+
+#[test]
+fn test_premiere_window_out_of_range() {
+    use anilist_sdk::utils::seasons::premiere_window;
+    use chrono::NaiveDate;
+
+    let today = NaiveDate::from_ymd_opt(2024, 10, 20).unwrap();
+    // Ends in a year too large for a FuzzyDateInt
+    assert!(matches!(
+        premiere_window(today, u32::MAX),
+        Err(AniListError::InvalidInput { field, .. }) if field == "days"
+    ));
+    // Ends past the last date chrono can represent
+    assert!(matches!(
+        premiere_window(NaiveDate::MAX, 1),
+        Err(AniListError::InvalidInput { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_out_of_range_dates_send_no_request() {
+    let transport = Arc::new(MockApi::ok(EMPTY_PAGE));
+    let client = transport.client();
+
+    let result = client.anime().get_premiering_within(u32::MAX, 1, 10).await;
+    assert!(matches!(result, Err(AniListError::InvalidInput { field, .. }) if field == "days"));
+
+    let result = client
+        .anime()
+        .get_season_chart(MediaSeason::Fall, 250_000, 1, 10)
+        .await;
+    assert!(matches!(result, Err(AniListError::InvalidInput { field, .. }) if field == "year"));
+    assert_eq!(transport.calls(), 0);
+}
+
+fn chart_entry(
+    id: i32,
+    format: &str,
+    season: Option<(&str, i32)>,
+    start: Option<(i32, Option<i32>)>,
+) -> anilist_sdk::models::Anime {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "title": { "romaji": format!("Entry {id}") },
+        "format": format,
+        "season": season.map(|(season, _)| season),
+        "seasonYear": season.map(|(_, year)| year),
+        "startDate": start.map(|(year, month)| serde_json::json!({
+            "year": year, "month": month, "day": month.map(|_| 5)
+        })),
+    }))
+    .expect("chart fixture should deserialize")
+}
+
+#[test]
+fn test_season_start() {
+    use anilist_sdk::utils::seasons::season_start;
+    use chrono::NaiveDate;
+
+    let expected = [
+        (1, MediaSeason::Winter),
+        (4, MediaSeason::Spring),
+        (7, MediaSeason::Summer),
+        (10, MediaSeason::Fall),
+    ];
+    for (month, season) in expected {
+        assert_eq!(
+            season_start((season, 2023)),
+            NaiveDate::from_ymd_opt(2023, month, 1).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_classify_season_entries() {
+    use anilist_sdk::utils::seasons::SeasonEntryKind::{Leftover, MovieSpecial, New};
+    use anilist_sdk::utils::seasons::classify_season_entry;
+
+    let fall_2023 = (MediaSeason::Fall, 2023);
+    let cases = [
+        // Premiered this season
+        (
+            chart_entry(1, "TV", Some(("FALL", 2023)), Some((2023, Some(10)))),
+            New,
+        ),
+        // Pre-aired in late September but listed as Fall by AniList
+        (
+            chart_entry(2, "TV", Some(("FALL", 2023)), Some((2023, Some(9)))),
+            New,
+        ),
+        // Continuing from Summer
+        (
+            chart_entry(3, "TV", Some(("SUMMER", 2023)), Some((2023, Some(7)))),
+            Leftover,
+        ),
+        // Split-cour show whose single entry resumes after a one-season gap
+        (
+            chart_entry(4, "TV", Some(("SPRING", 2023)), Some((2023, Some(4)))),
+            Leftover,
+        ),
+        // Second cour released as its own entry
+        (
+            chart_entry(5, "TV", Some(("FALL", 2023)), Some((2023, Some(10)))),
+            New,
+        ),
+        // Long-running show from a previous year
+        (
+            chart_entry(6, "TV", Some(("FALL", 1999)), Some((1999, Some(10)))),
+            Leftover,
+        ),
+        // No season: falls back to the start date
+        (chart_entry(7, "TV", None, Some((2023, Some(8)))), Leftover),
+        (chart_entry(8, "ONA", None, Some((2023, Some(11)))), New),
+        // Year only: earlier only when the year is
+        (chart_entry(9, "TV", None, Some((2022, None))), Leftover),
+        (chart_entry(10, "TV", None, Some((2023, None))), New),
+        // Null start date and no season
+        (chart_entry(11, "TV", None, None), New),
+        // Shorts are series too
+        (
+            chart_entry(12, "TV_SHORT", Some(("SUMMER", 2023)), None),
+            Leftover,
+        ),
+        // Movies and specials regardless of start
+        (
+            chart_entry(13, "MOVIE", Some(("FALL", 2023)), Some((2023, Some(11)))),
+            MovieSpecial,
+        ),
+        (
+            chart_entry(14, "SPECIAL", Some(("SPRING", 2023)), None),
+            MovieSpecial,
+        ),
+        (chart_entry(15, "OVA", None, None), MovieSpecial),
+        (
+            chart_entry(16, "MUSIC", None, Some((2023, Some(10)))),
+            MovieSpecial,
+        ),
+    ];
+
+    for (anime, expected) in &cases {
+        assert_eq!(
+            classify_season_entry(anime, fall_2023),
+            *expected,
+            "entry {}",
+            anime.id
+        );
+    }
+
+    // The endpoint helper keeps order and pairs every entry with its kind
+    let client = AniListClient::new();
+    let entries: Vec<_> = cases.iter().map(|(anime, _)| anime.clone()).collect();
+    let chart = client
+        .anime()
+        .classify_season_entries(entries, MediaSeason::Fall, 2023);
+    let kinds: Vec<_> = chart
+        .iter()
+        .map(|(anime, kind)| (anime.id, *kind))
+        .collect();
+    let expected: Vec<_> = cases
+        .iter()
+        .map(|(anime, kind)| (anime.id, *kind))
+        .collect();
+    assert_eq!(kinds, expected);
+}
+
+#[test]
+fn test_winter_leftovers_cross_the_year_boundary() {
+    use anilist_sdk::utils::seasons::{SeasonEntryKind, classify_season_entry};
+
+    let winter_2024 = (MediaSeason::Winter, 2024);
+    let fall_show = chart_entry(1, "TV", Some(("FALL", 2023)), Some((2023, Some(10))));
+    let winter_show = chart_entry(2, "TV", None, Some((2024, Some(1))));
+    assert_eq!(
+        classify_season_entry(&fall_show, winter_2024),
+        SeasonEntryKind::Leftover
+    );
+    assert_eq!(
+        classify_season_entry(&winter_show, winter_2024),
+        SeasonEntryKind::New
+    );
+}
@@ -14,7 +14,7 @@ async fn test_get_popular_staff() {
     // Check that all staff have required fields
     for staff in &staff_list {
         assert!(staff.id > 0);
-        assert!(staff.name.is_some());
+        assert!(staff.name.full.is_some());
     }
 }
 
@@ -26,7 +26,7 @@ async fn test_get_staff_by_id() {
 
     let staff = result.expect("Failed to get staff by ID");
     assert_eq!(staff.id, 95128);
-    assert!(staff.name.is_some());
+    assert!(staff.name.full.is_some());
 }
 
 #[tokio::test]
@@ -39,17 +39,14 @@ async fn test_search_staff() {
 
     // Check that results contain "Miyazaki" in some form
     let has_miyazaki = staff_list.iter().any(|staff| {
-        if let Some(name) = &staff.name {
-            name.full
+        let name = &staff.name;
+        name.full
+            .as_ref()
+            .is_some_and(|n| n.to_lowercase().contains("miyazaki"))
+            || name
+                .last
                 .as_ref()
                 .is_some_and(|n| n.to_lowercase().contains("miyazaki"))
-                || name
-                    .last
-                    .as_ref()
-                    .is_some_and(|n| n.to_lowercase().contains("miyazaki"))
-        } else {
-            false
-        }
     });
     assert!(has_miyazaki);
 }
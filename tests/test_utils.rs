@@ -1,7 +1,17 @@
+// Shared by every test binary, each of which uses only some of the helpers
+#![allow(dead_code)]
+
+use anilist_sdk::client::{AniListClient, AniListClientBuilder};
 use anilist_sdk::error::AniListError;
-use std::sync::atomic::{AtomicU64, Ordering};
+use anilist_sdk::transport::{Transport, TransportFuture, TransportRequest, TransportResponse};
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::time::{Duration, sleep};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::time::{Duration, Instant, sleep};
 
 /// Global rate limiter to coordinate between all tests
 static LAST_REQUEST_TIME: AtomicU64 = AtomicU64::new(0);
@@ -47,55 +57,19 @@ pub async fn with_retry<T>(
 
         match operation().await {
             Ok(result) => return Ok(result),
-            Err(AniListError::RateLimit {
-                retry_after,
-                reset_at,
-                ..
-            }) => {
-                if attempts >= MAX_RETRIES {
-                    return Err(AniListError::RateLimit {
-                        limit: 90,
-                        remaining: 0,
-                        reset_at,
-                        retry_after,
-                    });
-                }
-
-                println!(
-                    "Rate limited (attempt {}/{}), waiting {} seconds before retry...",
-                    attempts, MAX_RETRIES, retry_after
-                );
-
-                // Wait the specified retry_after duration plus a small buffer
-                sleep(Duration::from_secs(retry_after as u64 + 1)).await;
-            }
-            Err(AniListError::RateLimitSimple) => {
-                if attempts >= MAX_RETRIES {
-                    return Err(AniListError::RateLimitSimple);
-                }
-
-                println!(
-                    "Rate limited (attempt {}/{}), waiting 60 seconds before retry...",
-                    attempts, MAX_RETRIES
-                );
-
-                // Conservative wait time when we don't have specific timing
-                sleep(Duration::from_secs(60)).await;
-            }
-            Err(AniListError::BurstLimit) => {
-                if attempts >= MAX_RETRIES {
-                    return Err(AniListError::BurstLimit);
-                }
-
+            Err(error) if error.is_rate_limited() && attempts < MAX_RETRIES => {
+                // Wait the suggested delay plus a small buffer
+                let wait = error.retry_delay().unwrap_or_default() + Duration::from_secs(1);
                 println!(
-                    "Burst limit hit (attempt {}/{}), waiting 5 seconds before retry...",
-                    attempts, MAX_RETRIES
+                    "{} (attempt {}/{}), waiting {} seconds before retry...",
+                    error,
+                    attempts,
+                    MAX_RETRIES,
+                    wait.as_secs()
                 );
-
-                // Shorter wait for burst limits
-                sleep(Duration::from_secs(5)).await;
+                sleep(wait).await;
             }
-            Err(other_error) => return Err(other_error),
+            Err(error) => return Err(error),
         }
     }
 }
@@ -329,3 +303,301 @@ macro_rules! notification_api_call {
         result
     }};
 }
+
+/// A minimal `Media` response: Cowboy Bebop with just its ID and title.
+pub const MEDIA: &str = r#"{"data":{"Media":{"id":1,"title":{"romaji":"Cowboy Bebop"}}}}"#;
+
+/// An empty page of media.
+pub const EMPTY_PAGE: &str = r#"{"data":{"Page":{"media":[]}}}"#;
+
+/// Serves one HTTP connection on a local port: reads the request, waits
+/// `delay`, then answers with `body`. Returns the base URL and a handle
+/// yielding the raw request.
+pub async fn serve_once(
+    delay: Duration,
+    body: &'static str,
+) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = vec![0; 8192];
+        let read = socket.read(&mut buffer).await.unwrap();
+        let request = String::from_utf8_lossy(&buffer[..read]).into_owned();
+
+        sleep(delay).await;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let _ = socket.write_all(response.as_bytes()).await;
+        request
+    });
+    (url, handle)
+}
+
+/// A 429 as AniList sends it, before any rate limit headers are added.
+pub fn too_many_requests() -> TransportResponse {
+    TransportResponse::new(429, r#"{"errors":[{"message":"Too Many Requests."}]}"#)
+}
+
+/// A 404 as AniList sends it for an ID that does not exist.
+pub fn not_found() -> TransportResponse {
+    TransportResponse::new(404, r#"{"errors":[{"message":"Not Found.","status":404}]}"#)
+}
+
+/// A minimal media item for a page, numbered `id`.
+pub fn media_item(id: usize) -> Value {
+    json!({"id": id, "title": {"romaji": format!("Title {id}")}})
+}
+
+/// Answers a `Page` query over the items `1..=total`, at the page and page
+/// size the request asks for, listing `item(id)` under `field`.
+pub fn page_of(
+    request: &TransportRequest,
+    field: &str,
+    total: usize,
+    item: impl Fn(usize) -> Value,
+) -> TransportResponse {
+    let variables = request.variables();
+    let page = variables["page"].as_u64().unwrap() as usize;
+    let per_page = variables["perPage"].as_u64().unwrap() as usize;
+    let start = ((page - 1) * per_page).min(total);
+    let end = (start + per_page).min(total);
+    let items: Vec<Value> = (start + 1..=end).map(item).collect();
+    let body = json!({"data": {"Page": {
+        field: items,
+        "pageInfo": {
+            "total": total,
+            "perPage": per_page,
+            "currentPage": page,
+            "lastPage": total.div_ceil(per_page),
+            "hasNextPage": end < total,
+        },
+    }}});
+    TransportResponse::new(200, body.to_string())
+}
+
+type Responder =
+    Box<dyn Fn(&TransportRequest) -> Result<TransportResponse, AniListError> + Send + Sync>;
+
+/// A stand-in for the AniList API in offline tests.
+///
+/// Answers with the scripted responses in order, then with its responder:
+/// [`MEDIA`] for [`MockApi::new`], or whatever the constructor set up. Every
+/// request is logged with the time it was sent, and a delay can be added
+/// before each answer.
+pub struct MockApi {
+    script: Mutex<VecDeque<TransportResponse>>,
+    respond: Responder,
+    delay: Box<dyn Fn(&TransportRequest) -> Duration + Send + Sync>,
+    requests: Mutex<Vec<(TransportRequest, Instant)>>,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    answered: AtomicUsize,
+}
+
+/// Counts a request as in flight until it is answered or abandoned.
+struct Answering<'a>(&'a AtomicUsize);
+
+impl Drop for Answering<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Default for MockApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockApi {
+    /// Answers every request with [`MEDIA`].
+    pub fn new() -> Self {
+        Self::ok(MEDIA)
+    }
+
+    /// Answers every request with a 200 and `body`.
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self::respond(TransportResponse::new(200, body.into()))
+    }
+
+    /// Answers every request with `response`.
+    pub fn respond(response: TransportResponse) -> Self {
+        Self::from_fn(move |_| response.clone())
+    }
+
+    /// Answers each request with `respond(request)`.
+    pub fn from_fn(
+        respond: impl Fn(&TransportRequest) -> TransportResponse + Send + Sync + 'static,
+    ) -> Self {
+        Self::try_from_fn(move |request| Ok(respond(request)))
+    }
+
+    /// Answers each request with `respond(request)`, which may fail the way a
+    /// transport does, e.g. when the network is down.
+    pub fn try_from_fn(
+        respond: impl Fn(&TransportRequest) -> Result<TransportResponse, AniListError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self {
+            script: Mutex::new(VecDeque::new()),
+            respond: Box::new(respond),
+            delay: Box::new(|_| Duration::ZERO),
+            requests: Mutex::new(Vec::new()),
+            in_flight: AtomicUsize::new(0),
+            max_in_flight: AtomicUsize::new(0),
+            answered: AtomicUsize::new(0),
+        }
+    }
+
+    /// Answers with `responses` in order and panics on any further request.
+    pub fn scripted(responses: impl IntoIterator<Item = TransportResponse>) -> Self {
+        Self::from_fn(|request| panic!("unexpected request: {}", request.query())).script(responses)
+    }
+
+    /// Answers with `responses` first, before falling back to the responder.
+    pub fn script(self, responses: impl IntoIterator<Item = TransportResponse>) -> Self {
+        self.script.lock().unwrap().extend(responses);
+        self
+    }
+
+    /// Waits `delay` before each answer.
+    pub fn delay(self, delay: Duration) -> Self {
+        self.delay_with(move |_| delay)
+    }
+
+    /// Waits `delay(request)` before each answer.
+    pub fn delay_with(
+        mut self,
+        delay: impl Fn(&TransportRequest) -> Duration + Send + Sync + 'static,
+    ) -> Self {
+        self.delay = Box::new(delay);
+        self
+    }
+
+    /// A default client whose requests go to this API.
+    pub fn client(self: &std::sync::Arc<Self>) -> AniListClient {
+        self.attach(AniListClient::builder())
+    }
+
+    /// Builds `builder` into a client whose requests go to this API.
+    pub fn attach(self: &std::sync::Arc<Self>, builder: AniListClientBuilder) -> AniListClient {
+        let mut client = builder.build().unwrap();
+        client.set_transport(self.clone());
+        client
+    }
+
+    /// Number of requests received.
+    pub fn calls(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Every request received, oldest first.
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .map(|(request, _)| request.clone())
+            .collect()
+    }
+
+    /// The most recent request; panics if there was none.
+    pub fn last_request(&self) -> TransportRequest {
+        self.requests().pop().expect("no request was sent")
+    }
+
+    /// The variables of every request, oldest first.
+    pub fn variables(&self) -> Vec<Value> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .map(|(request, _)| request.variables().clone())
+            .collect()
+    }
+
+    /// The `page` variable of every request that sent one, oldest first.
+    pub fn pages_requested(&self) -> Vec<i64> {
+        self.variables()
+            .iter()
+            .filter_map(|variables| variables["page"].as_i64())
+            .collect()
+    }
+
+    /// The `Authorization` header of every request, empty when there was none.
+    pub fn authorizations(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .map(|(request, _)| authorization(request).to_string())
+            .collect()
+    }
+
+    /// When each request was received, oldest first.
+    pub fn times(&self) -> Vec<Instant> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|(_, sent)| *sent).collect()
+    }
+
+    /// The time between each request and the next.
+    pub fn gaps(&self) -> Vec<Duration> {
+        self.times()
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect()
+    }
+
+    /// The most requests that were waiting for an answer at once.
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Requests waiting for an answer right now.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Number of requests answered, leaving out those abandoned before
+    /// their delay was up.
+    pub fn answered(&self) -> usize {
+        self.answered.load(Ordering::SeqCst)
+    }
+}
+
+impl Transport for MockApi {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        let delay = (self.delay)(&request);
+        let scripted = self.script.lock().unwrap().pop_front();
+        let response = match scripted {
+            Some(response) => Ok(response),
+            None => (self.respond)(&request),
+        };
+        self.requests
+            .lock()
+            .unwrap()
+            .push((request, Instant::now()));
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+        let answering = Answering(&self.in_flight);
+        Box::pin(async move {
+            if !delay.is_zero() {
+                sleep(delay).await;
+            }
+            drop(answering);
+            self.answered.fetch_add(1, Ordering::SeqCst);
+            response
+        })
+    }
+}
+
+/// The `Authorization` header of `request`, empty when there is none.
+pub fn authorization(request: &TransportRequest) -> &str {
+    request
+        .headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,64 @@
+use anilist_sdk::client::{AniListClient, ClientConfig, DEFAULT_TIMEOUT};
+use anilist_sdk::error::AniListError;
+use std::sync::Arc;
+use test_utils::MockApi;
+use tokio::time::{Duration, Instant};
+
+mod test_utils;
+
+/// A client with `timeout` whose requests are answered after `delay`.
+fn slow_client(delay: Duration, timeout: Duration) -> AniListClient {
+    Arc::new(MockApi::new().delay(delay)).attach(AniListClient::builder().timeout(timeout))
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_slow_requests_fail_with_timeout() {
+    let client = slow_client(Duration::from_secs(60), Duration::from_secs(2));
+    let started = Instant::now();
+
+    let error = client.anime().get_by_id(1).await.unwrap_err();
+
+    match error {
+        AniListError::Timeout { elapsed } => assert_eq!(elapsed, Duration::from_secs(2)),
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+    assert_eq!(started.elapsed(), Duration::from_secs(2));
+    assert_eq!(client.metrics().network_errors, 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_with_timeout_overrides_the_client_timeout() {
+    let client = slow_client(Duration::from_secs(3), Duration::from_secs(10));
+
+    let error = client
+        .with_timeout(Duration::from_secs(1))
+        .anime()
+        .get_by_id(1)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AniListError::Timeout { .. }), "{error:?}");
+
+    // The original client keeps its own, longer timeout
+    let anime = client.anime().get_by_id(1).await.unwrap();
+    assert_eq!(anime.id, 1);
+    assert_eq!(client.timeout(), Some(Duration::from_secs(10)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_clients_time_out_by_default() {
+    let mut client = AniListClient::new();
+    client.set_transport(Arc::new(MockApi::new().delay(Duration::from_secs(3600))));
+
+    let error = client.anime().get_by_id(1).await.unwrap_err();
+
+    assert!(matches!(error, AniListError::Timeout { elapsed } if elapsed == DEFAULT_TIMEOUT));
+    assert_eq!(ClientConfig::default().timeout, Some(DEFAULT_TIMEOUT));
+}
+
+#[test]
+fn test_timeout_message_names_the_duration() {
+    let error = AniListError::Timeout {
+        elapsed: Duration::from_millis(1500),
+    };
+    assert_eq!(error.to_string(), "Request timed out after 1.5s");
+}
@@ -0,0 +1,170 @@
+use anilist_sdk::auth::RefreshedToken;
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::transport::TransportResponse;
+use chrono::{Duration as ChronoDuration, Utc};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use test_utils::{MEDIA, MockApi, authorization};
+
+mod test_utils;
+
+const UNAUTHORIZED: &str = r#"{"errors":[{"message":"Invalid token","status":401}]}"#;
+
+/// Rejects requests carrying one of the `expired` tokens with a 401 and
+/// answers the rest with [`MEDIA`].
+fn token_checking_api(expired: &[&'static str]) -> Arc<MockApi> {
+    let expired = expired.to_vec();
+    Arc::new(MockApi::from_fn(move |request| {
+        let authorization = authorization(request);
+        if expired
+            .iter()
+            .any(|token| authorization == format!("Bearer {token}"))
+        {
+            TransportResponse::new(401, UNAUTHORIZED)
+        } else {
+            TransportResponse::new(200, MEDIA)
+        }
+    }))
+}
+
+fn client(token: &str, transport: &Arc<MockApi>) -> AniListClient {
+    transport.attach(AniListClient::builder().token(token))
+}
+
+/// Registers a callback returning `token` and counting its calls.
+fn refresh_to(client: &mut AniListClient, token: &'static str) -> Arc<AtomicU32> {
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    client.on_token_expired(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async move { Ok(token.to_string()) }
+    });
+    calls
+}
+
+#[tokio::test]
+async fn test_rejected_token_is_refreshed_and_the_request_retried() {
+    let transport = token_checking_api(&["old"]);
+    let mut client = client("old", &transport);
+    let calls = refresh_to(&mut client, "new");
+    // Endpoint structs created before the refresh pick up the new token too
+    let anime = client.anime();
+
+    assert_eq!(anime.get_by_id(1).await.unwrap().id, 1);
+    assert_eq!(client.anime().get_by_id(1).await.unwrap().id, 1);
+    assert_eq!(anime.get_by_id(1).await.unwrap().id, 1);
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        transport.authorizations(),
+        ["Bearer old", "Bearer new", "Bearer new", "Bearer new"]
+    );
+}
+
+#[tokio::test]
+async fn test_failing_callback_returns_the_original_error() {
+    let transport = token_checking_api(&["old"]);
+    let mut client = client("old", &transport);
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    client.on_token_expired(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async {
+            Err::<String, _>(AniListError::Config {
+                message: "refresh endpoint down".to_string(),
+            })
+        }
+    });
+
+    let error = client.anime().get_by_id(1).await.unwrap_err();
+
+    assert!(
+        matches!(error, AniListError::AuthenticationRequired),
+        "{error:?}"
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(transport.authorizations(), ["Bearer old"]);
+}
+
+#[tokio::test]
+async fn test_refreshed_token_that_is_also_rejected_is_not_refreshed_again() {
+    let transport = token_checking_api(&["old", "new"]);
+    let mut client = client("old", &transport);
+    let calls = refresh_to(&mut client, "new");
+
+    let error = client.anime().get_by_id(1).await.unwrap_err();
+
+    assert!(
+        matches!(error, AniListError::AuthenticationRequired),
+        "{error:?}"
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(transport.authorizations(), ["Bearer old", "Bearer new"]);
+}
+
+#[tokio::test]
+async fn test_expired_token_is_refreshed_before_sending() {
+    let transport = token_checking_api(&[]);
+    let mut client = transport.attach(
+        AniListClient::builder()
+            .token("old")
+            .token_expires_at(Utc::now() - ChronoDuration::minutes(1)),
+    );
+    let next_expiry = Utc::now() + ChronoDuration::days(365);
+    client.on_token_expired(move || async move {
+        Ok(RefreshedToken::new("new").expires_at(next_expiry))
+    });
+
+    client.anime().get_by_id(1).await.unwrap();
+
+    assert_eq!(transport.authorizations(), ["Bearer new"]);
+    assert_eq!(client.token_expires_at(), Some(next_expiry));
+}
+
+#[tokio::test]
+async fn test_concurrent_rejections_share_one_refresh() {
+    let transport = token_checking_api(&["old"]);
+    let mut client = client("old", &transport);
+    let calls = refresh_to(&mut client, "new");
+
+    let (anime, manga) = (client.anime(), client.manga());
+    let (first, second, third) =
+        tokio::join!(anime.get_by_id(1), anime.get_by_id(2), manga.get_by_id(3));
+
+    assert!(first.is_ok() && second.is_ok() && third.is_ok());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_set_token_on_a_clone_switches_every_handle() {
+    let transport = token_checking_api(&["old"]);
+    let mut client = client("old", &transport);
+    let calls = refresh_to(&mut client, "new");
+    let anime = client.anime();
+    let mut other_handle = client.clone();
+    other_handle.set_token("other".to_string());
+
+    anime.get_by_id(1).await.unwrap();
+    client.anime().get_by_id(1).await.unwrap();
+    assert_eq!(transport.authorizations(), ["Bearer other", "Bearer other"]);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    other_handle.clear_token();
+    assert!(!client.has_token());
+    assert!(!client.without_retry().has_token());
+}
+
+#[tokio::test]
+async fn test_clients_without_a_callback_still_fail() {
+    let transport = token_checking_api(&["old"]);
+    let client = client("old", &transport);
+
+    let error = client.anime().get_by_id(1).await.unwrap_err();
+
+    assert!(
+        matches!(error, AniListError::AuthenticationRequired),
+        "{error:?}"
+    );
+    assert_eq!(transport.authorizations(), ["Bearer old"]);
+}
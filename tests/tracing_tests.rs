@@ -0,0 +1,146 @@
+#![cfg(feature = "tracing")]
+
+use anilist_sdk::client::AniListClient;
+use anilist_sdk::error::AniListError;
+use anilist_sdk::utils::{RetryConfig, retry_with_backoff};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_utils::{MockApi, too_many_requests};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+mod test_utils;
+
+/// Records span names and event fields as `name=value` strings.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Records>);
+
+#[derive(Default)]
+struct Records {
+    next_id: AtomicU64,
+    spans: Mutex<Vec<String>>,
+    events: Mutex<Vec<String>>,
+}
+
+impl Recorder {
+    fn spans(&self) -> Vec<String> {
+        self.0.spans.lock().unwrap().clone()
+    }
+
+    fn events(&self) -> Vec<String> {
+        self.0.events.lock().unwrap().clone()
+    }
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!(" {}={value:?}", field.name()));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(span.metadata().name().to_string());
+        span.record(&mut fields);
+        self.0.spans.lock().unwrap().push(fields.0);
+        Id::from_u64(self.0.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(event.metadata().level().to_string());
+        event.record(&mut fields);
+        self.0.events.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+fn quick_retry() -> RetryConfig {
+    RetryConfig::builder()
+        .max_retries(3)
+        .base_delay(Duration::from_secs(1))
+        .max_delay(Duration::from_secs(30))
+        .build()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_with_backoff_reports_retries_as_events() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let calls = Mutex::new(0);
+    let result = retry_with_backoff(
+        || {
+            let mut calls = calls.lock().unwrap();
+            *calls += 1;
+            let result = if *calls < 3 {
+                Err(AniListError::BurstLimit)
+            } else {
+                Ok(*calls)
+            };
+            async move { result }
+        },
+        quick_retry(),
+    )
+    .await;
+    assert_eq!(result.unwrap(), 3);
+
+    let events = recorder.events();
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(events[0].starts_with("WARN"), "{events:?}");
+    assert!(
+        events[0].contains("reason=\"burst limit exceeded\""),
+        "{events:?}"
+    );
+    assert!(events[0].contains("attempt=1"), "{events:?}");
+    assert!(events[0].contains("max_retries=3"), "{events:?}");
+    assert!(events[0].contains("sleep_secs=2.0"), "{events:?}");
+    assert!(events[1].contains("attempt=2"), "{events:?}");
+
+    let spans = recorder.spans();
+    assert_eq!(
+        spans,
+        [
+            "anilist_attempt attempt=0",
+            "anilist_attempt attempt=1",
+            "anilist_attempt attempt=2"
+        ]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_client_retries_are_traced() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let api = Arc::new(MockApi::new().script([too_many_requests()]));
+    let client = api.attach(AniListClient::builder().retry(quick_retry()));
+    client.anime().get_by_id(1).await.unwrap();
+
+    let events = recorder.events();
+    assert_eq!(events.len(), 1, "{events:?}");
+    assert!(events[0].contains("attempt=1"), "{events:?}");
+    assert!(events[0].contains("sleep_secs=1.0"), "{events:?}");
+
+    let spans = recorder.spans();
+    let attempts: Vec<_> = spans
+        .iter()
+        .filter(|span| span.starts_with("anilist_attempt"))
+        .collect();
+    assert_eq!(attempts.len(), 2, "{spans:?}");
+}
@@ -96,3 +96,841 @@ async fn test_client_integration() {
     let staff_result = crate::staff_api_call!(client, get_popular, 1, 1);
     staff_result.expect("Failed to get popular staff");
 }
+
+#[test]
+fn test_favourite_sync_plan_diffs_all_kinds() {
+    use anilist_sdk::models::user::{FavouriteItem, FavouriteKind, FavouriteSet};
+
+    let mut current = FavouriteSet::default();
+    current.anime.extend([1, 2, 3]);
+    current.characters.insert(10);
+    current.studios.insert(21);
+
+    let mut desired = FavouriteSet::default();
+    desired.anime.extend([2, 3, 4]);
+    desired.staff.insert(95269);
+    desired.studios.insert(21);
+
+    let plan = current.plan_sync(&desired);
+
+    assert_eq!(
+        plan.to_add,
+        vec![
+            FavouriteItem {
+                kind: FavouriteKind::Anime,
+                id: 4
+            },
+            FavouriteItem {
+                kind: FavouriteKind::Staff,
+                id: 95269
+            },
+        ]
+    );
+    assert_eq!(
+        plan.to_remove,
+        vec![
+            FavouriteItem {
+                kind: FavouriteKind::Anime,
+                id: 1
+            },
+            FavouriteItem {
+                kind: FavouriteKind::Character,
+                id: 10
+            },
+        ]
+    );
+    assert_eq!(plan.unchanged.len(), 3);
+    assert!(!plan.is_empty());
+}
+
+#[test]
+fn test_favourite_sync_plan_is_empty_when_in_sync() {
+    use anilist_sdk::models::user::FavouriteSet;
+
+    let mut current = FavouriteSet::default();
+    current.manga.extend([30013, 30002]);
+
+    let plan = current.plan_sync(&current.clone());
+    assert!(plan.is_empty());
+    assert_eq!(plan.unchanged.len(), 2);
+
+    let plan = FavouriteSet::default().plan_sync(&FavouriteSet::default());
+    assert!(plan.is_empty());
+    assert!(plan.unchanged.is_empty());
+}
+
+#[test]
+fn test_favourite_set_same_id_different_kinds() {
+    use anilist_sdk::models::user::{FavouriteItem, FavouriteKind, FavouriteSet};
+
+    let mut current = FavouriteSet::default();
+    current.anime.insert(7);
+
+    let mut desired = FavouriteSet::default();
+    desired.manga.insert(7);
+
+    let plan = current.plan_sync(&desired);
+    assert_eq!(
+        plan.to_add,
+        vec![FavouriteItem {
+            kind: FavouriteKind::Manga,
+            id: 7
+        }]
+    );
+    assert_eq!(
+        plan.to_remove,
+        vec![FavouriteItem {
+            kind: FavouriteKind::Anime,
+            id: 7
+        }]
+    );
+    assert_eq!(current.len(), 1);
+}
+
+/// A page of `user().get_favourite_set()` with `anime` favourited, and
+/// `has_next_page` reported for the anime connection only.
+fn favourites_page(anime: &[i32], has_next_page: bool) -> serde_json::Value {
+    let empty = serde_json::json!({ "pageInfo": { "hasNextPage": false }, "nodes": [] });
+    let nodes: Vec<_> = anime
+        .iter()
+        .map(|id| serde_json::json!({ "id": id }))
+        .collect();
+    serde_json::json!({ "data": { "Viewer": { "id": 1, "favourites": {
+        "anime": { "pageInfo": { "hasNextPage": has_next_page }, "nodes": nodes },
+        "manga": empty,
+        "characters": empty,
+        "staff": empty,
+        "studios": empty,
+    } } } })
+}
+
+#[tokio::test]
+async fn test_favourite_set_reads_every_page() {
+    use anilist_sdk::transport::TransportResponse;
+    use std::sync::Arc;
+    use test_utils::MockApi;
+
+    let transport = Arc::new(MockApi::from_fn(|request| {
+        let body = match request.variables()["page"].as_i64() {
+            Some(1) => favourites_page(&[1, 2], true),
+            _ => favourites_page(&[3], false),
+        };
+        TransportResponse::new(200, body.to_string())
+    }));
+    let client = transport.attach(AniListClient::builder().token("token"));
+
+    let set = client.user().get_favourite_set().await.unwrap();
+    assert_eq!(set.anime.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_favourite_set_fails_instead_of_truncating() {
+    use anilist_sdk::error::AniListError;
+    use anilist_sdk::models::user::{FavouriteSet, FavouriteSyncOptions};
+    use anilist_sdk::transport::TransportResponse;
+    use std::sync::Arc;
+    use test_utils::MockApi;
+
+    // Every page claims there is another one
+    let transport = Arc::new(MockApi::from_fn(|request| {
+        let page = request.variables()["page"].as_i64().unwrap() as i32;
+        TransportResponse::new(200, favourites_page(&[page], true).to_string())
+    }));
+    let client = transport.attach(AniListClient::builder().token("token"));
+
+    let result = client.user().get_favourite_set().await;
+    assert!(
+        matches!(result, Err(AniListError::PageLimitReached { pages: 200 })),
+        "{result:?}"
+    );
+    assert_eq!(transport.calls(), 200);
+
+    // A sync does not toggle anything against the partial set
+    let options = FavouriteSyncOptions {
+        dry_run: false,
+        delay_ms: 0,
+    };
+    let result = client
+        .user()
+        .sync_favourites(&FavouriteSet::default(), options)
+        .await;
+    assert!(matches!(result, Err(AniListError::PageLimitReached { .. })));
+    assert_eq!(transport.calls(), 400);
+    assert!(
+        transport
+            .variables()
+            .iter()
+            .all(|variables| variables.get("animeId").is_none())
+    );
+}
+
+#[tokio::test]
+async fn test_favourite_set_rejects_undecodable_pages() {
+    use anilist_sdk::error::AniListError;
+    use std::sync::Arc;
+    use test_utils::MockApi;
+
+    let mut body = favourites_page(&[1], false);
+    body["data"]["Viewer"]["favourites"]["anime"]["nodes"][0]["id"] = serde_json::json!(null);
+    let transport = Arc::new(MockApi::ok(body.to_string()));
+    let client = transport.attach(AniListClient::builder().token("token"));
+
+    let result = client.user().get_favourite_set().await;
+    assert!(matches!(result, Err(AniListError::Json(_))), "{result:?}");
+}
+
+#[test]
+fn test_list_entry_episodes_behind() {
+    use anilist_sdk::models::MediaList;
+
+    let entry = |progress: i32, media: serde_json::Value| -> MediaList {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "userId": 1,
+            "mediaId": media["id"],
+            "score": 0,
+            "repeat": 0,
+            "priority": 0,
+            "private": false,
+            "hiddenFromStatusLists": false,
+            "startedAt": {},
+            "completedAt": {},
+            "status": "CURRENT",
+            "progress": progress,
+            "media": media
+        }))
+        .unwrap()
+    };
+
+    // Airing: episode 9 airs next, so 8 have aired
+    let airing = entry(
+        5,
+        serde_json::json!({
+            "id": 10,
+            "status": "RELEASING",
+            "episodes": 12,
+            "nextAiringEpisode": {
+                "id": 100,
+                "airingAt": 1_700_000_000,
+                "timeUntilAiring": 86_400,
+                "episode": 9,
+                "mediaId": 10
+            }
+        }),
+    );
+    assert_eq!(airing.episodes_behind(), Some(3));
+    let countdown = airing.media.as_ref().unwrap().next_airing_episode.as_ref();
+    assert_eq!(countdown.map(|next| next.time_until_airing), Some(86_400));
+
+    // Finished: all episodes have aired
+    let finished = entry(
+        20,
+        serde_json::json!({ "id": 11, "status": "FINISHED", "episodes": 24 }),
+    );
+    assert_eq!(finished.episodes_behind(), Some(4));
+
+    // Caught up or ahead never goes negative
+    let caught_up = entry(
+        30,
+        serde_json::json!({ "id": 12, "status": "FINISHED", "episodes": 24 }),
+    );
+    assert_eq!(caught_up.episodes_behind(), Some(0));
+
+    // Not yet airing: unknown
+    let upcoming = entry(
+        0,
+        serde_json::json!({ "id": 13, "status": "NOT_YET_RELEASED", "episodes": 12 }),
+    );
+    assert_eq!(upcoming.episodes_behind(), None);
+}
+
+/// A captured `Viewer` payload selecting every field modelled by `User`.
+fn viewer_fixture() -> serde_json::Value {
+    // Profile queries select only `total` and `hasNextPage`
+    let page_info = serde_json::json!({
+        "total": 1,
+        "perPage": null,
+        "currentPage": null,
+        "lastPage": null,
+        "hasNextPage": false
+    });
+    serde_json::json!({
+        "id": 5123456,
+        "name": "Donator",
+        "about": "Hello __world__",
+        "avatar": {
+            "large": "https://s4.anilist.co/file/anilistcdn/user/avatar/large/b5123456.png",
+            "medium": "https://s4.anilist.co/file/anilistcdn/user/avatar/medium/b5123456.png"
+        },
+        "bannerImage": "https://s4.anilist.co/file/anilistcdn/user/banner/b5123456.jpg",
+        "isFollowing": false,
+        "isFollower": true,
+        "isBlocked": false,
+        "options": {
+            "titleLanguage": "ROMAJI",
+            "displayAdultContent": false,
+            "airingNotifications": true,
+            "profileColor": "blue",
+            "notificationOptions": [{ "type": "ACTIVITY_REPLY", "enabled": true }],
+            "timezone": "+09:00",
+            "activityMergeTime": 720,
+            "staffNameLanguage": "ROMAJI_WESTERN"
+        },
+        "mediaListOptions": {
+            "scoreFormat": "POINT_10_DECIMAL",
+            "rowOrder": "score",
+            "animeList": {
+                "sectionOrder": ["Watching", "Completed"],
+                "splitCompletedSectionByFormat": true,
+                "customLists": ["Rewatch"],
+                "advancedScoring": ["Story", "Characters"],
+                "advancedScoringEnabled": false
+            },
+            "mangaList": {
+                "sectionOrder": ["Reading"],
+                "splitCompletedSectionByFormat": false,
+                "customLists": [],
+                "advancedScoring": [],
+                "advancedScoringEnabled": false
+            }
+        },
+        "favourites": {
+            "anime": { "pageInfo": page_info, "nodes": [{ "id": 1, "title": { "userPreferred": "Cowboy Bebop" } }] },
+            "manga": { "pageInfo": page_info, "nodes": [{ "id": 30013, "title": { "userPreferred": "ONE PIECE" } }] },
+            "characters": { "pageInfo": page_info, "nodes": [{ "id": 1, "name": { "userPreferred": "Spike Spiegel" } }] },
+            "staff": { "pageInfo": page_info, "nodes": [{ "id": 95185, "name": { "userPreferred": "Shinichirou Watanabe" } }] },
+            "studios": { "pageInfo": page_info, "nodes": [{ "id": 14, "name": "Sunrise" }] }
+        },
+        "statistics": {
+            "anime": {
+                "count": 412,
+                "meanScore": 74.2,
+                "standardDeviation": 11.5,
+                "minutesWatched": 210345,
+                "episodesWatched": 8712,
+                "chaptersRead": 0,
+                "volumesRead": 0
+            },
+            "manga": {
+                "count": 58,
+                "meanScore": 78.9,
+                "standardDeviation": 9.1,
+                "minutesWatched": 0,
+                "episodesWatched": 0,
+                "chaptersRead": 4120,
+                "volumesRead": 310
+            }
+        },
+        "unreadNotificationCount": 3,
+        "siteUrl": "https://anilist.co/user/Donator",
+        "donatorTier": 3,
+        "donatorBadge": "Supporter",
+        "moderatorRoles": null,
+        "createdAt": 1_550_000_000,
+        "updatedAt": 1_700_000_000
+    })
+}
+
+#[test]
+fn test_user_deserializes_donator_badge() {
+    use anilist_sdk::models::User;
+
+    let user: User = serde_json::from_value(viewer_fixture()).unwrap();
+    assert_eq!(user.donator_tier, Some(3));
+    assert_eq!(user.donator_badge.as_deref(), Some("Supporter"));
+}
+
+#[test]
+fn test_user_round_trips_full_viewer_payload() {
+    use anilist_sdk::models::User;
+
+    let fixture = viewer_fixture();
+    let user: User = serde_json::from_value(fixture.clone()).unwrap();
+    let serialized = serde_json::to_value(&user).unwrap();
+
+    // Any rename typo drops the value on the way in and emits it under the wrong
+    // key on the way out, so a lossless round trip proves every key matches.
+    assert_eq!(serialized, fixture);
+}
+
+#[test]
+fn test_next_up_sorts_by_available_episodes() {
+    use anilist_sdk::models::MediaList;
+    use anilist_sdk::models::media_list::next_up;
+
+    let entry = |id: i32, progress: i32, status: &str, episodes: Option<i32>, next: Option<i32>| {
+        serde_json::from_value::<MediaList>(serde_json::json!({
+            "id": id,
+            "userId": 1,
+            "mediaId": id,
+            "score": 0,
+            "repeat": 0,
+            "priority": 0,
+            "private": false,
+            "hiddenFromStatusLists": false,
+            "startedAt": {},
+            "completedAt": {},
+            "status": "CURRENT",
+            "progress": progress,
+            "media": {
+                "id": id,
+                "status": status,
+                "episodes": episodes,
+                "nextAiringEpisode": next.map(|episode| serde_json::json!({
+                    "id": id * 100,
+                    "airingAt": 1_700_000_000,
+                    "timeUntilAiring": 3_600,
+                    "episode": episode,
+                    "mediaId": id
+                }))
+            }
+        }))
+        .unwrap()
+    };
+
+    let entries = vec![
+        // Airing, caught up: episode 5 is next and 4 are watched
+        entry(1, 4, "RELEASING", Some(12), Some(5)),
+        // Airing, one behind
+        entry(2, 2, "RELEASING", None, Some(4)),
+        // Finished airing (null nextAiringEpisode): uses `episodes`
+        entry(3, 10, "FINISHED", Some(24), None),
+        // Not yet airing: skipped
+        entry(4, 0, "NOT_YET_RELEASED", Some(12), None),
+        // Finished and completed progress: skipped
+        entry(5, 13, "FINISHED", Some(13), None),
+        // Also one behind, keeps its order after entry 2
+        entry(6, 0, "RELEASING", None, Some(2)),
+    ];
+
+    let next = next_up(entries);
+    let summary: Vec<(i32, i32)> = next
+        .iter()
+        .map(|item| (item.entry.id, item.available_episodes))
+        .collect();
+    assert_eq!(summary, vec![(3, 14), (2, 1), (6, 1)]);
+}
+
+fn updated_entry(id: i32, updated_at: Option<i64>) -> anilist_sdk::models::MediaList {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "userId": 1,
+        "mediaId": id,
+        "score": 0,
+        "progress": 0,
+        "repeat": 0,
+        "priority": 0,
+        "private": false,
+        "hiddenFromStatusLists": false,
+        "startedAt": {},
+        "completedAt": {},
+        "status": "CURRENT",
+        "updatedAt": updated_at
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_updated_since_stops_at_checkpoint_mid_page() {
+    use anilist_sdk::models::UpdatedSince;
+
+    let since = 1_700_000_000;
+    let mut sync = UpdatedSince::new(since);
+
+    // Page 1: everything is newer than the checkpoint, keep going
+    let page_1 = vec![
+        updated_entry(1, Some(since + 300)),
+        updated_entry(2, Some(since + 200)),
+    ];
+    assert!(sync.push_page(page_1, true));
+    assert!(!sync.is_done());
+
+    // Page 2: the checkpoint is crossed halfway through, stop even though
+    // more pages exist
+    let page_2 = vec![
+        updated_entry(3, Some(since + 100)),
+        updated_entry(4, Some(since)),
+        updated_entry(5, Some(since - 100)),
+    ];
+    assert!(!sync.push_page(page_2, true));
+    assert!(sync.is_done());
+
+    // A page 3 would never be requested; pushing one is ignored
+    assert!(!sync.push_page(vec![updated_entry(6, Some(since + 999))], false));
+
+    let ids: Vec<i32> = sync.entries.iter().map(|entry| entry.id).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+    assert_eq!(sync.pages_fetched, 2);
+    assert_eq!(sync.newest_updated_at, Some(since + 300));
+    assert_eq!(sync.next_checkpoint(), since + 300);
+}
+
+#[test]
+fn test_updated_since_reads_every_page_when_all_new() {
+    use anilist_sdk::models::UpdatedSince;
+
+    let mut sync = UpdatedSince::new(0);
+    assert!(sync.push_page(vec![updated_entry(1, Some(30))], true));
+    assert!(sync.push_page(vec![updated_entry(2, Some(20))], true));
+    assert!(!sync.push_page(vec![updated_entry(3, Some(10))], false));
+
+    assert!(sync.is_done());
+    assert_eq!(sync.entries.len(), 3);
+    assert_eq!(sync.pages_fetched, 3);
+    assert_eq!(sync.next_checkpoint(), 30);
+}
+
+#[test]
+fn test_updated_since_no_changes_keeps_checkpoint() {
+    use anilist_sdk::models::UpdatedSince;
+
+    let since = 1_700_000_000;
+    let mut sync = UpdatedSince::new(since);
+
+    // Entries without a timestamp count as old
+    assert!(!sync.push_page(vec![updated_entry(1, None)], true));
+    assert!(sync.entries.is_empty());
+    assert_eq!(sync.newest_updated_at, None);
+    assert_eq!(sync.next_checkpoint(), since);
+
+    let mut empty = UpdatedSince::new(since);
+    assert!(!empty.push_page(Vec::new(), false));
+    assert_eq!(empty.next_checkpoint(), since);
+}
+
+#[test]
+fn test_list_entry_timestamps_exceed_i32() {
+    // Far-future timestamps must not overflow
+    let entry = updated_entry(1, Some(4_102_444_800));
+    assert_eq!(entry.updated_at, Some(4_102_444_800));
+}
+
+fn collection_entry(id: i32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id, "userId": 1, "mediaId": id * 10, "status": "COMPLETED", "score": 0,
+        "progress": 0, "repeat": 0, "priority": 0, "private": false,
+        "hiddenFromStatusLists": false, "startedAt": {}, "completedAt": {}
+    })
+}
+
+/// A `MediaListCollection` chunk with a status list and a custom list.
+fn collection_json(ids: &[i32], has_next_chunk: bool) -> serde_json::Value {
+    let (first, second) = ids.split_at(ids.len() / 2);
+    let entries = |ids: &[i32]| {
+        ids.iter()
+            .copied()
+            .map(collection_entry)
+            .collect::<Vec<_>>()
+    };
+    serde_json::json!({
+        "hasNextChunk": has_next_chunk,
+        "lists": [{ "entries": entries(first) }, { "entries": entries(second) }]
+    })
+}
+
+fn collection_chunk(ids: &[i32], has_next_chunk: bool) -> anilist_sdk::models::MediaListCollection {
+    serde_json::from_value(collection_json(ids, has_next_chunk)).unwrap()
+}
+
+#[test]
+fn test_list_collection_follows_chunks_until_complete() {
+    use anilist_sdk::models::ListCollection;
+
+    let mut collection = ListCollection::new(10).unwrap();
+    assert!(collection.push_collection_chunk(collection_chunk(&[1, 2, 3, 4], true)));
+    assert!(collection.push_collection_chunk(collection_chunk(&[5, 6, 7, 8], true)));
+    assert!(!collection.push_collection_chunk(collection_chunk(&[9, 10], false)));
+    assert!(collection.is_done());
+
+    let ids: Vec<i32> = collection.entries.iter().map(|entry| entry.id).collect();
+    assert_eq!(ids, (1..=10).collect::<Vec<_>>());
+    assert_eq!(collection.chunks_fetched, 3);
+    assert!(!collection.truncated);
+
+    // Nothing more is accepted once done
+    assert!(!collection.push_collection_chunk(collection_chunk(&[11], false)));
+    assert_eq!(collection.entries.len(), 10);
+}
+
+#[test]
+fn test_list_collection_max_chunks_guard() {
+    use anilist_sdk::models::ListCollection;
+
+    let mut collection = ListCollection::new(2).unwrap();
+    assert!(collection.push_collection_chunk(collection_chunk(&[1, 2], true)));
+    // The guard is reached while AniList still has more
+    assert!(!collection.push_collection_chunk(collection_chunk(&[3, 4], true)));
+    assert!(collection.truncated);
+    assert!(collection.is_done());
+    assert_eq!(collection.entries.len(), 4);
+
+    // Reaching the limit exactly on the last chunk is not a truncation
+    let mut collection = ListCollection::new(2).unwrap();
+    assert!(collection.push_collection_chunk(collection_chunk(&[1, 2], true)));
+    assert!(!collection.push_collection_chunk(collection_chunk(&[3], false)));
+    assert!(!collection.truncated);
+
+    // A zero limit could never return anything
+    assert!(matches!(
+        ListCollection::new(0),
+        Err(anilist_sdk::error::AniListError::InvalidInput { .. })
+    ));
+}
+
+#[test]
+fn test_list_collection_dedupes_entries_on_several_lists() {
+    use anilist_sdk::models::ListCollection;
+
+    // Entry 2 is on a custom list as well as its status list, and entry 3
+    // shows up again in the next chunk
+    let mut collection = ListCollection::new(5).unwrap();
+    assert!(collection.push_collection_chunk(collection_chunk(&[1, 2, 2, 3], true)));
+    assert!(!collection.push_collection_chunk(collection_chunk(&[3, 4], false)));
+
+    let ids: Vec<i32> = collection.entries.iter().map(|entry| entry.id).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn test_get_list_collection_rejects_undecodable_entries() {
+    use anilist_sdk::error::AniListError;
+    use anilist_sdk::models::social::MediaType;
+    use anilist_sdk::transport::TransportResponse;
+    use std::sync::Arc;
+    use test_utils::MockApi;
+
+    let mut chunk = collection_json(&[1, 2], false);
+    chunk["lists"][1]["entries"][0]["status"] = serde_json::json!("REWATCHING_FOREVER");
+    let body = serde_json::json!({ "data": { "MediaListCollection": chunk } });
+    let transport = Arc::new(MockApi::respond(TransportResponse::new(
+        200,
+        body.to_string(),
+    )));
+    let client = transport.client();
+
+    let result = client
+        .user()
+        .get_list_collection(1, MediaType::Anime, None, 5)
+        .await;
+    assert!(matches!(result, Err(AniListError::Json(_))), "{result:?}");
+
+    // A zero chunk limit is rejected before anything is sent
+    let result = client
+        .user()
+        .get_list_collection(1, MediaType::Anime, None, 0)
+        .await;
+    assert!(matches!(result, Err(AniListError::InvalidInput { .. })));
+    assert_eq!(transport.calls(), 1);
+}
+
+#[test]
+fn test_list_collection_single_unchunked_response() {
+    use anilist_sdk::models::ListCollection;
+
+    // Older responses without `hasNextChunk` and a null list are complete
+    let mut collection = ListCollection::new(5).unwrap();
+    let response = serde_json::json!({
+        "lists": [
+            { "entries": [{
+                "id": 1, "userId": 1, "mediaId": 1, "status": "CURRENT", "score": 0,
+                "progress": 0, "repeat": 0, "priority": 0, "private": false,
+                "hiddenFromStatusLists": false, "startedAt": {}, "completedAt": {}
+            }] },
+            { "entries": null }
+        ]
+    });
+    let response = serde_json::from_value(response).unwrap();
+    assert!(!collection.push_collection_chunk(response));
+    assert_eq!(collection.entries.len(), 1);
+    assert!(!collection.truncated);
+}
+
+#[tokio::test]
+async fn test_get_list_collection() {
+    use anilist_sdk::models::social::MediaType;
+
+    let client = AniListClient::new();
+    // Chunk size 500 with a generous guard; the list of user 1 is small
+    let collection =
+        crate::user_api_call!(client, get_list_collection, 1, MediaType::Anime, None, 5)
+            .expect("Failed to get list collection");
+
+    assert!(collection.chunks_fetched >= 1);
+    assert!(!collection.truncated);
+    assert!(collection.entries.iter().all(|entry| entry.user_id == 1));
+}
+
+#[tokio::test]
+async fn test_update_about_requires_token() {
+    let client = AniListClient::new();
+    let result = client.user().update_about("Hi!").await;
+    assert!(matches!(
+        result,
+        Err(anilist_sdk::error::AniListError::AuthenticationRequired)
+    ));
+}
+
+#[tokio::test]
+async fn test_update_about_dry_run_sends_nothing() {
+    use anilist_sdk::models::ProfileUpdateOptions;
+
+    // The token is never sent, so a fake one is fine
+    let client = AniListClient::with_token("token".to_string());
+    let options = ProfileUpdateOptions { dry_run: true };
+
+    let about = "~~~img220(https://i.imgur.com/banner.png)~~~\nHi! ~!I cried at the end!~";
+    let saved = client
+        .user()
+        .update_about_with_options(about, options.clone())
+        .await
+        .unwrap();
+    assert_eq!(saved, about);
+
+    // Clearing the section is allowed
+    let saved = client
+        .user()
+        .update_about_with_options("", options)
+        .await
+        .unwrap();
+    assert_eq!(saved, "");
+}
+
+#[test]
+fn test_user_deserializes_html_about() {
+    use anilist_sdk::models::User;
+
+    let user: User = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "name": "Josh",
+        "about": "<p><center><img width=\"220\" src=\"https://i.imgur.com/banner.png\"></center></p>"
+    }))
+    .unwrap();
+    assert!(user.about.unwrap().starts_with("<p><center>"));
+
+    let user: User =
+        serde_json::from_value(serde_json::json!({ "id": 2, "name": "Quiet", "about": null }))
+            .unwrap();
+    assert_eq!(user.about, None);
+}
+
+#[test]
+fn test_save_list_entry_variables() {
+    use anilist_sdk::models::{FuzzyDate, MediaListStatus, SaveListEntry};
+    use serde_json::json;
+
+    let entry = SaveListEntry::for_media(21)
+        .status(MediaListStatus::Current)
+        .progress(1100)
+        .private(true);
+    let variables = entry.variables();
+    assert_eq!(variables.len(), 4);
+    assert_eq!(variables["mediaId"], json!(21));
+    assert_eq!(variables["status"], json!("CURRENT"));
+    assert_eq!(variables["progress"], json!(1100));
+    assert_eq!(variables["private"], json!(true));
+
+    // Unset fields are not sent, so AniList keeps their values
+    let variables = SaveListEntry::for_entry(99)
+        .score(8.5)
+        .completed_at(FuzzyDate {
+            year: Some(2024),
+            month: Some(3),
+            day: Some(15),
+        })
+        .variables();
+    assert_eq!(variables["id"], json!(99));
+    assert_eq!(variables["score"], json!(8.5));
+    assert_eq!(
+        variables["completedAt"],
+        json!({ "year": 2024, "month": 3, "day": 15 })
+    );
+    assert!(!variables.contains_key("private"));
+    assert!(!variables.contains_key("mediaId"));
+}
+
+#[tokio::test]
+async fn test_save_list_entry_checks() {
+    use anilist_sdk::error::AniListError;
+    use anilist_sdk::models::SaveListEntry;
+
+    let entry = SaveListEntry::for_media(21).progress(1);
+    let result = AniListClient::new().user().save_list_entry(&entry).await;
+    assert!(matches!(result, Err(AniListError::AuthenticationRequired)));
+
+    // Nothing identifies the entry
+    let client = AniListClient::with_token("token".to_string());
+    let entry = SaveListEntry::default().private(true);
+    let result = client.user().save_list_entry(&entry).await;
+    assert!(matches!(result, Err(AniListError::BadRequest { .. })));
+}
+
+#[test]
+fn test_saved_private_entry_fixture() {
+    use anilist_sdk::models::MediaList;
+
+    let saved: MediaList = serde_json::from_value(serde_json::json!({
+        "id": 99, "userId": 5, "mediaId": 21, "status": "CURRENT", "score": 0, "progress": 1100,
+        "repeat": 0, "priority": 0, "private": true, "hiddenFromStatusLists": false,
+        "startedAt": {}, "completedAt": {},
+        "updatedAt": 1_700_000_000_i64, "createdAt": 1_600_000_000_i64
+    }))
+    .unwrap();
+    assert!(saved.private);
+    assert_eq!(saved.progress, 1100);
+}
+
+#[test]
+fn test_profile_favourites_report_truncation() {
+    use anilist_sdk::models::User;
+
+    // A user with 60 favourite anime: the profile only carries the first 25
+    let anime_nodes: Vec<_> = (1..=25)
+        .map(|id| serde_json::json!({ "id": id, "title": { "userPreferred": format!("Anime {id}") } }))
+        .collect();
+    let fixture = serde_json::json!({
+        "id": 1,
+        "name": "Collector",
+        "favourites": {
+            "anime": {
+                "pageInfo": { "total": 60, "hasNextPage": true },
+                "nodes": anime_nodes
+            },
+            "manga": { "pageInfo": { "total": 0, "hasNextPage": false }, "nodes": [] },
+            "characters": {
+                "pageInfo": { "total": 1, "hasNextPage": false },
+                "nodes": [{ "id": 40, "name": { "userPreferred": "Lelouch Lamperouge" } }]
+            },
+            "staff": { "nodes": [] },
+            "studios": { "pageInfo": { "total": 0, "hasNextPage": false }, "nodes": [] }
+        }
+    });
+    let user: User = serde_json::from_value(fixture).unwrap();
+    let favourites = user.favourites.unwrap();
+
+    let anime = favourites.anime.as_ref().unwrap();
+    assert!(anime.has_next_page());
+    assert_eq!(anime.total(), Some(60));
+    assert_eq!(anime.nodes.as_ref().unwrap().len(), 25);
+
+    let characters = favourites.characters.as_ref().unwrap();
+    assert!(!characters.has_next_page());
+    assert_eq!(characters.total(), Some(1));
+
+    // Payloads without pageInfo are never reported as truncated
+    let staff = favourites.staff.as_ref().unwrap();
+    assert!(!staff.has_next_page());
+    assert_eq!(staff.total(), None);
+
+    assert!(favourites.is_truncated());
+}
+
+#[test]
+fn test_profile_queries_select_favourites_page_info() {
+    use anilist_sdk::queries::user;
+
+    for query in [user::GET_BY_ID, user::GET_BY_NAME, user::GET_CURRENT_USER] {
+        assert_eq!(query.matches("hasNextPage").count(), 5);
+        assert_eq!(query.matches("total").count(), 5);
+    }
+}